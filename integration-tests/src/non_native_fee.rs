@@ -16,6 +16,7 @@ use orml_traits::currency::MultiCurrency;
 use pallet_xyk::XYKSpotPrice;
 use polkadot_primitives::v2::BlockNumber;
 use primitives::asset::AssetPair;
+use sp_runtime::FixedPointNumber;
 use xcm_emulator::TestExt;
 
 pub fn basilisk_run_to_block(to: BlockNumber) {
@@ -24,6 +25,7 @@ pub fn basilisk_run_to_block(to: BlockNumber) {
 
 		basilisk_runtime::System::on_finalize(b);
 		basilisk_runtime::MultiTransactionPayment::on_finalize(b);
+		basilisk_runtime::PriceOracle::on_finalize(b);
 
 		basilisk_runtime::System::on_initialize(b + 1);
 		basilisk_runtime::MultiTransactionPayment::on_initialize(b + 1);
@@ -33,7 +35,7 @@ pub fn basilisk_run_to_block(to: BlockNumber) {
 }
 
 #[test]
-fn non_native_fee_payment_works_with_xyk_spot_price() {
+fn non_native_fee_payment_works_with_oracle_price() {
 	use pallet_transaction_multi_payment::TransactionMultiPaymentDataProvider;
 
 	TestNet::reset();
@@ -81,7 +83,7 @@ fn non_native_fee_payment_works_with_xyk_spot_price() {
 		));
 
 		let spot_price = XYKSpotPrice::<basilisk_runtime::Runtime>::spot_price(currency_0, currency_1);
-		assert_eq!(spot_price, Some(Price::from_float(0.5)));
+		assert_eq!(spot_price, Some(Price::saturating_from_rational(5_015, 10_000))); // 0.5 + 0.3% pool fee
 
 		basilisk_run_to_block(2);
 
@@ -94,7 +96,8 @@ fn non_native_fee_payment_works_with_xyk_spot_price() {
 			false,
 		));
 
-		basilisk_run_to_block(3);
+		// the oracle price is used once the pair has been tracked for the whole oracle period
+		basilisk_run_to_block(53);
 
 		assert_eq!(
 			basilisk_runtime::XYK::get_pool_assets(&pair_account),
@@ -108,14 +111,14 @@ fn non_native_fee_payment_works_with_xyk_spot_price() {
 		));
 
 		let dave_balance = basilisk_runtime::Tokens::free_balance(1, &AccountId::from(DAVE));
-		assert_eq!(dave_balance, 974_342_185_521_892);
+		assert_eq!(dave_balance, 974_343_201_370_207);
 
 		expect_basilisk_events(vec![
 			pallet_transaction_multi_payment::Event::FeeWithdrawn {
 				account_id: DAVE.into(),
 				asset_id: 1,
 				native_fee_amount: 44_756_635_000_000,
-				non_native_fee_amount: 25_657_814_478_108,
+				non_native_fee_amount: 25_656_798_629_793, // pool price after the buy: 535.416702355461 KSM for 934 BSX
 				destination_account_id: basilisk_runtime::MultiTransactionPayment::get_fee_receiver(),
 			}
 			.into(),
@@ -130,6 +133,67 @@ fn non_native_fee_payment_works_with_xyk_spot_price() {
 	});
 }
 
+#[test]
+fn non_native_fee_payment_should_use_fallback_price_when_oracle_is_not_mature() {
+	TestNet::reset();
+
+	Basilisk::execute_with(|| {
+		let currency_0 = 0;
+		let currency_1 = 1;
+
+		assert_ok!(basilisk_runtime::Tokens::set_balance(
+			basilisk_runtime::Origin::root(),
+			ALICE.into(),
+			1,
+			2_000_000_000_000 * UNITS,
+			0,
+		));
+
+		assert_ok!(basilisk_runtime::XYK::create_pool(
+			basilisk_runtime::Origin::signed(ALICE.into()),
+			currency_0,
+			1_000 * UNITS,
+			currency_1,
+			500 * UNITS,
+		));
+
+		basilisk_run_to_block(2);
+
+		assert_ok!(basilisk_runtime::XYK::buy(
+			basilisk_runtime::Origin::signed(ALICE.into()),
+			0,
+			1,
+			66 * UNITS,
+			1_000 * UNITS,
+			false,
+		));
+
+		basilisk_run_to_block(3);
+
+		assert_ok!(basilisk_runtime::MultiTransactionPayment::set_currency(
+			basilisk_runtime::Origin::signed(DAVE.into()),
+			currency_1,
+		));
+
+		// fee is converted with the price set for the accepted currency, not the pool reserves
+		expect_basilisk_events(vec![
+			pallet_transaction_multi_payment::Event::FeeWithdrawn {
+				account_id: DAVE.into(),
+				asset_id: 1,
+				native_fee_amount: 44_756_635_000_000,
+				non_native_fee_amount: 20_720_664,
+				destination_account_id: basilisk_runtime::MultiTransactionPayment::get_fee_receiver(),
+			}
+			.into(),
+			pallet_transaction_multi_payment::Event::CurrencySet {
+				account_id: DAVE.into(),
+				asset_id: 1,
+			}
+			.into(),
+		]);
+	});
+}
+
 const HITCHHIKER: [u8; 32] = [42u8; 32];
 
 #[test]
@@ -1320,6 +1320,439 @@ mod lbp_router_tests {
 	}
 }
 
+mod router_extensions_tests {
+	use crate::kusama_test_net::*;
+
+	use basilisk_runtime::{Origin, RouterExtensions};
+	use xcm_emulator::TestExt;
+
+	use frame_support::assert_ok;
+	use hydradx_traits::router::PoolType;
+	use pallet_route_executor::Trade;
+
+	use super::*;
+
+	#[test]
+	fn sell_with_deadline_should_work_when_deadline_has_not_passed() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(BSX, KSM);
+
+			let amount_to_sell = 10 * UNITS;
+			let limit = 0;
+			let trades = vec![Trade {
+				pool: PoolType::XYK,
+				asset_in: BSX,
+				asset_out: KSM,
+			}];
+
+			//Act
+			assert_ok!(RouterExtensions::sell_with_deadline(
+				Origin::signed(TRADER.into()),
+				BSX,
+				KSM,
+				amount_to_sell,
+				limit,
+				trades,
+				BlockNumber::MAX
+			));
+
+			//Assert
+			let amount_out = 4_531_818_181_819_u128;
+
+			assert_trader_bsx_balance!(BOB_INITIAL_BSX_BALANCE - amount_to_sell);
+			assert_trader_non_native_balance!(amount_out, KSM);
+
+			expect_basilisk_events(vec![pallet_route_executor::Event::RouteExecuted {
+				asset_in: BSX,
+				asset_out: KSM,
+				amount_in: amount_to_sell,
+				amount_out,
+			}
+			.into()]);
+		});
+	}
+
+	#[test]
+	fn sell_all_should_sell_whole_transferable_balance() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(AUSD, BSX);
+
+			let trades = vec![Trade {
+				pool: PoolType::XYK,
+				asset_in: AUSD,
+				asset_out: BSX,
+			}];
+
+			//Act
+			assert_ok!(RouterExtensions::sell_all(
+				Origin::signed(TRADER.into()),
+				AUSD,
+				BSX,
+				0,
+				trades
+			));
+
+			//Assert
+			assert_trader_non_native_balance!(0, AUSD);
+			assert!(basilisk_runtime::Balances::free_balance(&AccountId::from(TRADER)) > BOB_INITIAL_BSX_BALANCE);
+		});
+	}
+}
+
+mod weighted_pool_router_tests {
+	use crate::kusama_test_net::*;
+
+	use basilisk_runtime::{Origin, Router, WeightedPool};
+	use xcm_emulator::TestExt;
+
+	use frame_support::assert_ok;
+	use hydradx_traits::router::PoolType;
+	use pallet_route_executor::Trade;
+
+	use super::*;
+
+	#[test]
+	fn sell_should_work_when_route_contains_weighted_pool_trade() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_weighted_pool(AUSD, KSM);
+
+			let amount_to_sell = 10 * UNITS;
+			let (amount_out, _) = WeightedPool::quote_sell(AUSD, KSM, amount_to_sell).unwrap();
+			let trades = vec![Trade {
+				pool: PoolType::Stableswap(weighted_pool_share_token(AUSD, KSM)),
+				asset_in: AUSD,
+				asset_out: KSM,
+			}];
+
+			//Act
+			assert_ok!(Router::sell(
+				Origin::signed(TRADER.into()),
+				AUSD,
+				KSM,
+				amount_to_sell,
+				amount_out,
+				trades
+			));
+
+			//Assert
+			assert_trader_non_native_balance!(BOB_INITIAL_AUSD_BALANCE - amount_to_sell, AUSD);
+			assert_trader_non_native_balance!(amount_out, KSM);
+
+			expect_basilisk_events(vec![pallet_route_executor::Event::RouteExecuted {
+				asset_in: AUSD,
+				asset_out: KSM,
+				amount_in: amount_to_sell,
+				amount_out,
+			}
+			.into()]);
+		});
+	}
+
+	#[test]
+	fn buy_should_work_when_route_contains_weighted_pool_trade() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_weighted_pool(AUSD, KSM);
+
+			let amount_to_buy = 5 * UNITS;
+			let (amount_in, _) = WeightedPool::quote_buy(AUSD, KSM, amount_to_buy).unwrap();
+			let trades = vec![Trade {
+				pool: PoolType::Stableswap(weighted_pool_share_token(AUSD, KSM)),
+				asset_in: AUSD,
+				asset_out: KSM,
+			}];
+
+			//Act
+			assert_ok!(Router::buy(
+				Origin::signed(TRADER.into()),
+				AUSD,
+				KSM,
+				amount_to_buy,
+				amount_in,
+				trades
+			));
+
+			//Assert
+			assert_trader_non_native_balance!(BOB_INITIAL_AUSD_BALANCE - amount_in, AUSD);
+			assert_trader_non_native_balance!(amount_to_buy, KSM);
+
+			expect_basilisk_events(vec![pallet_route_executor::Event::RouteExecuted {
+				asset_in: AUSD,
+				asset_out: KSM,
+				amount_in,
+				amount_out: amount_to_buy,
+			}
+			.into()]);
+		});
+	}
+}
+
+mod route_finder_tests {
+	use crate::kusama_test_net::*;
+
+	use basilisk_runtime::{route_finder::RouteFinder, Origin, Router, Runtime};
+	use xcm_emulator::TestExt;
+
+	use frame_support::assert_ok;
+	use pallet_router_rpc_runtime_api::{PoolType, Trade};
+
+	use super::*;
+
+	fn executor_route(route: Vec<Trade<AssetId>>) -> Vec<pallet_route_executor::Trade<AssetId>> {
+		route
+			.into_iter()
+			.map(|trade| pallet_route_executor::Trade {
+				pool: match trade.pool {
+					PoolType::XYK => hydradx_traits::router::PoolType::XYK,
+					PoolType::LBP => hydradx_traits::router::PoolType::LBP,
+					PoolType::Stableswap(pool_id) => hydradx_traits::router::PoolType::Stableswap(pool_id),
+				},
+				asset_in: trade.asset_in,
+				asset_out: trade.asset_out,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn find_best_sell_route_should_return_route_through_different_pools() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(AUSD, BSX);
+			create_lbp_pool(BSX, NEW_BOOTSTRAPPED_TOKEN);
+			create_xyk_pool(NEW_BOOTSTRAPPED_TOKEN, KSM);
+
+			start_lbp_campaign();
+
+			let amount_to_sell = 10 * UNITS;
+
+			//Act
+			let quote = RouteFinder::<Runtime>::find_best_sell_route(AUSD, KSM, amount_to_sell).unwrap();
+
+			//Assert
+			assert_eq!(
+				quote.route,
+				vec![
+					Trade {
+						pool: PoolType::XYK,
+						asset_in: AUSD,
+						asset_out: BSX,
+					},
+					Trade {
+						pool: PoolType::LBP,
+						asset_in: BSX,
+						asset_out: NEW_BOOTSTRAPPED_TOKEN,
+					},
+					Trade {
+						pool: PoolType::XYK,
+						asset_in: NEW_BOOTSTRAPPED_TOKEN,
+						asset_out: KSM,
+					},
+				]
+			);
+			assert_eq!(quote.amount_in, amount_to_sell);
+			assert_eq!(quote.amount_out, 1208552472394);
+
+			assert_ok!(Router::sell(
+				Origin::signed(TRADER.into()),
+				AUSD,
+				KSM,
+				amount_to_sell,
+				quote.amount_out,
+				executor_route(quote.route)
+			));
+
+			assert_trader_non_native_balance!(quote.amount_out, KSM);
+		});
+	}
+
+	#[test]
+	fn find_best_buy_route_should_return_route_through_different_pools() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(AUSD, BSX);
+			create_lbp_pool(BSX, NEW_BOOTSTRAPPED_TOKEN);
+			create_xyk_pool(NEW_BOOTSTRAPPED_TOKEN, KSM);
+
+			start_lbp_campaign();
+
+			let amount_to_buy = 1 * UNITS;
+
+			//Act
+			let quote = RouteFinder::<Runtime>::find_best_buy_route(AUSD, KSM, amount_to_buy).unwrap();
+
+			//Assert
+			assert_eq!(quote.route.len(), 3);
+			assert_eq!(quote.route[0].asset_in, AUSD);
+			assert_eq!(quote.route[2].asset_out, KSM);
+			assert_eq!(quote.amount_in, 8049720201692);
+			assert_eq!(quote.amount_out, amount_to_buy);
+
+			assert_ok!(Router::buy(
+				Origin::signed(TRADER.into()),
+				AUSD,
+				KSM,
+				amount_to_buy,
+				quote.amount_in,
+				executor_route(quote.route)
+			));
+
+			assert_trader_non_native_balance!(BOB_INITIAL_AUSD_BALANCE - quote.amount_in, AUSD);
+		});
+	}
+
+	#[test]
+	fn find_best_route_should_prefer_route_with_better_price() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(AUSD, KSM);
+			create_xyk_pool(AUSD, BSX);
+			create_xyk_pool(KSM, BSX);
+
+			let route_via_bsx = vec![
+				Trade {
+					pool: PoolType::XYK,
+					asset_in: AUSD,
+					asset_out: BSX,
+				},
+				Trade {
+					pool: PoolType::XYK,
+					asset_in: BSX,
+					asset_out: KSM,
+				},
+			];
+
+			//Act & Assert
+			let sell_quote = RouteFinder::<Runtime>::find_best_sell_route(AUSD, KSM, 10 * UNITS).unwrap();
+			assert_eq!(sell_quote.route, route_via_bsx);
+
+			let buy_quote = RouteFinder::<Runtime>::find_best_buy_route(AUSD, KSM, 1 * UNITS).unwrap();
+			assert_eq!(buy_quote.route, route_via_bsx);
+		});
+	}
+
+	#[test]
+	fn find_best_route_should_return_direct_route_when_every_pair_of_assets_has_pool() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(AUSD, KSM);
+			create_xyk_pool(AUSD, MOVR);
+			create_xyk_pool(AUSD, NEW_BOOTSTRAPPED_TOKEN);
+			create_xyk_pool(KSM, MOVR);
+			create_xyk_pool(KSM, NEW_BOOTSTRAPPED_TOKEN);
+			create_xyk_pool(MOVR, NEW_BOOTSTRAPPED_TOKEN);
+
+			let direct_route = vec![Trade {
+				pool: PoolType::XYK,
+				asset_in: AUSD,
+				asset_out: KSM,
+			}];
+
+			//Act
+			let sell_quote = RouteFinder::<Runtime>::find_best_sell_route(AUSD, KSM, 10 * UNITS).unwrap();
+			let buy_quote = RouteFinder::<Runtime>::find_best_buy_route(AUSD, KSM, 1 * UNITS).unwrap();
+
+			//Assert
+			assert_eq!(sell_quote.route, direct_route);
+			assert_eq!(buy_quote.route, direct_route);
+
+			assert_ok!(Router::sell(
+				Origin::signed(TRADER.into()),
+				AUSD,
+				KSM,
+				10 * UNITS,
+				sell_quote.amount_out,
+				executor_route(sell_quote.route)
+			));
+
+			assert_trader_non_native_balance!(sell_quote.amount_out, KSM);
+		});
+	}
+
+	#[test]
+	fn find_best_sell_route_should_return_route_through_weighted_pool() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(BSX, AUSD);
+			create_weighted_pool(AUSD, KSM);
+
+			let amount_to_sell = 10 * UNITS;
+
+			//Act
+			let quote = RouteFinder::<Runtime>::find_best_sell_route(BSX, KSM, amount_to_sell).unwrap();
+
+			//Assert
+			assert_eq!(
+				quote.route,
+				vec![
+					Trade {
+						pool: PoolType::XYK,
+						asset_in: BSX,
+						asset_out: AUSD,
+					},
+					Trade {
+						pool: PoolType::Stableswap(weighted_pool_share_token(AUSD, KSM)),
+						asset_in: AUSD,
+						asset_out: KSM,
+					},
+				]
+			);
+
+			assert_ok!(Router::sell(
+				Origin::signed(TRADER.into()),
+				BSX,
+				KSM,
+				amount_to_sell,
+				quote.amount_out,
+				executor_route(quote.route)
+			));
+
+			assert_trader_non_native_balance!(quote.amount_out, KSM);
+		});
+	}
+
+	#[test]
+	fn find_best_route_should_skip_lbp_pool_when_sale_is_not_running() {
+		TestNet::reset();
+
+		Basilisk::execute_with(|| {
+			//Arrange
+			create_xyk_pool(AUSD, BSX);
+			create_lbp_pool(BSX, NEW_BOOTSTRAPPED_TOKEN);
+
+			//Act & Assert
+			assert_eq!(
+				RouteFinder::<Runtime>::find_best_sell_route(AUSD, NEW_BOOTSTRAPPED_TOKEN, 10 * UNITS),
+				None
+			);
+			assert_eq!(
+				RouteFinder::<Runtime>::find_best_buy_route(AUSD, NEW_BOOTSTRAPPED_TOKEN, 1 * UNITS),
+				None
+			);
+		});
+	}
+}
+
 fn create_xyk_pool(asset_a: u32, asset_b: u32) {
 	assert_ok!(XYK::create_pool(
 		Origin::signed(ALICE.into()),
@@ -1330,6 +1763,23 @@ fn create_xyk_pool(asset_a: u32, asset_b: u32) {
 	));
 }
 
+fn create_weighted_pool(asset_a: u32, asset_b: u32) {
+	assert_ok!(basilisk_runtime::WeightedPool::create_pool(
+		Origin::signed(ALICE.into()),
+		asset_a,
+		100 * UNITS,
+		asset_b,
+		50 * UNITS,
+		80_000_000,
+		(3, 1_000),
+	));
+}
+
+fn weighted_pool_share_token(asset_a: AssetId, asset_b: AssetId) -> AssetId {
+	let pool_account = basilisk_runtime::WeightedPool::pair_account_from_assets(asset_a, asset_b);
+	basilisk_runtime::WeightedPool::pools(pool_account).unwrap().share_token
+}
+
 fn create_lbp_pool(accumulated_asset: u32, distributed_asset: u32) {
 	assert_ok!(LBP::create_pool(
 		Origin::root(),
@@ -1344,6 +1794,7 @@ fn create_lbp_pool(accumulated_asset: u32, distributed_asset: u32) {
 		(2, 1_000),
 		CHARLIE.into(),
 		0,
+		None,
 	));
 
 	let account_id = get_lbp_pair_account_id(accumulated_asset, distributed_asset);
@@ -1359,6 +1810,7 @@ fn create_lbp_pool(accumulated_asset: u32, distributed_asset: u32) {
 		None,
 		None,
 		None,
+		None,
 	));
 }
 
@@ -2,12 +2,12 @@
 
 use crate::kusama_test_net::*;
 
-use basilisk_runtime::{DustRemovalWhitelist, Origin, XYK};
+use basilisk_runtime::{Call, DustRemovalWhitelist, Origin, TransactionPause, XYK};
 use hydradx_traits::AMM;
 use primitives::{asset::AssetPair, AssetId};
 use xcm_emulator::TestExt;
 
-use frame_support::{assert_ok, traits::Contains};
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable, traits::Contains};
 
 fn pair_account(asset_a: AssetId, asset_b: AssetId) -> AccountId {
 	let asset_pair = AssetPair {
@@ -103,3 +103,89 @@ fn pool_should_be_created_when_it_was_destroyed_previously() {
 		));
 	});
 }
+
+#[test]
+fn flash_swap_should_be_filtered_when_paused() {
+	TestNet::reset();
+
+	Basilisk::execute_with(|| {
+		//arrange
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE.into()),
+			AUSD,
+			100 * UNITS,
+			MOVR,
+			100 * UNITS,
+		));
+
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::root(),
+			b"XYK".to_vec(),
+			b"flash_swap".to_vec()
+		));
+
+		let call = Call::XYK(pallet_xyk::Call::flash_swap {
+			asset_out: MOVR,
+			asset_in: AUSD,
+			amount: UNITS,
+			max_limit: 10 * UNITS,
+			callback: Box::new(Call::System(frame_system::Call::remark { remark: vec![] })),
+		});
+
+		//act & assert
+		assert_noop!(
+			call.dispatch(Origin::signed(ALICE.into())).map_err(|e| e.error),
+			frame_system::Error::<basilisk_runtime::Runtime>::CallFiltered
+		);
+	});
+}
+
+#[test]
+fn flash_swap_callback_should_be_filtered_when_paused() {
+	TestNet::reset();
+
+	Basilisk::execute_with(|| {
+		//arrange
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE.into()),
+			AUSD,
+			100 * UNITS,
+			MOVR,
+			100 * UNITS,
+		));
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE.into()),
+			KSM,
+			100 * UNITS,
+			MOVR,
+			100 * UNITS,
+		));
+
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::root(),
+			b"XYK".to_vec(),
+			b"sell".to_vec()
+		));
+
+		let callback = Call::XYK(pallet_xyk::Call::sell {
+			asset_in: MOVR,
+			asset_out: KSM,
+			amount: UNITS,
+			max_limit: 0,
+			discount: false,
+		});
+
+		//act & assert
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(ALICE.into()),
+				MOVR,
+				AUSD,
+				UNITS,
+				10 * UNITS,
+				Box::new(callback)
+			),
+			frame_system::Error::<basilisk_runtime::Runtime>::CallFiltered
+		);
+	});
+}
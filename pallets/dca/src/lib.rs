@@ -0,0 +1,647 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # DCA pallet
+//!
+//! Dollar-cost averaging: an account schedules a trade of an equal amount which is executed once per period,
+//! until its budget is spent.
+//!
+//! A schedule sells `amount` of `asset_in` (`SELL`) or buys `amount` of `asset_out` (`BUY`) in every period.
+//! The whole budget, in `asset_in`, is reserved when the schedule is created. Every trade is executed through
+//! `pallet_route_executor` along the route of the schedule, so a route can span multiple XYK and LBP pools.
+//! The limit of every trade is derived from the reference price of the route given by `PriceProvider` and
+//! the slippage of the schedule. The reference price does not depend on the current reserves of the pools, which can
+//! be moved right before the trade in the same block.
+//!
+//! Schedules are executed in `on_initialize` of the block they are planned for. A schedule is paused automatically
+//! when `MaxConsecutiveFailures` of its trades fail in a row. The owner can pause, resume or terminate a schedule;
+//! terminating unreserves the rest of the budget.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::too_many_arguments)]
+
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional, weights::Weight};
+use frame_system::{ensure_signed, RawOrigin};
+use hydradx_traits::pools::SpotPriceProvider;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use pallet_route_executor::weights::WeightInfo as RouterWeightInfo;
+use pallet_route_executor::Trade;
+use primitives::{AssetId, Balance, IntentionType, Price};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedMul, One, Saturating, Zero},
+	DispatchError, FixedPointNumber, PerThing, Permill, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// Identifier of a schedule.
+pub type ScheduleId = u32;
+
+/// Number of consecutive blocks tried when a planned block is full.
+const MAX_PLANNING_ATTEMPTS: u32 = 10;
+
+#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, PartialEq, Eq, TypeInfo)]
+pub enum ScheduleStatus {
+	/// Trades of the schedule are executed.
+	Active,
+	/// Schedule was paused by its owner or after repeated failures.
+	Paused,
+}
+
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub struct Schedule<AccountId, BlockNumber> {
+	pub owner: AccountId,
+	pub asset_in: AssetId,
+	pub asset_out: AssetId,
+	pub order_type: IntentionType,
+	/// Amount of `asset_in` sold (`SELL`) or amount of `asset_out` bought (`BUY`) in every period.
+	pub amount: Balance,
+	pub period: BlockNumber,
+	/// Reserved amount of `asset_in` which was not spent yet.
+	pub remaining_budget: Balance,
+	/// Max difference between the amount at the reference price and the limit of a trade.
+	pub slippage: Permill,
+	pub route: Vec<Trade<AssetId>>,
+	pub status: ScheduleStatus,
+	/// Number of consecutive failed trades.
+	pub failures: u8,
+	/// Block in which the next trade is executed. `None` if the schedule is paused.
+	pub next_execution_block: Option<BlockNumber>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(crate) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + pallet_route_executor::Config<AssetId = AssetId, Balance = Balance>
+	{
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Multi currency used to reserve budgets of schedules.
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = AssetId, Balance = Balance>;
+
+		/// Maximum number of schedules executed in a block.
+		#[pallet::constant]
+		type MaxSchedulesPerBlock: Get<u32>;
+
+		/// Number of consecutive failed trades after which a schedule is paused.
+		#[pallet::constant]
+		type MaxConsecutiveFailures: Get<u8>;
+
+		/// Reference price of the assets of a trade, e.g. a price oracle.
+		/// Must not depend on the current reserves of the pools.
+		type PriceProvider: SpotPriceProvider<AssetId, Price = Price>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// It is not allowed to trade an asset for itself.
+		CannotTradeSameAsset,
+
+		/// Amount per period is zero.
+		ZeroAmount,
+
+		/// Period is zero.
+		ZeroPeriod,
+
+		/// Budget does not cover the amount sold in a period.
+		BudgetTooLow,
+
+		/// Route is empty, too long or does not lead from asset in to asset out.
+		InvalidRoute,
+
+		/// Execution block is not in the future.
+		BlockNumberInPast,
+
+		/// No block with a free slot was found around the requested block.
+		NoFreeBlockFound,
+
+		/// Schedule does not exist.
+		ScheduleNotFound,
+
+		/// Account is not the owner of the schedule.
+		NotScheduleOwner,
+
+		/// Schedule is not active.
+		ScheduleNotActive,
+
+		/// Schedule is not paused.
+		ScheduleNotPaused,
+
+		/// Reference price of a trade in the route is not available.
+		PriceNotAvailable,
+
+		/// Overflow
+		Overflow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Schedule was created and its budget reserved.
+		Scheduled {
+			id: ScheduleId,
+			who: T::AccountId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			order_type: IntentionType,
+			amount: Balance,
+			period: T::BlockNumber,
+			total_budget: Balance,
+		},
+
+		/// Next trade of the schedule was planned for `block`.
+		ExecutionPlanned {
+			id: ScheduleId,
+			who: T::AccountId,
+			block: T::BlockNumber,
+		},
+
+		/// Trade of the schedule was executed.
+		TradeExecuted {
+			id: ScheduleId,
+			who: T::AccountId,
+			amount_in: Balance,
+			amount_out: Balance,
+		},
+
+		/// Trade of the schedule failed.
+		TradeFailed {
+			id: ScheduleId,
+			who: T::AccountId,
+			error: DispatchError,
+		},
+
+		/// Schedule was paused by its owner or after repeated failures.
+		Paused { id: ScheduleId, who: T::AccountId },
+
+		/// Schedule was resumed by its owner.
+		Resumed { id: ScheduleId, who: T::AccountId },
+
+		/// Schedule was terminated by its owner and the rest of its budget unreserved.
+		Terminated { id: ScheduleId, who: T::AccountId },
+
+		/// Budget of the schedule was spent. Remaining reserved amount was unreserved.
+		Completed { id: ScheduleId, who: T::AccountId },
+	}
+
+	/// Identifier of the next schedule.
+	#[pallet::storage]
+	#[pallet::getter(fn next_schedule_id)]
+	pub(crate) type NextScheduleId<T: Config> = StorageValue<_, ScheduleId, ValueQuery>;
+
+	/// Schedules.
+	#[pallet::storage]
+	#[pallet::getter(fn schedules)]
+	pub(crate) type Schedules<T: Config> =
+		StorageMap<_, Twox64Concat, ScheduleId, Schedule<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// Schedules executed in a block.
+	#[pallet::storage]
+	#[pallet::getter(fn schedule_ids_per_block)]
+	pub(crate) type ScheduleIdsPerBlock<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<ScheduleId, T::MaxSchedulesPerBlock>, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let schedule_ids = ScheduleIdsPerBlock::<T>::take(n);
+
+			for id in schedule_ids.iter() {
+				Self::process_schedule(*id, n);
+			}
+
+			// `on_initialize` weight does not include the trades, they are weighed by the route executor weights.
+			let count = schedule_ids.len() as u32;
+			<T as Config>::WeightInfo::on_initialize(count)
+				.saturating_add(Self::max_trade_weight().saturating_mul(count as u64))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a schedule and reserve its budget.
+		///
+		/// Every `period` blocks, `amount` of `asset_in` is sold (`SELL`) or `amount` of `asset_out` is bought (`BUY`)
+		/// along `route`, until `total_budget` of `asset_in` is spent.
+		/// The first trade is executed in `start_execution_block`, or in the next block if not specified.
+		///
+		/// Emits `Scheduled` and `ExecutionPlanned` events when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::schedule())]
+		#[transactional]
+		pub fn schedule(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			order_type: IntentionType,
+			amount: Balance,
+			period: T::BlockNumber,
+			total_budget: Balance,
+			slippage: Permill,
+			route: Vec<Trade<AssetId>>,
+			start_execution_block: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(asset_in != asset_out, Error::<T>::CannotTradeSameAsset);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+			match order_type {
+				IntentionType::SELL => ensure!(total_budget >= amount, Error::<T>::BudgetTooLow),
+				IntentionType::BUY => ensure!(!total_budget.is_zero(), Error::<T>::BudgetTooLow),
+			}
+			Self::validate_route(asset_in, asset_out, &route)?;
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let start_block = start_execution_block.unwrap_or_else(|| current_block.saturating_add(One::one()));
+			ensure!(start_block > current_block, Error::<T>::BlockNumberInPast);
+
+			<T as Config>::Currency::reserve(asset_in, &who, total_budget)?;
+
+			let id = Self::next_schedule_id();
+			NextScheduleId::<T>::put(id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+
+			let block = Self::plan_execution(id, start_block)?;
+
+			Schedules::<T>::insert(
+				id,
+				Schedule {
+					owner: who.clone(),
+					asset_in,
+					asset_out,
+					order_type,
+					amount,
+					period,
+					remaining_budget: total_budget,
+					slippage,
+					route,
+					status: ScheduleStatus::Active,
+					failures: 0,
+					next_execution_block: Some(block),
+				},
+			);
+
+			Self::deposit_event(Event::Scheduled {
+				id,
+				who: who.clone(),
+				asset_in,
+				asset_out,
+				order_type,
+				amount,
+				period,
+				total_budget,
+			});
+			Self::deposit_event(Event::ExecutionPlanned { id, who, block });
+
+			Ok(())
+		}
+
+		/// Pause an active schedule.
+		///
+		/// Can only be called by the owner of the schedule. Budget of the schedule stays reserved.
+		///
+		/// Emits `Paused` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, id: ScheduleId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Schedules::<T>::try_mutate(id, |maybe_schedule| -> DispatchResult {
+				let schedule = maybe_schedule.as_mut().ok_or(Error::<T>::ScheduleNotFound)?;
+				ensure!(schedule.owner == who, Error::<T>::NotScheduleOwner);
+				ensure!(schedule.status == ScheduleStatus::Active, Error::<T>::ScheduleNotActive);
+
+				Self::unplan_execution(id, schedule);
+				schedule.status = ScheduleStatus::Paused;
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Paused { id, who });
+
+			Ok(())
+		}
+
+		/// Resume a paused schedule.
+		///
+		/// Can only be called by the owner of the schedule. Next trade is executed in `next_execution_block`,
+		/// or in the next block if not specified.
+		///
+		/// Emits `Resumed` and `ExecutionPlanned` events when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::resume())]
+		pub fn resume(
+			origin: OriginFor<T>,
+			id: ScheduleId,
+			next_execution_block: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let next_block = next_execution_block.unwrap_or_else(|| current_block.saturating_add(One::one()));
+			ensure!(next_block > current_block, Error::<T>::BlockNumberInPast);
+
+			let block = Schedules::<T>::try_mutate(id, |maybe_schedule| -> Result<T::BlockNumber, DispatchError> {
+				let schedule = maybe_schedule.as_mut().ok_or(Error::<T>::ScheduleNotFound)?;
+				ensure!(schedule.owner == who, Error::<T>::NotScheduleOwner);
+				ensure!(schedule.status == ScheduleStatus::Paused, Error::<T>::ScheduleNotPaused);
+
+				let block = Self::plan_execution(id, next_block)?;
+				schedule.status = ScheduleStatus::Active;
+				schedule.failures = 0;
+				schedule.next_execution_block = Some(block);
+
+				Ok(block)
+			})?;
+
+			Self::deposit_event(Event::Resumed { id, who: who.clone() });
+			Self::deposit_event(Event::ExecutionPlanned { id, who, block });
+
+			Ok(())
+		}
+
+		/// Terminate a schedule and unreserve the rest of its budget.
+		///
+		/// Can only be called by the owner of the schedule.
+		///
+		/// Emits `Terminated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::terminate())]
+		pub fn terminate(origin: OriginFor<T>, id: ScheduleId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut schedule = Self::schedules(id).ok_or(Error::<T>::ScheduleNotFound)?;
+			ensure!(schedule.owner == who, Error::<T>::NotScheduleOwner);
+
+			Self::unplan_execution(id, &mut schedule);
+			Self::close_schedule(id, &schedule);
+
+			Self::deposit_event(Event::Terminated { id, who });
+
+			Ok(())
+		}
+	}
+}
+
+/// Result of a trade of a schedule.
+enum TradeResult {
+	Executed { amount_in: Balance, amount_out: Balance },
+	BudgetExhausted,
+}
+
+impl<T: Config> Pallet<T> {
+	fn validate_route(asset_in: AssetId, asset_out: AssetId, route: &[Trade<AssetId>]) -> DispatchResult {
+		ensure!(
+			!route.is_empty() && route.len() <= <T as pallet_route_executor::Config>::MaxNumberOfTrades::get() as usize,
+			Error::<T>::InvalidRoute
+		);
+
+		let mut expected_asset_in = asset_in;
+		for trade in route.iter() {
+			ensure!(trade.asset_in == expected_asset_in, Error::<T>::InvalidRoute);
+			expected_asset_in = trade.asset_out;
+		}
+		ensure!(expected_asset_in == asset_out, Error::<T>::InvalidRoute);
+
+		Ok(())
+	}
+
+	/// Add the schedule to the first block, starting at `block`, which is not full.
+	fn plan_execution(id: ScheduleId, block: T::BlockNumber) -> Result<T::BlockNumber, DispatchError> {
+		let mut block = block;
+		for _ in 0..MAX_PLANNING_ATTEMPTS {
+			if ScheduleIdsPerBlock::<T>::try_mutate(block, |ids| ids.try_push(id)).is_ok() {
+				return Ok(block);
+			}
+			block = block.saturating_add(One::one());
+		}
+
+		Err(Error::<T>::NoFreeBlockFound.into())
+	}
+
+	fn unplan_execution(id: ScheduleId, schedule: &mut Schedule<T::AccountId, T::BlockNumber>) {
+		if let Some(block) = schedule.next_execution_block.take() {
+			ScheduleIdsPerBlock::<T>::mutate(block, |ids| ids.retain(|planned_id| *planned_id != id));
+		}
+	}
+
+	/// Remove the schedule and unreserve the rest of its budget.
+	fn close_schedule(id: ScheduleId, schedule: &Schedule<T::AccountId, T::BlockNumber>) {
+		<T as Config>::Currency::unreserve(schedule.asset_in, &schedule.owner, schedule.remaining_budget);
+		Schedules::<T>::remove(id);
+	}
+
+	fn process_schedule(id: ScheduleId, block: T::BlockNumber) {
+		let mut schedule = match Self::schedules(id) {
+			Some(schedule) => schedule,
+			None => return,
+		};
+		schedule.next_execution_block = None;
+
+		match Self::execute_trade(&schedule) {
+			Ok(TradeResult::Executed { amount_in, amount_out }) => {
+				schedule.remaining_budget = schedule.remaining_budget.saturating_sub(amount_in);
+				schedule.failures = 0;
+
+				Self::deposit_event(Event::TradeExecuted {
+					id,
+					who: schedule.owner.clone(),
+					amount_in,
+					amount_out,
+				});
+			}
+			Ok(TradeResult::BudgetExhausted) => {
+				Self::complete_schedule(id, schedule);
+				return;
+			}
+			Err(error) => {
+				schedule.failures = schedule.failures.saturating_add(1);
+
+				Self::deposit_event(Event::TradeFailed {
+					id,
+					who: schedule.owner.clone(),
+					error,
+				});
+
+				if schedule.failures >= T::MaxConsecutiveFailures::get() {
+					Self::pause_schedule(id, schedule);
+					return;
+				}
+			}
+		}
+
+		if schedule.remaining_budget.is_zero() {
+			Self::complete_schedule(id, schedule);
+			return;
+		}
+
+		match Self::plan_execution(id, block.saturating_add(schedule.period)) {
+			Ok(next_block) => {
+				schedule.next_execution_block = Some(next_block);
+				Schedules::<T>::insert(id, schedule.clone());
+				Self::deposit_event(Event::ExecutionPlanned {
+					id,
+					who: schedule.owner,
+					block: next_block,
+				});
+			}
+			Err(_) => Self::pause_schedule(id, schedule),
+		}
+	}
+
+	fn complete_schedule(id: ScheduleId, schedule: Schedule<T::AccountId, T::BlockNumber>) {
+		Self::close_schedule(id, &schedule);
+		Self::deposit_event(Event::Completed {
+			id,
+			who: schedule.owner,
+		});
+	}
+
+	fn pause_schedule(id: ScheduleId, mut schedule: Schedule<T::AccountId, T::BlockNumber>) {
+		schedule.status = ScheduleStatus::Paused;
+		Schedules::<T>::insert(id, schedule.clone());
+		Self::deposit_event(Event::Paused {
+			id,
+			who: schedule.owner,
+		});
+	}
+
+	/// Execute one trade of the schedule through the route executor.
+	#[transactional]
+	fn execute_trade(schedule: &Schedule<T::AccountId, T::BlockNumber>) -> Result<TradeResult, DispatchError> {
+		let origin: <T as frame_system::Config>::Origin = RawOrigin::Signed(schedule.owner.clone()).into();
+
+		match schedule.order_type {
+			IntentionType::SELL => {
+				let amount_in = schedule.amount.min(schedule.remaining_budget);
+				let amount_out = Self::reference_price(&schedule.route, IntentionType::SELL)?
+					.checked_mul_int(amount_in)
+					.ok_or(Error::<T>::Overflow)?;
+				let min_amount_out = schedule.slippage.left_from_one().mul_floor(amount_out);
+
+				<T as Config>::Currency::unreserve(schedule.asset_in, &schedule.owner, amount_in);
+
+				let balance_before = <T as Config>::Currency::free_balance(schedule.asset_out, &schedule.owner);
+				pallet_route_executor::Pallet::<T>::sell(
+					origin,
+					schedule.asset_in,
+					schedule.asset_out,
+					amount_in,
+					min_amount_out,
+					schedule.route.clone(),
+				)?;
+				let received = <T as Config>::Currency::free_balance(schedule.asset_out, &schedule.owner)
+					.saturating_sub(balance_before);
+
+				Ok(TradeResult::Executed {
+					amount_in,
+					amount_out: received,
+				})
+			}
+			IntentionType::BUY => {
+				let amount_in = Self::reference_price(&schedule.route, IntentionType::BUY)?
+					.checked_mul_int(schedule.amount)
+					.ok_or(Error::<T>::Overflow)?;
+				if amount_in > schedule.remaining_budget {
+					return Ok(TradeResult::BudgetExhausted);
+				}
+				let max_amount_in = amount_in
+					.saturating_add(schedule.slippage.mul_ceil(amount_in))
+					.min(schedule.remaining_budget);
+
+				<T as Config>::Currency::unreserve(schedule.asset_in, &schedule.owner, max_amount_in);
+
+				let balance_before = <T as Config>::Currency::free_balance(schedule.asset_in, &schedule.owner);
+				pallet_route_executor::Pallet::<T>::buy(
+					origin,
+					schedule.asset_in,
+					schedule.asset_out,
+					schedule.amount,
+					max_amount_in,
+					schedule.route.clone(),
+				)?;
+				let paid = balance_before.saturating_sub(<T as Config>::Currency::free_balance(
+					schedule.asset_in,
+					&schedule.owner,
+				));
+
+				<T as Config>::Currency::reserve(
+					schedule.asset_in,
+					&schedule.owner,
+					max_amount_in.saturating_sub(paid),
+				)?;
+
+				Ok(TradeResult::Executed {
+					amount_in: paid,
+					amount_out: schedule.amount,
+				})
+			}
+		}
+	}
+
+	/// Price of the route at the reference prices of its trades.
+	///
+	/// `SELL` returns the amount of the last asset out received for one unit of the first asset in,
+	/// `BUY` returns the amount of the first asset in paid for one unit of the last asset out.
+	fn reference_price(route: &[Trade<AssetId>], order_type: IntentionType) -> Result<Price, DispatchError> {
+		route.iter().try_fold(Price::one(), |price, trade| {
+			let trade_price = match order_type {
+				IntentionType::SELL => T::PriceProvider::spot_price(trade.asset_in, trade.asset_out),
+				IntentionType::BUY => T::PriceProvider::spot_price(trade.asset_out, trade.asset_in),
+			}
+			.ok_or(Error::<T>::PriceNotAvailable)?;
+
+			price
+				.checked_mul(&trade_price)
+				.ok_or_else(|| Error::<T>::Overflow.into())
+		})
+	}
+
+	/// Weight of the most expensive trade of a schedule, including the reads of the reference prices.
+	fn max_trade_weight() -> Weight {
+		let max_trades = <T as pallet_route_executor::Config>::MaxNumberOfTrades::get() as u32;
+		let sell_weight = <T as pallet_route_executor::Config>::WeightInfo::sell(max_trades);
+		let buy_weight = <T as pallet_route_executor::Config>::WeightInfo::buy(max_trades);
+		sell_weight
+			.max(buy_weight)
+			.saturating_add(T::DbWeight::get().reads(max_trades as u64))
+	}
+}
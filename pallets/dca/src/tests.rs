@@ -0,0 +1,471 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::mock::{
+	expect_events, new_test_ext, run_to_block, Currency, Origin, System, Test, ACA, ALICE, BOB, DCA, DOT, HDX, ONE, XYK,
+};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::router::PoolType;
+
+fn hdx_dot_route() -> Vec<Trade<AssetId>> {
+	vec![Trade {
+		pool: PoolType::XYK,
+		asset_in: HDX,
+		asset_out: DOT,
+	}]
+}
+
+fn hdx_aca_route() -> Vec<Trade<AssetId>> {
+	vec![
+		Trade {
+			pool: PoolType::XYK,
+			asset_in: HDX,
+			asset_out: DOT,
+		},
+		Trade {
+			pool: PoolType::XYK,
+			asset_in: DOT,
+			asset_out: ACA,
+		},
+	]
+}
+
+fn schedule_sell(amount: Balance, period: u64, total_budget: Balance) {
+	assert_ok!(DCA::schedule(
+		Origin::signed(BOB),
+		HDX,
+		DOT,
+		IntentionType::SELL,
+		amount,
+		period,
+		total_budget,
+		Permill::from_percent(5),
+		hdx_dot_route(),
+		None,
+	));
+}
+
+#[test]
+fn schedule_should_reserve_budget_and_plan_execution() {
+	new_test_ext().execute_with(|| {
+		schedule_sell(100 * ONE, 10, 1_000 * ONE);
+
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 1_000 * ONE);
+		assert_eq!(DCA::next_schedule_id(), 1);
+		assert_eq!(DCA::schedule_ids_per_block(2).into_inner(), vec![0]);
+
+		let schedule = DCA::schedules(0).unwrap();
+		assert_eq!(schedule.owner, BOB);
+		assert_eq!(schedule.remaining_budget, 1_000 * ONE);
+		assert_eq!(schedule.status, ScheduleStatus::Active);
+		assert_eq!(schedule.next_execution_block, Some(2));
+
+		expect_events(vec![
+			Event::Scheduled {
+				id: 0,
+				who: BOB,
+				asset_in: HDX,
+				asset_out: DOT,
+				order_type: IntentionType::SELL,
+				amount: 100 * ONE,
+				period: 10,
+				total_budget: 1_000 * ONE,
+			}
+			.into(),
+			Event::ExecutionPlanned {
+				id: 0,
+				who: BOB,
+				block: 2,
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn schedule_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		let slippage = Permill::from_percent(5);
+		let sell = IntentionType::SELL;
+
+		assert_noop!(
+			DCA::schedule(
+				Origin::signed(BOB),
+				HDX,
+				HDX,
+				sell,
+				ONE,
+				10,
+				10 * ONE,
+				slippage,
+				hdx_dot_route(),
+				None
+			),
+			Error::<Test>::CannotTradeSameAsset
+		);
+		assert_noop!(
+			DCA::schedule(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				sell,
+				0,
+				10,
+				10 * ONE,
+				slippage,
+				hdx_dot_route(),
+				None
+			),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			DCA::schedule(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				sell,
+				ONE,
+				0,
+				10 * ONE,
+				slippage,
+				hdx_dot_route(),
+				None
+			),
+			Error::<Test>::ZeroPeriod
+		);
+		assert_noop!(
+			DCA::schedule(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				sell,
+				ONE,
+				10,
+				ONE - 1,
+				slippage,
+				hdx_dot_route(),
+				None
+			),
+			Error::<Test>::BudgetTooLow
+		);
+		assert_noop!(
+			DCA::schedule(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				sell,
+				ONE,
+				10,
+				10 * ONE,
+				slippage,
+				vec![],
+				None
+			),
+			Error::<Test>::InvalidRoute
+		);
+		assert_noop!(
+			DCA::schedule(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				sell,
+				ONE,
+				10,
+				10 * ONE,
+				slippage,
+				hdx_aca_route(),
+				None
+			),
+			Error::<Test>::InvalidRoute
+		);
+		assert_noop!(
+			DCA::schedule(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				sell,
+				ONE,
+				10,
+				10 * ONE,
+				slippage,
+				hdx_dot_route(),
+				Some(1)
+			),
+			Error::<Test>::BlockNumberInPast
+		);
+	});
+}
+
+#[test]
+fn schedule_should_be_planned_in_next_block_when_block_is_full() {
+	new_test_ext().execute_with(|| {
+		schedule_sell(ONE, 10, 10 * ONE);
+		schedule_sell(ONE, 10, 10 * ONE);
+		schedule_sell(ONE, 10, 10 * ONE);
+
+		assert_eq!(DCA::schedule_ids_per_block(2).into_inner(), vec![0, 1]);
+		assert_eq!(DCA::schedule_ids_per_block(3).into_inner(), vec![2]);
+		assert_eq!(DCA::schedules(2).unwrap().next_execution_block, Some(3));
+	});
+}
+
+#[test]
+fn sell_schedule_should_be_executed_every_period() {
+	new_test_ext().execute_with(|| {
+		schedule_sell(100 * ONE, 10, 1_000 * ONE);
+
+		let dot_balance = Currency::free_balance(DOT, &BOB);
+
+		run_to_block(2);
+
+		let received = Currency::free_balance(DOT, &BOB) - dot_balance;
+		assert!(received > 0);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 900 * ONE);
+
+		let schedule = DCA::schedules(0).unwrap();
+		assert_eq!(schedule.remaining_budget, 900 * ONE);
+		assert_eq!(schedule.next_execution_block, Some(12));
+
+		expect_events(vec![
+			Event::TradeExecuted {
+				id: 0,
+				who: BOB,
+				amount_in: 100 * ONE,
+				amount_out: received,
+			}
+			.into(),
+			Event::ExecutionPlanned {
+				id: 0,
+				who: BOB,
+				block: 12,
+			}
+			.into(),
+		]);
+
+		run_to_block(11);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 900 * ONE);
+
+		run_to_block(12);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 800 * ONE);
+	});
+}
+
+#[test]
+fn sell_schedule_should_complete_when_budget_is_spent() {
+	new_test_ext().execute_with(|| {
+		schedule_sell(100 * ONE, 5, 150 * ONE);
+
+		run_to_block(7);
+
+		// the last trade sells the rest of the budget
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 0);
+		assert_eq!(Currency::free_balance(HDX, &BOB), 1_000_000 * ONE - 150 * ONE);
+		assert!(DCA::schedules(0).is_none());
+
+		expect_events(vec![Event::Completed { id: 0, who: BOB }.into()]);
+	});
+}
+
+#[test]
+fn buy_schedule_should_be_executed_along_multi_hop_route() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DCA::schedule(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			IntentionType::BUY,
+			10 * ONE,
+			5,
+			12 * ONE,
+			Permill::from_percent(5),
+			hdx_aca_route(),
+			None,
+		));
+
+		run_to_block(2);
+
+		assert_eq!(Currency::free_balance(ACA, &BOB), 10 * ONE);
+		let remaining_budget = DCA::schedules(0).unwrap().remaining_budget;
+		assert!(remaining_budget < 7 * ONE);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), remaining_budget);
+		assert_eq!(Currency::free_balance(HDX, &BOB), 1_000_000 * ONE - 12 * ONE);
+
+		run_to_block(7);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 20 * ONE);
+
+		// remaining budget does not cover the next trade
+		run_to_block(12);
+		assert_eq!(Currency::free_balance(ACA, &BOB), 20 * ONE);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 0);
+		assert!(DCA::schedules(0).is_none());
+
+		expect_events(vec![Event::Completed { id: 0, who: BOB }.into()]);
+	});
+}
+
+#[test]
+fn sell_should_fail_when_pool_is_moved_away_from_reference_price() {
+	new_test_ext().execute_with(|| {
+		schedule_sell(100 * ONE, 10, 1_000 * ONE);
+
+		// pool price moves far below the reference price of 2 DOT for 1 HDX right before the trade
+		assert_ok!(XYK::sell(Origin::signed(ALICE), HDX, DOT, 50_000 * ONE, ONE, false));
+
+		let dot_balance = Currency::free_balance(DOT, &BOB);
+
+		run_to_block(2);
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), dot_balance);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 1_000 * ONE);
+
+		let schedule = DCA::schedules(0).unwrap();
+		assert_eq!(schedule.remaining_budget, 1_000 * ONE);
+		assert_eq!(schedule.failures, 1);
+		assert_eq!(schedule.next_execution_block, Some(12));
+
+		assert!(System::events()
+			.iter()
+			.any(|record| matches!(record.event, crate::mock::Event::DCA(Event::TradeFailed { id: 0, .. }))));
+	});
+}
+
+#[test]
+fn trade_should_fail_when_reference_price_is_not_available() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DCA::schedule(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			IntentionType::SELL,
+			100 * ONE,
+			10,
+			1_000 * ONE,
+			Permill::from_percent(5),
+			vec![Trade {
+				pool: PoolType::XYK,
+				asset_in: HDX,
+				asset_out: ACA,
+			}],
+			None,
+		));
+
+		run_to_block(2);
+
+		assert_eq!(DCA::schedules(0).unwrap().failures, 1);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 1_000 * ONE);
+
+		expect_events(vec![
+			Event::TradeFailed {
+				id: 0,
+				who: BOB,
+				error: Error::<Test>::PriceNotAvailable.into(),
+			}
+			.into(),
+			Event::ExecutionPlanned {
+				id: 0,
+				who: BOB,
+				block: 12,
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn schedule_should_be_paused_after_repeated_failures() {
+	new_test_ext().execute_with(|| {
+		// amount is below the min trading limit of the pool, every trade fails
+		schedule_sell(500, 10, 5_000);
+
+		run_to_block(2);
+		run_to_block(12);
+
+		let schedule = DCA::schedules(0).unwrap();
+		assert_eq!(schedule.failures, 2);
+		assert_eq!(schedule.status, ScheduleStatus::Active);
+
+		run_to_block(22);
+
+		let schedule = DCA::schedules(0).unwrap();
+		assert_eq!(schedule.failures, 3);
+		assert_eq!(schedule.status, ScheduleStatus::Paused);
+		assert_eq!(schedule.next_execution_block, None);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 5_000);
+
+		expect_events(vec![Event::Paused { id: 0, who: BOB }.into()]);
+
+		run_to_block(32);
+		assert_eq!(DCA::schedules(0).unwrap().failures, 3);
+	});
+}
+
+#[test]
+fn pause_and_resume_should_work() {
+	new_test_ext().execute_with(|| {
+		schedule_sell(100 * ONE, 10, 1_000 * ONE);
+
+		assert_noop!(DCA::pause(Origin::signed(ALICE), 0), Error::<Test>::NotScheduleOwner);
+		assert_noop!(
+			DCA::resume(Origin::signed(BOB), 0, None),
+			Error::<Test>::ScheduleNotPaused
+		);
+
+		assert_ok!(DCA::pause(Origin::signed(BOB), 0));
+		assert!(DCA::schedule_ids_per_block(2).is_empty());
+		assert_noop!(DCA::pause(Origin::signed(BOB), 0), Error::<Test>::ScheduleNotActive);
+
+		run_to_block(2);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 1_000 * ONE);
+
+		assert_ok!(DCA::resume(Origin::signed(BOB), 0, Some(5)));
+		assert_eq!(DCA::schedules(0).unwrap().next_execution_block, Some(5));
+
+		run_to_block(5);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 900 * ONE);
+
+		expect_events(vec![
+			Event::Paused { id: 0, who: BOB }.into(),
+			Event::Resumed { id: 0, who: BOB }.into(),
+		]);
+	});
+}
+
+#[test]
+fn terminate_should_unreserve_remaining_budget() {
+	new_test_ext().execute_with(|| {
+		schedule_sell(100 * ONE, 10, 1_000 * ONE);
+
+		run_to_block(2);
+
+		assert_noop!(
+			DCA::terminate(Origin::signed(ALICE), 0),
+			Error::<Test>::NotScheduleOwner
+		);
+		assert_noop!(DCA::terminate(Origin::signed(BOB), 1), Error::<Test>::ScheduleNotFound);
+
+		assert_ok!(DCA::terminate(Origin::signed(BOB), 0));
+
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 0);
+		assert_eq!(Currency::free_balance(HDX, &BOB), 1_000_000 * ONE - 100 * ONE);
+		assert!(DCA::schedules(0).is_none());
+		assert!(DCA::schedule_ids_per_block(12).is_empty());
+		assert_eq!(System::block_number(), 2);
+
+		expect_events(vec![Event::Terminated { id: 0, who: BOB }.into()]);
+	});
+}
@@ -23,26 +23,34 @@
 
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::{
-	traits::{AtLeast32BitUnsigned, BlockNumberProvider, Saturating, Zero},
-	DispatchError, RuntimeDebug,
+	traits::{
+		AtLeast32BitUnsigned, BlakeTwo256, BlockNumberProvider, CheckedAdd, CheckedMul, Hash, One, Saturating, Zero,
+	},
+	DispatchError, FixedPointNumber, Permill, RuntimeDebug,
 };
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
-	traits::{EnsureOrigin, Get, LockIdentifier},
+	traits::{fungibles::Inspect, ConstU32, EnsureOrigin, Get, LockIdentifier},
 	transactional,
+	weights::Weight,
+	BoundedVec,
 };
 use frame_system::ensure_signed;
 use hydra_dx_math::types::LBPWeight;
-use hydradx_traits::{AMMTransfer, AssetPairAccountIdFor, CanCreatePool, LockedBalance, AMM};
+use hydradx_traits::{
+	pools::SpotPriceProvider, AMMTransfer, AssetPairAccountIdFor, CanCreatePool, LockedBalance, OnCreatePoolHandler,
+	OnTradeHandler, AMM,
+};
 use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency};
 use primitives::{
 	asset::AssetPair,
 	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO},
-	Amount, AssetId, Balance,
+	Amount, AssetId, Balance, Price,
 };
 
 use scale_info::TypeInfo;
+use sp_core::H256;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -56,6 +64,10 @@ mod tests;
 
 mod benchmarking;
 
+pub mod math;
+
+pub mod migration;
+
 #[allow(clippy::all)]
 pub mod weights;
 
@@ -68,10 +80,30 @@ pub use pallet::*;
 type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
 type PoolId<T> = <T as frame_system::Config>::AccountId;
 
+/// Max number of breakpoints of a piecewise linear weight curve
+pub const MAX_WEIGHT_CURVE_BREAKPOINTS: u32 = 10;
+
+/// Max rate of the exponential decay weight curve, corresponds to `k = 20`
+pub const MAX_EXPONENTIAL_DECAY_RATE: u32 = 20 * math::EXPONENTIAL_RATE_PRECISION;
+
+/// Breakpoints `(progress of the sale, weight of the asset_a)` of a piecewise linear weight curve
+pub type WeightCurveBreakpoints = BoundedVec<(Permill, LBPWeight), ConstU32<MAX_WEIGHT_CURVE_BREAKPOINTS>>;
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, PartialEq, Eq, TypeInfo)]
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
 pub enum WeightCurveType {
+	/// Weight changes linearly from the initial to the final weight.
 	Linear,
+	/// Weight changes quickly at the start of the sale and slows down towards the final weight,
+	/// following `1 - e^(-k * x)` where `k = rate / 1_000` and `x` is the elapsed part of the sale.
+	/// `rate` has to be within `1..=MAX_EXPONENTIAL_DECAY_RATE`.
+	ExponentialDecay { rate: u32 },
+	/// Sale is divided into `steps` intervals of equal length. The weight is constant within an interval
+	/// and equals the linear weight at the start of the interval. At least one step is required.
+	StepWise { steps: u32 },
+	/// Weight changes linearly between the initial weight, the breakpoints and the final weight.
+	/// Progress of the breakpoints has to be strictly increasing and within the sale.
+	Piecewise(WeightCurveBreakpoints),
 }
 
 impl Default for WeightCurveType {
@@ -86,9 +118,36 @@ pub const MAX_WEIGHT: LBPWeight = 100_000_000;
 /// Max sale duration is 14 days, assuming 6 sec blocks
 pub const MAX_SALE_DURATION: u32 = (60 * 60 * 24 / 6) * 14;
 
+/// Repay fee of pools created without explicit repay fee
+pub const DEFAULT_REPAY_FEE: (u32, u32) = (2, 10);
+
 /// Lock Identifier for the collected fees
 pub const COLLECTOR_LOCK_ID: LockIdentifier = *b"lbpcllct";
 
+/// Lock Identifier for the share tokens received for graduated liquidity
+pub const GRADUATION_LOCK_ID: LockIdentifier = *b"lbpgrdtn";
+
+/// Origin which paused a sale
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum PauseOrigin {
+	/// `SaleAdminOrigin`, the sale can be resumed only by `SaleAdminOrigin`
+	Admin,
+	/// pool owner, the sale can be resumed by the pool owner or `SaleAdminOrigin`
+	Owner,
+}
+
+/// Migration of the final reserves of a sale into a XYK pool of the same asset pair
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub struct Graduation<BlockNumber> {
+	/// part of the final reserves seeding the XYK pool, the rest is returned to the pool owner
+	pub liquidity: Permill,
+
+	/// share tokens of the XYK pool are locked in the owner's account until this block
+	pub lock_shares_until: Option<BlockNumber>,
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
 pub struct Pool<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
@@ -119,8 +178,14 @@ pub struct Pool<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
 	/// person that receives the fee
 	pub fee_collector: AccountId,
 
-	/// repayment target of the accumulated asset in fee collectors account, when this target is reached fee drops from 20% to fee
+	/// repayment target of the accumulated asset in fee collectors account, when this target is reached fee drops from repay fee to fee
 	pub repay_target: Balance,
+
+	/// fee charged until the repay target is reached
+	pub repay_fee: (u32, u32),
+
+	/// migration of the liquidity into a XYK pool when the sale ends
+	pub graduation: Option<Graduation<BlockNumber>>,
 }
 
 impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> Pool<AccountId, BlockNumber> {
@@ -134,6 +199,7 @@ impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> Pool<AccountId, BlockN
 		fee: (u32, u32),
 		fee_collector: AccountId,
 		repay_target: Balance,
+		repay_fee: (u32, u32),
 	) -> Self {
 		Pool {
 			owner: pool_owner,
@@ -146,13 +212,82 @@ impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> Pool<AccountId, BlockN
 			fee,
 			fee_collector,
 			repay_target,
+			repay_fee,
+			graduation: None,
 		}
 	}
 }
 
+/// Max number of accounts in an explicit allowlist of a sale
+pub const MAX_ALLOWLIST_LENGTH: u32 = 100;
+
+/// Max number of nodes of a Merkle proof of an allowlisted account
+pub const MAX_ALLOWLIST_PROOF_LENGTH: u32 = 32;
+
+/// Accounts allowed to trade in a pool
+pub type AllowedAccounts<AccountId> = BoundedVec<AccountId, ConstU32<MAX_ALLOWLIST_LENGTH>>;
+
+/// Merkle proof of an allowlisted account
+pub type AllowlistProof = BoundedVec<H256, ConstU32<MAX_ALLOWLIST_PROOF_LENGTH>>;
+
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub enum Allowlist<AccountId> {
+	/// Root of a Merkle tree whose leaves are `blake2_256` hashes of the encoded accounts. Nodes are hashes
+	/// of their sorted children. Accounts join the allowlist by proving their membership with `join_allowlist`.
+	MerkleRoot(H256),
+	/// Explicit set of the accounts.
+	Accounts(AllowedAccounts<AccountId>),
+}
+
+/// Limits of trades in a pool, amounts are denominated in the accumulated asset
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+pub struct SaleLimits<AccountId> {
+	/// max amount of the accumulated asset an account can spend in the sale, fees included
+	pub max_spend_per_account: Option<Balance>,
+
+	/// max amount of the accumulated asset spent by all trades of the pool in a block, fees included
+	pub max_trade_per_block: Option<Balance>,
+
+	/// only allowlisted accounts can trade in the pool
+	pub allowlist: Option<Allowlist<AccountId>>,
+}
+
+/// Creation of the pool which receives liquidity of a graduated LBP pool.
+pub trait GraduationHandler<AccountId> {
+	/// Create pool of `asset_a` and `asset_b` with liquidity provided by `who`.
+	///
+	/// Returns the share token of the new pool and the amount of shares received by `who`.
+	fn create_pool(
+		who: &AccountId,
+		asset_a: AssetId,
+		amount_a: Balance,
+		asset_b: AssetId,
+		amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError>;
+
+	/// Weight of `create_pool`.
+	fn create_pool_weight() -> Weight;
+}
+
+impl<AccountId> GraduationHandler<AccountId> for () {
+	fn create_pool(
+		_who: &AccountId,
+		_asset_a: AssetId,
+		_amount_a: Balance,
+		_asset_b: AssetId,
+		_amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError> {
+		Err(DispatchError::Other("Graduation is not supported"))
+	}
+
+	fn create_pool_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
 pub trait LBPWeightCalculation<BlockNumber: AtLeast32BitUnsigned> {
 	fn calculate_weight(
-		weight_curve: WeightCurveType,
+		weight_curve: &WeightCurveType,
 		start: BlockNumber,
 		end: BlockNumber,
 		initial_weight: LBPWeight,
@@ -164,14 +299,27 @@ pub trait LBPWeightCalculation<BlockNumber: AtLeast32BitUnsigned> {
 pub struct LBPWeightFunction;
 impl<BlockNumber: AtLeast32BitUnsigned> LBPWeightCalculation<BlockNumber> for LBPWeightFunction {
 	fn calculate_weight(
-		_weight_curve: WeightCurveType,
+		weight_curve: &WeightCurveType,
 		start: BlockNumber,
 		end: BlockNumber,
 		initial_weight: LBPWeight,
 		final_weight: LBPWeight,
 		at: BlockNumber,
 	) -> Option<LBPWeight> {
-		hydra_dx_math::lbp::calculate_linear_weights(start, end, initial_weight, final_weight, at).ok()
+		match weight_curve {
+			WeightCurveType::Linear => {
+				hydra_dx_math::lbp::calculate_linear_weights(start, end, initial_weight, final_weight, at).ok()
+			}
+			WeightCurveType::ExponentialDecay { rate } => {
+				math::calculate_exponential_weights(start, end, initial_weight, final_weight, *rate, at)
+			}
+			WeightCurveType::StepWise { steps } => {
+				math::calculate_step_weights(start, end, initial_weight, final_weight, *steps, at)
+			}
+			WeightCurveType::Piecewise(breakpoints) => {
+				math::calculate_piecewise_weights(start, end, initial_weight, final_weight, breakpoints, at)
+			}
+		}
 	}
 }
 
@@ -181,7 +329,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::OriginFor;
 
+	/// Current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -193,12 +345,18 @@ pub mod pallet {
 		type MultiCurrency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Amount = Amount, Balance = Balance>
 			+ MultiLockableCurrency<Self::AccountId>;
 
+		/// Balance inspection used to get the transferable balance sold by `sell_all`
+		type BalanceInspect: Inspect<Self::AccountId, AssetId = AssetId, Balance = Balance>;
+
 		/// Universal locked balance getter for tracking of fee collector balance
 		type LockedBalance: LockedBalance<AssetId, Self::AccountId, Balance>;
 
 		/// The origin which can create a new pool
 		type CreatePoolOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The origin which can pause, resume and cancel a sale of any pool
+		type SaleAdminOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Function for calculation of LBP weights
 		type LBPWeightFunction: LBPWeightCalculation<Self::BlockNumber>;
 
@@ -224,12 +382,90 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxOutRatio: Get<u128>;
 
+		/// Minimum repay fee rate which can be set for a pool
+		#[pallet::constant]
+		type MinRepayFee: Get<(u32, u32)>;
+
+		/// Maximum repay fee rate which can be set for a pool
+		#[pallet::constant]
+		type MaxRepayFee: Get<(u32, u32)>;
+
 		/// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// AMM handlers
+		type AMMHandler: OnCreatePoolHandler<AssetId> + OnTradeHandler<AssetId, Balance>;
+
+		/// Creation of XYK pools receiving liquidity of graduated sales
+		type GraduationHandler: GraduationHandler<Self::AccountId>;
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			// read and update of the cursor
+			let base_weight = T::DbWeight::get().reads_writes(1, 1);
+			if remaining_weight <= base_weight {
+				return Weight::zero();
+			}
+
+			let graduation_weight =
+				T::WeightInfo::graduate_pool().saturating_add(T::GraduationHandler::create_pool_weight());
+
+			// every pending pool costs at most the read of its key and the graduation
+			let pool_weight = graduation_weight.saturating_add(T::DbWeight::get().reads(1));
+			let max_pools = usize::try_from(
+				remaining_weight.saturating_sub(base_weight).ref_time() / pool_weight.ref_time().max(1),
+			)
+			.unwrap_or(usize::MAX);
+
+			// pending pools are checked in turns, so pools whose sale has not ended yet can not take the place
+			// of ended pools in every block
+			let cursor = <GraduationCursor<T>>::get();
+			let pending: Vec<PoolId<T>> = match cursor.clone() {
+				Some(cursor) => <PendingGraduations<T>>::iter_keys_from(cursor)
+					.take(max_pools)
+					.collect(),
+				None => <PendingGraduations<T>>::iter_keys().take(max_pools).collect(),
+			};
+			let mut used_weight = base_weight.saturating_add(T::DbWeight::get().reads(pending.len() as u64));
+			let mut checked = 0;
+
+			for pool_id in pending.iter() {
+				if used_weight.saturating_add(graduation_weight) > remaining_weight {
+					break;
+				}
+				checked += 1;
+
+				match <PoolData<T>>::get(pool_id) {
+					Some(pool_data) if Self::has_pool_ended(&pool_data) => {
+						Self::graduate_pool(pool_id, pool_data);
+						used_weight = used_weight.saturating_add(graduation_weight);
+					}
+					Some(_) => {
+						used_weight = used_weight.saturating_add(T::DbWeight::get().reads(1));
+					}
+					None => {
+						<PendingGraduations<T>>::remove(pool_id);
+						used_weight = used_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+					}
+				}
+			}
+
+			if checked == pending.len() && pending.len() < max_pools {
+				// all pending pools were checked, start from the first one in the next block
+				if cursor.is_some() {
+					<GraduationCursor<T>>::kill();
+				}
+			} else if let Some(last) = checked.checked_sub(1).and_then(|i| pending.get(i)) {
+				<GraduationCursor<T>>::put(<PendingGraduations<T>>::hashed_key_for(last));
+			}
+
+			used_weight.saturating_add(Self::clean_up_destroyed_pools(
+				remaining_weight.saturating_sub(used_weight),
+			))
+		}
+	}
 
 	#[pallet::error]
 	pub enum Error<T> {
@@ -301,6 +537,54 @@ pub mod pallet {
 
 		/// Not more than one fee collector per asset id
 		FeeCollectorWithAssetAlreadyUsed,
+
+		/// Trade deadline has passed
+		DeadlineExpired,
+
+		/// Weight curve parameters are invalid
+		InvalidWeightCurve,
+
+		/// Graduation has to migrate non-zero part of the liquidity
+		InvalidGraduation,
+
+		/// Liquidity of the ended sale is waiting for graduation
+		GraduationPending,
+
+		/// Account has no locked shares of the pool
+		SharesNotLocked,
+
+		/// Shares are still locked
+		SharesLocked,
+
+		/// Sale limits have to contain non-zero amounts and non-empty allowlist
+		InvalidSaleLimits,
+
+		/// Account is not allowed to trade in the pool
+		NotAllowlisted,
+
+		/// Proof does not match Merkle root of the allowlist
+		InvalidAllowlistProof,
+
+		/// Account has reached the max spend of the sale
+		SpendLimitExceeded,
+
+		/// Trades of the pool have reached the max amount of the block
+		BlockTradeLimitExceeded,
+
+		/// Repay fee is not within `MinRepayFee` and `MaxRepayFee` bounds
+		InvalidRepayFee,
+
+		/// Sale is paused
+		SalePaused,
+
+		/// Sale is not paused
+		SaleNotPaused,
+
+		/// Sale was paused by `SaleAdminOrigin` and can be resumed only by it
+		SalePausedByAdmin,
+
+		/// Storage of a previous pool of the asset pair is still being removed
+		PoolCleanupPending,
 	}
 
 	#[pallet::event]
@@ -357,6 +641,51 @@ pub mod pallet {
 			fee_asset: AssetId,
 			fee_amount: BalanceOf<T>,
 		},
+
+		/// Graduation of the pool was set or removed.
+		GraduationSet {
+			pool: PoolId<T>,
+			graduation: Option<Graduation<T::BlockNumber>>,
+		},
+
+		/// Liquidity of the ended sale seeded a XYK pool.
+		PoolGraduated {
+			pool: PoolId<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			amount_a: BalanceOf<T>,
+			amount_b: BalanceOf<T>,
+			share_token: AssetId,
+			shares: BalanceOf<T>,
+		},
+
+		/// XYK pool could not be created, the liquidity can be removed by the pool owner.
+		GraduationFailed { pool: PoolId<T>, error: DispatchError },
+
+		/// Shares received for graduated liquidity were unlocked.
+		GraduatedSharesUnlocked { who: T::AccountId, share_token: AssetId },
+
+		/// Sale limits of a pool were set or removed.
+		SaleLimitsSet {
+			pool: PoolId<T>,
+			limits: Option<SaleLimits<T::AccountId>>,
+		},
+
+		/// Account proved membership in the allowlist of a pool.
+		AllowlistJoined { pool: PoolId<T>, who: T::AccountId },
+
+		/// Sale was paused.
+		SalePaused { pool: PoolId<T> },
+
+		/// Sale was resumed, remaining schedule was shifted by the paused duration.
+		SaleResumed {
+			pool: PoolId<T>,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+		},
+
+		/// Sale was cancelled, the liquidity can be removed by the pool owner.
+		SaleCancelled { pool: PoolId<T> },
 	}
 
 	/// Details of a pool.
@@ -371,10 +700,56 @@ pub mod pallet {
 	pub type FeeCollectorWithAsset<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, bool, ValueQuery>;
 
+	/// Pools whose liquidity is migrated into a XYK pool when the sale ends
+	#[pallet::storage]
+	pub type PendingGraduations<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, (), OptionQuery>;
+
+	/// Raw key of the last pending graduation checked in `on_idle`, checking continues after it in the next block
+	#[pallet::storage]
+	pub type GraduationCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Block until which the shares received for graduated liquidity are locked
+	#[pallet::storage]
+	#[pallet::getter(fn graduated_shares_lock)]
+	pub type GraduatedSharesLocks<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, T::BlockNumber, OptionQuery>;
+
+	/// Trade limits of a pool
+	#[pallet::storage]
+	#[pallet::getter(fn sale_limits)]
+	pub type PoolSaleLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId<T>, SaleLimits<T::AccountId>, OptionQuery>;
+
+	/// Amount of the accumulated asset spent by an account in a sale
+	#[pallet::storage]
+	#[pallet::getter(fn account_spend)]
+	pub type AccountSpend<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, PoolId<T>, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// Amount of the accumulated asset spent by trades of a pool in the last block with a trade
+	#[pallet::storage]
+	pub type BlockTradeVolume<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId<T>, (T::BlockNumber, Balance), OptionQuery>;
+
+	/// Accounts which proved membership in the allowlist of a pool, with the Merkle root they proved it against
+	#[pallet::storage]
+	pub type AllowlistedAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, PoolId<T>, Blake2_128Concat, T::AccountId, H256, OptionQuery>;
+
+	/// Block at which the sale of a pool was paused and the origin which paused it
+	#[pallet::storage]
+	#[pallet::getter(fn paused_sale)]
+	pub type PausedSales<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolId<T>, (T::BlockNumber, PauseOrigin), OptionQuery>;
+
+	/// Destroyed pools whose account spends and allowlisted accounts are removed in `on_idle`
+	#[pallet::storage]
+	pub type PendingCleanups<T: Config> = StorageMap<_, Blake2_128Concat, PoolId<T>, (), OptionQuery>;
+
 	#[pallet::extra_constants]
 	impl<T: Config> Pallet<T> {
-		pub fn repay_fee() -> (u32, u32) {
-			(2, 10)
+		pub fn default_repay_fee() -> (u32, u32) {
+			DEFAULT_REPAY_FEE
 		}
 	}
 
@@ -400,12 +775,14 @@ pub mod pallet {
 		/// this should be higher than final weight
 		/// - `final_weight`: Final weight of the asset_a. 1_000_000 corresponding to 1% and 100_000_000 to 100%
 		/// this should be lower than initial weight
-		/// - `weight_curve`: The weight function used to update the LBP weights. Currently,
-		/// there is only one weight function implemented, the linear function.
+		/// - `weight_curve`: The weight function used to update the LBP weights. Linear, exponential decay,
+		/// step-wise and piecewise linear functions are implemented, see `WeightCurveType`.
 		/// - `fee`: The trading fee charged on every trade distributed to `fee_collector`.
 		/// - `fee_collector`: The account to which trading fees will be transferred.
 		/// - `repay_target`: The amount of tokens to repay to separate fee_collector account. Until this amount is
-		/// reached, fee will be increased to 20% and taken from the pool
+		/// reached, fee will be increased to `repay_fee` and taken from the pool
+		/// - `repay_fee`: The fee charged until `repay_target` is reached, `DEFAULT_REPAY_FEE` if not specified.
+		/// It has to be within `MinRepayFee` and `MaxRepayFee` bounds.
 		///
 		/// Emits `PoolCreated` event when successful.
 		///
@@ -427,6 +804,7 @@ pub mod pallet {
 			fee: (u32, u32),
 			fee_collector: T::AccountId,
 			repay_target: Balance,
+			repay_fee: Option<(u32, u32)>,
 		) -> DispatchResult {
 			T::CreatePoolOrigin::ensure_origin(origin)?;
 
@@ -444,6 +822,11 @@ pub mod pallet {
 
 			ensure!(!Self::exists(asset_pair), Error::<T>::PoolAlreadyExists);
 
+			ensure!(
+				!<PendingCleanups<T>>::contains_key(Self::get_pair_id(asset_pair)),
+				Error::<T>::PoolCleanupPending
+			);
+
 			ensure!(
 				!<FeeCollectorWithAsset<T>>::contains_key(fee_collector.clone(), asset_a),
 				Error::<T>::FeeCollectorWithAssetAlreadyUsed
@@ -469,6 +852,7 @@ pub mod pallet {
 				fee,
 				fee_collector.clone(),
 				repay_target,
+				repay_fee.unwrap_or(DEFAULT_REPAY_FEE),
 			);
 
 			Self::validate_pool_data(&pool_data)?;
@@ -478,6 +862,8 @@ pub mod pallet {
 			<PoolData<T>>::insert(&pool_id, &pool_data);
 			<FeeCollectorWithAsset<T>>::insert(fee_collector, asset_a, true);
 
+			let _ = T::AMMHandler::on_create_pool(asset_a, asset_b);
+
 			Self::deposit_event(Event::PoolCreated {
 				pool: pool_id.clone(),
 				data: pool_data,
@@ -513,6 +899,8 @@ pub mod pallet {
 		/// - `final_weight`: The new final weight. This parameter is optional.
 		/// - `fee`: The new trading fee charged on every trade. This parameter is optional.
 		/// - `fee_collector`: The new receiver of trading fees. This parameter is optional.
+		/// - `repay_target`: The new repayment target of the fee collector. This parameter is optional.
+		/// - `repay_fee`: The new fee charged until the repay target is reached. This parameter is optional.
 		///
 		/// Emits `PoolUpdated` event when successful.
 		#[pallet::weight(<T as Config>::WeightInfo::update_pool_data())]
@@ -527,6 +915,7 @@ pub mod pallet {
 			fee: Option<(u32, u32)>,
 			fee_collector: Option<T::AccountId>,
 			repay_target: Option<Balance>,
+			repay_fee: Option<(u32, u32)>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -539,7 +928,8 @@ pub mod pallet {
 						|| end.is_some() || initial_weight.is_some()
 						|| final_weight.is_some()
 						|| fee.is_some() || fee_collector.is_some()
-						|| repay_target.is_some(),
+						|| repay_target.is_some()
+						|| repay_fee.is_some(),
 					Error::<T>::NothingToUpdate
 				);
 
@@ -578,6 +968,8 @@ pub mod pallet {
 
 				pool.repay_target = repay_target.unwrap_or(pool.repay_target);
 
+				pool.repay_fee = repay_fee.unwrap_or(pool.repay_fee);
+
 				Self::validate_pool_data(pool)?;
 
 				Self::deposit_event(Event::PoolUpdated {
@@ -652,7 +1044,8 @@ pub mod pallet {
 		/// Transfer all the liquidity from a pool back to the pool owner and destroy the pool.
 		/// The pool data are also removed from the storage.
 		///
-		/// The pool can't be destroyed during the sale.
+		/// The pool can't be destroyed during the sale. Liquidity of an ended sale with graduation is migrated
+		/// into a XYK pool and can't be removed.
 		///
 		/// The dispatch origin for this call must be signed by the pool owner.
 		///
@@ -670,28 +1063,14 @@ pub mod pallet {
 
 			ensure!(!Self::is_pool_running(&pool_data), Error::<T>::SaleNotEnded);
 
-			let (asset_a, asset_b) = pool_data.assets;
-
-			let amount_a = T::MultiCurrency::free_balance(asset_a, &pool_id);
-			let amount_b = T::MultiCurrency::free_balance(asset_b, &pool_id);
+			ensure!(!<PausedSales<T>>::contains_key(&pool_id), Error::<T>::SalePaused);
 
-			T::MultiCurrency::transfer(asset_a, &pool_id, &who, amount_a)?;
-			T::MultiCurrency::transfer(asset_b, &pool_id, &who, amount_b)?;
-
-			if Self::collected_fees(&pool_data) > 0 {
-				T::MultiCurrency::remove_lock(COLLECTOR_LOCK_ID, asset_a, &pool_data.fee_collector)?;
-			}
-
-			<FeeCollectorWithAsset<T>>::remove(pool_data.fee_collector, pool_data.assets.0);
-			<PoolData<T>>::remove(&pool_id);
+			ensure!(
+				pool_data.graduation.is_none() || !Self::has_pool_ended(&pool_data),
+				Error::<T>::GraduationPending
+			);
 
-			Self::deposit_event(Event::LiquidityRemoved {
-				who: pool_id,
-				asset_a,
-				asset_b,
-				amount_a,
-				amount_b,
-			});
+			Self::destroy_pool(&pool_id, &pool_data)?;
 
 			Ok(())
 		}
@@ -710,7 +1089,7 @@ pub mod pallet {
 		/// - `max_limit`: minimum amount of `asset_out` / amount of asset_out to be obtained from the pool in exchange for `asset_in`.
 		///
 		/// Emits `SellExecuted` when successful.
-		#[pallet::weight(<T as Config>::WeightInfo::sell())]
+		#[pallet::weight(<T as Config>::WeightInfo::sell() + <T as Config>::AMMHandler::on_trade_weight())]
 		pub fn sell(
 			origin: OriginFor<T>,
 			asset_in: AssetId,
@@ -739,7 +1118,7 @@ pub mod pallet {
 		/// - `max_limit`: maximum amount of `asset_in` to be sold in exchange for `asset_out`.
 		///
 		/// Emits `BuyExecuted` when successful.
-		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		#[pallet::weight(<T as Config>::WeightInfo::buy() + <T as Config>::AMMHandler::on_trade_weight())]
 		pub fn buy(
 			origin: OriginFor<T>,
 			asset_out: AssetId,
@@ -753,16 +1132,517 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Trade `asset_in` for `asset_out` if the trade is executed no later than at block `deadline`.
+		///
+		/// Same as `sell`, but fails with `DeadlineExpired` when the current block provided by
+		/// `BlockNumberProvider` is greater than `deadline`.
+		///
+		/// Emits `SellExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell() + <T as Config>::AMMHandler::on_trade_weight() + T::DbWeight::get().reads(1))]
+		pub fn sell_with_deadline(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: BalanceOf<T>,
+			max_limit: BalanceOf<T>,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_deadline(deadline)?;
+
+			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, max_limit, false)?;
+
+			Ok(())
+		}
+
+		/// Trade `asset_in` for `asset_out` if the trade is executed no later than at block `deadline`.
+		///
+		/// Same as `buy`, but fails with `DeadlineExpired` when the current block provided by
+		/// `BlockNumberProvider` is greater than `deadline`.
+		///
+		/// Emits `BuyExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy() + <T as Config>::AMMHandler::on_trade_weight() + T::DbWeight::get().reads(1))]
+		pub fn buy_with_deadline(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: BalanceOf<T>,
+			max_limit: BalanceOf<T>,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_deadline(deadline)?;
+
+			<Self as AMM<_, _, _, _>>::buy(&who, AssetPair { asset_in, asset_out }, amount, max_limit, false)?;
+
+			Ok(())
+		}
+
+		/// Trade whole transferable balance of `asset_in` for `asset_out`.
+		///
+		/// Same as `sell`, but the amount sold is the transferable balance of `asset_in` of the caller at the time
+		/// of execution, i.e. the free balance without locked funds after the transaction fee has been paid.
+		///
+		/// Parameters:
+		/// - `asset_in`: The identifier of the asset being transferred from the account to the pool.
+		/// - `asset_out`: The identifier of the asset being transferred from the pool to the account.
+		/// - `max_limit`: minimum amount of `asset_out` to be obtained from the pool in exchange for `asset_in`.
+		///
+		/// Emits `SellExecuted` with the amount sold when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell() + <T as Config>::AMMHandler::on_trade_weight() + T::DbWeight::get().reads(1))]
+		pub fn sell_all(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			max_limit: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let amount = T::BalanceInspect::reducible_balance(asset_in, &who, false);
+
+			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, max_limit, false)?;
+
+			Ok(())
+		}
+
+		/// Set or remove graduation of a pool.
+		///
+		/// When the sale of a pool with graduation ends, `liquidity` part of the final reserves seeds a XYK pool
+		/// of the same asset pair at the final spot price of the sale. The rest of the reserves is returned
+		/// to the pool owner, who also receives the share tokens of the XYK pool, optionally locked until
+		/// `lock_shares_until`. XYK pool of the asset pair can not be created by anyone else until then.
+		///
+		/// The dispatch origin for this call must be signed by the pool owner.
+		///
+		/// The graduation can be updated only if the sale has not already started.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool to be updated.
+		/// - `graduation`: The graduation of the pool, `None` removes the graduation.
+		///
+		/// Emits `GraduationSet` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_graduation())]
+		pub fn set_graduation(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T>,
+			graduation: Option<Graduation<T::BlockNumber>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<PoolData<T>>::try_mutate(&pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+				ensure!(who == pool.owner, Error::<T>::NotOwner);
+
+				ensure!(!Self::has_pool_started(pool), Error::<T>::SaleStarted);
+
+				if let Some(graduation) = graduation {
+					ensure!(!graduation.liquidity.is_zero(), Error::<T>::InvalidGraduation);
+					<PendingGraduations<T>>::insert(&pool_id, ());
+				} else {
+					<PendingGraduations<T>>::remove(&pool_id);
+				}
+
+				pool.graduation = graduation;
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::GraduationSet {
+				pool: pool_id,
+				graduation,
+			});
+
+			Ok(())
+		}
+
+		/// Unlock share tokens received for graduated liquidity.
+		///
+		/// The dispatch origin for this call must be signed by the owner of the shares.
+		///
+		/// Parameters:
+		/// - `share_token`: The share token of the XYK pool seeded by the graduated liquidity.
+		///
+		/// Emits `GraduatedSharesUnlocked` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::unlock_graduated_shares())]
+		pub fn unlock_graduated_shares(origin: OriginFor<T>, share_token: AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let until = <GraduatedSharesLocks<T>>::get(&who, share_token).ok_or(Error::<T>::SharesNotLocked)?;
+
+			ensure!(
+				T::BlockNumberProvider::current_block_number() >= until,
+				Error::<T>::SharesLocked
+			);
+
+			T::MultiCurrency::remove_lock(GRADUATION_LOCK_ID, share_token, &who)?;
+			<GraduatedSharesLocks<T>>::remove(&who, share_token);
+
+			Self::deposit_event(Event::GraduatedSharesUnlocked { who, share_token });
+
+			Ok(())
+		}
+
+		/// Set or remove trade limits of a pool.
+		///
+		/// Limits restrict how much of the accumulated asset an account can spend during the sale and how
+		/// much of it can be spent by all trades of the pool in a block. Allowlist restricts trading in the pool
+		/// to the listed accounts.
+		///
+		/// The dispatch origin for this call must be signed by the pool owner.
+		///
+		/// The limits can be updated only if the sale has not already started.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool to be updated.
+		/// - `limits`: The trade limits of the pool, `None` removes the limits.
+		///
+		/// Emits `SaleLimitsSet` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_sale_limits())]
+		pub fn set_sale_limits(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T>,
+			limits: Option<SaleLimits<T::AccountId>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+
+			ensure!(!Self::has_pool_started(&pool_data), Error::<T>::SaleStarted);
+
+			if let Some(limits) = &limits {
+				ensure!(Self::are_sale_limits_valid(limits), Error::<T>::InvalidSaleLimits);
+				<PoolSaleLimits<T>>::insert(&pool_id, limits);
+			} else {
+				<PoolSaleLimits<T>>::remove(&pool_id);
+			}
+
+			Self::deposit_event(Event::SaleLimitsSet { pool: pool_id, limits });
+
+			Ok(())
+		}
+
+		/// Join Merkle allowlist of a pool.
+		///
+		/// The dispatch origin for this call must be signed by the allowlisted account.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		/// - `proof`: Merkle proof of the account, hashes of the sibling nodes from the leaf up to the root.
+		///
+		/// Emits `AllowlistJoined` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::join_allowlist())]
+		pub fn join_allowlist(origin: OriginFor<T>, pool_id: PoolId<T>, proof: AllowlistProof) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let limits = <PoolSaleLimits<T>>::get(&pool_id).ok_or(Error::<T>::InvalidAllowlistProof)?;
+
+			let root = match limits.allowlist {
+				Some(Allowlist::MerkleRoot(root)) => root,
+				_ => return Err(Error::<T>::InvalidAllowlistProof.into()),
+			};
+
+			ensure!(
+				Self::merkle_root(&who, &proof) == root,
+				Error::<T>::InvalidAllowlistProof
+			);
+
+			<AllowlistedAccounts<T>>::insert(&pool_id, &who, root);
+
+			Self::deposit_event(Event::AllowlistJoined { pool: pool_id, who });
+
+			Ok(())
+		}
+
+		/// Pause a running sale.
+		///
+		/// Trades are disallowed and the liquidity can not be removed while the sale is paused.
+		///
+		/// The dispatch origin for this call must be `T::SaleAdminOrigin` or signed by the pool owner.
+		/// A sale paused by `T::SaleAdminOrigin` can be resumed only by `T::SaleAdminOrigin`.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		///
+		/// Emits `SalePaused` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::pause_sale())]
+		pub fn pause_sale(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			let paused_by = match T::SaleAdminOrigin::try_origin(origin) {
+				Ok(_) => PauseOrigin::Admin,
+				Err(origin) => {
+					let who = ensure_signed(origin)?;
+					ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+					PauseOrigin::Owner
+				}
+			};
+
+			ensure!(!<PausedSales<T>>::contains_key(&pool_id), Error::<T>::SalePaused);
+			ensure!(Self::is_pool_running(&pool_data), Error::<T>::SaleIsNotRunning);
+
+			<PausedSales<T>>::insert(&pool_id, (T::BlockNumberProvider::current_block_number(), paused_by));
+
+			Self::deposit_event(Event::SalePaused { pool: pool_id });
+
+			Ok(())
+		}
+
+		/// Resume a paused sale.
+		///
+		/// Start and end of the sale are shifted by the number of blocks the sale was paused for,
+		/// so the sale continues with the weights it was paused with.
+		///
+		/// The dispatch origin for this call must be `T::SaleAdminOrigin` or signed by the pool owner.
+		/// The pool owner can resume only a sale paused by the pool owner.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		///
+		/// Emits `SaleResumed` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::resume_sale())]
+		pub fn resume_sale(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+			let pool_data = <PoolData<T>>::try_get(&pool_id).map_err(|_| Error::<T>::PoolNotFound)?;
+
+			let (paused_at, paused_by) = <PausedSales<T>>::get(&pool_id).ok_or(Error::<T>::SaleNotPaused)?;
+
+			if let Err(origin) = T::SaleAdminOrigin::try_origin(origin) {
+				let who = ensure_signed(origin)?;
+				ensure!(who == pool_data.owner, Error::<T>::NotOwner);
+				ensure!(paused_by == PauseOrigin::Owner, Error::<T>::SalePausedByAdmin);
+			}
+
+			let paused_for = T::BlockNumberProvider::current_block_number().saturating_sub(paused_at);
+
+			<PoolData<T>>::try_mutate(&pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+				let (start, end) = match (pool.start, pool.end) {
+					(Some(start), Some(end)) => (
+						start.checked_add(&paused_for).ok_or(Error::<T>::Overflow)?,
+						end.checked_add(&paused_for).ok_or(Error::<T>::Overflow)?,
+					),
+					_ => return Err(Error::<T>::SaleIsNotRunning.into()),
+				};
+
+				pool.start = Some(start);
+				pool.end = Some(end);
+
+				<PausedSales<T>>::remove(&pool_id);
+
+				Self::deposit_event(Event::SaleResumed {
+					pool: pool_id.clone(),
+					start,
+					end,
+				});
+
+				Ok(())
+			})
+		}
+
+		/// Cancel a running or paused sale.
+		///
+		/// The sale ends immediately, graduation of the pool is dropped and the liquidity can be removed
+		/// by the pool owner.
+		///
+		/// The dispatch origin for this call must be `T::SaleAdminOrigin`.
+		///
+		/// Parameters:
+		/// - `pool_id`: The identifier of the pool.
+		///
+		/// Emits `SaleCancelled` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_sale())]
+		pub fn cancel_sale(origin: OriginFor<T>, pool_id: PoolId<T>) -> DispatchResult {
+			T::SaleAdminOrigin::ensure_origin(origin)?;
+
+			let was_paused = <PausedSales<T>>::contains_key(&pool_id);
+
+			<PoolData<T>>::try_mutate(&pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+				ensure!(was_paused || Self::is_pool_running(pool), Error::<T>::SaleIsNotRunning);
+
+				// the sale has ended in the previous block
+				let end = T::BlockNumberProvider::current_block_number().saturating_sub(One::one());
+				pool.start = pool.start.map(|start| start.min(end));
+				pool.end = Some(end);
+				pool.graduation = None;
+
+				Ok(())
+			})?;
+
+			<PausedSales<T>>::remove(&pool_id);
+			<PendingGraduations<T>>::remove(&pool_id);
+
+			Self::deposit_event(Event::SaleCancelled { pool: pool_id });
+
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Transfer all the liquidity of a pool to the pool owner and remove the pool.
+	fn destroy_pool(
+		pool_id: &PoolId<T>,
+		pool_data: &Pool<T::AccountId, T::BlockNumber>,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let (asset_a, asset_b) = pool_data.assets;
+
+		let amount_a = T::MultiCurrency::free_balance(asset_a, pool_id);
+		let amount_b = T::MultiCurrency::free_balance(asset_b, pool_id);
+
+		T::MultiCurrency::transfer(asset_a, pool_id, &pool_data.owner, amount_a)?;
+		T::MultiCurrency::transfer(asset_b, pool_id, &pool_data.owner, amount_b)?;
+
+		if Self::collected_fees(pool_data) > 0 {
+			T::MultiCurrency::remove_lock(COLLECTOR_LOCK_ID, asset_a, &pool_data.fee_collector)?;
+		}
+
+		<FeeCollectorWithAsset<T>>::remove(&pool_data.fee_collector, asset_a);
+		<PendingGraduations<T>>::remove(pool_id);
+		<PoolSaleLimits<T>>::remove(pool_id);
+		<BlockTradeVolume<T>>::remove(pool_id);
+		<PendingCleanups<T>>::insert(pool_id, ());
+		<PoolData<T>>::remove(pool_id);
+
+		Self::deposit_event(Event::LiquidityRemoved {
+			who: pool_id.clone(),
+			asset_a,
+			asset_b,
+			amount_a,
+			amount_b,
+		});
+
+		Ok((amount_a, amount_b))
+	}
+
+	/// Remove account spends and allowlisted accounts of destroyed pools within `remaining_weight`.
+	///
+	/// A pool is removed from `PendingCleanups` once all its entries are removed, the removal of the rest
+	/// of the entries continues in the next block otherwise.
+	fn clean_up_destroyed_pools(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let entry_weight = db_weight.reads_writes(1, 1);
+		let max_entries = |used_weight: Weight| -> u32 {
+			let entries = remaining_weight.saturating_sub(used_weight).ref_time() / entry_weight.ref_time().max(1);
+			u32::try_from(entries).unwrap_or(u32::MAX)
+		};
+
+		let mut used_weight = Weight::zero();
+
+		// read of the next pool and removal of at least one entry and of the pool
+		while used_weight.saturating_add(db_weight.reads_writes(2, 2)) <= remaining_weight {
+			used_weight = used_weight.saturating_add(db_weight.reads(1));
+			let pool_id = match <PendingCleanups<T>>::iter_keys().next() {
+				Some(pool_id) => pool_id,
+				None => break,
+			};
+
+			let removed = <AccountSpend<T>>::clear_prefix(&pool_id, max_entries(used_weight), None);
+			used_weight =
+				used_weight.saturating_add(db_weight.reads_writes(removed.loops.into(), removed.unique.into()));
+			if removed.maybe_cursor.is_some() {
+				break;
+			}
+
+			let removed = <AllowlistedAccounts<T>>::clear_prefix(&pool_id, max_entries(used_weight), None);
+			used_weight =
+				used_weight.saturating_add(db_weight.reads_writes(removed.loops.into(), removed.unique.into()));
+			if removed.maybe_cursor.is_some() {
+				break;
+			}
+
+			<PendingCleanups<T>>::remove(&pool_id);
+			used_weight = used_weight.saturating_add(db_weight.writes(1));
+		}
+
+		used_weight
+	}
+
+	/// Seed a XYK pool with the liquidity of an ended sale.
+	///
+	/// If the XYK pool can not be created, the graduation is removed and the liquidity is left in the pool
+	/// to be removed by the pool owner.
+	fn graduate_pool(pool_id: &PoolId<T>, pool_data: Pool<T::AccountId, T::BlockNumber>) {
+		<PendingGraduations<T>>::remove(pool_id);
+
+		if let Err(error) = Self::migrate_liquidity(pool_id, &pool_data) {
+			<PoolData<T>>::mutate(pool_id, |maybe_pool| {
+				if let Some(pool) = maybe_pool {
+					pool.graduation = None;
+				}
+			});
+
+			Self::deposit_event(Event::GraduationFailed {
+				pool: pool_id.clone(),
+				error,
+			});
+		}
+	}
+
+	#[transactional]
+	fn migrate_liquidity(pool_id: &PoolId<T>, pool_data: &Pool<T::AccountId, T::BlockNumber>) -> DispatchResult {
+		let graduation = pool_data.graduation.ok_or(Error::<T>::InvalidGraduation)?;
+		let end = pool_data.end.ok_or(Error::<T>::InvalidBlockRange)?;
+		let (asset_a, asset_b) = pool_data.assets;
+
+		let (weight_a, weight_b) = Self::calculate_weights(pool_data, end)?;
+
+		let (amount_a, amount_b) = math::calculate_graduation_liquidity(
+			T::MultiCurrency::free_balance(asset_a, pool_id),
+			T::MultiCurrency::free_balance(asset_b, pool_id),
+			weight_a,
+			weight_b,
+			graduation.liquidity,
+		)
+		.ok_or(Error::<T>::Overflow)?;
+
+		Self::destroy_pool(pool_id, pool_data)?;
+
+		let (share_token, shares) =
+			T::GraduationHandler::create_pool(&pool_data.owner, asset_a, amount_a, asset_b, amount_b)?;
+
+		if let Some(until) = graduation.lock_shares_until {
+			if T::BlockNumberProvider::current_block_number() < until {
+				T::MultiCurrency::set_lock(GRADUATION_LOCK_ID, share_token, &pool_data.owner, shares)?;
+				<GraduatedSharesLocks<T>>::insert(&pool_data.owner, share_token, until);
+			}
+		}
+
+		Self::deposit_event(Event::PoolGraduated {
+			pool: pool_id.clone(),
+			asset_a,
+			asset_b,
+			amount_a,
+			amount_b,
+			share_token,
+			shares,
+		});
+
+		Ok(())
+	}
+
+	/// Ensure that the current block provided by `BlockNumberProvider` has not passed `deadline`.
+	fn ensure_deadline(deadline: T::BlockNumber) -> DispatchResult {
+		ensure!(
+			T::BlockNumberProvider::current_block_number() <= deadline,
+			Error::<T>::DeadlineExpired
+		);
+		Ok(())
+	}
+
 	fn calculate_weights(
 		pool_data: &Pool<T::AccountId, T::BlockNumber>,
 		at: T::BlockNumber,
 	) -> Result<(LBPWeight, LBPWeight), DispatchError> {
 		let weight_a = T::LBPWeightFunction::calculate_weight(
-			pool_data.weight_curve,
+			&pool_data.weight_curve,
 			pool_data.start.unwrap_or_else(Zero::zero),
 			pool_data.end.unwrap_or_else(Zero::zero),
 			pool_data.initial_weight,
@@ -809,11 +1689,50 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::InvalidWeight
 		);
 
+		ensure!(
+			Self::is_weight_curve_valid(&pool_data.weight_curve),
+			Error::<T>::InvalidWeightCurve
+		);
+
 		ensure!(!pool_data.fee.1.is_zero(), Error::<T>::FeeAmountInvalid);
 
+		ensure!(
+			Self::is_repay_fee_allowed(pool_data.repay_fee),
+			Error::<T>::InvalidRepayFee
+		);
+
 		Ok(())
 	}
 
+	/// Check whether `fee` is a valid fee rate within `MinRepayFee` and `MaxRepayFee` bounds.
+	fn is_repay_fee_allowed(fee: (u32, u32)) -> bool {
+		let (min_fee, max_fee) = (T::MinRepayFee::get(), T::MaxRepayFee::get());
+
+		// Compare fractions by cross multiplication, u32 products always fit into u64.
+		let fee_ge = |a: (u32, u32), b: (u32, u32)| (a.0 as u64) * (b.1 as u64) >= (b.0 as u64) * (a.1 as u64);
+
+		fee.1 != 0 && fee.0 <= fee.1 && fee_ge(fee, min_fee) && fee_ge(max_fee, fee)
+	}
+
+	fn is_weight_curve_valid(weight_curve: &WeightCurveType) -> bool {
+		match weight_curve {
+			WeightCurveType::Linear => true,
+			WeightCurveType::ExponentialDecay { rate } => !rate.is_zero() && *rate <= MAX_EXPONENTIAL_DECAY_RATE,
+			WeightCurveType::StepWise { steps } => !steps.is_zero(),
+			WeightCurveType::Piecewise(breakpoints) => {
+				let is_breakpoint_valid = |(progress, weight): &(Permill, LBPWeight)| {
+					!progress.is_zero()
+						&& *progress < Permill::from_percent(100)
+						&& !weight.is_zero() && *weight < MAX_WEIGHT
+				};
+
+				!breakpoints.is_empty()
+					&& breakpoints.iter().all(is_breakpoint_valid)
+					&& breakpoints.windows(2).all(|pair| pair[0].0 < pair[1].0)
+			}
+		}
+	}
+
 	fn get_sorted_weight(
 		asset_in: AssetId,
 		now: T::BlockNumber,
@@ -832,15 +1751,21 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// return true if now is in interval <pool.start, pool.end>
-	fn is_pool_running(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> bool {
+	/// return true if now is in interval <pool.start, pool.end> and the sale is not paused
+	pub fn is_pool_running(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> bool {
 		let now = T::BlockNumberProvider::current_block_number();
 		match (pool_data.start, pool_data.end) {
-			(Some(start), Some(end)) => start <= now && now <= end,
+			(Some(start), Some(end)) => start <= now && now <= end && !Self::is_sale_paused(pool_data),
 			_ => false,
 		}
 	}
 
+	/// return true if the sale of the pool is paused
+	pub fn is_sale_paused(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> bool {
+		let pool_id = Self::pair_account_from_assets(pool_data.assets.0, pool_data.assets.1);
+		<PausedSales<T>>::contains_key(&pool_id)
+	}
+
 	/// return true if now is > pool.start and pool has been initialized
 	fn has_pool_started(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> bool {
 		let now = T::BlockNumberProvider::current_block_number();
@@ -850,6 +1775,15 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// return true if now is > pool.end, pool has been initialized and the sale is not paused
+	fn has_pool_ended(pool_data: &Pool<T::AccountId, T::BlockNumber>) -> bool {
+		let now = T::BlockNumberProvider::current_block_number();
+		match pool_data.end {
+			Some(end) => end < now && !Self::is_sale_paused(pool_data),
+			_ => false,
+		}
+	}
+
 	/// returns fees collected and locked in the fee collector account
 	/// note: after LBP finishes and liquidity is removed this will be 0
 	fn collected_fees(pool: &Pool<T::AccountId, T::BlockNumber>) -> BalanceOf<T> {
@@ -861,11 +1795,95 @@ impl<T: Config> Pallet<T> {
 		Self::collected_fees(pool) < pool.repay_target
 	}
 
+	fn are_sale_limits_valid(limits: &SaleLimits<T::AccountId>) -> bool {
+		let is_allowlist_valid = match &limits.allowlist {
+			Some(Allowlist::Accounts(accounts)) => !accounts.is_empty(),
+			_ => true,
+		};
+
+		limits.max_spend_per_account != Some(0) && limits.max_trade_per_block != Some(0) && is_allowlist_valid
+	}
+
+	/// Calculate Merkle root from the leaf of `who` and the sibling nodes in `proof`
+	fn merkle_root(who: &T::AccountId, proof: &[H256]) -> H256 {
+		proof.iter().fold(BlakeTwo256::hash_of(who), |node, sibling| {
+			let (left, right) = if node <= *sibling {
+				(node, *sibling)
+			} else {
+				(*sibling, node)
+			};
+			BlakeTwo256::hash(&[left.as_bytes(), right.as_bytes()].concat())
+		})
+	}
+
+	fn is_allowlisted(pool_id: &PoolId<T>, allowlist: &Allowlist<T::AccountId>, who: &T::AccountId) -> bool {
+		match allowlist {
+			Allowlist::Accounts(accounts) => accounts.contains(who),
+			Allowlist::MerkleRoot(root) => <AllowlistedAccounts<T>>::get(pool_id, who) == Some(*root),
+		}
+	}
+
+	/// Ensure the trade is within the sale limits of the pool and record the spent accumulated asset
+	fn apply_sale_limits(
+		pool_id: &PoolId<T>,
+		pool_data: &Pool<T::AccountId, T::BlockNumber>,
+		transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>,
+	) -> DispatchResult {
+		let limits = match <PoolSaleLimits<T>>::get(pool_id) {
+			Some(limits) => limits,
+			None => return Ok(()),
+		};
+
+		if let Some(allowlist) = &limits.allowlist {
+			ensure!(
+				Self::is_allowlisted(pool_id, allowlist, &transfer.origin),
+				Error::<T>::NotAllowlisted
+			);
+		}
+
+		// accumulated asset is spent only when it is sold to the pool, the trader pays the fee then
+		if transfer.assets.asset_in != pool_data.assets.0 {
+			return Ok(());
+		}
+
+		let spent = transfer
+			.amount
+			.checked_add(transfer.fee.1)
+			.ok_or(Error::<T>::Overflow)?;
+
+		if let Some(max_spend) = limits.max_spend_per_account {
+			let total_spent = Self::account_spend(pool_id, &transfer.origin)
+				.checked_add(spent)
+				.ok_or(Error::<T>::Overflow)?;
+
+			ensure!(total_spent <= max_spend, Error::<T>::SpendLimitExceeded);
+
+			<AccountSpend<T>>::insert(pool_id, &transfer.origin, total_spent);
+		}
+
+		if let Some(max_trade) = limits.max_trade_per_block {
+			let now = T::BlockNumberProvider::current_block_number();
+			let block_volume = match <BlockTradeVolume<T>>::get(pool_id) {
+				Some((block, volume)) if block == now => volume,
+				_ => Zero::zero(),
+			};
+			let block_volume = block_volume.checked_add(spent).ok_or(Error::<T>::Overflow)?;
+
+			ensure!(block_volume <= max_trade, Error::<T>::BlockTradeLimitExceeded);
+
+			<BlockTradeVolume<T>>::insert(pool_id, (now, block_volume));
+		}
+
+		Ok(())
+	}
+
 	#[transactional]
 	fn execute_trade(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
 		let pool_account = Self::get_pair_id(transfer.assets);
 		let pool = <PoolData<T>>::try_get(&pool_account).map_err(|_| Error::<T>::PoolNotFound)?;
 
+		Self::apply_sale_limits(&pool_account, &pool, transfer)?;
+
 		// Transfer assets between pool and user
 		T::MultiCurrency::transfer(
 			transfer.assets.asset_in,
@@ -894,6 +1912,15 @@ impl<T: Config> Pallet<T> {
 		let collected_fee_total = Self::collected_fees(&pool) + fee_amount;
 		T::MultiCurrency::set_lock(COLLECTOR_LOCK_ID, fee_asset, &pool.fee_collector, collected_fee_total)?;
 
+		// LBP pools have no shares, the reserve of the first pool asset is reported as liquidity
+		T::AMMHandler::on_trade(
+			transfer.assets.asset_in,
+			transfer.assets.asset_out,
+			transfer.amount,
+			transfer.amount_out,
+			T::MultiCurrency::free_balance(pool.assets.0, &pool_account),
+		);
+
 		Ok(())
 	}
 
@@ -903,7 +1930,7 @@ impl<T: Config> Pallet<T> {
 		amount: BalanceOf<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
 		let fee = if Self::is_repay_fee_applied(pool) {
-			Self::repay_fee()
+			pool.repay_fee
 		} else {
 			pool.fee
 		};
@@ -1256,12 +2283,39 @@ impl<T: Config> CanCreatePool<AssetId> for DisallowWhenLBPPoolRunning<T> {
 		let pool_id = Pallet::<T>::pair_account_from_assets(asset_a, asset_b);
 		let now = T::BlockNumberProvider::current_block_number();
 		match <PoolData<T>>::try_get(&pool_id) {
-			// returns true if the pool exists and the sale ended
+			// returns true if the pool exists and the sale ended, unless the liquidity waits for graduation
+			// or the sale is paused
 			Ok(data) => match data.end {
-				Some(end) => end < now,
+				Some(end) => end < now && data.graduation.is_none() && !<PausedSales<T>>::contains_key(&pool_id),
 				None => false,
 			},
 			_ => true,
 		}
 	}
 }
+
+/// Spot price of LBP pool assets given by the reserves and the current weights of the sale.
+pub struct LBPSpotPrice<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> SpotPriceProvider<AssetId> for LBPSpotPrice<T> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		<PoolData<T>>::contains_key(Pallet::<T>::pair_account_from_assets(asset_a, asset_b))
+	}
+
+	/// Price of `asset_a` denominated in `asset_b`, `(reserve_b / weight_b) / (reserve_a / weight_a)`.
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		let pool_id = Pallet::<T>::pair_account_from_assets(asset_a, asset_b);
+		let pool_data = <PoolData<T>>::get(&pool_id)?;
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let (weight_a, weight_b) = Pallet::<T>::get_sorted_weight(asset_a, now, &pool_data).ok()?;
+
+		let reserve_a = T::MultiCurrency::free_balance(asset_a, &pool_id);
+		let reserve_b = T::MultiCurrency::free_balance(asset_b, &pool_id);
+
+		Price::checked_from_rational(reserve_b, reserve_a)?
+			.checked_mul(&Price::checked_from_rational(weight_a, weight_b)?)
+	}
+}
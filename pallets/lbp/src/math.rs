@@ -0,0 +1,224 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Non-linear weight curves of LBP sales and graduation of ended sales.
+//!
+//! Linear weights are calculated by `hydra_dx_math::lbp::calculate_linear_weights`.
+//!
+//! All weight curve functions move the weight from `initial_weight` at block `start` to `final_weight`
+//! at block `end` and return `None` when `at` is outside of the sale or a calculation overflows.
+
+use hydra_dx_math::types::LBPWeight;
+use primitive_types::U256;
+use primitives::Balance;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, UniqueSaturatedInto},
+	PerThing, Permill,
+};
+
+/// Precision of the exponential decay rate, rate of `1_000` corresponds to `k = 1`.
+pub const EXPONENTIAL_RATE_PRECISION: u32 = 1_000;
+
+/// Fixed point one used in the exponential calculation.
+const FIXED_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// Maximum number of Taylor series terms of the exponential function.
+const MAX_ITERATIONS: u32 = 128;
+
+/// Calculate weight of the exponential decay curve `1 - e^(-k * x)` normalized to reach `final_weight` at `end`.
+///
+/// `x` is the elapsed part of the sale and `k = rate / EXPONENTIAL_RATE_PRECISION`.
+pub fn calculate_exponential_weights<BlockNumber: AtLeast32BitUnsigned>(
+	start: BlockNumber,
+	end: BlockNumber,
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+	rate: u32,
+	at: BlockNumber,
+) -> Option<LBPWeight> {
+	let (elapsed, duration) = sale_progress(start, end, at)?;
+
+	if rate == 0 {
+		return None;
+	}
+
+	let precision = u128::from(EXPONENTIAL_RATE_PRECISION);
+
+	// (1 - e^(-kx)) / (1 - e^(-k)) = (e^k - e^(k(1 - x))) / (e^k - 1)
+	let exp_k = exp(u128::from(rate), precision)?;
+	let exp_remaining = exp(
+		u128::from(rate).checked_mul(duration.checked_sub(elapsed)?)?,
+		precision.checked_mul(duration)?,
+	)?;
+
+	interpolate(
+		initial_weight,
+		final_weight,
+		exp_k.saturating_sub(exp_remaining),
+		exp_k.checked_sub(FIXED_ONE)?,
+	)
+}
+
+/// Calculate weight of the curve which divides the sale into `steps` intervals of equal length.
+///
+/// The weight stays constant within an interval and equals the linear weight at the start of the interval.
+pub fn calculate_step_weights<BlockNumber: AtLeast32BitUnsigned>(
+	start: BlockNumber,
+	end: BlockNumber,
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+	steps: u32,
+	at: BlockNumber,
+) -> Option<LBPWeight> {
+	let (elapsed, duration) = sale_progress(start, end, at)?;
+
+	if steps == 0 {
+		return None;
+	}
+
+	let steps = u128::from(steps);
+	let step = elapsed.checked_mul(steps)?.checked_div(duration)?;
+
+	interpolate(initial_weight, final_weight, step, steps)
+}
+
+/// Calculate weight of the piecewise linear curve going through `breakpoints`.
+///
+/// Breakpoint `(progress, weight)` sets `weight` at `progress` of the sale. Breakpoints are expected to be sorted
+/// by progress. The curve starts at `initial_weight` and ends at `final_weight`.
+pub fn calculate_piecewise_weights<BlockNumber: AtLeast32BitUnsigned>(
+	start: BlockNumber,
+	end: BlockNumber,
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+	breakpoints: &[(Permill, LBPWeight)],
+	at: BlockNumber,
+) -> Option<LBPWeight> {
+	let (elapsed, duration) = sale_progress(start, end, at)?;
+
+	let mut from = (0u128, initial_weight);
+
+	for (progress, weight) in breakpoints {
+		let to = (progress.mul_floor(duration), *weight);
+		if elapsed <= to.0 {
+			return interpolate_between(from, to, elapsed);
+		}
+		from = to;
+	}
+
+	interpolate_between(from, (duration, final_weight), elapsed)
+}
+
+/// Calculate amounts of a graduated pool liquidity.
+///
+/// `liquidity` part of the reserve of asset a is paired with asset b at the spot price given by the reserves
+/// and weights, `reserve_b * weight_a / (reserve_a * weight_b)`. Amounts are reduced to fit into the reserve
+/// of asset b if there is not enough of it.
+///
+/// Returns `(amount_a, amount_b)`.
+pub fn calculate_graduation_liquidity(
+	reserve_a: Balance,
+	reserve_b: Balance,
+	weight_a: LBPWeight,
+	weight_b: LBPWeight,
+	liquidity: Permill,
+) -> Option<(Balance, Balance)> {
+	// price of asset a denominated in asset b as a fraction
+	let price_n = U256::from(reserve_b).checked_mul(U256::from(weight_a))?;
+	let price_d = U256::from(reserve_a).checked_mul(U256::from(weight_b))?;
+
+	if price_n.is_zero() || price_d.is_zero() {
+		return None;
+	}
+
+	let amount_a = U256::from(liquidity.mul_floor(reserve_a));
+	let amount_b = amount_a.checked_mul(price_n)?.checked_div(price_d)?;
+
+	let available_b = U256::from(reserve_b);
+
+	let (amount_a, amount_b) = if amount_b > available_b {
+		(available_b.checked_mul(price_d)?.checked_div(price_n)?, available_b)
+	} else {
+		(amount_a, amount_b)
+	};
+
+	Some((Balance::try_from(amount_a).ok()?, Balance::try_from(amount_b).ok()?))
+}
+
+/// Return elapsed blocks and duration of the sale.
+fn sale_progress<BlockNumber: AtLeast32BitUnsigned>(
+	start: BlockNumber,
+	end: BlockNumber,
+	at: BlockNumber,
+) -> Option<(u128, u128)> {
+	if start >= end || at < start || at > end {
+		return None;
+	}
+
+	let elapsed: u128 = (at - start.clone()).unique_saturated_into();
+	let duration: u128 = (end - start).unique_saturated_into();
+
+	Some((elapsed, duration))
+}
+
+/// Linearly interpolate weight between points `(block, weight)` of the curve.
+///
+/// Weight of the first point is used if both points are at the same block.
+fn interpolate_between(from: (u128, LBPWeight), to: (u128, LBPWeight), at: u128) -> Option<LBPWeight> {
+	if to.0 <= from.0 {
+		return Some(from.1);
+	}
+
+	interpolate(from.1, to.1, at.checked_sub(from.0)?, to.0 - from.0)
+}
+
+/// Move `numerator / denominator` of the way from `from` weight to `to` weight. Rounded towards `from`.
+fn interpolate(from: LBPWeight, to: LBPWeight, numerator: u128, denominator: u128) -> Option<LBPWeight> {
+	if numerator > denominator {
+		return None;
+	}
+
+	let distance = u128::from(from.max(to) - from.min(to));
+	let delta = LBPWeight::try_from(distance.checked_mul(numerator)?.checked_div(denominator)?).ok()?;
+
+	if to >= from {
+		from.checked_add(delta)
+	} else {
+		from.checked_sub(delta)
+	}
+}
+
+/// Calculate `e^(numerator / denominator)` scaled by `FIXED_ONE`.
+fn exp(numerator: u128, denominator: u128) -> Option<u128> {
+	let one = U256::from(FIXED_ONE);
+	let x = U256::from(numerator)
+		.checked_mul(one)?
+		.checked_div(U256::from(denominator))?;
+
+	let mut sum = one;
+	let mut term = one;
+
+	for n in 1..=MAX_ITERATIONS {
+		term = term.checked_mul(x)?.checked_div(one.checked_mul(U256::from(n))?)?;
+		if term.is_zero() {
+			return u128::try_from(sum).ok();
+		}
+		sum = sum.checked_add(term)?;
+	}
+
+	None
+}
@@ -0,0 +1,137 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Config, Graduation, Pallet, Pool, PoolData, WeightCurveType, DEFAULT_REPAY_FEE};
+use codec::{Decode, Encode};
+use frame_support::{
+	sp_runtime::traits::AtLeast32BitUnsigned,
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use hydra_dx_math::types::LBPWeight;
+use primitives::{AssetId, Balance};
+
+/// Pool as stored before graduation was introduced.
+#[derive(Encode, Decode)]
+struct PoolV0<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
+	owner: AccountId,
+	start: Option<BlockNumber>,
+	end: Option<BlockNumber>,
+	assets: (AssetId, AssetId),
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+	weight_curve: WeightCurveType,
+	fee: (u32, u32),
+	fee_collector: AccountId,
+	repay_target: Balance,
+}
+
+/// Pool as stored before repay fee was introduced.
+#[derive(Encode, Decode)]
+struct PoolV1<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> {
+	owner: AccountId,
+	start: Option<BlockNumber>,
+	end: Option<BlockNumber>,
+	assets: (AssetId, AssetId),
+	initial_weight: LBPWeight,
+	final_weight: LBPWeight,
+	weight_curve: WeightCurveType,
+	fee: (u32, u32),
+	fee_collector: AccountId,
+	repay_target: Balance,
+	graduation: Option<Graduation<BlockNumber>>,
+}
+
+impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> From<PoolV0<AccountId, BlockNumber>>
+	for PoolV1<AccountId, BlockNumber>
+{
+	fn from(pool: PoolV0<AccountId, BlockNumber>) -> Self {
+		PoolV1 {
+			owner: pool.owner,
+			start: pool.start,
+			end: pool.end,
+			assets: pool.assets,
+			initial_weight: pool.initial_weight,
+			final_weight: pool.final_weight,
+			weight_curve: pool.weight_curve,
+			fee: pool.fee,
+			fee_collector: pool.fee_collector,
+			repay_target: pool.repay_target,
+			graduation: None,
+		}
+	}
+}
+
+impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> From<PoolV1<AccountId, BlockNumber>>
+	for Pool<AccountId, BlockNumber>
+{
+	fn from(pool: PoolV1<AccountId, BlockNumber>) -> Self {
+		Pool {
+			owner: pool.owner,
+			start: pool.start,
+			end: pool.end,
+			assets: pool.assets,
+			initial_weight: pool.initial_weight,
+			final_weight: pool.final_weight,
+			weight_curve: pool.weight_curve,
+			fee: pool.fee,
+			fee_collector: pool.fee_collector,
+			repay_target: pool.repay_target,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: pool.graduation,
+		}
+	}
+}
+
+/// Add empty graduation (v1) and the default repay fee (v2) to all stored pools.
+///
+/// Runs only if the on-chain storage version is lower than 2.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 2 {
+		log::info!(
+			target: "runtime::lbp",
+			"Pools are already migrated to v2, skipping migration"
+		);
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut pools: u64 = 0;
+
+	if on_chain_version < 1 {
+		<PoolData<T>>::translate_values::<PoolV0<T::AccountId, T::BlockNumber>, _>(|pool| {
+			pools = pools.saturating_add(1);
+			Some(PoolV1::from(pool).into())
+		});
+	} else {
+		<PoolData<T>>::translate_values::<PoolV1<T::AccountId, T::BlockNumber>, _>(|pool| {
+			pools = pools.saturating_add(1);
+			Some(pool.into())
+		});
+	}
+
+	StorageVersion::new(2).put::<Pallet<T>>();
+
+	log::info!(
+		target: "runtime::lbp",
+		"Migrated {} pools to v2",
+		pools
+	);
+
+	T::DbWeight::get().reads_writes(pools.saturating_add(1), pools.saturating_add(1))
+}
@@ -43,6 +43,9 @@ pub const KUSD_BSX_POOL_ID: AccountId = 2_003_000;
 
 pub const DEFAULT_FEE: (u32, u32) = (2, 1_000);
 
+pub const XYK_POOL_ID: AccountId = 100;
+pub const XYK_SHARE_TOKEN: AssetId = 9_000;
+
 pub const SAMPLE_POOL_DATA: Pool<AccountId, BlockNumber> = Pool {
 	owner: ALICE,
 	start: SALE_START,
@@ -54,6 +57,8 @@ pub const SAMPLE_POOL_DATA: Pool<AccountId, BlockNumber> = Pool {
 	fee: DEFAULT_FEE,
 	fee_collector: CHARLIE,
 	repay_target: 0,
+	repay_fee: DEFAULT_REPAY_FEE,
+	graduation: None,
 };
 
 pub const SAMPLE_AMM_TRANSFER: AMMTransfer<AccountId, AssetId, AssetPair, Balance> = AMMTransfer {
@@ -159,6 +164,8 @@ parameter_types! {
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const MinRepayFee: (u32, u32) = (0, 1);
+	pub const MaxRepayFee: (u32, u32) = (2, 10);
 }
 
 pub struct MultiLockedBalance();
@@ -185,11 +192,41 @@ impl LockedBalance<AssetId, AccountId, Balance> for MultiLockedBalance {
 	}
 }
 
+/// Moves graduated liquidity into `XYK_POOL_ID` and mints `amount_a` of `XYK_SHARE_TOKEN`.
+pub struct XYKGraduationMock;
+
+impl GraduationHandler<AccountId> for XYKGraduationMock {
+	fn create_pool(
+		who: &AccountId,
+		asset_a: AssetId,
+		amount_a: Balance,
+		asset_b: AssetId,
+		amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError> {
+		ensure!(
+			Currency::free_balance(asset_a, &XYK_POOL_ID).is_zero(),
+			DispatchError::Other("Pool already exists")
+		);
+
+		<Currency as MultiCurrency<AccountId>>::transfer(asset_a, who, &XYK_POOL_ID, amount_a)?;
+		<Currency as MultiCurrency<AccountId>>::transfer(asset_b, who, &XYK_POOL_ID, amount_b)?;
+		<Currency as MultiCurrency<AccountId>>::deposit(XYK_SHARE_TOKEN, who, amount_a)?;
+
+		Ok((XYK_SHARE_TOKEN, amount_a))
+	}
+
+	fn create_pool_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
 impl Config for Test {
 	type Event = Event;
 	type MultiCurrency = Currency;
+	type BalanceInspect = Currency;
 	type LockedBalance = MultiLockedBalance;
 	type CreatePoolOrigin = frame_system::EnsureRoot<u64>;
+	type SaleAdminOrigin = frame_system::EnsureRoot<u64>;
 	type LBPWeightFunction = lbp::LBPWeightFunction;
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type WeightInfo = ();
@@ -197,7 +234,11 @@ impl Config for Test {
 	type MinPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MinRepayFee = MinRepayFee;
+	type MaxRepayFee = MaxRepayFee;
 	type BlockNumberProvider = System;
+	type AMMHandler = ();
+	type GraduationHandler = XYKGraduationMock;
 }
 
 pub struct ExtBuilder {
@@ -18,14 +18,19 @@
 #![allow(clippy::bool_assert_comparison)]
 use super::*;
 use crate::mock::{
-	expect_events, generate_trades, run_to_sale_end, run_to_sale_start, Call, DEFAULT_FEE, EXISTENTIAL_DEPOSIT,
-	HDX_BSX_POOL_ID, INITIAL_BALANCE, KUSD_BSX_POOL_ID, SALE_END, SALE_START, SAMPLE_AMM_TRANSFER, SAMPLE_POOL_DATA,
+	expect_events, generate_trades, run_to_sale_end, run_to_sale_start, AccountId, BlockNumber, Call, DEFAULT_FEE,
+	EXISTENTIAL_DEPOSIT, HDX_BSX_POOL_ID, INITIAL_BALANCE, KUSD_BSX_POOL_ID, SALE_END, SALE_START, SAMPLE_AMM_TRANSFER,
+	SAMPLE_POOL_DATA, XYK_POOL_ID, XYK_SHARE_TOKEN,
 };
 pub use crate::mock::{
 	set_block_number, Currency, Event as TestEvent, ExtBuilder, LBPPallet, Origin, Test, ALICE, BOB, BSX, CHARLIE, ETH,
 	HDX, KUSD,
 };
-use frame_support::{assert_err, assert_noop, assert_ok, dispatch::Dispatchable};
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	dispatch::Dispatchable,
+	traits::{GetStorageVersion, Hooks, StorageVersion},
+};
 use hydradx_traits::{AMMTransfer, LockedBalance};
 use sp_runtime::traits::BadOrigin;
 use sp_std::convert::TryInto;
@@ -58,6 +63,7 @@ pub fn predefined_test_ext() -> sp_io::TestExternalities {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -71,6 +77,7 @@ pub fn predefined_test_ext() -> sp_io::TestExternalities {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		let pool_data2 = Pool {
@@ -84,6 +91,8 @@ pub fn predefined_test_ext() -> sp_io::TestExternalities {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: None,
 		};
 
 		assert_eq!(<PoolData<Test>>::get(KUSD_BSX_POOL_ID).unwrap(), pool_data2);
@@ -125,6 +134,7 @@ pub fn predefined_test_ext_with_repay_target() -> sp_io::TestExternalities {
 			DEFAULT_FEE,
 			CHARLIE,
 			initial_liquidity,
+			None,
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -138,6 +148,7 @@ pub fn predefined_test_ext_with_repay_target() -> sp_io::TestExternalities {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		assert_ok!(LBPPallet::add_liquidity(
@@ -173,6 +184,8 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: None,
 		};
 		assert_ok!(LBPPallet::validate_pool_data(&pool_data));
 
@@ -188,6 +201,8 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: None,
 		};
 		assert_ok!(LBPPallet::validate_pool_data(&pool_data));
 
@@ -202,6 +217,8 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: None,
 		};
 		assert_noop!(
 			LBPPallet::validate_pool_data(&pool_data),
@@ -219,6 +236,8 @@ fn validate_pool_data_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: None,
 		};
 		assert_noop!(
 			LBPPallet::validate_pool_data(&pool_data),
@@ -241,6 +260,8 @@ fn max_sale_duration_ckeck() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: None,
 		}));
 		assert_noop!(
 			LBPPallet::validate_pool_data(&Pool {
@@ -254,6 +275,8 @@ fn max_sale_duration_ckeck() {
 				fee: DEFAULT_FEE,
 				fee_collector: CHARLIE,
 				repay_target: 0,
+				repay_fee: DEFAULT_REPAY_FEE,
+				graduation: None,
 			}),
 			Error::<Test>::MaxSaleDurationExceeded
 		);
@@ -274,6 +297,8 @@ fn calculate_weights_should_work() {
 			fee: DEFAULT_FEE,
 			fee_collector: CHARLIE,
 			repay_target: 0,
+			repay_fee: DEFAULT_REPAY_FEE,
+			graduation: None,
 		};
 		assert_eq!(LBPPallet::calculate_weights(&pool_data, 170), Ok((38333333, 61666667)));
 
@@ -335,6 +360,7 @@ fn create_pool_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_eq!(Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID), 1_000_000_000);
@@ -390,6 +416,7 @@ fn create_pool_from_basic_origin_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			BadOrigin
 		);
@@ -412,6 +439,7 @@ fn create_same_pool_should_not_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_noop!(
@@ -428,6 +456,7 @@ fn create_same_pool_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::PoolAlreadyExists
 		);
@@ -460,6 +489,7 @@ fn create_pool_with_same_assets_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::CannotCreatePoolWithSameAssets
 		);
@@ -482,6 +512,7 @@ fn create_pool_with_non_existing_fee_collector_with_asset_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_ok!(LBPPallet::create_pool(
@@ -497,6 +528,7 @@ fn create_pool_with_non_existing_fee_collector_with_asset_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		),);
 	});
 }
@@ -517,6 +549,7 @@ fn create_pool_with_existing_fee_collector_with_asset_should_not_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_noop!(
@@ -533,6 +566,7 @@ fn create_pool_with_existing_fee_collector_with_asset_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::FeeCollectorWithAssetAlreadyUsed
 		);
@@ -556,6 +590,7 @@ fn create_pool_with_insufficient_liquidity_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::InsufficientLiquidity
 		);
@@ -574,6 +609,7 @@ fn create_pool_with_insufficient_liquidity_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::InsufficientLiquidity
 		);
@@ -592,6 +628,7 @@ fn create_pool_with_insufficient_liquidity_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::InsufficientLiquidity
 		);
@@ -615,6 +652,7 @@ fn create_pool_with_insufficient_balance_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::InsufficientAssetBalance
 		);
@@ -636,6 +674,7 @@ fn update_pool_data_should_work() {
 			Some((5, 100)),
 			Some(BOB),
 			None,
+			None,
 		));
 
 		// verify changes
@@ -664,6 +703,7 @@ fn update_pool_data_should_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		// verify changes
@@ -687,6 +727,7 @@ fn update_pool_data_should_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		// verify changes
@@ -710,6 +751,7 @@ fn update_pool_data_should_work() {
 			None,
 			Some(ALICE),
 			None,
+			None,
 		));
 
 		// verify changes
@@ -733,6 +775,7 @@ fn update_pool_data_should_work() {
 			Some((6, 1_000)),
 			None,
 			None,
+			None,
 		));
 
 		// verify changes
@@ -758,6 +801,7 @@ fn update_pool_data_should_work() {
 			None,
 			None,
 			Some(repayment),
+			None,
 		));
 		let updated_pool_data_6 = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
 		assert_eq!(updated_pool_data_6.repay_target, repayment);
@@ -812,6 +856,7 @@ fn update_non_existing_pool_data_should_not_work() {
 				Some((5, 100)),
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::PoolNotFound
 		);
@@ -834,6 +879,7 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some((5, 100)),
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -852,6 +898,7 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some((5, 100)),
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -868,6 +915,7 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some((5, 100)),
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -884,6 +932,7 @@ fn update_pool_with_invalid_data_should_not_work() {
 				Some((5, 100)),
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -905,6 +954,7 @@ fn update_pool_data_without_changes_should_not_work() {
 				None,
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::NothingToUpdate
 		);
@@ -926,6 +976,7 @@ fn update_pool_data_by_non_owner_should_not_work() {
 				None,
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::NotOwner
 		);
@@ -946,6 +997,7 @@ fn update_pool_owner_by_new_owner_should_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		let pool_data1 = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
@@ -961,6 +1013,7 @@ fn update_pool_owner_by_new_owner_should_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		let pool_data2 = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
@@ -994,6 +1047,7 @@ fn update_pool_data_for_running_lbp_should_not_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		set_block_number::<Test>(16);
@@ -1011,6 +1065,7 @@ fn update_pool_data_for_running_lbp_should_not_work() {
 				Some((5, 100)),
 				Some(BOB),
 				None,
+				None,
 			),
 			Error::<Test>::SaleStarted
 		);
@@ -1041,6 +1096,7 @@ fn update_pool_with_existing_fee_collector_should_not_work() {
 			DEFAULT_FEE,
 			BOB,
 			0,
+			None,
 		));
 
 		assert_noop!(
@@ -1055,6 +1111,7 @@ fn update_pool_with_existing_fee_collector_should_not_work() {
 				Some((5, 100)),
 				Some(BOB),
 				None,
+				None,
 			),
 			Error::<Test>::FeeCollectorWithAssetAlreadyUsed
 		);
@@ -1077,6 +1134,7 @@ fn update_pool_interval_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		set_block_number::<Test>(15);
@@ -1093,6 +1151,7 @@ fn update_pool_interval_should_work() {
 				None,
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::InvalidBlockRange
 		);
@@ -1108,6 +1167,7 @@ fn update_pool_interval_should_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		// verify changes
@@ -1498,6 +1558,7 @@ fn remove_liquidity_from_not_started_pool_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		let user_balance_a_before = Currency::free_balance(HDX, &ALICE);
@@ -1875,6 +1936,7 @@ fn zero_weight_should_not_work() {
 				DEFAULT_FEE,
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::InvalidWeight
 		);
@@ -1889,6 +1951,7 @@ fn zero_weight_should_not_work() {
 			fee: Some((5, 100)),
 			fee_collector: Some(BOB),
 			repay_target: Some(0),
+			repay_fee: None,
 		});
 
 		assert_noop!(call.dispatch(Origin::signed(ALICE)), Error::<Test>::InvalidWeight);
@@ -2277,6 +2340,7 @@ fn buy_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		let pool_data1 = LBPPallet::pool_data(pool_id2).unwrap();
@@ -2328,6 +2392,7 @@ fn buy_should_work() {
 			None,
 			None,
 			None,
+			None,
 			None
 		));
 
@@ -2468,6 +2533,7 @@ fn update_pool_data_after_sale_should_not_work() {
 				None,
 				None,
 				None,
+				None,
 			),
 			Error::<Test>::SaleStarted
 		);
@@ -2526,6 +2592,7 @@ fn sell_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 		let pool_data1 = LBPPallet::pool_data(pool_id2).unwrap();
 
@@ -2577,6 +2644,7 @@ fn sell_should_work() {
 			None,
 			None,
 			None,
+			None,
 			None
 		));
 
@@ -2631,6 +2699,7 @@ fn zero_fee_should_work() {
 			(0, 100),
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -2643,6 +2712,7 @@ fn zero_fee_should_work() {
 			None,
 			None,
 			None,
+			None,
 			None
 		));
 
@@ -2670,6 +2740,7 @@ fn invalid_fee_should_not_work() {
 				(10, 0),
 				CHARLIE,
 				0,
+				None,
 			),
 			Error::<Test>::FeeAmountInvalid
 		);
@@ -2770,6 +2841,7 @@ fn amm_trait_should_work() {
 			(400, 1_000),
 			CHARLIE,
 			0,
+			None,
 		));
 
 		let pool_id = LBPPallet::get_pair_id(AssetPair {
@@ -2799,6 +2871,7 @@ fn get_spot_price_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -2811,6 +2884,7 @@ fn get_spot_price_should_work() {
 			None,
 			None,
 			None,
+			None,
 			None
 		));
 
@@ -2953,6 +3027,7 @@ fn simulate_lbp_event_should_work() {
 			fee,
 			fee_collector,
 			0,
+			None,
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -2965,6 +3040,7 @@ fn simulate_lbp_event_should_work() {
 			None,
 			None,
 			None,
+			None,
 			None
 		));
 
@@ -3239,6 +3315,7 @@ fn validate_trade_should_not_work() {
 			(10, 1),
 			CHARLIE,
 			0,
+			None,
 		));
 		let pool_id2 = LBPPallet::get_pair_id(AssetPair {
 			asset_in: KUSD,
@@ -3254,6 +3331,7 @@ fn validate_trade_should_not_work() {
 			None,
 			None,
 			None,
+			None,
 			None
 		));
 	});
@@ -3314,6 +3392,7 @@ fn calculate_fees_should_work() {
 			(10, 1),
 			CHARLIE,
 			0,
+			None,
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -3327,6 +3406,7 @@ fn calculate_fees_should_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 
 		let pool = LBPPallet::pool_data(HDX_BSX_POOL_ID).unwrap();
@@ -3364,6 +3444,7 @@ fn can_create_should_work() {
 			DEFAULT_FEE,
 			CHARLIE,
 			0,
+			None,
 		));
 		// pool is not initialized
 		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(
@@ -3382,6 +3463,7 @@ fn can_create_should_work() {
 			None,
 			None,
 			None,
+			None,
 		));
 		// pool is initialized but is not running
 		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(
@@ -3478,9 +3560,167 @@ fn calculate_repay_fee() {
 }
 
 #[test]
-fn repay_fee_should_work() {
+fn default_repay_fee_should_work() {
+	predefined_test_ext().execute_with(|| {
+		assert_eq!(LBPPallet::default_repay_fee(), (2, 10));
+		assert_eq!(
+			LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap().repay_fee,
+			DEFAULT_REPAY_FEE
+		);
+	});
+}
+
+#[test]
+fn create_pool_with_repay_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::create_pool(
+			Origin::root(),
+			ALICE,
+			KUSD,
+			1_000_000_000,
+			BSX,
+			2_000_000_000,
+			80_000_000,
+			20_000_000,
+			WeightCurveType::Linear,
+			DEFAULT_FEE,
+			CHARLIE,
+			1_000_000_000,
+			Some((1, 10)),
+		));
+
+		let pool = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool.repay_fee, (1, 10));
+		assert_eq!(LBPPallet::calculate_fees(&pool, 1000).unwrap(), 100);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_repay_fee_is_invalid() {
+	new_test_ext().execute_with(|| {
+		for repay_fee in [(3, 10), (1, 0), (2, 1)] {
+			assert_noop!(
+				LBPPallet::create_pool(
+					Origin::root(),
+					ALICE,
+					KUSD,
+					1_000_000_000,
+					BSX,
+					2_000_000_000,
+					80_000_000,
+					20_000_000,
+					WeightCurveType::Linear,
+					DEFAULT_FEE,
+					CHARLIE,
+					1_000_000_000,
+					Some(repay_fee),
+				),
+				Error::<Test>::InvalidRepayFee
+			);
+		}
+	});
+}
+
+#[test]
+fn update_pool_data_should_update_repay_fee() {
+	predefined_test_ext_with_repay_target().execute_with(|| {
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some((0, 1)),
+		));
+
+		let pool = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool.repay_fee, (0, 1));
+		assert!(LBPPallet::is_repay_fee_applied(&pool));
+
+		// the repay target is not reached, so the zero repay fee is charged instead of the trading fee
+		assert_eq!(LBPPallet::calculate_fees(&pool, 1000).unwrap(), 0);
+
+		assert_noop!(
+			LBPPallet::update_pool_data(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				None,
+				None,
+				None,
+				None,
+				None,
+				None,
+				None,
+				None,
+				Some((3, 10)),
+			),
+			Error::<Test>::InvalidRepayFee
+		);
+	});
+}
+
+#[test]
+fn migration_should_add_graduation_and_default_repay_fee() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(LBPPallet::repay_fee(), (2, 10));
+		let pool = SAMPLE_POOL_DATA;
+		let pool_v0 = (
+			pool.owner,
+			pool.start,
+			pool.end,
+			pool.assets,
+			pool.initial_weight,
+			pool.final_weight,
+			pool.weight_curve.clone(),
+			pool.fee,
+			pool.fee_collector,
+			pool.repay_target,
+		);
+		frame_support::storage::unhashed::put(&<PoolData<Test>>::hashed_key_for(KUSD_BSX_POOL_ID), &pool_v0);
+
+		let graduation = Some(Graduation {
+			liquidity: Permill::from_percent(10),
+			lock_shares_until: None,
+		});
+		let pool_v1 = (
+			pool.owner,
+			pool.start,
+			pool.end,
+			(HDX, BSX),
+			pool.initial_weight,
+			pool.final_weight,
+			pool.weight_curve.clone(),
+			pool.fee,
+			pool.fee_collector,
+			pool.repay_target,
+			graduation,
+		);
+
+		crate::migration::migrate_to_v2::<Test>();
+
+		assert_eq!(LBPPallet::pool_data(KUSD_BSX_POOL_ID), Some(SAMPLE_POOL_DATA));
+		assert_eq!(LBPPallet::on_chain_storage_version(), StorageVersion::new(2));
+
+		// pools stored in v1 keep their graduation
+		StorageVersion::new(1).put::<LBPPallet>();
+		<PoolData<Test>>::remove(KUSD_BSX_POOL_ID);
+		frame_support::storage::unhashed::put(&<PoolData<Test>>::hashed_key_for(HDX_BSX_POOL_ID), &pool_v1);
+
+		crate::migration::migrate_to_v2::<Test>();
+
+		assert_eq!(
+			LBPPallet::pool_data(HDX_BSX_POOL_ID),
+			Some(Pool {
+				assets: (HDX, BSX),
+				graduation,
+				..SAMPLE_POOL_DATA
+			})
+		);
+		assert_eq!(LBPPallet::on_chain_storage_version(), StorageVersion::new(2));
 	});
 }
 
@@ -3581,6 +3821,7 @@ fn simulate_lbp_event_with_repayment() {
 			fee,
 			fee_collector,
 			0,
+			None,
 		));
 
 		assert_ok!(LBPPallet::update_pool_data(
@@ -3593,6 +3834,7 @@ fn simulate_lbp_event_with_repayment() {
 			None,
 			None,
 			None,
+			None,
 			None
 		));
 
@@ -3661,3 +3903,1547 @@ fn simulate_lbp_event_with_repayment() {
 		assert_eq!(Currency::free_balance(sold_asset, &fee_collector), 0);
 	});
 }
+
+#[test]
+fn spot_price_provider_should_use_current_weights() {
+	predefined_test_ext().execute_with(|| {
+		use hydradx_traits::pools::SpotPriceProvider;
+
+		assert!(LBPSpotPrice::<Test>::pair_exists(BSX, KUSD));
+		assert!(!LBPSpotPrice::<Test>::pair_exists(HDX, KUSD));
+		assert_eq!(LBPSpotPrice::<Test>::spot_price(HDX, KUSD), None);
+
+		run_to_sale_start();
+
+		// (2_000_000_000 / 80%) / (1_000_000_000 / 20%)
+		assert_eq!(
+			LBPSpotPrice::<Test>::spot_price(KUSD, BSX),
+			Some(Price::saturating_from_rational(1, 2))
+		);
+		assert_eq!(
+			LBPSpotPrice::<Test>::spot_price(BSX, KUSD),
+			Some(Price::saturating_from_rational(2, 1))
+		);
+
+		// weights are 50% - 50% in the middle of the sale
+		set_block_number::<Test>(25);
+
+		assert_eq!(
+			LBPSpotPrice::<Test>::spot_price(KUSD, BSX),
+			Some(Price::saturating_from_rational(2, 1))
+		);
+	});
+}
+
+#[test]
+fn buy_with_deadline_should_work_when_deadline_is_not_reached() {
+	predefined_test_ext().execute_with(|| {
+		let buyer = BOB;
+
+		//start sale
+		set_block_number::<Test>(11);
+		assert_ok!(LBPPallet::buy_with_deadline(
+			Origin::signed(buyer),
+			BSX,
+			KUSD,
+			10_000_000_u128,
+			2_000_000_000_u128,
+			11
+		));
+
+		expect_events(vec![Event::BuyExecuted {
+			who: buyer,
+			asset_out: BSX,
+			asset_in: KUSD,
+			amount: 17_894_744,
+			buy_price: 10_000_000,
+			fee_asset: KUSD,
+			fee_amount: 35_860,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn trade_with_deadline_should_not_work_when_deadline_has_passed() {
+	predefined_test_ext().execute_with(|| {
+		//start sale
+		set_block_number::<Test>(11);
+
+		assert_noop!(
+			LBPPallet::sell_with_deadline(Origin::signed(BOB), KUSD, BSX, 800_000_u128, 200_000_u128, 10),
+			Error::<Test>::DeadlineExpired
+		);
+		assert_noop!(
+			LBPPallet::buy_with_deadline(Origin::signed(BOB), BSX, KUSD, 10_000_000_u128, 2_000_000_000_u128, 10),
+			Error::<Test>::DeadlineExpired
+		);
+	});
+}
+
+#[test]
+fn trade_execution_should_not_support_pair_without_pool() {
+	predefined_test_ext().execute_with(|| {
+		use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+
+		set_block_number::<Test>(11);
+
+		assert!(matches!(
+			LBPPallet::calculate_sell(PoolType::LBP, HDX, BSX, 1_000_000),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			LBPPallet::calculate_buy(PoolType::LBP, HDX, BSX, 1_000_000),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			<LBPPallet as TradeExecution<_, _, _, _>>::execute_sell(
+				Origin::signed(BOB),
+				PoolType::LBP,
+				HDX,
+				BSX,
+				1_000_000,
+				0
+			),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			<LBPPallet as TradeExecution<_, _, _, _>>::execute_buy(
+				Origin::signed(BOB),
+				PoolType::LBP,
+				HDX,
+				BSX,
+				1_000_000,
+				u128::MAX
+			),
+			Err(ExecutorError::NotSupported)
+		));
+
+		assert!(LBPPallet::calculate_sell(PoolType::LBP, KUSD, BSX, 1_000_000).is_ok());
+	});
+}
+
+#[test]
+fn sell_all_should_sell_whole_free_balance() {
+	predefined_test_ext().execute_with(|| {
+		let seller = 4;
+
+		<Test as Config>::MultiCurrency::update_balance(KUSD, &seller, 10_000_000).unwrap();
+
+		//start sale
+		set_block_number::<Test>(11);
+
+		assert_ok!(LBPPallet::sell_all(Origin::signed(seller), KUSD, BSX, 2_000_u128));
+
+		expect_events(vec![Event::SellExecuted {
+			who: seller,
+			asset_in: KUSD,
+			asset_out: BSX,
+			amount: 9_980_000,
+			sale_price: 5_605_128,
+			fee_asset: KUSD,
+			fee_amount: 20_000,
+		}
+		.into()]);
+
+		assert_eq!(Currency::free_balance(KUSD, &seller), 0);
+		assert_eq!(Currency::free_balance(BSX, &seller), 5_605_128);
+	});
+}
+
+#[test]
+fn sell_all_should_not_sell_locked_balance() {
+	predefined_test_ext().execute_with(|| {
+		let seller = 4;
+
+		<Test as Config>::MultiCurrency::update_balance(KUSD, &seller, 10_000_000).unwrap();
+		assert_ok!(Currency::set_lock(*b"testlock", KUSD, &seller, 4_000_000));
+
+		//start sale
+		set_block_number::<Test>(11);
+
+		assert_ok!(LBPPallet::sell_all(Origin::signed(seller), KUSD, BSX, 0_u128));
+
+		assert_eq!(Currency::free_balance(KUSD, &seller), 4_000_000);
+		assert!(Currency::free_balance(BSX, &seller) > 0);
+	});
+}
+
+#[test]
+fn sell_all_should_respect_limit() {
+	predefined_test_ext().execute_with(|| {
+		let seller = 4;
+
+		<Test as Config>::MultiCurrency::update_balance(KUSD, &seller, 10_000_000).unwrap();
+
+		//start sale
+		set_block_number::<Test>(11);
+
+		assert_noop!(
+			LBPPallet::sell_all(Origin::signed(seller), KUSD, BSX, 5_605_129_u128),
+			Error::<Test>::TradingLimitReached
+		);
+	});
+}
+
+#[test]
+fn sell_all_should_not_work_when_balance_is_zero() {
+	predefined_test_ext().execute_with(|| {
+		//start sale
+		set_block_number::<Test>(11);
+
+		assert_noop!(
+			LBPPallet::sell_all(Origin::signed(CHARLIE), KUSD, BSX, 0_u128),
+			Error::<Test>::ZeroAmount
+		);
+	});
+}
+
+fn breakpoints(points: Vec<(Permill, LBPWeight)>) -> WeightCurveBreakpoints {
+	WeightCurveBreakpoints::try_from(points).unwrap()
+}
+
+#[test]
+fn invalid_weight_curve_should_not_work() {
+	predefined_test_ext().execute_with(|| {
+		let invalid_curves = vec![
+			WeightCurveType::ExponentialDecay { rate: 0 },
+			WeightCurveType::ExponentialDecay {
+				rate: MAX_EXPONENTIAL_DECAY_RATE + 1,
+			},
+			WeightCurveType::StepWise { steps: 0 },
+			WeightCurveType::Piecewise(breakpoints(vec![])),
+			WeightCurveType::Piecewise(breakpoints(vec![(Permill::zero(), 50_000_000)])),
+			WeightCurveType::Piecewise(breakpoints(vec![(Permill::from_percent(100), 50_000_000)])),
+			WeightCurveType::Piecewise(breakpoints(vec![(Permill::from_percent(50), 0)])),
+			WeightCurveType::Piecewise(breakpoints(vec![(Permill::from_percent(50), MAX_WEIGHT)])),
+			WeightCurveType::Piecewise(breakpoints(vec![
+				(Permill::from_percent(50), 50_000_000),
+				(Permill::from_percent(50), 60_000_000),
+			])),
+			WeightCurveType::Piecewise(breakpoints(vec![
+				(Permill::from_percent(60), 50_000_000),
+				(Permill::from_percent(40), 60_000_000),
+			])),
+		];
+
+		for weight_curve in invalid_curves {
+			assert_noop!(
+				LBPPallet::create_pool(
+					Origin::root(),
+					ALICE,
+					ETH,
+					1_000_000_000,
+					KUSD,
+					2_000_000_000,
+					20_000_000,
+					80_000_000,
+					weight_curve,
+					DEFAULT_FEE,
+					CHARLIE,
+					0,
+					None,
+				),
+				Error::<Test>::InvalidWeightCurve
+			);
+		}
+	});
+}
+
+#[test]
+fn create_pool_with_non_linear_weight_curve_should_work() {
+	predefined_test_ext().execute_with(|| {
+		let weight_curve = WeightCurveType::Piecewise(breakpoints(vec![(Permill::from_percent(50), 30_000_000)]));
+
+		assert_ok!(LBPPallet::create_pool(
+			Origin::root(),
+			ALICE,
+			ETH,
+			1_000_000_000,
+			KUSD,
+			2_000_000_000,
+			20_000_000,
+			80_000_000,
+			weight_curve.clone(),
+			DEFAULT_FEE,
+			CHARLIE,
+			0,
+			None,
+		));
+
+		let pool_id = LBPPallet::get_pair_id(AssetPair::new(ETH, KUSD));
+
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			pool_id,
+			None,
+			Some(10),
+			Some(40),
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+		));
+
+		let pool = LBPPallet::pool_data(pool_id).unwrap();
+		assert_eq!(pool.weight_curve, weight_curve);
+
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool, 25),
+			Ok((30_000_000, MAX_WEIGHT - 30_000_000))
+		);
+		assert_eq!(
+			LBPPallet::calculate_weights(&pool, 40),
+			Ok((80_000_000, MAX_WEIGHT - 80_000_000))
+		);
+
+		set_block_number::<Test>(25);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, ETH, 1_000_000, 0));
+	});
+}
+
+#[test]
+fn step_weights_should_be_constant_within_step() {
+	assert_eq!(
+		math::calculate_step_weights(10u64, 40, 20_000_000, 80_000_000, 3, 10),
+		Some(20_000_000)
+	);
+	assert_eq!(
+		math::calculate_step_weights(10u64, 40, 20_000_000, 80_000_000, 3, 19),
+		Some(20_000_000)
+	);
+	assert_eq!(
+		math::calculate_step_weights(10u64, 40, 20_000_000, 80_000_000, 3, 20),
+		Some(40_000_000)
+	);
+	assert_eq!(
+		math::calculate_step_weights(10u64, 40, 20_000_000, 80_000_000, 3, 39),
+		Some(60_000_000)
+	);
+	assert_eq!(
+		math::calculate_step_weights(10u64, 40, 20_000_000, 80_000_000, 3, 40),
+		Some(80_000_000)
+	);
+	assert_eq!(
+		math::calculate_step_weights(10u64, 40, 80_000_000, 20_000_000, 3, 20),
+		Some(60_000_000)
+	);
+	assert_eq!(
+		math::calculate_step_weights(10u64, 40, 20_000_000, 80_000_000, 3, 41),
+		None
+	);
+}
+
+#[test]
+fn piecewise_weights_should_go_through_breakpoints() {
+	let points = vec![
+		(Permill::from_percent(25), 60_000_000),
+		(Permill::from_percent(50), 30_000_000),
+	];
+
+	assert_eq!(
+		math::calculate_piecewise_weights(0u64, 100, 20_000_000, 80_000_000, &points, 0),
+		Some(20_000_000)
+	);
+	assert_eq!(
+		math::calculate_piecewise_weights(0u64, 100, 20_000_000, 80_000_000, &points, 10),
+		Some(36_000_000)
+	);
+	assert_eq!(
+		math::calculate_piecewise_weights(0u64, 100, 20_000_000, 80_000_000, &points, 25),
+		Some(60_000_000)
+	);
+	assert_eq!(
+		math::calculate_piecewise_weights(0u64, 100, 20_000_000, 80_000_000, &points, 40),
+		Some(42_000_000)
+	);
+	assert_eq!(
+		math::calculate_piecewise_weights(0u64, 100, 20_000_000, 80_000_000, &points, 50),
+		Some(30_000_000)
+	);
+	assert_eq!(
+		math::calculate_piecewise_weights(0u64, 100, 20_000_000, 80_000_000, &points, 75),
+		Some(55_000_000)
+	);
+	assert_eq!(
+		math::calculate_piecewise_weights(0u64, 100, 20_000_000, 80_000_000, &points, 100),
+		Some(80_000_000)
+	);
+}
+
+#[test]
+fn exponential_weights_should_change_faster_at_the_start() {
+	assert_eq!(
+		math::calculate_exponential_weights(10u64, 40, 20_000_000, 80_000_000, 1_000, 10),
+		Some(20_000_000)
+	);
+	assert_eq!(
+		math::calculate_exponential_weights(10u64, 40, 20_000_000, 80_000_000, 1_000, 40),
+		Some(80_000_000)
+	);
+
+	// (e - e^0.5) / (e - 1) of the way from the initial to the final weight
+	let weight = math::calculate_exponential_weights(10u64, 40, 20_000_000, 80_000_000, 1_000, 25).unwrap();
+	assert!((57_347_000..=57_348_000).contains(&weight));
+
+	let weight = math::calculate_exponential_weights(10u64, 40, 80_000_000, 20_000_000, 1_000, 25).unwrap();
+	assert!((42_652_000..=42_653_000).contains(&weight));
+}
+
+fn graduation(liquidity: u32, lock_shares_until: Option<BlockNumber>) -> Graduation<BlockNumber> {
+	Graduation {
+		liquidity: Permill::from_percent(liquidity),
+		lock_shares_until,
+	}
+}
+
+#[test]
+fn set_graduation_should_work() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, Some(50))),
+		));
+
+		assert_eq!(
+			LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap().graduation,
+			Some(graduation(10, Some(50)))
+		);
+		assert!(<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+
+		expect_events(vec![Event::GraduationSet {
+			pool: KUSD_BSX_POOL_ID,
+			graduation: Some(graduation(10, Some(50))),
+		}
+		.into()]);
+
+		assert_ok!(LBPPallet::set_graduation(Origin::signed(ALICE), KUSD_BSX_POOL_ID, None));
+
+		assert_eq!(LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap().graduation, None);
+		assert!(!<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn set_graduation_should_not_work_when_invalid() {
+	predefined_test_ext().execute_with(|| {
+		assert_noop!(
+			LBPPallet::set_graduation(Origin::signed(BOB), KUSD_BSX_POOL_ID, Some(graduation(10, None))),
+			Error::<Test>::NotOwner
+		);
+
+		assert_noop!(
+			LBPPallet::set_graduation(Origin::signed(ALICE), HDX_BSX_POOL_ID, Some(graduation(10, None))),
+			Error::<Test>::PoolNotFound
+		);
+
+		assert_noop!(
+			LBPPallet::set_graduation(Origin::signed(ALICE), KUSD_BSX_POOL_ID, Some(graduation(0, None))),
+			Error::<Test>::InvalidGraduation
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::set_graduation(Origin::signed(ALICE), KUSD_BSX_POOL_ID, Some(graduation(10, None))),
+			Error::<Test>::SaleStarted
+		);
+	});
+}
+
+#[test]
+fn graduation_should_seed_xyk_pool_at_final_spot_price() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, None)),
+		));
+
+		run_to_sale_end();
+
+		// liquidity is not removed before the graduation
+		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+		assert_noop!(
+			LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::GraduationPending
+		);
+
+		LBPPallet::on_idle(SALE_END.unwrap() + 1, Weight::MAX);
+
+		assert!(!<PoolData<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert!(!<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert!(DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+
+		// final weights are 80% KUSD and 20% BSX, so 1 KUSD is priced at 8 BSX
+		assert_eq!(Currency::free_balance(KUSD, &XYK_POOL_ID), 100_000_000);
+		assert_eq!(Currency::free_balance(BSX, &XYK_POOL_ID), 800_000_000);
+
+		assert_eq!(Currency::free_balance(KUSD, &ALICE), INITIAL_BALANCE - 100_000_000);
+		assert_eq!(Currency::free_balance(BSX, &ALICE), INITIAL_BALANCE - 800_000_000);
+		assert_eq!(Currency::free_balance(XYK_SHARE_TOKEN, &ALICE), 100_000_000);
+
+		assert_eq!(LBPPallet::graduated_shares_lock(ALICE, XYK_SHARE_TOKEN), None);
+
+		expect_events(vec![
+			Event::LiquidityRemoved {
+				who: KUSD_BSX_POOL_ID,
+				asset_a: KUSD,
+				asset_b: BSX,
+				amount_a: 1_000_000_000,
+				amount_b: 2_000_000_000,
+			}
+			.into(),
+			Event::PoolGraduated {
+				pool: KUSD_BSX_POOL_ID,
+				asset_a: KUSD,
+				asset_b: BSX,
+				amount_a: 100_000_000,
+				amount_b: 800_000_000,
+				share_token: XYK_SHARE_TOKEN,
+				shares: 100_000_000,
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn graduation_should_be_limited_by_reserve_of_asset_b() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(50, None)),
+		));
+
+		run_to_sale_end();
+
+		LBPPallet::on_idle(SALE_END.unwrap() + 1, Weight::MAX);
+
+		assert_eq!(Currency::free_balance(KUSD, &XYK_POOL_ID), 250_000_000);
+		assert_eq!(Currency::free_balance(BSX, &XYK_POOL_ID), 2_000_000_000);
+	});
+}
+
+#[test]
+fn graduation_should_wait_for_sale_end() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, None)),
+		));
+
+		set_block_number::<Test>(SALE_END.unwrap());
+
+		LBPPallet::on_idle(SALE_END.unwrap(), Weight::MAX);
+
+		assert!(<PoolData<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert!(<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert_eq!(Currency::free_balance(KUSD, &XYK_POOL_ID), 0);
+	});
+}
+
+#[test]
+fn graduation_should_wait_for_enough_remaining_weight() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, None)),
+		));
+
+		run_to_sale_end();
+
+		let graduation_weight = <Test as Config>::WeightInfo::graduate_pool()
+			.saturating_add(<Test as Config>::GraduationHandler::create_pool_weight())
+			.saturating_add(<Test as frame_system::Config>::DbWeight::get().reads_writes(2, 1));
+
+		LBPPallet::on_idle(
+			SALE_END.unwrap() + 1,
+			graduation_weight.saturating_sub(Weight::from_ref_time(1)),
+		);
+
+		assert!(<PoolData<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert!(<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+
+		let used_weight = LBPPallet::on_idle(SALE_END.unwrap() + 2, graduation_weight);
+
+		assert!(used_weight <= graduation_weight);
+		assert!(!<PoolData<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert!(!<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn ended_pool_should_graduate_when_more_pools_are_pending_than_fit_in_a_block() {
+	predefined_test_ext().execute_with(|| {
+		let mut pools = vec![KUSD_BSX_POOL_ID];
+
+		let pairs = [(HDX, BSX), (ETH, BSX), (HDX, KUSD), (ETH, KUSD), (HDX, ETH)];
+		for (i, (asset_a, asset_b)) in pairs.into_iter().enumerate() {
+			assert_ok!(LBPPallet::create_pool(
+				Origin::root(),
+				ALICE,
+				asset_a,
+				1_000_000_000,
+				asset_b,
+				2_000_000_000,
+				80_000_000,
+				20_000_000,
+				WeightCurveType::Linear,
+				DEFAULT_FEE,
+				200 + i as AccountId,
+				0,
+				None,
+			));
+
+			pools.push(LBPPallet::get_pair_id(AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			}));
+		}
+
+		// every sale ends long after SALE_END
+		for pool_id in pools.iter() {
+			assert_ok!(LBPPallet::update_pool_data(
+				Origin::signed(ALICE),
+				pool_id.clone(),
+				None,
+				SALE_START,
+				Some(1_000),
+				None,
+				None,
+				None,
+				None,
+				None,
+				None,
+			));
+			assert_ok!(LBPPallet::set_graduation(
+				Origin::signed(ALICE),
+				pool_id.clone(),
+				Some(graduation(10, None)),
+			));
+		}
+
+		// except the sale of the pool checked last
+		let ended_pool = <PendingGraduations<Test>>::iter_keys().last().unwrap();
+		assert_ok!(LBPPallet::update_pool_data(
+			Origin::signed(ALICE),
+			ended_pool.clone(),
+			None,
+			None,
+			SALE_END,
+			None,
+			None,
+			None,
+			None,
+			None,
+			None,
+		));
+
+		run_to_sale_end();
+
+		// enough weight to check a single pending pool in a block
+		let block_weight = <Test as Config>::WeightInfo::graduate_pool()
+			.saturating_add(<Test as Config>::GraduationHandler::create_pool_weight())
+			.saturating_add(<Test as frame_system::Config>::DbWeight::get().reads_writes(2, 1));
+
+		for n in 1..pools.len() as u64 {
+			LBPPallet::on_idle(SALE_END.unwrap() + n, block_weight);
+		}
+
+		assert!(<PendingGraduations<Test>>::contains_key(&ended_pool));
+
+		LBPPallet::on_idle(SALE_END.unwrap() + pools.len() as u64, block_weight);
+
+		assert!(!<PendingGraduations<Test>>::contains_key(&ended_pool));
+		assert!(!<PoolData<Test>>::contains_key(&ended_pool));
+		assert_eq!(<PendingGraduations<Test>>::iter_keys().count(), pools.len() - 1);
+	});
+}
+
+#[test]
+fn graduated_shares_should_be_locked() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, Some(50))),
+		));
+
+		run_to_sale_end();
+
+		LBPPallet::on_idle(SALE_END.unwrap() + 1, Weight::MAX);
+
+		assert_eq!(LBPPallet::graduated_shares_lock(ALICE, XYK_SHARE_TOKEN), Some(50));
+		assert_noop!(
+			Currency::transfer(Origin::signed(ALICE), BOB, XYK_SHARE_TOKEN, 1),
+			orml_tokens::Error::<Test>::LiquidityRestrictions
+		);
+
+		assert_noop!(
+			LBPPallet::unlock_graduated_shares(Origin::signed(ALICE), XYK_SHARE_TOKEN),
+			Error::<Test>::SharesLocked
+		);
+		assert_noop!(
+			LBPPallet::unlock_graduated_shares(Origin::signed(BOB), XYK_SHARE_TOKEN),
+			Error::<Test>::SharesNotLocked
+		);
+
+		set_block_number::<Test>(50);
+
+		assert_ok!(LBPPallet::unlock_graduated_shares(
+			Origin::signed(ALICE),
+			XYK_SHARE_TOKEN
+		));
+
+		assert_eq!(LBPPallet::graduated_shares_lock(ALICE, XYK_SHARE_TOKEN), None);
+		assert_ok!(Currency::transfer(Origin::signed(ALICE), BOB, XYK_SHARE_TOKEN, 1));
+
+		expect_events(vec![Event::GraduatedSharesUnlocked {
+			who: ALICE,
+			share_token: XYK_SHARE_TOKEN,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn failed_graduation_should_leave_liquidity_to_the_owner() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, None)),
+		));
+
+		// XYK pool of the asset pair already exists
+		assert_ok!(<Currency as MultiCurrency<AccountId>>::deposit(
+			KUSD,
+			&XYK_POOL_ID,
+			1_000
+		));
+
+		run_to_sale_end();
+
+		LBPPallet::on_idle(SALE_END.unwrap() + 1, Weight::MAX);
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.graduation, None);
+		assert!(!<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert_eq!(Currency::free_balance(KUSD, &KUSD_BSX_POOL_ID), 1_000_000_000);
+		assert_eq!(Currency::free_balance(BSX, &KUSD_BSX_POOL_ID), 2_000_000_000);
+
+		expect_events(vec![Event::GraduationFailed {
+			pool: KUSD_BSX_POOL_ID,
+			error: DispatchError::Other("Pool already exists"),
+		}
+		.into()]);
+
+		assert!(DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn removing_liquidity_before_sale_should_cancel_graduation() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, None)),
+		));
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert!(!<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+	});
+}
+
+fn sale_limits(
+	max_spend_per_account: Option<Balance>,
+	max_trade_per_block: Option<Balance>,
+	allowlist: Option<Allowlist<AccountId>>,
+) -> SaleLimits<AccountId> {
+	SaleLimits {
+		max_spend_per_account,
+		max_trade_per_block,
+		allowlist,
+	}
+}
+
+fn allowed_accounts(accounts: Vec<AccountId>) -> Option<Allowlist<AccountId>> {
+	Some(Allowlist::Accounts(accounts.try_into().unwrap()))
+}
+
+#[test]
+fn set_sale_limits_should_work() {
+	predefined_test_ext().execute_with(|| {
+		let limits = sale_limits(Some(1_000), Some(2_000), allowed_accounts(vec![BOB]));
+
+		assert_ok!(LBPPallet::set_sale_limits(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(limits.clone()),
+		));
+
+		assert_eq!(LBPPallet::sale_limits(KUSD_BSX_POOL_ID), Some(limits.clone()));
+
+		expect_events(vec![Event::SaleLimitsSet {
+			pool: KUSD_BSX_POOL_ID,
+			limits: Some(limits),
+		}
+		.into()]);
+
+		assert_ok!(LBPPallet::set_sale_limits(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			None
+		));
+
+		assert_eq!(LBPPallet::sale_limits(KUSD_BSX_POOL_ID), None);
+	});
+}
+
+#[test]
+fn set_sale_limits_should_not_work_when_invalid() {
+	predefined_test_ext().execute_with(|| {
+		assert_noop!(
+			LBPPallet::set_sale_limits(
+				Origin::signed(BOB),
+				KUSD_BSX_POOL_ID,
+				Some(sale_limits(Some(1_000), None, None))
+			),
+			Error::<Test>::NotOwner
+		);
+
+		assert_noop!(
+			LBPPallet::set_sale_limits(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				Some(sale_limits(Some(0), None, None))
+			),
+			Error::<Test>::InvalidSaleLimits
+		);
+
+		assert_noop!(
+			LBPPallet::set_sale_limits(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				Some(sale_limits(None, Some(0), None))
+			),
+			Error::<Test>::InvalidSaleLimits
+		);
+
+		assert_noop!(
+			LBPPallet::set_sale_limits(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				Some(sale_limits(None, None, allowed_accounts(vec![])))
+			),
+			Error::<Test>::InvalidSaleLimits
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::set_sale_limits(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				Some(sale_limits(Some(1_000), None, None))
+			),
+			Error::<Test>::SaleStarted
+		);
+	});
+}
+
+#[test]
+fn spend_of_account_should_be_limited() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_sale_limits(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(sale_limits(Some(1_500_000), None, None)),
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0));
+
+		// fee is included in the spent amount
+		assert_eq!(LBPPallet::account_spend(KUSD_BSX_POOL_ID, BOB), 1_000_000);
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0),
+			Error::<Test>::SpendLimitExceeded
+		);
+
+		// selling the distributed asset does not spend the accumulated asset
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), BSX, KUSD, 1_000_000, 0));
+		assert_eq!(LBPPallet::account_spend(KUSD_BSX_POOL_ID, BOB), 1_000_000);
+
+		// limit is per account
+		assert_ok!(LBPPallet::sell(Origin::signed(ALICE), KUSD, BSX, 1_000_000, 0));
+	});
+}
+
+#[test]
+fn trades_in_block_should_be_limited() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_sale_limits(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(sale_limits(None, Some(1_500_000), None)),
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0));
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(ALICE), KUSD, BSX, 1_000_000, 0),
+			Error::<Test>::BlockTradeLimitExceeded
+		);
+
+		set_block_number::<Test>(SALE_START.unwrap() + 1);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(ALICE), KUSD, BSX, 1_000_000, 0));
+	});
+}
+
+#[test]
+fn trades_should_be_limited_to_allowed_accounts() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_sale_limits(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(sale_limits(None, None, allowed_accounts(vec![BOB]))),
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0));
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(ALICE), KUSD, BSX, 1_000_000, 0),
+			Error::<Test>::NotAllowlisted
+		);
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(ALICE), KUSD, BSX, 1_000_000, 2_000_000),
+			Error::<Test>::NotAllowlisted
+		);
+	});
+}
+
+#[test]
+fn trades_should_be_limited_to_accounts_proving_merkle_allowlist() {
+	predefined_test_ext().execute_with(|| {
+		let bob_leaf = BlakeTwo256::hash_of(&BOB);
+		let charlie_leaf = BlakeTwo256::hash_of(&CHARLIE);
+		let (left, right) = if bob_leaf <= charlie_leaf {
+			(bob_leaf, charlie_leaf)
+		} else {
+			(charlie_leaf, bob_leaf)
+		};
+		let root = BlakeTwo256::hash(&[left.as_bytes(), right.as_bytes()].concat());
+
+		assert_ok!(LBPPallet::set_sale_limits(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(sale_limits(None, None, Some(Allowlist::MerkleRoot(root)))),
+		));
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0),
+			Error::<Test>::NotAllowlisted
+		);
+
+		assert_noop!(
+			LBPPallet::join_allowlist(
+				Origin::signed(BOB),
+				KUSD_BSX_POOL_ID,
+				vec![H256::zero()].try_into().unwrap()
+			),
+			Error::<Test>::InvalidAllowlistProof
+		);
+		assert_noop!(
+			LBPPallet::join_allowlist(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				vec![charlie_leaf].try_into().unwrap()
+			),
+			Error::<Test>::InvalidAllowlistProof
+		);
+
+		assert_ok!(LBPPallet::join_allowlist(
+			Origin::signed(BOB),
+			KUSD_BSX_POOL_ID,
+			vec![charlie_leaf].try_into().unwrap()
+		));
+
+		expect_events(vec![Event::AllowlistJoined {
+			pool: KUSD_BSX_POOL_ID,
+			who: BOB,
+		}
+		.into()]);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0));
+	});
+}
+
+#[test]
+fn remove_liquidity_should_clean_up_sale_limits() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_sale_limits(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(sale_limits(Some(1_500_000), Some(1_500_000), None)),
+		));
+
+		run_to_sale_start();
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0));
+
+		run_to_sale_end();
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert_eq!(LBPPallet::sale_limits(KUSD_BSX_POOL_ID), None);
+		assert!(!<BlockTradeVolume<Test>>::contains_key(KUSD_BSX_POOL_ID));
+
+		// account spends are removed in `on_idle`
+		assert!(<PendingCleanups<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert!(<AccountSpend<Test>>::contains_key(KUSD_BSX_POOL_ID, BOB));
+
+		LBPPallet::on_idle(SALE_END.unwrap() + 1, Weight::MAX);
+
+		assert_eq!(LBPPallet::account_spend(KUSD_BSX_POOL_ID, BOB), 0);
+		assert!(!<AccountSpend<Test>>::contains_key(KUSD_BSX_POOL_ID, BOB));
+		assert!(!<PendingCleanups<Test>>::contains_key(KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_until_previous_pool_is_cleaned_up() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+		assert!(<PendingCleanups<Test>>::contains_key(KUSD_BSX_POOL_ID));
+
+		assert_noop!(
+			LBPPallet::create_pool(
+				Origin::root(),
+				ALICE,
+				KUSD,
+				1_000_000_000,
+				BSX,
+				2_000_000_000,
+				20_000_000,
+				80_000_000,
+				WeightCurveType::Linear,
+				DEFAULT_FEE,
+				CHARLIE,
+				0,
+				None,
+			),
+			Error::<Test>::PoolCleanupPending
+		);
+
+		LBPPallet::on_idle(1, Weight::MAX);
+
+		assert_ok!(LBPPallet::create_pool(
+			Origin::root(),
+			ALICE,
+			KUSD,
+			1_000_000_000,
+			BSX,
+			2_000_000_000,
+			20_000_000,
+			80_000_000,
+			WeightCurveType::Linear,
+			DEFAULT_FEE,
+			CHARLIE,
+			0,
+			None,
+		));
+	});
+}
+
+#[test]
+fn pause_sale_should_work() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), Some((20, PauseOrigin::Admin)));
+		assert!(!LBPPallet::is_pool_running(
+			&LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap()
+		));
+
+		expect_events(vec![Event::SalePaused { pool: KUSD_BSX_POOL_ID }.into()]);
+	});
+}
+
+#[test]
+fn pause_sale_should_work_when_called_by_owner() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::signed(BOB), KUSD_BSX_POOL_ID),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), Some((20, PauseOrigin::Owner)));
+	});
+}
+
+#[test]
+fn pause_sale_should_not_work_when_sale_is_not_running() {
+	predefined_test_ext().execute_with(|| {
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleIsNotRunning
+		);
+
+		run_to_sale_end();
+
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleIsNotRunning
+		);
+
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::root(), HDX_BSX_POOL_ID),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn pause_sale_should_not_work_when_sale_is_paused() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		assert_noop!(
+			LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SalePaused
+		);
+	});
+}
+
+#[test]
+fn paused_sale_should_disallow_trades_and_liquidity_removal() {
+	predefined_test_ext().execute_with(|| {
+		use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0),
+			Error::<Test>::SaleIsNotRunning
+		);
+		assert_noop!(
+			LBPPallet::buy(Origin::signed(BOB), BSX, KUSD, 1_000_000, u128::MAX),
+			Error::<Test>::SaleIsNotRunning
+		);
+		assert!(matches!(
+			LBPPallet::calculate_sell(PoolType::LBP, KUSD, BSX, 1_000_000),
+			Err(ExecutorError::Error(_))
+		));
+		assert!(matches!(
+			LBPPallet::calculate_buy(PoolType::LBP, BSX, KUSD, 1_000_000),
+			Err(ExecutorError::Error(_))
+		));
+		assert!(matches!(
+			<LBPPallet as TradeExecution<_, _, _, _>>::execute_sell(
+				Origin::signed(BOB),
+				PoolType::LBP,
+				KUSD,
+				BSX,
+				1_000_000,
+				0
+			),
+			Err(ExecutorError::Error(_))
+		));
+
+		assert_noop!(
+			LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SalePaused
+		);
+		assert_noop!(
+			LBPPallet::update_pool_data(
+				Origin::signed(ALICE),
+				KUSD_BSX_POOL_ID,
+				None,
+				None,
+				Some(100),
+				None,
+				None,
+				None,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::SaleStarted
+		);
+
+		// the sale stays paused after the original end of the sale
+		run_to_sale_end();
+
+		assert!(!DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+		assert_noop!(
+			LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SalePaused
+		);
+	});
+}
+
+#[test]
+fn paused_sale_should_not_be_graduated() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, None)),
+		));
+
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		run_to_sale_end();
+
+		LBPPallet::on_idle(SALE_END.unwrap() + 1, Weight::MAX);
+
+		assert!(<PoolData<Test>>::contains_key(KUSD_BSX_POOL_ID));
+		assert!(<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn resume_sale_should_shift_schedule_by_paused_duration() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		let weights = LBPPallet::calculate_weights(&pool_data, 20).unwrap();
+
+		assert_ok!(LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		set_block_number::<Test>(30);
+
+		assert_ok!(LBPPallet::resume_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.start, Some(20));
+		assert_eq!(pool_data.end, Some(50));
+		assert_eq!(LBPPallet::calculate_weights(&pool_data, 30).unwrap(), weights);
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), None);
+
+		expect_events(vec![Event::SaleResumed {
+			pool: KUSD_BSX_POOL_ID,
+			start: 20,
+			end: 50,
+		}
+		.into()]);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0));
+
+		set_block_number::<Test>(50);
+		assert!(LBPPallet::is_pool_running(&pool_data));
+	});
+}
+
+#[test]
+fn resume_sale_should_not_work_when_not_allowed() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_noop!(
+			LBPPallet::resume_sale(Origin::root(), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleNotPaused
+		);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert_noop!(
+			LBPPallet::resume_sale(Origin::signed(BOB), KUSD_BSX_POOL_ID),
+			Error::<Test>::NotOwner
+		);
+
+		assert_noop!(LBPPallet::resume_sale(Origin::none(), KUSD_BSX_POOL_ID), BadOrigin);
+	});
+}
+
+#[test]
+fn resume_sale_should_work_when_called_by_owner() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		set_block_number::<Test>(30);
+
+		assert_ok!(LBPPallet::resume_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.start, Some(20));
+		assert_eq!(pool_data.end, Some(50));
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), None);
+
+		assert_ok!(LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0));
+	});
+}
+
+#[test]
+fn resume_sale_should_not_work_when_called_by_owner_and_paused_by_admin() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		set_block_number::<Test>(30);
+
+		assert_noop!(
+			LBPPallet::resume_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			Error::<Test>::SalePausedByAdmin
+		);
+
+		assert_ok!(LBPPallet::resume_sale(Origin::root(), KUSD_BSX_POOL_ID));
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), None);
+	});
+}
+
+#[test]
+fn cancel_sale_should_allow_owner_to_withdraw() {
+	predefined_test_ext().execute_with(|| {
+		assert_ok!(LBPPallet::set_graduation(
+			Origin::signed(ALICE),
+			KUSD_BSX_POOL_ID,
+			Some(graduation(10, None)),
+		));
+
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::cancel_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		let pool_data = LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap();
+		assert_eq!(pool_data.start, SALE_START);
+		assert_eq!(pool_data.end, Some(19));
+		assert_eq!(pool_data.graduation, None);
+		assert!(!<PendingGraduations<Test>>::contains_key(KUSD_BSX_POOL_ID));
+
+		expect_events(vec![Event::SaleCancelled { pool: KUSD_BSX_POOL_ID }.into()]);
+
+		assert_noop!(
+			LBPPallet::sell(Origin::signed(BOB), KUSD, BSX, 1_000_000, 0),
+			Error::<Test>::SaleIsNotRunning
+		);
+		assert!(DisallowWhenLBPPoolRunning::<Test>::can_create(KUSD, BSX));
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		assert!(!<PoolData<Test>>::contains_key(KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn cancel_sale_should_work_when_sale_is_paused() {
+	predefined_test_ext().execute_with(|| {
+		set_block_number::<Test>(20);
+
+		assert_ok!(LBPPallet::pause_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+
+		set_block_number::<Test>(50);
+
+		assert_ok!(LBPPallet::cancel_sale(Origin::root(), KUSD_BSX_POOL_ID));
+
+		assert_eq!(LBPPallet::paused_sale(KUSD_BSX_POOL_ID), None);
+		assert_eq!(LBPPallet::pool_data(KUSD_BSX_POOL_ID).unwrap().end, Some(49));
+
+		assert_ok!(LBPPallet::remove_liquidity(Origin::signed(ALICE), KUSD_BSX_POOL_ID));
+	});
+}
+
+#[test]
+fn cancel_sale_should_not_work_when_not_allowed() {
+	predefined_test_ext().execute_with(|| {
+		assert_noop!(
+			LBPPallet::cancel_sale(Origin::root(), KUSD_BSX_POOL_ID),
+			Error::<Test>::SaleIsNotRunning
+		);
+
+		run_to_sale_start();
+
+		assert_noop!(
+			LBPPallet::cancel_sale(Origin::signed(ALICE), KUSD_BSX_POOL_ID),
+			BadOrigin
+		);
+		assert_noop!(
+			LBPPallet::cancel_sale(Origin::root(), HDX_BSX_POOL_ID),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+mod weight_curves {
+	use super::*;
+	use proptest::prelude::*;
+
+	const START: u64 = 1_000;
+
+	fn weight() -> impl Strategy<Value = LBPWeight> {
+		1..MAX_WEIGHT
+	}
+
+	fn duration() -> impl Strategy<Value = u64> {
+		1..MAX_SALE_DURATION as u64
+	}
+
+	fn elapsed_and_duration() -> impl Strategy<Value = (u64, u64)> {
+		duration().prop_flat_map(|duration| (0..=duration, Just(duration)))
+	}
+
+	fn rate() -> impl Strategy<Value = u32> {
+		1..=MAX_EXPONENTIAL_DECAY_RATE
+	}
+
+	fn steps() -> impl Strategy<Value = u32> {
+		1..1_000u32
+	}
+
+	fn breakpoints() -> impl Strategy<Value = Vec<(Permill, LBPWeight)>> {
+		prop::collection::btree_map(1..1_000_000u32, weight(), 1..=MAX_WEIGHT_CURVE_BREAKPOINTS as usize).prop_map(
+			|points| {
+				points
+					.into_iter()
+					.map(|(progress, weight)| (Permill::from_parts(progress), weight))
+					.collect()
+			},
+		)
+	}
+
+	fn calculate(
+		weight_curve: &WeightCurveType,
+		initial_weight: LBPWeight,
+		final_weight: LBPWeight,
+		duration: u64,
+		at: u64,
+	) -> LBPWeight {
+		<LBPWeightFunction as LBPWeightCalculation<u64>>::calculate_weight(
+			weight_curve,
+			START,
+			START + duration,
+			initial_weight,
+			final_weight,
+			START + at,
+		)
+		.unwrap()
+	}
+
+	/// Weight at the start and at the end of the sale equals the initial and the final weight
+	/// and stays within their bounds during the sale.
+	fn assert_weight_bounds(
+		weight_curve: &WeightCurveType,
+		initial_weight: LBPWeight,
+		final_weight: LBPWeight,
+		elapsed: u64,
+		duration: u64,
+	) {
+		let weight = calculate(weight_curve, initial_weight, final_weight, duration, elapsed);
+
+		assert!(initial_weight.min(final_weight) <= weight && weight <= initial_weight.max(final_weight));
+		assert_eq!(
+			calculate(weight_curve, initial_weight, final_weight, duration, 0),
+			initial_weight
+		);
+		assert_eq!(
+			calculate(weight_curve, initial_weight, final_weight, duration, duration),
+			final_weight
+		);
+	}
+
+	/// Weight moves only in the direction of the final weight.
+	fn assert_weight_monotonic(
+		weight_curve: &WeightCurveType,
+		initial_weight: LBPWeight,
+		final_weight: LBPWeight,
+		elapsed: u64,
+		duration: u64,
+	) {
+		if elapsed == duration {
+			return;
+		}
+
+		let weight = calculate(weight_curve, initial_weight, final_weight, duration, elapsed);
+		let next_weight = calculate(weight_curve, initial_weight, final_weight, duration, elapsed + 1);
+
+		if initial_weight <= final_weight {
+			assert!(weight <= next_weight);
+		} else {
+			assert!(weight >= next_weight);
+		}
+	}
+
+	proptest! {
+		#![proptest_config(ProptestConfig::with_cases(1000))]
+		#[test]
+		fn exponential_weights_should_stay_within_bounds(
+			initial_weight in weight(),
+			final_weight in weight(),
+			(elapsed, duration) in elapsed_and_duration(),
+			rate in rate(),
+		) {
+			let weight_curve = WeightCurveType::ExponentialDecay { rate };
+			assert_weight_bounds(&weight_curve, initial_weight, final_weight, elapsed, duration);
+			assert_weight_monotonic(&weight_curve, initial_weight, final_weight, elapsed, duration);
+		}
+
+		#[test]
+		fn step_weights_should_stay_within_bounds(
+			initial_weight in weight(),
+			final_weight in weight(),
+			(elapsed, duration) in elapsed_and_duration(),
+			steps in steps(),
+		) {
+			let weight_curve = WeightCurveType::StepWise { steps };
+			assert_weight_bounds(&weight_curve, initial_weight, final_weight, elapsed, duration);
+			assert_weight_monotonic(&weight_curve, initial_weight, final_weight, elapsed, duration);
+		}
+
+		#[test]
+		fn piecewise_weights_should_stay_within_bounds(
+			initial_weight in weight(),
+			final_weight in weight(),
+			(elapsed, duration) in elapsed_and_duration(),
+			points in breakpoints(),
+		) {
+			let lowest = points.iter().map(|(_, weight)| *weight).chain([initial_weight, final_weight]).min().unwrap();
+			let highest = points.iter().map(|(_, weight)| *weight).chain([initial_weight, final_weight]).max().unwrap();
+
+			let weight_curve = WeightCurveType::Piecewise(super::breakpoints(points));
+			assert!(LBPPallet::is_weight_curve_valid(&weight_curve));
+
+			let weight = calculate(&weight_curve, initial_weight, final_weight, duration, elapsed);
+
+			assert!(lowest <= weight && weight <= highest);
+			assert_eq!(calculate(&weight_curve, initial_weight, final_weight, duration, 0), initial_weight);
+			assert_eq!(calculate(&weight_curve, initial_weight, final_weight, duration, duration), final_weight);
+		}
+	}
+}
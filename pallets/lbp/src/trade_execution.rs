@@ -7,6 +7,8 @@ use primitives::{AssetId, Balance};
 use sp_runtime::traits::BlockNumberProvider;
 use sp_runtime::DispatchError;
 
+/// Asset pairs without a LBP pool are not supported, so `PoolType::LBP` trades of such pairs can be handled
+/// by another executor, e.g. weighted pools.
 impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pallet<T> {
 	type Error = DispatchError;
 
@@ -16,7 +18,7 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 		asset_out: AssetId,
 		amount_in: Balance,
 	) -> Result<Balance, ExecutorError<Self::Error>> {
-		if pool_type != PoolType::LBP {
+		if pool_type != PoolType::LBP || !Self::exists(AssetPair { asset_in, asset_out }) {
 			return Err(ExecutorError::NotSupported);
 		}
 
@@ -25,6 +27,11 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 		let pool_data =
 			<PoolData<T>>::try_get(&pool_id).map_err(|_| ExecutorError::Error(Error::<T>::PoolNotFound.into()))?;
 
+		// paused and cancelled sales are not running
+		if !Self::is_pool_running(&pool_data) {
+			return Err(ExecutorError::Error(Error::<T>::SaleIsNotRunning.into()));
+		}
+
 		let now = T::BlockNumberProvider::current_block_number();
 		let (weight_in, weight_out) = Self::get_sorted_weight(assets.asset_in, now, &pool_data)
 			.map_err(|err| ExecutorError::Error(err.into()))?;
@@ -59,7 +66,7 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 		asset_out: AssetId,
 		amount_out: Balance,
 	) -> Result<Balance, ExecutorError<Self::Error>> {
-		if pool_type != PoolType::LBP {
+		if pool_type != PoolType::LBP || !Self::exists(AssetPair { asset_in, asset_out }) {
 			return Err(ExecutorError::NotSupported);
 		}
 
@@ -68,6 +75,11 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 		let pool_data =
 			<PoolData<T>>::try_get(&pool_id).map_err(|_| ExecutorError::Error(Error::<T>::PoolNotFound.into()))?;
 
+		// paused and cancelled sales are not running
+		if !Self::is_pool_running(&pool_data) {
+			return Err(ExecutorError::Error(Error::<T>::SaleIsNotRunning.into()));
+		}
+
 		let now = T::BlockNumberProvider::current_block_number();
 		let (weight_in, weight_out) = Self::get_sorted_weight(assets.asset_in, now, &pool_data)
 			.map_err(|err| ExecutorError::Error(err.into()))?;
@@ -113,7 +125,7 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 		amount_in: Balance,
 		min_limit: Balance,
 	) -> Result<(), ExecutorError<Self::Error>> {
-		if pool_type != PoolType::LBP {
+		if pool_type != PoolType::LBP || !Self::exists(AssetPair { asset_in, asset_out }) {
 			return Err(ExecutorError::NotSupported);
 		}
 
@@ -128,7 +140,7 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 		amount_out: Balance,
 		max_limit: Balance,
 	) -> Result<(), ExecutorError<Self::Error>> {
-		if pool_type != PoolType::LBP {
+		if pool_type != PoolType::LBP || !Self::exists(AssetPair { asset_in, asset_out }) {
 			return Err(ExecutorError::NotSupported);
 		}
 
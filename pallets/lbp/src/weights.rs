@@ -52,6 +52,14 @@ pub trait WeightInfo {
 	fn remove_liquidity() -> Weight;
 	fn sell() -> Weight;
 	fn buy() -> Weight;
+	fn set_graduation() -> Weight;
+	fn unlock_graduated_shares() -> Weight;
+	fn graduate_pool() -> Weight;
+	fn set_sale_limits() -> Weight;
+	fn join_allowlist() -> Weight;
+	fn pause_sale() -> Weight;
+	fn resume_sale() -> Weight;
+	fn cancel_sale() -> Weight;
 }
 
 /// Weights for lbp using the hack.hydraDX node and recommended hardware.
@@ -87,6 +95,46 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6 as u64))
 			.saturating_add(T::DbWeight::get().writes(6 as u64))
 	}
+	fn set_graduation() -> Weight {
+		Weight::from_ref_time(31_462_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn unlock_graduated_shares() -> Weight {
+		Weight::from_ref_time(38_204_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn graduate_pool() -> Weight {
+		Weight::from_ref_time(134_517_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	fn set_sale_limits() -> Weight {
+		Weight::from_ref_time(36_871_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn join_allowlist() -> Weight {
+		Weight::from_ref_time(41_592_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn pause_sale() -> Weight {
+		Weight::from_ref_time(32_416_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn resume_sale() -> Weight {
+		Weight::from_ref_time(36_870_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn cancel_sale() -> Weight {
+		Weight::from_ref_time(38_125_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -121,4 +169,44 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6 as u64))
 			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 	}
+	fn set_graduation() -> Weight {
+		Weight::from_ref_time(31_462_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn unlock_graduated_shares() -> Weight {
+		Weight::from_ref_time(38_204_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn graduate_pool() -> Weight {
+		Weight::from_ref_time(134_517_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn set_sale_limits() -> Weight {
+		Weight::from_ref_time(36_871_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn join_allowlist() -> Weight {
+		Weight::from_ref_time(41_592_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn pause_sale() -> Weight {
+		Weight::from_ref_time(32_416_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn resume_sale() -> Weight {
+		Weight::from_ref_time(36_870_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn cancel_sale() -> Weight {
+		Weight::from_ref_time(38_125_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
 }
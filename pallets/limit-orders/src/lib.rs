@@ -0,0 +1,488 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Limit orders pallet
+//!
+//! Users place orders to sell an asset for at least a limit price or to buy an asset for at most a limit price.
+//! Funds needed by an order are reserved when the order is placed and unreserved when it is filled or cancelled.
+//!
+//! Orders are stored as `ExchangeIntention`s and filled against the pools of `AMM` in `on_idle`, whenever the price
+//! offered by the pool, trading fee included, reaches the limit price of the order. If the pool can take only a part
+//! of the order at the limit price, the order is filled partially and stays open for the rest.
+//!
+//! * A `SELL` order sells `amount_in` of `assets.asset_in` for at least `trade_limit` of `assets.asset_out`.
+//! * A `BUY` order buys `amount_out` of `assets.asset_out` for at most `trade_limit` of `assets.asset_in`.
+//!
+//! Every fill decreases the traded amount and `trade_limit` proportionally, so the limit price of an order never
+//! changes. The other amount of the intention accumulates the amount received (`SELL`) or paid (`BUY`) by fills.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::sp_runtime::traits::Zero;
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional};
+use frame_system::ensure_signed;
+use hydradx_traits::AMM;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use primitive_types::U256;
+use primitives::{asset::AssetPair, AssetId, Balance, ExchangeIntention, IntentionType};
+use sp_std::vec::Vec;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+const LOG_TARGET: &str = "runtime::limit-orders";
+
+/// Identifier of an order.
+pub type OrderId = u32;
+
+/// Limit order of an account.
+pub type Order<AccountId> = ExchangeIntention<AccountId, Balance, OrderId>;
+
+/// Reserves of the pools of `AMM` used to calculate the fill of an order.
+pub trait PoolReserves<AccountId> {
+	/// Return reserve of `asset` in the pool of `pool_account`.
+	///
+	/// `None` if the pool can not be traded at the moment, e.g. while it is locked by a flash swap.
+	fn pool_reserve(pool_account: &AccountId, asset: AssetId) -> Option<Balance>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(crate) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Multi currency used to reserve the funds of orders.
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = AssetId, Balance = Balance>;
+
+		/// AMM whose pools fill the orders.
+		type AMM: AMM<Self::AccountId, AssetId, AssetPair, Balance>;
+
+		/// Reserves of the pools of `AMM`.
+		type PoolReserves: PoolReserves<Self::AccountId>;
+
+		/// Maximum number of orders checked for a fill in a block.
+		#[pallet::constant]
+		type MaxOrdersPerBlock: Get<u32>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// It is not allowed to trade an asset for itself.
+		CannotTradeSameAsset,
+
+		/// Pool for given assets does not exist.
+		PoolNotFound,
+
+		/// Amount is less than min trading limit of the AMM.
+		InsufficientTradingAmount,
+
+		/// Trade limit of the order is zero.
+		ZeroTradeLimit,
+
+		/// Order does not exist.
+		OrderNotFound,
+
+		/// Account is not the owner of the order.
+		NotOrderOwner,
+
+		/// Overflow
+		Overflow,
+
+		/// Reserves of the pool are not available, e.g. the pool is locked by a flash swap.
+		PoolReservesUnavailable,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Order was placed.
+		/// `amount` is the amount to sell for `SELL` orders and the amount to buy for `BUY` orders.
+		OrderPlaced {
+			order_id: OrderId,
+			who: T::AccountId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			intention_type: IntentionType,
+			amount: Balance,
+			trade_limit: Balance,
+		},
+
+		/// Order was filled, completely or partially, by a trade of `amount_in` for `amount_out`.
+		OrderFilled {
+			order_id: OrderId,
+			who: T::AccountId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			amount_out: Balance,
+		},
+
+		/// Order was completely filled and closed. Remaining reserved funds were unreserved.
+		OrderCompleted { order_id: OrderId, who: T::AccountId },
+
+		/// Order was cancelled by its owner. Remaining reserved funds were unreserved.
+		OrderCancelled { order_id: OrderId, who: T::AccountId },
+	}
+
+	/// Identifier of the next order.
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub(crate) type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// Open orders.
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub(crate) type Orders<T: Config> = StorageMap<_, Twox64Concat, OrderId, Order<T::AccountId>, OptionQuery>;
+
+	/// Last order checked for a fill. Checking continues with the following order in the next block.
+	#[pallet::storage]
+	#[pallet::getter(fn last_checked_order)]
+	pub(crate) type LastCheckedOrder<T: Config> = StorageValue<_, OrderId, OptionQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let base_weight = T::DbWeight::get().reads_writes(2, 1);
+			if remaining_weight <= base_weight {
+				return Weight::zero();
+			}
+
+			let fill_weight = T::WeightInfo::fill_order();
+			let max_orders = (remaining_weight.saturating_sub(base_weight).ref_time() / fill_weight.ref_time().max(1))
+				.min(T::MaxOrdersPerBlock::get() as u64) as usize;
+
+			let order_ids: Vec<OrderId> = match Self::last_checked_order() {
+				Some(last) => Orders::<T>::iter_keys_from(Orders::<T>::hashed_key_for(last)),
+				None => Orders::<T>::iter_keys(),
+			}
+			.take(max_orders)
+			.collect();
+
+			for order_id in order_ids.iter() {
+				if let Err(e) = Self::fill_order(*order_id) {
+					log::debug!(target: LOG_TARGET, "Order {:?} was not filled: {:?}", order_id, e);
+				}
+			}
+
+			// All orders up to the end of the map were checked, start from the beginning in the next block.
+			if order_ids.len() < max_orders {
+				LastCheckedOrder::<T>::kill();
+			} else {
+				LastCheckedOrder::<T>::set(order_ids.last().copied());
+			}
+
+			base_weight.saturating_add(fill_weight.saturating_mul(order_ids.len() as u64))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Place a limit order.
+		///
+		/// For a `SELL` order, `amount` of `asset_in` is sold for at least `trade_limit` of `asset_out`
+		/// and `amount` of `asset_in` is reserved.
+		/// For a `BUY` order, `amount` of `asset_out` is bought for at most `trade_limit` of `asset_in`
+		/// and `trade_limit` of `asset_in` is reserved.
+		///
+		/// Emits `OrderPlaced` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			intention_type: IntentionType,
+			amount: Balance,
+			trade_limit: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(asset_in != asset_out, Error::<T>::CannotTradeSameAsset);
+
+			let assets = AssetPair { asset_in, asset_out };
+			ensure!(T::AMM::exists(assets), Error::<T>::PoolNotFound);
+
+			ensure!(
+				amount >= T::AMM::get_min_trading_limit(),
+				Error::<T>::InsufficientTradingAmount
+			);
+			ensure!(!trade_limit.is_zero(), Error::<T>::ZeroTradeLimit);
+
+			let order_id = Self::next_order_id();
+			let order = match intention_type {
+				IntentionType::SELL => {
+					T::Currency::reserve(asset_in, &who, amount)?;
+					Order {
+						who: who.clone(),
+						assets,
+						amount_in: amount,
+						amount_out: Balance::zero(),
+						trade_limit,
+						discount: false,
+						sell_or_buy: intention_type,
+						intention_id: order_id,
+					}
+				}
+				IntentionType::BUY => {
+					T::Currency::reserve(asset_in, &who, trade_limit)?;
+					Order {
+						who: who.clone(),
+						assets,
+						amount_in: Balance::zero(),
+						amount_out: amount,
+						trade_limit,
+						discount: false,
+						sell_or_buy: intention_type,
+						intention_id: order_id,
+					}
+				}
+			};
+
+			NextOrderId::<T>::put(order_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+			Orders::<T>::insert(order_id, order);
+
+			Self::deposit_event(Event::OrderPlaced {
+				order_id,
+				who,
+				asset_in,
+				asset_out,
+				intention_type,
+				amount,
+				trade_limit,
+			});
+
+			Ok(())
+		}
+
+		/// Cancel an open order and unreserve its remaining funds.
+		///
+		/// Can only be called by the owner of the order.
+		///
+		/// Emits `OrderCancelled` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.who == who, Error::<T>::NotOrderOwner);
+
+			Self::close_order(order_id, &order);
+
+			Self::deposit_event(Event::OrderCancelled { order_id, who });
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Fill as much of the order as the pool allows at the limit price of the order.
+	///
+	/// Nothing is done if the price of the pool does not reach the limit price.
+	#[transactional]
+	pub fn fill_order(order_id: OrderId) -> DispatchResult {
+		let mut order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+
+		ensure!(T::AMM::exists(order.assets), Error::<T>::PoolNotFound);
+
+		let pool = T::AMM::get_pair_id(order.assets);
+		let reserve_in =
+			T::PoolReserves::pool_reserve(&pool, order.assets.asset_in).ok_or(Error::<T>::PoolReservesUnavailable)?;
+		let reserve_out =
+			T::PoolReserves::pool_reserve(&pool, order.assets.asset_out).ok_or(Error::<T>::PoolReservesUnavailable)?;
+		let fee = T::AMM::get_fee(&pool);
+		let min_trading_limit = T::AMM::get_min_trading_limit();
+
+		let (amount_in, amount_out, remaining) = match order.sell_or_buy {
+			IntentionType::SELL => {
+				let amount =
+					calculate_max_sell_amount(reserve_in, reserve_out, order.amount_in, order.trade_limit, fee)
+						.min(order.amount_in)
+						.min(reserve_in / T::AMM::get_max_in_ratio());
+				if amount < min_trading_limit {
+					return Ok(());
+				}
+
+				let min_bought = if amount == order.amount_in {
+					order.trade_limit
+				} else {
+					multiply_by_rational_ceil(amount, order.trade_limit, order.amount_in).ok_or(Error::<T>::Overflow)?
+				};
+
+				T::Currency::unreserve(order.assets.asset_in, &order.who, amount);
+
+				let balance_before = T::Currency::free_balance(order.assets.asset_out, &order.who);
+				T::AMM::sell(&order.who, order.assets, amount, min_bought, false)?;
+				let received =
+					T::Currency::free_balance(order.assets.asset_out, &order.who).saturating_sub(balance_before);
+
+				order.amount_in = order.amount_in.saturating_sub(amount);
+				order.trade_limit = order.trade_limit.saturating_sub(min_bought);
+				order.amount_out = order.amount_out.saturating_add(received);
+
+				(amount, received, order.amount_in)
+			}
+			IntentionType::BUY => {
+				let amount =
+					calculate_max_buy_amount(reserve_in, reserve_out, order.amount_out, order.trade_limit, fee)
+						.min(order.amount_out)
+						.min(reserve_out / T::AMM::get_max_out_ratio());
+				if amount < min_trading_limit {
+					return Ok(());
+				}
+
+				let max_sold = if amount == order.amount_out {
+					order.trade_limit
+				} else {
+					multiply_by_rational_floor(amount, order.trade_limit, order.amount_out)
+						.ok_or(Error::<T>::Overflow)?
+				};
+
+				T::Currency::unreserve(order.assets.asset_in, &order.who, max_sold);
+
+				let balance_before = T::Currency::free_balance(order.assets.asset_in, &order.who);
+				T::AMM::buy(&order.who, order.assets, amount, max_sold, false)?;
+				let paid = balance_before.saturating_sub(T::Currency::free_balance(order.assets.asset_in, &order.who));
+
+				order.amount_out = order.amount_out.saturating_sub(amount);
+				order.trade_limit = order.trade_limit.saturating_sub(max_sold);
+				order.amount_in = order.amount_in.saturating_add(paid);
+
+				(paid, amount, order.amount_out)
+			}
+		};
+
+		Self::deposit_event(Event::OrderFilled {
+			order_id,
+			who: order.who.clone(),
+			asset_in: order.assets.asset_in,
+			asset_out: order.assets.asset_out,
+			amount_in,
+			amount_out,
+		});
+
+		// The rest of the order can not be traded anymore.
+		if remaining < min_trading_limit {
+			Self::close_order(order_id, &order);
+			Self::deposit_event(Event::OrderCompleted {
+				order_id,
+				who: order.who,
+			});
+		} else {
+			Orders::<T>::insert(order_id, order);
+		}
+
+		Ok(())
+	}
+
+	/// Remove the order and unreserve its remaining funds.
+	fn close_order(order_id: OrderId, order: &Order<T::AccountId>) {
+		let reserved = match order.sell_or_buy {
+			IntentionType::SELL => order.amount_in,
+			IntentionType::BUY => order.trade_limit,
+		};
+		T::Currency::unreserve(order.assets.asset_in, &order.who, reserved);
+
+		Orders::<T>::remove(order_id);
+	}
+}
+
+/// Fills are kept slightly inside the limit price, so that rounding of the pool math can not push them over it.
+const FILL_MARGIN: Balance = 1_000;
+
+/// Maximum amount `x` which can be sold to a pool at the price of at least `trade_limit / amount`.
+///
+/// With the fee `f` taken from the amount out: `x = (1 - f) * reserve_out * amount / trade_limit - reserve_in`.
+fn calculate_max_sell_amount(
+	reserve_in: Balance,
+	reserve_out: Balance,
+	amount: Balance,
+	trade_limit: Balance,
+	fee: (u32, u32),
+) -> Balance {
+	let (fee_numerator, fee_denominator) = if fee.1 == 0 { (0, 1) } else { fee };
+
+	let max_in = U256::from(fee_denominator.saturating_sub(fee_numerator))
+		.checked_mul(U256::from(reserve_out))
+		.and_then(|v| v.checked_mul(U256::from(amount)))
+		.and_then(|v| v.checked_div(U256::from(fee_denominator).checked_mul(U256::from(trade_limit))?))
+		.and_then(|v| Balance::try_from(v).ok())
+		.unwrap_or(Balance::MAX);
+
+	let max_amount = max_in.saturating_sub(reserve_in);
+	max_amount.saturating_sub(max_amount / FILL_MARGIN)
+}
+
+/// Maximum amount `y` which can be bought from a pool at the price of at most `trade_limit / amount`.
+///
+/// With the fee `f` added to the amount in: `y = reserve_out - (1 + f) * reserve_in * amount / trade_limit`.
+fn calculate_max_buy_amount(
+	reserve_in: Balance,
+	reserve_out: Balance,
+	amount: Balance,
+	trade_limit: Balance,
+	fee: (u32, u32),
+) -> Balance {
+	let (fee_numerator, fee_denominator) = if fee.1 == 0 { (0, 1) } else { fee };
+
+	let min_reserve_out = U256::from(fee_denominator.saturating_add(fee_numerator))
+		.checked_mul(U256::from(reserve_in))
+		.and_then(|v| v.checked_mul(U256::from(amount)))
+		.and_then(|v| v.checked_div(U256::from(fee_denominator).checked_mul(U256::from(trade_limit))?))
+		.and_then(|v| Balance::try_from(v).ok())
+		.unwrap_or(Balance::MAX);
+
+	let max_amount = reserve_out.saturating_sub(min_reserve_out);
+	max_amount.saturating_sub(max_amount / FILL_MARGIN)
+}
+
+fn multiply_by_rational_floor(a: Balance, b: Balance, c: Balance) -> Option<Balance> {
+	let result = U256::from(a).checked_mul(U256::from(b))?.checked_div(U256::from(c))?;
+	Balance::try_from(result).ok()
+}
+
+fn multiply_by_rational_ceil(a: Balance, b: Balance, c: Balance) -> Option<Balance> {
+	let c = U256::from(c);
+	let result = U256::from(a)
+		.checked_mul(U256::from(b))?
+		.checked_add(c.checked_sub(U256::one())?)?
+		.checked_div(c)?;
+	Balance::try_from(result).ok()
+}
@@ -0,0 +1,341 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::mock::{expect_events, new_test_ext, Currency, LimitOrders, Origin, Test, ALICE, BOB, DOT, HDX, ONE, XYK};
+use frame_support::traits::Hooks;
+use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiReservableCurrency;
+
+const ACA: AssetId = 3000;
+
+fn fill_orders() {
+	LimitOrders::on_idle(1, Weight::MAX);
+}
+
+#[test]
+fn place_sell_order_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			IntentionType::SELL,
+			100 * ONE,
+			210 * ONE,
+		));
+
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 100 * ONE);
+		assert_eq!(LimitOrders::next_order_id(), 1);
+
+		let order = LimitOrders::orders(0).unwrap();
+		assert_eq!(order.who, BOB);
+		assert_eq!(order.amount_in, 100 * ONE);
+		assert_eq!(order.amount_out, 0);
+		assert_eq!(order.trade_limit, 210 * ONE);
+		assert_eq!(order.sell_or_buy, IntentionType::SELL);
+
+		expect_events(vec![Event::OrderPlaced {
+			order_id: 0,
+			who: BOB,
+			asset_in: HDX,
+			asset_out: DOT,
+			intention_type: IntentionType::SELL,
+			amount: 100 * ONE,
+			trade_limit: 210 * ONE,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn place_buy_order_should_reserve_trade_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			IntentionType::BUY,
+			100 * ONE,
+			190 * ONE,
+		));
+
+		assert_eq!(Currency::reserved_balance(DOT, &BOB), 190 * ONE);
+
+		let order = LimitOrders::orders(0).unwrap();
+		assert_eq!(order.amount_in, 0);
+		assert_eq!(order.amount_out, 100 * ONE);
+		assert_eq!(order.trade_limit, 190 * ONE);
+	});
+}
+
+#[test]
+fn place_order_should_not_work_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LimitOrders::place_order(Origin::signed(BOB), HDX, HDX, IntentionType::SELL, 100 * ONE, ONE),
+			Error::<Test>::CannotTradeSameAsset
+		);
+		assert_noop!(
+			LimitOrders::place_order(Origin::signed(BOB), HDX, ACA, IntentionType::SELL, 100 * ONE, ONE),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			LimitOrders::place_order(Origin::signed(BOB), HDX, DOT, IntentionType::SELL, 999, ONE),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			LimitOrders::place_order(Origin::signed(BOB), HDX, DOT, IntentionType::SELL, 100 * ONE, 0),
+			Error::<Test>::ZeroTradeLimit
+		);
+		assert_noop!(
+			LimitOrders::place_order(Origin::signed(BOB), HDX, DOT, IntentionType::SELL, 2_000_000 * ONE, ONE),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn cancel_order_should_unreserve_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			IntentionType::SELL,
+			100 * ONE,
+			210 * ONE,
+		));
+
+		assert_noop!(
+			LimitOrders::cancel_order(Origin::signed(ALICE), 0),
+			Error::<Test>::NotOrderOwner
+		);
+		assert_noop!(
+			LimitOrders::cancel_order(Origin::signed(BOB), 1),
+			Error::<Test>::OrderNotFound
+		);
+
+		assert_ok!(LimitOrders::cancel_order(Origin::signed(BOB), 0));
+
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 0);
+		assert!(LimitOrders::orders(0).is_none());
+
+		expect_events(vec![Event::OrderCancelled { order_id: 0, who: BOB }.into()]);
+	});
+}
+
+#[test]
+fn sell_order_should_be_filled_when_price_reaches_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			IntentionType::SELL,
+			100 * ONE,
+			190 * ONE,
+		));
+
+		let dot_balance = Currency::free_balance(DOT, &BOB);
+
+		fill_orders();
+
+		let received = Currency::free_balance(DOT, &BOB) - dot_balance;
+		assert!(received >= 190 * ONE);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 0);
+		assert!(LimitOrders::orders(0).is_none());
+
+		expect_events(vec![
+			Event::OrderFilled {
+				order_id: 0,
+				who: BOB,
+				asset_in: HDX,
+				asset_out: DOT,
+				amount_in: 100 * ONE,
+				amount_out: received,
+			}
+			.into(),
+			Event::OrderCompleted { order_id: 0, who: BOB }.into(),
+		]);
+	});
+}
+
+#[test]
+fn sell_order_should_not_be_filled_when_price_is_below_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			IntentionType::SELL,
+			100 * ONE,
+			210 * ONE,
+		));
+
+		fill_orders();
+
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 100 * ONE);
+		assert_eq!(LimitOrders::orders(0).unwrap().amount_in, 100 * ONE);
+	});
+}
+
+#[test]
+fn sell_order_should_be_filled_partially_when_pool_moves_past_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			IntentionType::SELL,
+			10_000 * ONE,
+			19_000 * ONE,
+		));
+
+		let dot_balance = Currency::free_balance(DOT, &BOB);
+
+		fill_orders();
+
+		let order = LimitOrders::orders(0).unwrap();
+		let sold = 10_000 * ONE - order.amount_in;
+		let received = Currency::free_balance(DOT, &BOB) - dot_balance;
+
+		assert!(sold > 0 && sold < 10_000 * ONE);
+		assert_eq!(order.amount_out, received);
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), order.amount_in);
+
+		// the fill respects the limit price
+		assert!(received * 10_000 >= sold * 19_000);
+
+		// price of the pool moved to the limit, nothing more can be filled
+		fill_orders();
+		assert_eq!(LimitOrders::orders(0).unwrap().amount_in, order.amount_in);
+
+		// price recovers
+		assert_ok!(XYK::buy(
+			Origin::signed(ALICE),
+			HDX,
+			DOT,
+			10_000 * ONE,
+			u128::MAX,
+			false
+		));
+
+		fill_orders();
+		assert!(LimitOrders::orders(0).is_none());
+		assert_eq!(Currency::reserved_balance(HDX, &BOB), 0);
+	});
+}
+
+#[test]
+fn buy_order_should_be_filled_when_price_reaches_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			IntentionType::BUY,
+			100 * ONE,
+			210 * ONE,
+		));
+
+		let dot_balance = Currency::free_balance(DOT, &BOB) + Currency::reserved_balance(DOT, &BOB);
+		let hdx_balance = Currency::free_balance(HDX, &BOB);
+
+		fill_orders();
+
+		let paid = dot_balance - Currency::free_balance(DOT, &BOB);
+		assert!(paid <= 210 * ONE);
+		assert_eq!(Currency::free_balance(HDX, &BOB), hdx_balance + 100 * ONE);
+		assert_eq!(Currency::reserved_balance(DOT, &BOB), 0);
+		assert!(LimitOrders::orders(0).is_none());
+
+		expect_events(vec![
+			Event::OrderFilled {
+				order_id: 0,
+				who: BOB,
+				asset_in: DOT,
+				asset_out: HDX,
+				amount_in: paid,
+				amount_out: 100 * ONE,
+			}
+			.into(),
+			Event::OrderCompleted { order_id: 0, who: BOB }.into(),
+		]);
+	});
+}
+
+#[test]
+fn buy_order_should_not_be_filled_when_price_is_above_limit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			IntentionType::BUY,
+			100 * ONE,
+			190 * ONE,
+		));
+
+		fill_orders();
+
+		assert_eq!(Currency::reserved_balance(DOT, &BOB), 190 * ONE);
+		assert_eq!(LimitOrders::orders(0).unwrap().amount_out, 100 * ONE);
+	});
+}
+
+#[test]
+fn on_idle_should_check_at_most_max_orders_per_block() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..12 {
+			assert_ok!(LimitOrders::place_order(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				IntentionType::SELL,
+				10 * ONE,
+				10 * ONE,
+			));
+		}
+
+		fill_orders();
+		assert_eq!(Orders::<Test>::iter().count(), 2);
+		assert!(LimitOrders::last_checked_order().is_some());
+
+		fill_orders();
+		assert_eq!(Orders::<Test>::iter().count(), 0);
+		assert!(LimitOrders::last_checked_order().is_none());
+	});
+}
+
+#[test]
+fn on_idle_should_not_fill_orders_without_remaining_weight() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LimitOrders::place_order(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			IntentionType::SELL,
+			100 * ONE,
+			190 * ONE,
+		));
+
+		assert_eq!(LimitOrders::on_idle(1, Weight::zero()), Weight::zero());
+		assert!(LimitOrders::orders(0).is_some());
+	});
+}
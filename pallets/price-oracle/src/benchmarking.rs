@@ -0,0 +1,110 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::benchmarks;
+use frame_support::traits::{OnFinalize, OnInitialize};
+use frame_system::Pallet as System;
+
+use crate::Pallet as PriceOracle;
+
+const SOURCE: Source = *b"dummysrc";
+const ASSET_A: AssetId = 1_000;
+
+fn price() -> Price {
+	Price::saturating_from_rational(2, 1)
+}
+
+fn trade_entries<T: Config>(count: u32) {
+	for i in 0..count {
+		PriceOracle::<T>::on_trade(SOURCE, ASSET_A, ASSET_A + 1 + i, 1_000, 2_000, 10_000, price());
+	}
+}
+
+benchmarks! {
+	on_finalize_no_entry {
+		let block_num: T::BlockNumber = 5u32.into();
+	}: { PriceOracle::<T>::on_finalize(block_num); }
+	verify {
+		assert!(Accumulator::<T>::iter().next().is_none());
+	}
+
+	on_finalize_multiple_tokens {
+		let b in 1 .. T::MaxUniqueEntries::get();
+
+		// initialize the oracles so that the aggregation has to update existing entries
+		System::<T>::set_block_number(1u32.into());
+		trade_entries::<T>(b);
+		PriceOracle::<T>::on_finalize(1u32.into());
+
+		let block_num: T::BlockNumber = 5u32.into();
+		System::<T>::set_block_number(block_num);
+		PriceOracle::<T>::on_initialize(block_num);
+		trade_entries::<T>(b);
+	}: { PriceOracle::<T>::on_finalize(block_num); }
+	verify {
+		assert!(Accumulator::<T>::iter().next().is_none());
+		for i in 0..b {
+			let (entry, _) = PriceOracle::<T>::oracle((SOURCE, (ASSET_A, ASSET_A + 1 + i), OraclePeriod::Day)).unwrap();
+			assert_eq!(entry.timestamp, block_num);
+		}
+	}
+
+	on_trade_multiple_tokens {
+		let b in 1 .. (T::MaxUniqueEntries::get() - 1);
+
+		let block_num: T::BlockNumber = 5u32.into();
+		System::<T>::set_block_number(block_num);
+		trade_entries::<T>(b);
+		let asset_b = ASSET_A + 1 + b;
+	}: { PriceOracle::<T>::on_trade(SOURCE, ASSET_A, asset_b, 1_000, 2_000, 10_000, price()); }
+	verify {
+		assert!(Accumulator::<T>::contains_key(&(SOURCE, (ASSET_A, asset_b))));
+	}
+
+	get_entry {
+		System::<T>::set_block_number(1u32.into());
+		trade_entries::<T>(1);
+		PriceOracle::<T>::on_finalize(1u32.into());
+
+		System::<T>::set_block_number(100u32.into());
+		let mut result = None;
+	}: { result = Some(PriceOracle::<T>::get_price(ASSET_A, ASSET_A + 1, OraclePeriod::Day, SOURCE)); }
+	verify {
+		assert!(matches!(result, Some(Ok(_))));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Pallet::<Test>::test_benchmark_on_finalize_no_entry());
+			assert_ok!(Pallet::<Test>::test_benchmark_on_finalize_multiple_tokens());
+			assert_ok!(Pallet::<Test>::test_benchmark_on_trade_multiple_tokens());
+			assert_ok!(Pallet::<Test>::test_benchmark_get_entry());
+		});
+	}
+}
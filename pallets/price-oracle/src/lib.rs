@@ -0,0 +1,357 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Price oracle pallet
+//!
+//! Keeps exponential moving averages of prices, volumes and liquidity of asset pairs traded on AMM pools.
+//!
+//! Trades are reported via `OnTradeHandler` of `OnActivityHandler`, which is parametrized by the `Source`
+//! identifier of the reporting AMM and by the `SpotPriceProvider` of its pools. The recorded price is the spot
+//! price of the pool after the trade, not the execution price of the trade, so fees and slippage of a single
+//! large trade do not skew it. Trades of the same block are collected in the `Accumulator` and folded into
+//! the oracles of every `OraclePeriod` in `on_finalize`. Because the values of the current block only become
+//! visible in the next block and are smoothed over the period, a single block cannot move the aggregated price.
+//!
+//! Other pallets read the aggregated values through `AggregatedPriceOracle`. `OraclePriceProvider` exposes them
+//! as a `SpotPriceProvider` for consumers such as the transaction multi payment pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_support::sp_runtime::traits::{Saturating, Zero};
+use frame_support::sp_runtime::FixedPointNumber;
+use hydradx_traits::{pools::SpotPriceProvider, OnCreatePoolHandler, OnTradeHandler};
+use primitives::{AssetId, Balance, Price};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+
+pub mod types;
+#[allow(clippy::all)]
+pub mod weights;
+
+pub use types::*;
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+const LOG_TARGET: &str = "runtime::price-oracle";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_system::pallet_prelude::BlockNumberFor;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(crate) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+
+		/// Maximum number of distinct source and asset pair combinations traded in a single block.
+		#[pallet::constant]
+		type MaxUniqueEntries: Get<u32>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Asset pair is not tracked by the oracle.
+		OracleNotFound,
+		/// Price of an asset in itself was requested.
+		SameAsset,
+	}
+
+	/// Entries of the current block, aggregated into `Oracles` at the end of the block.
+	#[pallet::storage]
+	#[pallet::getter(fn accumulator)]
+	pub(crate) type Accumulator<T: Config> =
+		StorageMap<_, Twox64Concat, (Source, (AssetId, AssetId)), OracleEntry<T::BlockNumber>, OptionQuery>;
+
+	/// Number of entries in the `Accumulator`.
+	#[pallet::storage]
+	pub(crate) type AccumulatorCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Aggregated oracle entries with the block of their initialization.
+	///
+	/// Asset pairs are ordered, the price is the price of the first asset denominated in the second one.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle)]
+	pub(crate) type Oracles<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, Source>,
+			NMapKey<Twox64Concat, (AssetId, AssetId)>,
+			NMapKey<Twox64Concat, OraclePeriod>,
+		),
+		(OracleEntry<T::BlockNumber>, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			T::WeightInfo::on_finalize_no_entry()
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			Self::update_oracles_from_accumulator();
+		}
+
+		fn integrity_test() {
+			assert!(
+				!T::MaxUniqueEntries::get().is_zero(),
+				"At least one entry is required to be accumulated per block."
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Record a trade of `amount_in` of `asset_in` for `amount_out` of `asset_out` in the current block.
+	///
+	/// `price` is the spot price of `asset_in` denominated in `asset_out` in the pool after the trade.
+	pub(crate) fn on_trade(
+		source: Source,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		amount_out: Balance,
+		liquidity: Balance,
+		price: Price,
+	) {
+		if asset_in == asset_out || amount_in.is_zero() || amount_out.is_zero() || price.is_zero() {
+			return;
+		}
+
+		let (assets, price, volume) = if asset_in < asset_out {
+			(
+				(asset_in, asset_out),
+				Some(price),
+				Volume::from_a_in_b_out(amount_in, amount_out),
+			)
+		} else {
+			(
+				(asset_out, asset_in),
+				price.reciprocal(),
+				Volume::from_a_out_b_in(amount_out, amount_in),
+			)
+		};
+
+		let price = match price {
+			Some(price) if !price.is_zero() => price,
+			_ => return,
+		};
+
+		let entry = OracleEntry {
+			price,
+			volume,
+			liquidity,
+			timestamp: <frame_system::Pallet<T>>::block_number(),
+		};
+
+		Accumulator::<T>::mutate((source, assets), |maybe_entry| {
+			if let Some(existing) = maybe_entry {
+				existing.accumulate_volume_and_update_from(&entry);
+			} else if AccumulatorCount::<T>::get() < T::MaxUniqueEntries::get() {
+				AccumulatorCount::<T>::mutate(|count| *count = count.saturating_add(1));
+				*maybe_entry = Some(entry);
+			} else {
+				log::warn!(
+					target: LOG_TARGET,
+					"Too many oracle entries in a block, the trade of {:?} is not recorded.",
+					assets
+				);
+			}
+		});
+	}
+
+	fn update_oracles_from_accumulator() {
+		AccumulatorCount::<T>::kill();
+
+		for ((source, assets), entry) in Accumulator::<T>::drain() {
+			// The last block entry is used to update outdated entries of the other periods,
+			// so it has to be read before it is overwritten.
+			let last_block = Oracles::<T>::get((source, assets, OraclePeriod::LastBlock))
+				.map(|(last_block, _)| last_block)
+				.unwrap_or(entry);
+
+			for period in OraclePeriod::all_periods() {
+				Oracles::<T>::mutate((source, assets, *period), |oracle| match oracle {
+					Some((prev, _)) => *prev = prev.chained_update_via_ema_with(*period, &entry, &last_block),
+					None => *oracle = Some((entry, entry.timestamp)),
+				});
+			}
+		}
+	}
+
+	/// Returns the oracle entry of the ordered asset pair, brought up to the current block.
+	pub fn get_updated_entry(
+		source: Source,
+		assets: (AssetId, AssetId),
+		period: OraclePeriod,
+	) -> Option<(OracleEntry<T::BlockNumber>, T::BlockNumber)> {
+		let (entry, initialized) = Self::oracle((source, assets, period))?;
+		if period == OraclePeriod::LastBlock {
+			return Some((entry, initialized));
+		}
+
+		let (last_block, _) = Self::oracle((source, assets, OraclePeriod::LastBlock))?;
+		let current_block = <frame_system::Pallet<T>>::block_number();
+		Some((entry.updated_to(period, current_block, &last_block), initialized))
+	}
+}
+
+/// Upper bound of storage reads of a pool spot price lookup.
+const POOL_PRICE_READS: u64 = 5;
+
+/// Feeds trades reported by an AMM into the oracle under the `Source` identifier `S`.
+///
+/// The price of the pool after the trade is read from `P`, so the AMM has to report the trade after
+/// the pool state is updated.
+pub struct OnActivityHandler<T, S, P>(PhantomData<(T, S, P)>);
+
+impl<T: Config, S: Get<Source>, P> OnCreatePoolHandler<AssetId> for OnActivityHandler<T, S, P> {
+	// Oracles are initialized by the first trade of a pair, there is nothing to track before that.
+	fn on_create_pool(_asset_a: AssetId, _asset_b: AssetId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+impl<T: Config, S: Get<Source>, P: SpotPriceProvider<AssetId, Price = Price>> OnTradeHandler<AssetId, Balance>
+	for OnActivityHandler<T, S, P>
+{
+	fn on_trade(asset_in: AssetId, asset_out: AssetId, amount_in: Balance, amount_out: Balance, liquidity: Balance) {
+		match P::spot_price(asset_in, asset_out) {
+			Some(price) => {
+				Pallet::<T>::on_trade(S::get(), asset_in, asset_out, amount_in, amount_out, liquidity, price)
+			}
+			None => log::warn!(
+				target: LOG_TARGET,
+				"Pool price of {:?} is not available, the trade is not recorded.",
+				(asset_in, asset_out)
+			),
+		}
+	}
+
+	fn on_trade_weight() -> Weight {
+		// a trade is charged for the aggregation of its entry in on_finalize and for the pool price lookup as well
+		T::WeightInfo::on_trade_multiple_tokens(T::MaxUniqueEntries::get())
+			.saturating_add(T::WeightInfo::on_finalize_multiple_tokens(1))
+			.saturating_add(T::DbWeight::get().reads(POOL_PRICE_READS))
+	}
+}
+
+/// Provides prices aggregated over an `OraclePeriod`.
+pub trait AggregatedPriceOracle<AssetId, BlockNumber, Price> {
+	type Error;
+
+	/// Returns the price of `asset_a` denominated in `asset_b` reported by `source` and aggregated over `period`,
+	/// together with the number of blocks since the oracle was initialized.
+	fn get_price(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		period: OraclePeriod,
+		source: Source,
+	) -> Result<(Price, BlockNumber), Self::Error>;
+
+	fn get_price_weight() -> Weight;
+}
+
+impl<T: Config> AggregatedPriceOracle<AssetId, T::BlockNumber, Price> for Pallet<T> {
+	type Error = Error<T>;
+
+	fn get_price(
+		asset_a: AssetId,
+		asset_b: AssetId,
+		period: OraclePeriod,
+		source: Source,
+	) -> Result<(Price, T::BlockNumber), Self::Error> {
+		if asset_a == asset_b {
+			return Err(Error::<T>::SameAsset);
+		}
+
+		let assets = if asset_a < asset_b {
+			(asset_a, asset_b)
+		} else {
+			(asset_b, asset_a)
+		};
+
+		let (entry, initialized) = Self::get_updated_entry(source, assets, period).ok_or(Error::<T>::OracleNotFound)?;
+
+		let price = if asset_a < asset_b {
+			entry.price
+		} else {
+			entry.price.reciprocal().ok_or(Error::<T>::OracleNotFound)?
+		};
+
+		let age = <frame_system::Pallet<T>>::block_number().saturating_sub(initialized);
+
+		Ok((price, age))
+	}
+
+	fn get_price_weight() -> Weight {
+		T::WeightInfo::get_entry()
+	}
+}
+
+/// `SpotPriceProvider` backed by the oracle of period `P` fed by source `S`.
+///
+/// A price is only provided once the oracle has been tracking the pair for at least the length of the period.
+/// Otherwise `None` is returned and consumers are expected to fall back to their own governance-set prices.
+/// There is deliberately no fallback to the current pool reserves, which can be moved within a single block.
+pub struct OraclePriceProvider<T, S, P>(PhantomData<(T, S, P)>);
+
+impl<T: Config, S: Get<Source>, P: Get<OraclePeriod>> SpotPriceProvider<AssetId> for OraclePriceProvider<T, S, P> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		let assets = if asset_a < asset_b {
+			(asset_a, asset_b)
+		} else {
+			(asset_b, asset_a)
+		};
+		Oracles::<T>::contains_key((S::get(), assets, P::get()))
+	}
+
+	/// Price of `asset_a` denominated in `asset_b`.
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		let period = P::get();
+		let (price, age) =
+			<Pallet<T> as AggregatedPriceOracle<_, _, _>>::get_price(asset_a, asset_b, period, S::get()).ok()?;
+
+		if age < T::BlockNumber::from(period.as_period()) {
+			return None;
+		}
+
+		Some(price)
+	}
+}
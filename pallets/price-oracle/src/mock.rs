@@ -0,0 +1,148 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as price_oracle;
+use crate::{Config, OnActivityHandler, OraclePeriod, OraclePriceProvider, Source};
+use frame_support::parameter_types;
+use frame_support::traits::{Everything, Get, OnFinalize, OnInitialize};
+use frame_system as system;
+use hydradx_traits::pools::SpotPriceProvider;
+use primitives::{AssetId, Price};
+use sp_core::H256;
+use sp_runtime::FixedPointNumber;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const HDX: AssetId = 1_000;
+pub const DOT: AssetId = 2_000;
+pub const ACA: AssetId = 3_000;
+
+pub const XYK_SOURCE: Source = *b"xyk_pool";
+pub const LBP_SOURCE: Source = *b"lbp_pool";
+
+frame_support::construct_runtime!(
+	pub enum Test where
+	Block = Block,
+	NodeBlock = Block,
+	UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		PriceOracle: price_oracle,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 63;
+	pub const MaxUniqueEntries: u32 = 3;
+	pub const XYKSource: Source = XYK_SOURCE;
+	pub const LBPSource: Source = LBP_SOURCE;
+	pub const ShortPeriod: OraclePeriod = OraclePeriod::Short;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl Config for Test {
+	type WeightInfo = ();
+	type MaxUniqueEntries = MaxUniqueEntries;
+}
+
+thread_local! {
+	static POOL_PRICES: RefCell<HashMap<(Source, AssetId, AssetId), Price>> = RefCell::new(HashMap::default());
+}
+
+/// Set spot price of `asset_a` denominated in `asset_b` in the pool of `source`.
+pub fn set_pool_price(source: Source, asset_a: AssetId, asset_b: AssetId, price: Price) {
+	POOL_PRICES.with(|prices| {
+		let mut prices = prices.borrow_mut();
+		prices.remove(&(source, asset_b, asset_a));
+		prices.insert((source, asset_a, asset_b), price);
+	});
+}
+
+pub struct PoolPriceMock<S>(PhantomData<S>);
+
+impl<S: Get<Source>> SpotPriceProvider<AssetId> for PoolPriceMock<S> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		Self::spot_price(asset_a, asset_b).is_some()
+	}
+
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		POOL_PRICES.with(|prices| {
+			let prices = prices.borrow();
+			prices
+				.get(&(S::get(), asset_a, asset_b))
+				.copied()
+				.or_else(|| prices.get(&(S::get(), asset_b, asset_a))?.reciprocal())
+		})
+	}
+}
+
+pub type XYKHandler = OnActivityHandler<Test, XYKSource, PoolPriceMock<XYKSource>>;
+pub type LBPHandler = OnActivityHandler<Test, LBPSource, PoolPriceMock<LBPSource>>;
+pub type XYKShortPriceProvider = OraclePriceProvider<Test, XYKSource, ShortPeriod>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let b = System::block_number();
+		PriceOracle::on_finalize(b);
+		System::set_block_number(b + 1);
+		PriceOracle::on_initialize(b + 1);
+	}
+}
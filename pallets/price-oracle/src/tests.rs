@@ -0,0 +1,374 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::mock::*;
+use frame_support::sp_runtime::DispatchError;
+use hydradx_traits::pools::SpotPriceProvider;
+use proptest::prelude::*;
+
+const ONE: Balance = 1_000_000_000_000_000_000;
+
+fn price_of(n: u128, d: u128) -> Price {
+	Price::saturating_from_rational(n, d)
+}
+
+/// Report a trade of `source` which leaves the pool at the execution price of the trade.
+fn trade(
+	source: Source,
+	asset_in: AssetId,
+	asset_out: AssetId,
+	amount_in: Balance,
+	amount_out: Balance,
+	liquidity: Balance,
+) {
+	set_pool_price(source, asset_in, asset_out, price_of(amount_out, amount_in));
+	if source == XYK_SOURCE {
+		XYKHandler::on_trade(asset_in, asset_out, amount_in, amount_out, liquidity);
+	} else {
+		LBPHandler::on_trade(asset_in, asset_out, amount_in, amount_out, liquidity);
+	}
+}
+
+fn accumulator_is_empty() -> bool {
+	Accumulator::<Test>::iter().next().is_none() && AccumulatorCount::<Test>::get() == 0
+}
+
+#[test]
+fn on_trade_should_accumulate_entries_of_same_block() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+		trade(XYK_SOURCE, DOT, HDX, 500, 200, 11_000);
+
+		assert_eq!(
+			PriceOracle::accumulator((XYK_SOURCE, (HDX, DOT))),
+			Some(OracleEntry {
+				price: price_of(5, 2),
+				volume: Volume {
+					a_in: 1_000,
+					b_out: 2_000,
+					a_out: 200,
+					b_in: 500,
+				},
+				liquidity: 11_000,
+				timestamp: 1,
+			})
+		);
+	});
+}
+
+#[test]
+fn on_trade_should_record_pool_price_instead_of_execution_price() {
+	new_test_ext().execute_with(|| {
+		// the trade was executed at 2 DOT per HDX, but left the pool at 3/2
+		set_pool_price(XYK_SOURCE, HDX, DOT, price_of(3, 2));
+		XYKHandler::on_trade(HDX, DOT, 1_000, 2_000, 10_000);
+
+		assert_eq!(
+			PriceOracle::accumulator((XYK_SOURCE, (HDX, DOT))).map(|entry| entry.price),
+			Some(price_of(3, 2))
+		);
+
+		// the price of the reversed pair is inverted
+		set_pool_price(XYK_SOURCE, DOT, HDX, price_of(1, 2));
+		XYKHandler::on_trade(DOT, HDX, 1_000, 400, 10_000);
+
+		assert_eq!(
+			PriceOracle::accumulator((XYK_SOURCE, (HDX, DOT))).map(|entry| entry.price),
+			Some(price_of(2, 1))
+		);
+	});
+}
+
+#[test]
+fn on_trade_should_not_record_trade_without_pool_price() {
+	new_test_ext().execute_with(|| {
+		XYKHandler::on_trade(HDX, ACA, 1_000, 2_000, 10_000);
+
+		assert!(accumulator_is_empty());
+	});
+}
+
+#[test]
+fn on_trade_should_ignore_invalid_trades() {
+	new_test_ext().execute_with(|| {
+		set_pool_price(XYK_SOURCE, HDX, DOT, price_of(2, 1));
+		set_pool_price(XYK_SOURCE, HDX, HDX, price_of(1, 1));
+
+		XYKHandler::on_trade(HDX, HDX, 1_000, 1_000, 10_000);
+		XYKHandler::on_trade(HDX, DOT, 0, 2_000, 10_000);
+		XYKHandler::on_trade(HDX, DOT, 1_000, 0, 10_000);
+
+		set_pool_price(XYK_SOURCE, HDX, DOT, Price::zero());
+		XYKHandler::on_trade(HDX, DOT, 1_000, 2_000, 10_000);
+
+		assert!(accumulator_is_empty());
+	});
+}
+
+#[test]
+fn on_trade_should_not_record_more_entries_than_allowed() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+		trade(XYK_SOURCE, HDX, ACA, 1_000, 2_000, 10_000);
+		trade(XYK_SOURCE, DOT, ACA, 1_000, 2_000, 10_000);
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+		trade(LBP_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+
+		assert_eq!(Accumulator::<Test>::iter().count(), MaxUniqueEntries::get() as usize);
+		assert_eq!(AccumulatorCount::<Test>::get(), MaxUniqueEntries::get());
+		assert!(!Accumulator::<Test>::contains_key(&(LBP_SOURCE, (HDX, DOT))));
+		assert_eq!(
+			PriceOracle::accumulator((XYK_SOURCE, (HDX, DOT))).map(|entry| entry.volume.a_in),
+			Some(2_000)
+		);
+	});
+}
+
+#[test]
+fn on_finalize_should_initialize_oracles_of_all_periods() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+
+		run_to_block(2);
+
+		let expected = OracleEntry {
+			price: price_of(2, 1),
+			volume: Volume::from_a_in_b_out(1_000, 2_000),
+			liquidity: 10_000,
+			timestamp: 1,
+		};
+		for period in OraclePeriod::all_periods() {
+			assert_eq!(
+				PriceOracle::oracle((XYK_SOURCE, (HDX, DOT), *period)),
+				Some((expected, 1))
+			);
+		}
+		assert!(accumulator_is_empty());
+	});
+}
+
+#[test]
+fn on_finalize_should_update_oracles_via_ema() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, ONE, 2 * ONE, 10_000);
+		run_to_block(2);
+		trade(XYK_SOURCE, HDX, DOT, ONE, 4 * ONE, 10_000);
+		run_to_block(3);
+
+		assert_eq!(
+			PriceOracle::oracle((XYK_SOURCE, (HDX, DOT), OraclePeriod::LastBlock)),
+			Some((
+				OracleEntry {
+					price: price_of(4, 1),
+					volume: Volume::from_a_in_b_out(ONE, 4 * ONE),
+					liquidity: 10_000,
+					timestamp: 2,
+				},
+				1
+			))
+		);
+
+		// smoothing of the short period is 2 / 11
+		assert_eq!(
+			PriceOracle::oracle((XYK_SOURCE, (HDX, DOT), OraclePeriod::Short)),
+			Some((
+				OracleEntry {
+					price: Price::from_inner(2_363_636_363_636_363_636),
+					volume: Volume::from_a_in_b_out(ONE, 2_363_636_363_636_363_636),
+					liquidity: 10_000,
+					timestamp: 2,
+				},
+				1
+			))
+		);
+
+		let (day, _) = PriceOracle::oracle((XYK_SOURCE, (HDX, DOT), OraclePeriod::Day)).unwrap();
+		assert!(day.price > price_of(2, 1) && day.price < price_of(2_001, 1_000));
+	});
+}
+
+#[test]
+fn blocks_without_trades_should_decay_volume_but_keep_price() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, ONE, 2 * ONE, 10_000);
+		run_to_block(11);
+		trade(XYK_SOURCE, HDX, DOT, ONE, 2 * ONE, 10_000);
+		run_to_block(12);
+
+		let (short, _) = PriceOracle::oracle((XYK_SOURCE, (HDX, DOT), OraclePeriod::Short)).unwrap();
+		assert_eq!(short.price, price_of(2, 1));
+		assert_eq!(short.liquidity, 10_000);
+		assert!(short.volume.a_in < ONE);
+		assert_eq!(short.timestamp, 11);
+	});
+}
+
+#[test]
+fn sources_should_be_tracked_separately() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+		trade(LBP_SOURCE, HDX, DOT, 1_000, 3_000, 10_000);
+		run_to_block(2);
+
+		assert_eq!(
+			PriceOracle::get_price(HDX, DOT, OraclePeriod::Short, XYK_SOURCE).ok(),
+			Some((price_of(2, 1), 1))
+		);
+		assert_eq!(
+			PriceOracle::get_price(HDX, DOT, OraclePeriod::Short, LBP_SOURCE).ok(),
+			Some((price_of(3, 1), 1))
+		);
+	});
+}
+
+#[test]
+fn get_price_should_invert_price_of_reversed_pair() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, DOT, HDX, 1_000, 4_000, 10_000);
+		run_to_block(5);
+
+		assert_eq!(
+			PriceOracle::get_price(HDX, DOT, OraclePeriod::TenMinutes, XYK_SOURCE).ok(),
+			Some((price_of(1, 4), 4))
+		);
+		assert_eq!(
+			PriceOracle::get_price(DOT, HDX, OraclePeriod::TenMinutes, XYK_SOURCE).ok(),
+			Some((price_of(4, 1), 4))
+		);
+	});
+}
+
+#[test]
+fn get_price_should_fail_when_oracle_is_not_available() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+
+		// values of the current block are not available yet
+		assert_eq!(
+			PriceOracle::get_price(HDX, DOT, OraclePeriod::LastBlock, XYK_SOURCE).map_err(DispatchError::from),
+			Err(Error::<Test>::OracleNotFound.into())
+		);
+
+		run_to_block(2);
+
+		assert_eq!(
+			PriceOracle::get_price(HDX, ACA, OraclePeriod::LastBlock, XYK_SOURCE).map_err(DispatchError::from),
+			Err(Error::<Test>::OracleNotFound.into())
+		);
+		assert_eq!(
+			PriceOracle::get_price(HDX, DOT, OraclePeriod::LastBlock, LBP_SOURCE).map_err(DispatchError::from),
+			Err(Error::<Test>::OracleNotFound.into())
+		);
+		assert_eq!(
+			PriceOracle::get_price(HDX, HDX, OraclePeriod::LastBlock, XYK_SOURCE).map_err(DispatchError::from),
+			Err(Error::<Test>::SameAsset.into())
+		);
+	});
+}
+
+#[test]
+fn long_periods_should_resist_single_block_manipulation() {
+	new_test_ext().execute_with(|| {
+		for block in 1..100 {
+			trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+			run_to_block(block + 1);
+		}
+
+		// price is pushed up hundredfold for a single block
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 200_000, 10_000);
+		run_to_block(101);
+
+		let (last_block, _) = PriceOracle::get_price(HDX, DOT, OraclePeriod::LastBlock, XYK_SOURCE).unwrap();
+		assert_eq!(last_block, price_of(200, 1));
+
+		let (ten_minutes, _) = PriceOracle::get_price(HDX, DOT, OraclePeriod::TenMinutes, XYK_SOURCE).unwrap();
+		assert!(ten_minutes < price_of(10, 1));
+
+		let (day, _) = PriceOracle::get_price(HDX, DOT, OraclePeriod::Day, XYK_SOURCE).unwrap();
+		assert!(day < price_of(21, 10));
+	});
+}
+
+#[test]
+fn price_provider_should_provide_price_only_when_oracle_is_mature() {
+	new_test_ext().execute_with(|| {
+		assert!(!XYKShortPriceProvider::pair_exists(HDX, DOT));
+		assert_eq!(XYKShortPriceProvider::spot_price(HDX, DOT), None);
+
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+		run_to_block(2);
+
+		// the oracle has been tracking the pair for less than the period
+		assert!(XYKShortPriceProvider::pair_exists(DOT, HDX));
+		assert_eq!(XYKShortPriceProvider::spot_price(HDX, DOT), None);
+
+		run_to_block(11);
+
+		assert_eq!(XYKShortPriceProvider::spot_price(HDX, DOT), Some(price_of(2, 1)));
+		assert_eq!(XYKShortPriceProvider::spot_price(DOT, HDX), Some(price_of(1, 2)));
+	});
+}
+
+#[test]
+fn price_provider_should_not_follow_single_block_price_spike() {
+	new_test_ext().execute_with(|| {
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+		run_to_block(11);
+
+		trade(XYK_SOURCE, HDX, DOT, 1_000, 200_000, 10_000);
+
+		// trades of the current block are not reflected
+		assert_eq!(XYKShortPriceProvider::spot_price(HDX, DOT), Some(price_of(2, 1)));
+
+		run_to_block(12);
+
+		let price = XYKShortPriceProvider::spot_price(HDX, DOT).unwrap();
+		assert!(price < price_of(40, 1));
+	});
+}
+
+#[test]
+fn price_provider_should_ignore_other_sources() {
+	new_test_ext().execute_with(|| {
+		trade(LBP_SOURCE, HDX, DOT, 1_000, 2_000, 10_000);
+		run_to_block(20);
+
+		assert!(!XYKShortPriceProvider::pair_exists(HDX, DOT));
+		assert_eq!(XYKShortPriceProvider::spot_price(HDX, DOT), None);
+	});
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(1000))]
+	#[test]
+	fn ema_should_stay_between_previous_and_incoming_value(
+		prev in 1..u128::MAX / 2,
+		incoming in 1..u128::MAX / 2,
+		period in 1u32..10_000,
+		iterations in 1u32..100_000,
+	) {
+		let alpha = multi_step_smoothing(Price::saturating_from_rational(2u32, period + 1), iterations);
+		let (low, high) = if prev < incoming { (prev, incoming) } else { (incoming, prev) };
+
+		let balance = ema_balance(prev, incoming, alpha);
+		prop_assert!(low <= balance && balance <= high);
+
+		let price = ema_price(Price::from_inner(prev), Price::from_inner(incoming), alpha);
+		prop_assert!(Price::from_inner(low) <= price && price <= Price::from_inner(high));
+	}
+}
@@ -0,0 +1,229 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	traits::{AtLeast32BitUnsigned, One, Saturating, UniqueSaturatedInto},
+	FixedPointNumber, RuntimeDebug,
+};
+use primitives::{Balance, Price};
+use scale_info::TypeInfo;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Identifier of the AMM which reported a trade, e.g. `*b"xyk_pool"`.
+pub type Source = [u8; 8];
+
+/// Periods over which the oracle aggregates prices, volumes and liquidity.
+///
+/// Every period is an exponential moving average with smoothing factor `2 / (N + 1)`,
+/// where `N` is the length of the period in blocks.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, TypeInfo, MaxEncodedLen)]
+pub enum OraclePeriod {
+	/// Values of the last block with a trade.
+	LastBlock,
+	/// 10 blocks.
+	Short,
+	/// 50 blocks, ten minutes with 12 second blocks.
+	TenMinutes,
+	/// 7 200 blocks, one day with 12 second blocks.
+	Day,
+}
+
+impl OraclePeriod {
+	pub fn all_periods() -> &'static [OraclePeriod] {
+		&[
+			OraclePeriod::LastBlock,
+			OraclePeriod::Short,
+			OraclePeriod::TenMinutes,
+			OraclePeriod::Day,
+		]
+	}
+
+	/// Length of the period in blocks.
+	pub fn as_period(&self) -> u32 {
+		match self {
+			OraclePeriod::LastBlock => 1,
+			OraclePeriod::Short => 10,
+			OraclePeriod::TenMinutes => 50,
+			OraclePeriod::Day => 7_200,
+		}
+	}
+
+	/// Smoothing factor of the exponential moving average.
+	pub fn smoothing(&self) -> Price {
+		Price::saturating_from_rational(2u32, self.as_period().saturating_add(1))
+	}
+}
+
+/// Traded volumes of an asset pair `(a, b)` split by direction.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, Default, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct Volume<Balance> {
+	pub a_in: Balance,
+	pub b_out: Balance,
+	pub a_out: Balance,
+	pub b_in: Balance,
+}
+
+impl Volume<Balance> {
+	/// Volume of a trade selling `amount_in` of asset `a` for `amount_out` of asset `b`.
+	pub fn from_a_in_b_out(amount_in: Balance, amount_out: Balance) -> Self {
+		Self {
+			a_in: amount_in,
+			b_out: amount_out,
+			..Default::default()
+		}
+	}
+
+	/// Volume of a trade selling `amount_in` of asset `b` for `amount_out` of asset `a`.
+	pub fn from_a_out_b_in(amount_out: Balance, amount_in: Balance) -> Self {
+		Self {
+			a_out: amount_out,
+			b_in: amount_in,
+			..Default::default()
+		}
+	}
+
+	pub fn inverted(&self) -> Self {
+		Self {
+			a_in: self.b_in,
+			b_out: self.a_out,
+			a_out: self.b_out,
+			b_in: self.a_in,
+		}
+	}
+
+	pub fn saturating_add(&self, other: &Self) -> Self {
+		Self {
+			a_in: self.a_in.saturating_add(other.a_in),
+			b_out: self.b_out.saturating_add(other.b_out),
+			a_out: self.a_out.saturating_add(other.a_out),
+			b_in: self.b_in.saturating_add(other.b_in),
+		}
+	}
+
+	fn ema(&self, incoming: &Self, alpha: Price) -> Self {
+		Self {
+			a_in: ema_balance(self.a_in, incoming.a_in, alpha),
+			b_out: ema_balance(self.b_out, incoming.b_out, alpha),
+			a_out: ema_balance(self.a_out, incoming.a_out, alpha),
+			b_in: ema_balance(self.b_in, incoming.b_in, alpha),
+		}
+	}
+}
+
+/// Oracle values of an asset pair `(a, b)`.
+///
+/// `price` is the price of asset `a` denominated in asset `b`.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(RuntimeDebug, Encode, Decode, Copy, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct OracleEntry<BlockNumber> {
+	pub price: Price,
+	pub volume: Volume<Balance>,
+	pub liquidity: Balance,
+	/// Block of the last update.
+	pub timestamp: BlockNumber,
+}
+
+impl<BlockNumber> OracleEntry<BlockNumber>
+where
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Merge another entry of the same block into this one.
+	///
+	/// Volumes are summed up, price and liquidity are taken over from `other`.
+	pub fn accumulate_volume_and_update_from(&mut self, other: &Self) {
+		self.price = other.price;
+		self.liquidity = other.liquidity;
+		self.volume = self.volume.saturating_add(&other.volume);
+		self.timestamp = other.timestamp;
+	}
+
+	/// Returns the entry for the inverted asset pair `(b, a)`.
+	pub fn inverted(&self) -> Option<Self> {
+		Some(Self {
+			price: self.price.reciprocal()?,
+			volume: self.volume.inverted(),
+			liquidity: self.liquidity,
+			timestamp: self.timestamp,
+		})
+	}
+
+	/// Bring the entry of `period` up to `block` (exclusive), assuming no trade happened after the last update.
+	///
+	/// Price and liquidity of the blocks without trades are the values of `last_block`, volume is zero.
+	pub fn updated_to(&self, period: OraclePeriod, block: BlockNumber, last_block: &Self) -> Self {
+		let iterations: u32 = block
+			.saturating_sub(self.timestamp)
+			.saturating_sub(One::one())
+			.unique_saturated_into();
+		if iterations == 0 || period == OraclePeriod::LastBlock {
+			return *self;
+		}
+
+		let alpha = multi_step_smoothing(period.smoothing(), iterations);
+		Self {
+			price: ema_price(self.price, last_block.price, alpha),
+			volume: self.volume.ema(&Volume::default(), alpha),
+			liquidity: ema_balance(self.liquidity, last_block.liquidity, alpha),
+			timestamp: block.saturating_sub(One::one()),
+		}
+	}
+
+	/// Fold `incoming` into the entry of `period`.
+	///
+	/// Blocks between the last update and `incoming` are accounted for first via `updated_to`.
+	pub fn chained_update_via_ema_with(&self, period: OraclePeriod, incoming: &Self, last_block: &Self) -> Self {
+		if period == OraclePeriod::LastBlock {
+			return *incoming;
+		}
+
+		let current = self.updated_to(period, incoming.timestamp, last_block);
+		let alpha = period.smoothing();
+		Self {
+			price: ema_price(current.price, incoming.price, alpha),
+			volume: current.volume.ema(&incoming.volume, alpha),
+			liquidity: ema_balance(current.liquidity, incoming.liquidity, alpha),
+			timestamp: incoming.timestamp,
+		}
+	}
+}
+
+/// Smoothing factor equivalent to applying `alpha` to the same value `iterations` times in a row.
+pub fn multi_step_smoothing(alpha: Price, iterations: u32) -> Price {
+	let complement = Price::one().saturating_sub(alpha);
+	Price::one().saturating_sub(complement.saturating_pow(iterations as usize))
+}
+
+pub fn ema_price(prev: Price, incoming: Price, alpha: Price) -> Price {
+	if incoming >= prev {
+		prev.saturating_add(alpha.saturating_mul(incoming.saturating_sub(prev)))
+	} else {
+		prev.saturating_sub(alpha.saturating_mul(prev.saturating_sub(incoming)))
+	}
+}
+
+pub fn ema_balance(prev: Balance, incoming: Balance, alpha: Price) -> Balance {
+	if incoming >= prev {
+		prev.saturating_add(alpha.saturating_mul_int(incoming.saturating_sub(prev)))
+	} else {
+		prev.saturating_sub(alpha.saturating_mul_int(prev.saturating_sub(incoming)))
+	}
+}
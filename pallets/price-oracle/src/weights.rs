@@ -0,0 +1,103 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_price_oracle
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-11-02, STEPS: 5, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// target/release/basilisk
+// benchmark
+// pallet
+// --pallet=pallet-price-oracle
+// --chain=dev
+// --steps=5
+// --repeat=20
+// --extrinsic=*
+// --heap-pages=4096
+// --template=.maintain/pallet-weight-template-no-back.hbs
+// --output=pallets/price-oracle/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_price_oracle.
+pub trait WeightInfo {
+	fn on_finalize_no_entry() -> Weight;
+	fn on_finalize_multiple_tokens(b: u32) -> Weight;
+	fn on_trade_multiple_tokens(b: u32) -> Weight;
+	fn get_entry() -> Weight;
+}
+
+/// Weights for pallet_price_oracle using the hydraDX node and recommended hardware.
+pub struct HydraWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
+	fn on_finalize_no_entry() -> Weight {
+		Weight::from_ref_time(4_218_000 as u64).saturating_add(T::DbWeight::get().reads(1 as u64))
+	}
+	fn on_finalize_multiple_tokens(b: u32) -> Weight {
+		Weight::from_ref_time(0 as u64) // Standard Error: 39_000
+			.saturating_add(Weight::from_ref_time(35_472_000 as u64).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(b as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(b as u64)))
+	}
+	fn on_trade_multiple_tokens(b: u32) -> Weight {
+		Weight::from_ref_time(10_925_000 as u64) // Standard Error: 3_000
+			.saturating_add(Weight::from_ref_time(301_000 as u64).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn get_entry() -> Weight {
+		Weight::from_ref_time(17_816_000 as u64).saturating_add(T::DbWeight::get().reads(3 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn on_finalize_no_entry() -> Weight {
+		Weight::from_ref_time(4_218_000 as u64).saturating_add(RocksDbWeight::get().reads(1 as u64))
+	}
+	fn on_finalize_multiple_tokens(b: u32) -> Weight {
+		Weight::from_ref_time(0 as u64) // Standard Error: 39_000
+			.saturating_add(Weight::from_ref_time(35_472_000 as u64).saturating_mul(b as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(b as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(b as u64)))
+	}
+	fn on_trade_multiple_tokens(b: u32) -> Weight {
+		Weight::from_ref_time(10_925_000 as u64) // Standard Error: 3_000
+			.saturating_add(Weight::from_ref_time(301_000 as u64).saturating_mul(b as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn get_entry() -> Weight {
+		Weight::from_ref_time(17_816_000 as u64).saturating_add(RocksDbWeight::get().reads(3 as u64))
+	}
+}
@@ -0,0 +1,170 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Router extensions pallet
+//!
+//! Trade variants of `pallet_route_executor` which the route executor does not provide itself.
+//!
+//! `sell_with_deadline` and `buy_with_deadline` execute a trade along a route only if the current block
+//! provided by `BlockNumberProvider` has not passed the deadline of the trade. The trade itself is
+//! dispatched to `pallet_route_executor`, which emits `RouteExecuted`.
+//!
+//! `sell_all` sells the whole transferable balance of `asset_in` of the caller along a route. The balance is read
+//! at the time of execution, i.e. after the transaction fee has been paid, so no dust is left behind when
+//! the fee is paid in `asset_in`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::too_many_arguments)]
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::fungibles::Inspect};
+use frame_system::ensure_signed;
+use pallet_route_executor::weights::WeightInfo as RouterWeightInfo;
+use pallet_route_executor::Trade;
+use primitives::{AssetId, Balance};
+use sp_runtime::traits::BlockNumberProvider;
+use sp_std::vec::Vec;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(crate) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + pallet_route_executor::Config<AssetId = AssetId, Balance = Balance>
+	{
+		/// The block number provider used to check trade deadlines
+		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// Balance inspection used to get the transferable balance sold by `sell_all`
+		type BalanceInspect: Inspect<Self::AccountId, AssetId = AssetId, Balance = Balance>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Trade deadline has passed.
+		DeadlineExpired,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Sell `amount_in` of `asset_in` for `asset_out` along `route` if the trade is executed
+		/// no later than at block `deadline`.
+		///
+		/// Same as `pallet_route_executor::sell`, but fails with `DeadlineExpired` when the current block
+		/// provided by `BlockNumberProvider` is greater than `deadline`.
+		///
+		/// Emits `pallet_route_executor::Event::RouteExecuted` when successful.
+		#[pallet::weight(<T as pallet_route_executor::Config>::WeightInfo::sell(route.len() as u32)
+			.saturating_add(T::DbWeight::get().reads(1)))]
+		pub fn sell_with_deadline(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			min_amount_out: Balance,
+			route: Vec<Trade<AssetId>>,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_signed(origin.clone())?;
+
+			Self::ensure_deadline(deadline)?;
+
+			pallet_route_executor::Pallet::<T>::sell(origin, asset_in, asset_out, amount_in, min_amount_out, route)?;
+
+			Ok(())
+		}
+
+		/// Buy `amount_out` of `asset_out` for `asset_in` along `route` if the trade is executed
+		/// no later than at block `deadline`.
+		///
+		/// Same as `pallet_route_executor::buy`, but fails with `DeadlineExpired` when the current block
+		/// provided by `BlockNumberProvider` is greater than `deadline`.
+		///
+		/// Emits `pallet_route_executor::Event::RouteExecuted` when successful.
+		#[pallet::weight(<T as pallet_route_executor::Config>::WeightInfo::buy(route.len() as u32)
+			.saturating_add(T::DbWeight::get().reads(1)))]
+		pub fn buy_with_deadline(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_out: Balance,
+			max_amount_in: Balance,
+			route: Vec<Trade<AssetId>>,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_signed(origin.clone())?;
+
+			Self::ensure_deadline(deadline)?;
+
+			pallet_route_executor::Pallet::<T>::buy(origin, asset_in, asset_out, amount_out, max_amount_in, route)?;
+
+			Ok(())
+		}
+
+		/// Sell the whole transferable balance of `asset_in` for `asset_out` along `route`.
+		///
+		/// Same as `pallet_route_executor::sell`, but the amount sold is the transferable balance of `asset_in`
+		/// of the caller at the time of execution, i.e. the free balance without locked funds after
+		/// the transaction fee has been paid.
+		///
+		/// Emits `pallet_route_executor::Event::RouteExecuted` with the amount sold when successful.
+		#[pallet::weight(<T as pallet_route_executor::Config>::WeightInfo::sell(route.len() as u32)
+			.saturating_add(T::DbWeight::get().reads(1)))]
+		pub fn sell_all(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			min_amount_out: Balance,
+			route: Vec<Trade<AssetId>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+
+			let amount_in = T::BalanceInspect::reducible_balance(asset_in, &who, false);
+
+			pallet_route_executor::Pallet::<T>::sell(origin, asset_in, asset_out, amount_in, min_amount_out, route)?;
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure that the current block provided by `BlockNumberProvider` has not passed `deadline`.
+	fn ensure_deadline(deadline: T::BlockNumber) -> DispatchResult {
+		ensure!(
+			T::BlockNumberProvider::current_block_number() <= deadline,
+			Error::<T>::DeadlineExpired
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,281 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as router_extensions;
+use crate::Config;
+use frame_support::parameter_types;
+use frame_support::traits::{Everything, Nothing};
+use frame_system as system;
+use frame_system::{EnsureRoot, EnsureSigned};
+use hydradx_traits::{pools::DustRemovalAccountWhitelist, AssetPairAccountIdFor};
+use orml_traits::parameter_type_with_key;
+use pallet_xyk::AllowAllPools;
+use primitives::{
+	constants::chain::{DISCOUNTED_FEE, MAX_IN_RATIO, MAX_OUT_RATIO, MIN_POOL_LIQUIDITY, MIN_TRADING_LIMIT},
+	AssetId, Balance,
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, One},
+	DispatchError,
+};
+
+pub type Amount = i128;
+pub type AccountId = u64;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const TREASURY: AccountId = 99;
+
+pub const HDX: AssetId = 1000;
+pub const DOT: AssetId = 2000;
+pub const ACA: AssetId = 3000;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+	 Block = Block,
+	 NodeBlock = Block,
+	 UncheckedExtrinsic = UncheckedExtrinsic,
+	 {
+		 System: frame_system,
+		 RouterExtensions: router_extensions,
+		 Router: pallet_route_executor,
+		 XYK: pallet_xyk,
+		 Currency: orml_tokens,
+		 AssetRegistry: pallet_asset_registry,
+	 }
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const NativeAssetId: AssetId = HDX;
+	pub RegistryStringLimit: u32 = 100;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_asset_registry::Config for Test {
+	type Event = Event;
+	type RegistryOrigin = EnsureSigned<AccountId>;
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type AssetNativeLocation = u8;
+	type StringLimit = RegistryStringLimit;
+	type NativeAssetId = NativeAssetId;
+	type WeightInfo = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		One::one()
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type ReserveIdentifier = ();
+	type MaxReserves = ();
+}
+
+pub struct AssetPairAccountIdTest();
+
+impl AssetPairAccountIdFor<AssetId, u64> for AssetPairAccountIdTest {
+	fn from_assets(asset_a: AssetId, asset_b: AssetId, _: &str) -> u64 {
+		let mut a = asset_a as u128;
+		let mut b = asset_b as u128;
+		if a > b {
+			std::mem::swap(&mut a, &mut b)
+		}
+		(a * 1000 + b) as u64
+	}
+}
+
+pub struct Whitelist;
+
+impl DustRemovalAccountWhitelist<AccountId> for Whitelist {
+	type Error = DispatchError;
+
+	fn add_account(_account: &AccountId) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
+	fn remove_account(_account: &AccountId) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const MinTradingLimit: Balance = MIN_TRADING_LIMIT;
+	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
+	pub const MaxInRatio: u128 = MAX_IN_RATIO;
+	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+	pub const ExchangeFeeRate: (u32, u32) = (2, 1_000);
+	pub const DiscountedFeeRate: (u32, u32) = DISCOUNTED_FEE;
+	pub const MinPoolFee: (u32, u32) = (1, 10_000);
+	pub const MaxPoolFee: (u32, u32) = (1, 10);
+	pub const ProtocolFeeRate: (u32, u32) = (0, 0);
+	pub const ProtocolFeeReceiver: AccountId = TREASURY;
+	pub const VolumePeriodLength: u64 = 10;
+	pub const MaxVolumePeriods: u32 = 3;
+	pub const MaxPriceChangePerBlock: Option<(u32, u32)> = None;
+	pub const MaxLiquidityRemovalPerBlock: Option<(u32, u32)> = None;
+}
+
+impl pallet_xyk::Config for Test {
+	type Event = Event;
+	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = ();
+	type AssetPairAccountId = AssetPairAccountIdTest;
+	type Currency = Currency;
+	type BalanceInspect = Currency;
+	type NativeAssetId = NativeAssetId;
+	type WeightInfo = ();
+	type GetExchangeFee = ExchangeFeeRate;
+	type MinPoolFee = MinPoolFee;
+	type MaxPoolFee = MaxPoolFee;
+	type UpdatePoolFeeOrigin = EnsureRoot<AccountId>;
+	type UpdateReservesOrigin = EnsureRoot<AccountId>;
+	type UpdatePoolStateOrigin = EnsureRoot<AccountId>;
+	type ProtocolFee = ProtocolFeeRate;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type MinTradingLimit = MinTradingLimit;
+	type MinPoolLiquidity = MinPoolLiquidity;
+	type MaxInRatio = MaxInRatio;
+	type MaxOutRatio = MaxOutRatio;
+	type CanCreatePool = AllowAllPools;
+	type AMMHandler = ();
+	type DiscountedFee = DiscountedFeeRate;
+	type NonDustableWhitelistHandler = Whitelist;
+	type BlockNumberProvider = System;
+	type VolumePeriodLength = VolumePeriodLength;
+	type MaxVolumePeriods = MaxVolumePeriods;
+	type MaxPriceChangePerBlock = MaxPriceChangePerBlock;
+	type MaxLiquidityRemovalPerBlock = MaxLiquidityRemovalPerBlock;
+	type Call = Call;
+}
+
+parameter_types! {
+	pub const MaxNumberOfTrades: u8 = 3;
+}
+
+impl pallet_route_executor::Config for Test {
+	type Event = Event;
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type MaxNumberOfTrades = MaxNumberOfTrades;
+	type Currency = Currency;
+	type AMM = XYK;
+	type WeightInfo = ();
+}
+
+impl Config for Test {
+	type BlockNumberProvider = System;
+	type BalanceInspect = Currency;
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+}
+
+// Returns default values for genesis config
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![
+				(ALICE, HDX, 1_000_000 * ONE),
+				(ALICE, DOT, 1_000_000 * ONE),
+				(ALICE, ACA, 1_000_000 * ONE),
+				(BOB, HDX, 1_000_000 * ONE),
+				(BOB, DOT, 1_000_000 * ONE),
+				(CHARLIE, HDX, 1_000 * ONE),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		orml_tokens::GenesisConfig::<Test> {
+			balances: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			// HDX/DOT pool with the spot price of 2 DOT for 1 HDX
+			assert!(XYK::create_pool(Origin::signed(ALICE), HDX, 100_000 * ONE, DOT, 200_000 * ONE).is_ok());
+			// DOT/ACA pool with the spot price of 1 ACA for 1 DOT
+			assert!(XYK::create_pool(Origin::signed(ALICE), DOT, 100_000 * ONE, ACA, 100_000 * ONE).is_ok());
+		});
+		ext
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	ExtBuilder::default().build()
+}
+
+pub fn expect_events(e: Vec<Event>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
@@ -0,0 +1,174 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::mock::{
+	expect_events, new_test_ext, Currency, Origin, RouterExtensions, System, Test, ACA, BOB, CHARLIE, DOT, HDX, ONE,
+};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::router::PoolType;
+use orml_traits::{MultiCurrency, MultiLockableCurrency};
+
+fn hdx_aca_route() -> Vec<Trade<AssetId>> {
+	vec![
+		Trade {
+			pool: PoolType::XYK,
+			asset_in: HDX,
+			asset_out: DOT,
+		},
+		Trade {
+			pool: PoolType::XYK,
+			asset_in: DOT,
+			asset_out: ACA,
+		},
+	]
+}
+
+#[test]
+fn sell_with_deadline_should_work_when_deadline_has_not_passed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+
+		assert_ok!(RouterExtensions::sell_with_deadline(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000 * ONE,
+			ONE,
+			hdx_aca_route(),
+			10
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &BOB), 999_000 * ONE);
+		assert!(Currency::free_balance(ACA, &BOB) > ONE);
+	});
+}
+
+#[test]
+fn buy_with_deadline_should_work_when_deadline_has_not_passed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+
+		assert_ok!(RouterExtensions::buy_with_deadline(
+			Origin::signed(BOB),
+			HDX,
+			ACA,
+			1_000 * ONE,
+			1_000 * ONE,
+			hdx_aca_route(),
+			11
+		));
+
+		assert_eq!(Currency::free_balance(ACA, &BOB), 1_000 * ONE);
+		assert!(Currency::free_balance(HDX, &BOB) < 1_000_000 * ONE);
+	});
+}
+
+#[test]
+fn trade_with_deadline_should_not_work_when_deadline_has_passed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+
+		assert_noop!(
+			RouterExtensions::sell_with_deadline(Origin::signed(BOB), HDX, ACA, 1_000 * ONE, ONE, hdx_aca_route(), 9),
+			Error::<Test>::DeadlineExpired
+		);
+		assert_noop!(
+			RouterExtensions::buy_with_deadline(
+				Origin::signed(BOB),
+				HDX,
+				ACA,
+				1_000 * ONE,
+				1_000 * ONE,
+				hdx_aca_route(),
+				9
+			),
+			Error::<Test>::DeadlineExpired
+		);
+	});
+}
+
+#[test]
+fn sell_with_deadline_should_fail_when_route_executor_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+
+		assert_noop!(
+			RouterExtensions::sell_with_deadline(
+				Origin::signed(BOB),
+				HDX,
+				ACA,
+				2_000_000 * ONE,
+				ONE,
+				hdx_aca_route(),
+				10
+			),
+			pallet_route_executor::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn sell_all_should_sell_whole_transferable_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RouterExtensions::sell_all(
+			Origin::signed(CHARLIE),
+			HDX,
+			ACA,
+			ONE,
+			hdx_aca_route()
+		));
+
+		let amount_out = Currency::free_balance(ACA, &CHARLIE);
+
+		assert_eq!(Currency::free_balance(HDX, &CHARLIE), 0);
+		assert!(amount_out > ONE);
+
+		expect_events(vec![pallet_route_executor::Event::RouteExecuted {
+			asset_in: HDX,
+			asset_out: ACA,
+			amount_in: 1_000 * ONE,
+			amount_out,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn sell_all_should_not_sell_locked_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Currency::set_lock(*b"testlock", HDX, &CHARLIE, 400 * ONE));
+
+		assert_ok!(RouterExtensions::sell_all(
+			Origin::signed(CHARLIE),
+			HDX,
+			ACA,
+			ONE,
+			hdx_aca_route()
+		));
+
+		assert_eq!(Currency::free_balance(HDX, &CHARLIE), 400 * ONE);
+
+		expect_events(vec![pallet_route_executor::Event::RouteExecuted {
+			asset_in: HDX,
+			asset_out: ACA,
+			amount_in: 600 * ONE,
+			amount_out: Currency::free_balance(ACA, &CHARLIE),
+		}
+		.into()]);
+	});
+}
@@ -0,0 +1,80 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for route discovery of the route executor.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Type of the pool a trade is executed in.
+///
+/// Encoded as the `XYK`, `LBP` and `Stableswap` variants of `hydradx_traits::router::PoolType`.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum PoolType<AssetId> {
+	XYK,
+	/// LBP sale.
+	LBP,
+	/// Stableswap pool or weighted pool identified by its share token.
+	Stableswap(AssetId),
+}
+
+/// Single trade of a route.
+///
+/// Encoded as `pallet_route_executor::Trade`, so a route can be submitted to the route executor as it is.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Trade<AssetId> {
+	pub pool: PoolType<AssetId>,
+	pub asset_in: AssetId,
+	pub asset_out: AssetId,
+}
+
+/// Route with the amounts calculated along it.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RouteQuote<AssetId, Balance> {
+	/// Trades of the route, from asset in to asset out.
+	pub route: Vec<Trade<AssetId>>,
+	/// Amount of asset in sold for a sell or paid for a buy.
+	pub amount_in: Balance,
+	/// Amount of asset out received for a sell or bought for a buy.
+	pub amount_out: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to find the best route for the route executor.
+	pub trait RouterApi<AssetId, Balance> where
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Return the route selling `amount_in` of `asset_in` for the biggest amount of `asset_out`.
+		fn find_best_sell_route(asset_in: AssetId, asset_out: AssetId, amount_in: Balance) -> Option<RouteQuote<AssetId, Balance>>;
+
+		/// Return the route buying `amount_out` of `asset_out` for the smallest amount of `asset_in`.
+		fn find_best_buy_route(asset_in: AssetId, asset_out: AssetId, amount_out: Balance) -> Option<RouteQuote<AssetId, Balance>>;
+	}
+}
@@ -0,0 +1,126 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for route discovery of the route executor.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_router_rpc_runtime_api::RouteQuote;
+pub use pallet_router_rpc_runtime_api::RouterApi as RouterRuntimeApi;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc(server)]
+pub trait RouterApi<BlockHash, AssetId, Balance> {
+	/// Find the route selling `amount_in` of `asset_in` for the biggest amount of `asset_out`.
+	#[method(name = "router_findBestSellRoute")]
+	fn find_best_sell_route(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RouteQuote<AssetId, Balance>>>;
+
+	/// Find the route buying `amount_out` of `asset_out` for the smallest amount of `asset_in`.
+	#[method(name = "router_findBestBuyRoute")]
+	fn find_best_buy_route(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RouteQuote<AssetId, Balance>>>;
+}
+
+/// Provides RPC methods to find the best route for the route executor.
+pub struct Router<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Router<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn rpc_error(error: Error, message: &str, data: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(error.into(), message, Some(format!("{:?}", data)))).into()
+}
+
+impl<C, Block, AssetId, Balance> RouterApiServer<<Block as BlockT>::Hash, AssetId, Balance> for Router<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: RouterRuntimeApi<Block, AssetId, Balance>,
+	AssetId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn find_best_sell_route(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RouteQuote<AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.find_best_sell_route(&at, asset_in, asset_out, amount_in)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to find sell route.", e))
+	}
+
+	fn find_best_buy_route(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RouteQuote<AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.find_best_buy_route(&at, asset_in, asset_out, amount_out)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to find buy route.", e))
+	}
+}
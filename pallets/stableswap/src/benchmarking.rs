@@ -0,0 +1,158 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+const SEED: u32 = 1;
+
+const ONE: Balance = 1_000_000_000_000;
+const DECIMALS: u8 = 12;
+
+const AMPLIFICATION: u16 = 100;
+const FEE: (u32, u32) = (1, 1000);
+
+/// Register assets of a pool with maximum number of assets.
+fn register_assets<T: Config>() -> Result<Vec<AssetId>, DispatchError> {
+	(0..MAX_ASSETS_IN_POOL)
+		.map(|i| {
+			let mut name = b"asset".to_vec();
+			name.extend_from_slice(&i.to_le_bytes());
+			T::BenchmarkHelper::register_asset(name, DECIMALS)
+		})
+		.collect()
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32, assets: &[AssetId]) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	for asset_id in assets.iter() {
+		T::Currency::deposit(*asset_id, &caller, 1_000_000 * ONE).unwrap();
+	}
+	caller
+}
+
+/// Create pool with maximum number of assets and initial liquidity provided by `maker`.
+fn create_pool_with_liquidity<T: Config>() -> Result<(AssetId, Vec<AssetId>), DispatchError> {
+	let assets = register_assets::<T>()?;
+	let maker = funded_account::<T>("maker", 0, &assets);
+
+	Pallet::<T>::create_pool(RawOrigin::Root.into(), assets.clone(), AMPLIFICATION, FEE)?;
+
+	let pool_id = T::AssetRegistry::retrieve_asset(&Pallet::<T>::share_token_name(&assets))?;
+
+	let liquidity = assets.iter().map(|asset_id| (*asset_id, 10_000 * ONE)).collect();
+	Pallet::<T>::add_liquidity(RawOrigin::Signed(maker).into(), pool_id, liquidity, 0)?;
+
+	Ok((pool_id, assets))
+}
+
+benchmarks! {
+	create_pool {
+		let assets = register_assets::<T>()?;
+		let pool_id_name = Pallet::<T>::share_token_name(&assets);
+	}: _(RawOrigin::Root, assets, AMPLIFICATION, FEE)
+	verify {
+		let pool_id = T::AssetRegistry::retrieve_asset(&pool_id_name)?;
+		assert!(<Pools<T>>::contains_key(pool_id));
+	}
+
+	update_pool {
+		let (pool_id, _) = create_pool_with_liquidity::<T>()?;
+	}: _(RawOrigin::Root, pool_id, Some(AMPLIFICATION * 2), Some((2, 1000)))
+	verify {
+		assert_eq!(Pallet::<T>::pools(pool_id).unwrap().amplification, AMPLIFICATION * 2);
+	}
+
+	add_liquidity {
+		let (pool_id, assets) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, &assets);
+
+		// liquidity in all but one asset is the most expensive to calculate
+		let liquidity: Vec<(AssetId, Balance)> = assets.iter().skip(1).map(|asset_id| (*asset_id, 100 * ONE)).collect();
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, liquidity, 0)
+	verify {
+		assert!(T::Currency::free_balance(pool_id, &caller) > 0);
+	}
+
+	remove_liquidity {
+		let (pool_id, assets) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, &assets);
+
+		let liquidity = assets.iter().map(|asset_id| (*asset_id, 100 * ONE)).collect();
+		Pallet::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), pool_id, liquidity, 0)?;
+
+		let shares = T::Currency::free_balance(pool_id, &caller);
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, shares)
+	verify {
+		assert_eq!(T::Currency::free_balance(pool_id, &caller), 0);
+	}
+
+	remove_liquidity_one_asset {
+		let (pool_id, assets) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, &assets);
+
+		let liquidity = assets.iter().map(|asset_id| (*asset_id, 100 * ONE)).collect();
+		Pallet::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), pool_id, liquidity, 0)?;
+
+		let shares = T::Currency::free_balance(pool_id, &caller);
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, assets[0], shares, 0)
+	verify {
+		assert_eq!(T::Currency::free_balance(pool_id, &caller), 0);
+	}
+
+	sell {
+		let (pool_id, assets) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, &assets);
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, assets[0], assets[1], 100 * ONE, 0)
+	verify {
+		assert!(T::Currency::free_balance(assets[1], &caller) > 1_000_000 * ONE);
+	}
+
+	buy {
+		let (pool_id, assets) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, &assets);
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, assets[1], assets[0], 100 * ONE, Balance::MAX)
+	verify {
+		assert_eq!(T::Currency::free_balance(assets[1], &caller), 1_000_100 * ONE);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::mock::{ExtBuilder, System, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		ExtBuilder::default().build().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
+			assert_ok!(Pallet::<Test>::test_benchmark_update_pool());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity_one_asset());
+			assert_ok!(Pallet::<Test>::test_benchmark_sell());
+			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+		});
+	}
+}
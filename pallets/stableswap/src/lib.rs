@@ -0,0 +1,851 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Stableswap Pallet
+//!
+//! ## Overview
+//!
+//! Stableswap pallet provides pools of 2 to `MAX_ASSETS_IN_POOL` assets which are expected to keep the same value,
+//! such as different representations of one token or stablecoins.
+//!
+//! Pools use the Curve stableswap invariant. The amplification parameter of a pool determines how close to
+//! a constant price the pool trades when its reserves are balanced.
+//!
+//! Pools are created and updated by `AuthorityOrigin`. Each pool is identified by its share token,
+//! which is registered in the asset registry when the pool is created.
+//!
+//! Reserves of assets with different decimals are normalized to `math::TARGET_PRECISION` decimals.
+//!
+//! This pallet implements `TradeExecution` for `PoolType::Stableswap` so the pools can be used by the router.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{ConstU32, Get},
+	transactional, BoundedVec, PalletId,
+};
+use frame_system::ensure_signed;
+use hydradx_traits::Registry;
+use orml_traits::MultiCurrency;
+use primitive_types::U256;
+use primitives::{AssetId, Balance};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	DispatchError,
+};
+use sp_std::{vec, vec::Vec};
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+pub mod math;
+mod trade_execution;
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// Maximum number of assets in a pool.
+pub const MAX_ASSETS_IN_POOL: u32 = 5;
+
+/// Minimum number of assets in a pool.
+pub const MIN_ASSETS_IN_POOL: u32 = 2;
+
+/// Asset of a pool.
+#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PoolAsset {
+	pub asset_id: AssetId,
+	/// Decimals of the asset, used to normalize its reserve.
+	pub decimals: u8,
+}
+
+/// Stableswap pool.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PoolInfo {
+	/// Assets of the pool ordered by asset id.
+	pub assets: BoundedVec<PoolAsset, ConstU32<MAX_ASSETS_IN_POOL>>,
+	/// Amplification parameter.
+	pub amplification: u16,
+	/// Trading fee. Also charged on the imbalanced part of added or removed liquidity.
+	pub fee: (u32, u32),
+}
+
+impl PoolInfo {
+	/// Position of `asset_id` in the pool.
+	pub fn asset_index(&self, asset_id: AssetId) -> Option<usize> {
+		self.assets.iter().position(|asset| asset.asset_id == asset_id)
+	}
+
+	/// Ids of the pool assets.
+	pub fn asset_ids(&self) -> Vec<AssetId> {
+		self.assets.iter().map(|asset| asset.asset_id).collect()
+	}
+}
+
+/// Provides decimals of registered assets.
+pub trait AssetDecimals<AssetId> {
+	fn decimals(asset_id: AssetId) -> Option<u8>;
+}
+
+/// Prepares assets of pools used in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetId> {
+	/// Register new asset with given decimals.
+	fn register_asset(name: Vec<u8>, decimals: u8) -> Result<AssetId, DispatchError>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Multi currency for transfer of currencies
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = AssetId, Balance = Balance>;
+
+		/// Registry used to register share tokens of pools
+		type AssetRegistry: Registry<AssetId, Vec<u8>, Balance, DispatchError>;
+
+		/// Decimals of pool assets
+		type AssetDecimals: AssetDecimals<AssetId>;
+
+		/// Origin allowed to create and update pools
+		type AuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Pallet id used to derive accounts of pools
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Minimum amplification parameter of a pool
+		#[pallet::constant]
+		type MinAmplification: Get<u16>;
+
+		/// Maximum amplification parameter of a pool
+		#[pallet::constant]
+		type MaxAmplification: Get<u16>;
+
+		/// Maximum trading fee of a pool
+		#[pallet::constant]
+		type MaxPoolFee: Get<(u32, u32)>;
+
+		/// Trading limit
+		#[pallet::constant]
+		type MinTradingLimit: Get<Balance>;
+
+		/// Minimum amount of shares held by an account
+		#[pallet::constant]
+		type MinPoolLiquidity: Get<Balance>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<AssetId>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Number of assets is out of bounds or assets are not unique.
+		IncorrectAssets,
+
+		/// Amplification is out of bounds.
+		InvalidAmplification,
+
+		/// Fee is higher than allowed maximum.
+		InvalidFee,
+
+		/// Decimals of an asset are not known or exceed the target precision.
+		InvalidAssetDecimals,
+
+		/// Pool with the same share token already exists.
+		PoolExists,
+
+		/// Pool does not exist.
+		PoolNotFound,
+
+		/// Asset is not in the pool.
+		AssetNotInPool,
+
+		/// Nothing to update.
+		NothingToUpdate,
+
+		/// Amount is zero or asset is listed more than once.
+		InvalidAssetAmount,
+
+		/// Initial liquidity has to be provided in all assets of the pool.
+		IncorrectInitialLiquidity,
+
+		/// Pool has no liquidity.
+		InsufficientLiquidity,
+
+		/// Account does not have enough balance.
+		InsufficientBalance,
+
+		/// Account does not have enough shares or would be left with less than minimum amount of shares.
+		InsufficientShares,
+
+		/// All shares of the pool can be removed only proportionally.
+		InsufficientLiquidityRemaining,
+
+		/// Amount is less than minimum trading limit.
+		InsufficientTradingAmount,
+
+		/// Minimum limit has not been reached during trade.
+		BuyLimitNotReached,
+
+		/// Maximum limit has been exceeded during trade.
+		SellLimitExceeded,
+
+		/// Minimum amount of shares has not been reached.
+		SharesLimitNotReached,
+
+		/// Minimum amount of removed asset has not been reached.
+		RemoveLiquidityLimitNotReached,
+
+		/// Trading same assets is not allowed.
+		SameAssetTradeNotAllowed,
+
+		/// Overflow or calculation did not converge.
+		MathError,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Pool was created.
+		PoolCreated {
+			pool_id: AssetId,
+			assets: Vec<AssetId>,
+			amplification: u16,
+			fee: (u32, u32),
+		},
+
+		/// Amplification or fee of the pool was updated.
+		PoolUpdated {
+			pool_id: AssetId,
+			amplification: u16,
+			fee: (u32, u32),
+		},
+
+		/// Liquidity was provided to the pool.
+		LiquidityAdded {
+			pool_id: AssetId,
+			who: T::AccountId,
+			shares: Balance,
+			assets: Vec<(AssetId, Balance)>,
+		},
+
+		/// Liquidity was removed from the pool.
+		LiquidityRemoved {
+			pool_id: AssetId,
+			who: T::AccountId,
+			shares: Balance,
+			amounts: Vec<(AssetId, Balance)>,
+			fee: Balance,
+		},
+
+		/// Asset sale executed.
+		SellExecuted {
+			who: T::AccountId,
+			pool_id: AssetId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			amount_out: Balance,
+			fee: Balance,
+		},
+
+		/// Asset purchase executed.
+		BuyExecuted {
+			who: T::AccountId,
+			pool_id: AssetId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			amount_out: Balance,
+			fee: Balance,
+		},
+	}
+
+	/// Existing pools identified by their share token.
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub(crate) type Pools<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, PoolInfo, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create new pool of given assets.
+		///
+		/// Registers share token of the pool in asset registry. Id of the share token is the id of the pool.
+		///
+		/// Pool is created empty, initial liquidity has to be provided in all assets by `add_liquidity`.
+		///
+		/// Emits `PoolCreated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			assets: Vec<AssetId>,
+			amplification: u16,
+			fee: (u32, u32),
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			let assets_count = assets.len();
+
+			let mut assets = assets;
+			assets.sort_unstable();
+			assets.dedup();
+
+			ensure!(
+				assets.len() == assets_count
+					&& (MIN_ASSETS_IN_POOL..=MAX_ASSETS_IN_POOL).contains(&(assets_count as u32)),
+				Error::<T>::IncorrectAssets
+			);
+
+			Self::do_create_pool(assets, amplification, fee)
+		}
+
+		/// Update amplification or fee of the pool.
+		///
+		/// Emits `PoolUpdated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::update_pool())]
+		pub fn update_pool(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			amplification: Option<u16>,
+			fee: Option<(u32, u32)>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			ensure!(amplification.is_some() || fee.is_some(), Error::<T>::NothingToUpdate);
+
+			<Pools<T>>::try_mutate(pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+
+				if let Some(amplification) = amplification {
+					ensure!(
+						Self::is_amplification_valid(amplification),
+						Error::<T>::InvalidAmplification
+					);
+					pool.amplification = amplification;
+				}
+
+				if let Some(fee) = fee {
+					ensure!(Self::is_fee_valid(fee), Error::<T>::InvalidFee);
+					pool.fee = fee;
+				}
+
+				Self::deposit_event(Event::PoolUpdated {
+					pool_id,
+					amplification: pool.amplification,
+					fee: pool.fee,
+				});
+
+				Ok(())
+			})
+		}
+
+		/// Add liquidity to the pool in any subset of its assets.
+		///
+		/// Liquidity which changes the ratio of pool reserves pays fee of the pool from the imbalanced part.
+		/// Initial liquidity has to be provided in all assets of the pool.
+		///
+		/// `min_shares` - minimum amount of shares to be received.
+		///
+		/// Emits `LiquidityAdded` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			assets: Vec<(AssetId, Balance)>,
+			min_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			ensure!(!assets.is_empty(), Error::<T>::InvalidAssetAmount);
+
+			let initial_reserves = Self::normalized_reserves(&pool, &pool_account)?;
+			let mut updated_reserves = initial_reserves.clone();
+
+			for (i, (asset_id, amount)) in assets.iter().enumerate() {
+				ensure!(
+					!amount.is_zero() && !assets.iter().skip(i + 1).any(|(other, _)| other == asset_id),
+					Error::<T>::InvalidAssetAmount
+				);
+
+				let idx = pool.asset_index(*asset_id).ok_or(Error::<T>::AssetNotInPool)?;
+
+				ensure!(
+					T::Currency::free_balance(*asset_id, &who) >= *amount,
+					Error::<T>::InsufficientBalance
+				);
+
+				let normalized_amount =
+					math::normalize(*amount, pool.assets[idx].decimals).ok_or(Error::<T>::MathError)?;
+				updated_reserves[idx] = updated_reserves[idx]
+					.checked_add(normalized_amount)
+					.ok_or(Error::<T>::MathError)?;
+			}
+
+			let share_issuance = T::Currency::total_issuance(pool_id);
+
+			if share_issuance.is_zero() {
+				ensure!(
+					updated_reserves.iter().all(|reserve| !reserve.is_zero()),
+					Error::<T>::IncorrectInitialLiquidity
+				);
+			}
+
+			let shares = math::calculate_shares(
+				&initial_reserves,
+				&updated_reserves,
+				pool.amplification,
+				share_issuance,
+				pool.fee,
+			)
+			.ok_or(Error::<T>::MathError)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::InvalidAssetAmount);
+			ensure!(shares >= min_shares, Error::<T>::SharesLimitNotReached);
+			ensure!(
+				T::Currency::free_balance(pool_id, &who).saturating_add(shares) >= T::MinPoolLiquidity::get(),
+				Error::<T>::InsufficientShares
+			);
+
+			for (asset_id, amount) in assets.iter() {
+				T::Currency::transfer(*asset_id, &who, &pool_account, *amount)?;
+			}
+
+			T::Currency::deposit(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityAdded {
+				pool_id,
+				who,
+				shares,
+				assets,
+			});
+
+			Ok(())
+		}
+
+		/// Remove liquidity from the pool in all its assets proportionally to the reserves.
+		///
+		/// No fee is charged. This is the only way to remove all liquidity of the pool.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(origin: OriginFor<T>, pool_id: AssetId, shares: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			Self::ensure_shares_can_be_removed(pool_id, &who, shares)?;
+
+			let share_issuance = T::Currency::total_issuance(pool_id);
+
+			let mut amounts = Vec::with_capacity(pool.assets.len());
+
+			for asset in pool.assets.iter() {
+				let reserve = T::Currency::free_balance(asset.asset_id, &pool_account);
+				let amount = U256::from(reserve)
+					.checked_mul(U256::from(shares))
+					.and_then(|v| v.checked_div(U256::from(share_issuance)))
+					.and_then(|v| Balance::try_from(v).ok())
+					.ok_or(Error::<T>::MathError)?;
+
+				T::Currency::transfer(asset.asset_id, &pool_account, &who, amount)?;
+				amounts.push((asset.asset_id, amount));
+			}
+
+			T::Currency::withdraw(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityRemoved {
+				pool_id,
+				who,
+				shares,
+				amounts,
+				fee: Balance::zero(),
+			});
+
+			Ok(())
+		}
+
+		/// Remove liquidity from the pool in a single asset.
+		///
+		/// Fee of the pool is charged from the imbalanced part of the removed liquidity.
+		///
+		/// `min_amount_out` - minimum amount of `asset_id` to be received.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity_one_asset())]
+		#[transactional]
+		pub fn remove_liquidity_one_asset(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			asset_id: AssetId,
+			shares: Balance,
+			min_amount_out: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::pool_account(pool_id);
+
+			let idx = pool.asset_index(asset_id).ok_or(Error::<T>::AssetNotInPool)?;
+
+			Self::ensure_shares_can_be_removed(pool_id, &who, shares)?;
+
+			let share_issuance = T::Currency::total_issuance(pool_id);
+
+			ensure!(shares < share_issuance, Error::<T>::InsufficientLiquidityRemaining);
+
+			let reserves = Self::normalized_reserves(&pool, &pool_account)?;
+
+			let (amount, fee) = math::calculate_withdraw_one_asset(
+				&reserves,
+				shares,
+				idx,
+				share_issuance,
+				pool.amplification,
+				pool.fee,
+			)
+			.ok_or(Error::<T>::MathError)?;
+
+			let decimals = pool.assets[idx].decimals;
+			let amount = math::denormalize(amount, decimals, false).ok_or(Error::<T>::MathError)?;
+			let fee = math::denormalize(fee, decimals, false).ok_or(Error::<T>::MathError)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::InsufficientTradingAmount);
+			ensure!(amount >= min_amount_out, Error::<T>::RemoveLiquidityLimitNotReached);
+
+			T::Currency::transfer(asset_id, &pool_account, &who, amount)?;
+			T::Currency::withdraw(pool_id, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityRemoved {
+				pool_id,
+				who,
+				shares,
+				amounts: vec![(asset_id, amount)],
+				fee,
+			});
+
+			Ok(())
+		}
+
+		/// Trade `amount_in` of `asset_in` for `asset_out`.
+		///
+		/// Fee of the pool is deducted from the received amount.
+		///
+		/// `min_buy_amount` - minimum amount of `asset_out` to be received.
+		///
+		/// Emits `SellExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell())]
+		#[transactional]
+		pub fn sell(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			min_buy_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::Currency::free_balance(asset_in, &who) >= amount_in,
+				Error::<T>::InsufficientBalance
+			);
+
+			let (amount_out, fee) = Self::quote_sell(pool_id, asset_in, asset_out, amount_in)?;
+
+			ensure!(amount_out >= min_buy_amount, Error::<T>::BuyLimitNotReached);
+
+			let pool_account = Self::pool_account(pool_id);
+
+			T::Currency::transfer(asset_in, &who, &pool_account, amount_in)?;
+			T::Currency::transfer(asset_out, &pool_account, &who, amount_out)?;
+
+			Self::deposit_event(Event::SellExecuted {
+				who,
+				pool_id,
+				asset_in,
+				asset_out,
+				amount_in,
+				amount_out,
+				fee,
+			});
+
+			Ok(())
+		}
+
+		/// Buy `amount_out` of `asset_out` for `asset_in`.
+		///
+		/// Fee of the pool is added to the paid amount.
+		///
+		/// `max_sell_amount` - maximum amount of `asset_in` to be paid.
+		///
+		/// Emits `BuyExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			pool_id: AssetId,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount_out: Balance,
+			max_sell_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (amount_in, fee) = Self::quote_buy(pool_id, asset_in, asset_out, amount_out)?;
+
+			ensure!(amount_in <= max_sell_amount, Error::<T>::SellLimitExceeded);
+			ensure!(
+				T::Currency::free_balance(asset_in, &who) >= amount_in,
+				Error::<T>::InsufficientBalance
+			);
+
+			let pool_account = Self::pool_account(pool_id);
+
+			T::Currency::transfer(asset_in, &who, &pool_account, amount_in)?;
+			T::Currency::transfer(asset_out, &pool_account, &who, amount_out)?;
+
+			Self::deposit_event(Event::BuyExecuted {
+				who,
+				pool_id,
+				asset_in,
+				asset_out,
+				amount_in,
+				amount_out,
+				fee,
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account holding reserves of the pool.
+	pub fn pool_account(pool_id: AssetId) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(pool_id)
+	}
+
+	/// Return ids of all existing pools.
+	pub fn pool_ids() -> Vec<AssetId> {
+		<Pools<T>>::iter_keys().collect()
+	}
+
+	/// Name of the share token of a pool with given assets.
+	pub fn share_token_name(assets: &[AssetId]) -> Vec<u8> {
+		let mut name = Vec::new();
+
+		for (i, asset_id) in assets.iter().enumerate() {
+			if i > 0 {
+				name.extend_from_slice(b"STS");
+			}
+			name.extend_from_slice(&asset_id.to_le_bytes());
+		}
+
+		name
+	}
+
+	/// Calculate amount of `asset_out` received for `amount_in` of `asset_in`.
+	///
+	/// Returns `(amount_out, fee)`, fee is already deducted from the amount.
+	pub fn quote_sell(
+		pool_id: AssetId,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::SameAssetTradeNotAllowed);
+		ensure!(
+			amount_in >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let idx_in = pool.asset_index(asset_in).ok_or(Error::<T>::AssetNotInPool)?;
+		let idx_out = pool.asset_index(asset_out).ok_or(Error::<T>::AssetNotInPool)?;
+
+		let reserves = Self::normalized_reserves(&pool, &Self::pool_account(pool_id))?;
+		ensure!(
+			reserves.iter().all(|reserve| !reserve.is_zero()),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let amount_in = math::normalize(amount_in, pool.assets[idx_in].decimals).ok_or(Error::<T>::MathError)?;
+
+		let amount_out = math::calculate_out_given_in(&reserves, idx_in, idx_out, amount_in, pool.amplification)
+			.and_then(|amount| math::denormalize(amount, pool.assets[idx_out].decimals, false))
+			.ok_or(Error::<T>::MathError)?;
+
+		let fee = math::calculate_fee(amount_out, pool.fee).ok_or(Error::<T>::MathError)?;
+		let amount_out = amount_out.saturating_sub(fee);
+
+		ensure!(!amount_out.is_zero(), Error::<T>::InsufficientTradingAmount);
+
+		Ok((amount_out, fee))
+	}
+
+	/// Calculate amount of `asset_in` required to receive `amount_out` of `asset_out`.
+	///
+	/// Returns `(amount_in, fee)`, fee is already included in the amount.
+	pub fn quote_buy(
+		pool_id: AssetId,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::SameAssetTradeNotAllowed);
+		ensure!(
+			amount_out >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let idx_in = pool.asset_index(asset_in).ok_or(Error::<T>::AssetNotInPool)?;
+		let idx_out = pool.asset_index(asset_out).ok_or(Error::<T>::AssetNotInPool)?;
+
+		let reserves = Self::normalized_reserves(&pool, &Self::pool_account(pool_id))?;
+		ensure!(
+			reserves.iter().all(|reserve| !reserve.is_zero()),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let normalized_amount_out =
+			math::normalize(amount_out, pool.assets[idx_out].decimals).ok_or(Error::<T>::MathError)?;
+
+		ensure!(
+			normalized_amount_out < reserves[idx_out],
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let amount_in =
+			math::calculate_in_given_out(&reserves, idx_in, idx_out, normalized_amount_out, pool.amplification)
+				.and_then(|amount| math::denormalize(amount, pool.assets[idx_in].decimals, true))
+				.ok_or(Error::<T>::MathError)?;
+
+		let fee = math::calculate_fee(amount_in, pool.fee).ok_or(Error::<T>::MathError)?;
+		let amount_in = amount_in.checked_add(fee).ok_or(Error::<T>::MathError)?;
+
+		Ok((amount_in, fee))
+	}
+
+	fn do_create_pool(assets: Vec<AssetId>, amplification: u16, fee: (u32, u32)) -> DispatchResult {
+		ensure!(
+			Self::is_amplification_valid(amplification),
+			Error::<T>::InvalidAmplification
+		);
+		ensure!(Self::is_fee_valid(fee), Error::<T>::InvalidFee);
+
+		let mut pool_assets = Vec::with_capacity(assets.len());
+
+		for asset_id in assets.iter() {
+			let decimals = T::AssetDecimals::decimals(*asset_id)
+				.filter(|decimals| *decimals <= math::TARGET_PRECISION)
+				.ok_or(Error::<T>::InvalidAssetDecimals)?;
+			pool_assets.push(PoolAsset {
+				asset_id: *asset_id,
+				decimals,
+			});
+		}
+
+		let pool_assets: BoundedVec<PoolAsset, ConstU32<MAX_ASSETS_IN_POOL>> =
+			pool_assets.try_into().map_err(|_| Error::<T>::IncorrectAssets)?;
+
+		let pool_id =
+			T::AssetRegistry::get_or_create_asset(Self::share_token_name(&assets), T::MinPoolLiquidity::get())?;
+
+		ensure!(!<Pools<T>>::contains_key(pool_id), Error::<T>::PoolExists);
+
+		<Pools<T>>::insert(
+			pool_id,
+			PoolInfo {
+				assets: pool_assets,
+				amplification,
+				fee,
+			},
+		);
+
+		Self::deposit_event(Event::PoolCreated {
+			pool_id,
+			assets,
+			amplification,
+			fee,
+		});
+
+		Ok(())
+	}
+
+	fn ensure_shares_can_be_removed(pool_id: AssetId, who: &T::AccountId, shares: Balance) -> DispatchResult {
+		ensure!(!shares.is_zero(), Error::<T>::InvalidAssetAmount);
+
+		let account_shares = T::Currency::free_balance(pool_id, who);
+		ensure!(account_shares >= shares, Error::<T>::InsufficientShares);
+
+		let remaining_shares = account_shares.saturating_sub(shares);
+		ensure!(
+			remaining_shares.is_zero() || remaining_shares >= T::MinPoolLiquidity::get(),
+			Error::<T>::InsufficientShares
+		);
+
+		Ok(())
+	}
+
+	fn normalized_reserves(pool: &PoolInfo, pool_account: &T::AccountId) -> Result<Vec<Balance>, DispatchError> {
+		pool.assets
+			.iter()
+			.map(|asset| {
+				math::normalize(T::Currency::free_balance(asset.asset_id, pool_account), asset.decimals)
+					.ok_or_else(|| Error::<T>::MathError.into())
+			})
+			.collect()
+	}
+
+	fn is_amplification_valid(amplification: u16) -> bool {
+		amplification >= T::MinAmplification::get() && amplification <= T::MaxAmplification::get()
+	}
+
+	fn is_fee_valid(fee: (u32, u32)) -> bool {
+		let max_fee = T::MaxPoolFee::get();
+
+		fee.1 != 0 && (fee.0 as u64).saturating_mul(max_fee.1 as u64) <= (max_fee.0 as u64).saturating_mul(fee.1 as u64)
+	}
+}
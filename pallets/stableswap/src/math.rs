@@ -0,0 +1,319 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stableswap invariant math.
+//!
+//! Reserves passed to these functions are expected to be normalized to `TARGET_PRECISION` decimals.
+//! Amplification is used as in the Curve implementation, `Ann = amplification * n`.
+//!
+//! All functions return `None` when a calculation overflows or does not converge.
+
+use primitive_types::U256;
+use primitives::Balance;
+use sp_std::vec::Vec;
+
+/// Number of decimals all reserves are normalized to.
+pub const TARGET_PRECISION: u8 = 18;
+
+/// Maximum number of Newton's method iterations.
+const MAX_ITERATIONS: u8 = 255;
+
+/// Scale `amount` of an asset with `decimals` to `TARGET_PRECISION` decimals.
+pub fn normalize(amount: Balance, decimals: u8) -> Option<Balance> {
+	let exp = TARGET_PRECISION.checked_sub(decimals)?;
+	amount.checked_mul(10u128.checked_pow(exp as u32)?)
+}
+
+/// Scale normalized `amount` back to an asset with `decimals`.
+pub fn denormalize(amount: Balance, decimals: u8, round_up: bool) -> Option<Balance> {
+	let exp = TARGET_PRECISION.checked_sub(decimals)?;
+	let factor = 10u128.checked_pow(exp as u32)?;
+	let result = amount.checked_div(factor)?;
+
+	if round_up && result.checked_mul(factor)? < amount {
+		result.checked_add(1)
+	} else {
+		Some(result)
+	}
+}
+
+/// Calculate fee of `amount` for given fee rate. Rounded up.
+pub fn calculate_fee(amount: Balance, fee: (u32, u32)) -> Option<Balance> {
+	if fee.0 == 0 || fee.1 == 0 {
+		return Some(0);
+	}
+
+	let numerator = U256::from(amount).checked_mul(U256::from(fee.0))?;
+	let denominator = U256::from(fee.1);
+	let result = numerator
+		.checked_add(denominator)?
+		.checked_sub(U256::one())?
+		.checked_div(denominator)?;
+
+	Balance::try_from(result).ok()
+}
+
+/// Calculate invariant `D` of the pool.
+pub fn calculate_d(reserves: &[Balance], amplification: u16) -> Option<Balance> {
+	let n = U256::from(reserves.len());
+	let sum = reserves
+		.iter()
+		.try_fold(U256::zero(), |acc, reserve| acc.checked_add(U256::from(*reserve)))?;
+
+	if sum.is_zero() {
+		return Some(0);
+	}
+
+	if reserves.iter().any(|reserve| *reserve == 0) {
+		return None;
+	}
+
+	let ann = U256::from(amplification).checked_mul(n)?;
+
+	let mut d = sum;
+
+	for _ in 0..MAX_ITERATIONS {
+		let mut d_p = d;
+		for reserve in reserves {
+			d_p = d_p.checked_mul(d)?.checked_div(U256::from(*reserve).checked_mul(n)?)?;
+		}
+
+		let d_prev = d;
+
+		// d = (ann * sum + d_p * n) * d / ((ann - 1) * d + (n + 1) * d_p)
+		let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(n)?)?.checked_mul(d)?;
+		let denominator = ann
+			.checked_sub(U256::one())?
+			.checked_mul(d)?
+			.checked_add(n.checked_add(U256::one())?.checked_mul(d_p)?)?;
+
+		d = numerator.checked_div(denominator)?;
+
+		if has_converged(d_prev, d) {
+			return Balance::try_from(d).ok();
+		}
+	}
+
+	None
+}
+
+/// Calculate reserve of the asset at `idx` so the pool keeps invariant `d`.
+///
+/// Reserve at `idx` in `reserves` is ignored.
+pub fn calculate_y(reserves: &[Balance], idx: usize, d: Balance, amplification: u16) -> Option<Balance> {
+	if idx >= reserves.len() {
+		return None;
+	}
+
+	let n = U256::from(reserves.len());
+	let ann = U256::from(amplification).checked_mul(n)?;
+	let d = U256::from(d);
+
+	let mut c = d;
+	let mut sum = U256::zero();
+
+	for (i, reserve) in reserves.iter().enumerate() {
+		if i == idx {
+			continue;
+		}
+		let reserve = U256::from(*reserve);
+		sum = sum.checked_add(reserve)?;
+		c = c.checked_mul(d)?.checked_div(reserve.checked_mul(n)?)?;
+	}
+
+	c = c.checked_mul(d)?.checked_div(ann.checked_mul(n)?)?;
+	let b = sum.checked_add(d.checked_div(ann)?)?;
+
+	let mut y = d;
+
+	for _ in 0..MAX_ITERATIONS {
+		let y_prev = y;
+
+		// y = (y^2 + c) / (2y + b - d)
+		y = y
+			.checked_mul(y)?
+			.checked_add(c)?
+			.checked_div(y.checked_mul(U256::from(2))?.checked_add(b)?.checked_sub(d)?)?;
+
+		if has_converged(y_prev, y) {
+			return Balance::try_from(y).ok();
+		}
+	}
+
+	None
+}
+
+/// Calculate amount of asset at `idx_out` received for `amount_in` of asset at `idx_in`. Fee is not included.
+pub fn calculate_out_given_in(
+	reserves: &[Balance],
+	idx_in: usize,
+	idx_out: usize,
+	amount_in: Balance,
+	amplification: u16,
+) -> Option<Balance> {
+	if idx_in == idx_out || idx_in >= reserves.len() || idx_out >= reserves.len() {
+		return None;
+	}
+
+	let d = calculate_d(reserves, amplification)?;
+
+	let mut updated_reserves = reserves.to_vec();
+	updated_reserves[idx_in] = updated_reserves[idx_in].checked_add(amount_in)?;
+
+	let y = calculate_y(&updated_reserves, idx_out, d, amplification)?;
+
+	// Rounded down in favour of the pool.
+	Some(reserves[idx_out].checked_sub(y)?.saturating_sub(1))
+}
+
+/// Calculate amount of asset at `idx_in` required to receive `amount_out` of asset at `idx_out`. Fee is not included.
+pub fn calculate_in_given_out(
+	reserves: &[Balance],
+	idx_in: usize,
+	idx_out: usize,
+	amount_out: Balance,
+	amplification: u16,
+) -> Option<Balance> {
+	if idx_in == idx_out || idx_in >= reserves.len() || idx_out >= reserves.len() || amount_out >= reserves[idx_out] {
+		return None;
+	}
+
+	let d = calculate_d(reserves, amplification)?;
+
+	let mut updated_reserves = reserves.to_vec();
+	updated_reserves[idx_out] = updated_reserves[idx_out].checked_sub(amount_out)?;
+
+	let x = calculate_y(&updated_reserves, idx_in, d, amplification)?;
+
+	// Rounded up in favour of the pool.
+	x.checked_sub(reserves[idx_in])?.checked_add(1)
+}
+
+/// Calculate shares minted for liquidity which changes pool reserves from `initial_reserves` to `updated_reserves`.
+///
+/// Liquidity which moves the pool away from its current balance pays `fee` of the imbalance.
+pub fn calculate_shares(
+	initial_reserves: &[Balance],
+	updated_reserves: &[Balance],
+	amplification: u16,
+	share_issuance: Balance,
+	fee: (u32, u32),
+) -> Option<Balance> {
+	if initial_reserves.len() != updated_reserves.len() {
+		return None;
+	}
+
+	let d1 = calculate_d(updated_reserves, amplification)?;
+
+	if share_issuance == 0 {
+		return Some(d1);
+	}
+
+	let d0 = calculate_d(initial_reserves, amplification)?;
+
+	if d1 <= d0 {
+		return None;
+	}
+
+	let mut adjusted_reserves: Vec<Balance> = Vec::with_capacity(updated_reserves.len());
+
+	for (initial, updated) in initial_reserves.iter().zip(updated_reserves.iter()) {
+		let ideal_reserve = mul_div(*initial, d1, d0)?;
+		let difference = if ideal_reserve > *updated {
+			ideal_reserve - *updated
+		} else {
+			*updated - ideal_reserve
+		};
+		let fee_amount = imbalance_fee(difference, fee, initial_reserves.len())?;
+		adjusted_reserves.push(updated.checked_sub(fee_amount)?);
+	}
+
+	let d2 = calculate_d(&adjusted_reserves, amplification)?;
+
+	mul_div(share_issuance, d2.checked_sub(d0)?, d0)
+}
+
+/// Calculate amount of asset at `idx` received for `shares` and the fee paid for withdrawing a single asset.
+///
+/// Returns `(amount, fee)`.
+pub fn calculate_withdraw_one_asset(
+	reserves: &[Balance],
+	shares: Balance,
+	idx: usize,
+	share_issuance: Balance,
+	amplification: u16,
+	fee: (u32, u32),
+) -> Option<(Balance, Balance)> {
+	if idx >= reserves.len() || shares >= share_issuance {
+		return None;
+	}
+
+	let d0 = calculate_d(reserves, amplification)?;
+	let d1 = d0.checked_sub(mul_div(shares, d0, share_issuance)?)?;
+
+	let y = calculate_y(reserves, idx, d1, amplification)?;
+
+	let mut reduced_reserves: Vec<Balance> = Vec::with_capacity(reserves.len());
+
+	for (i, reserve) in reserves.iter().enumerate() {
+		let expected_reserve = mul_div(*reserve, d1, d0)?;
+		let difference = if i == idx {
+			expected_reserve.checked_sub(y)?
+		} else {
+			reserve.checked_sub(expected_reserve)?
+		};
+		let fee_amount = imbalance_fee(difference, fee, reserves.len())?;
+		reduced_reserves.push(reserve.checked_sub(fee_amount)?);
+	}
+
+	let y_with_fee = calculate_y(&reduced_reserves, idx, d1, amplification)?;
+
+	// Rounded down in favour of the pool.
+	let amount = reduced_reserves[idx].checked_sub(y_with_fee)?.saturating_sub(1);
+	let amount_without_fee = reserves[idx].checked_sub(y)?;
+
+	Some((amount, amount_without_fee.saturating_sub(amount)))
+}
+
+/// Fee charged on the imbalanced part of a liquidity change, `fee * n / (4 * (n - 1))`.
+fn imbalance_fee(amount: Balance, fee: (u32, u32), n: usize) -> Option<Balance> {
+	if fee.0 == 0 || fee.1 == 0 || n < 2 {
+		return Some(0);
+	}
+
+	let numerator = U256::from(amount)
+		.checked_mul(U256::from(fee.0))?
+		.checked_mul(U256::from(n))?;
+	let denominator = U256::from(fee.1)
+		.checked_mul(U256::from(4))?
+		.checked_mul(U256::from(n - 1))?;
+
+	Balance::try_from(numerator.checked_div(denominator)?).ok()
+}
+
+fn mul_div(a: Balance, b: Balance, c: Balance) -> Option<Balance> {
+	let result = U256::from(a).checked_mul(U256::from(b))?.checked_div(U256::from(c))?;
+	Balance::try_from(result).ok()
+}
+
+fn has_converged(prev: U256, current: U256) -> bool {
+	if current > prev {
+		current - prev <= U256::one()
+	} else {
+		prev - current <= U256::one()
+	}
+}
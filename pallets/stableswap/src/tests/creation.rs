@@ -0,0 +1,214 @@
+pub use super::mock::*;
+use crate::{Error, Event, PoolAsset, PoolInfo, Pools};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn create_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![USDT, AUSD, DAI],
+			100,
+			(1, 1000)
+		));
+
+		let pool_id = pool_id(&[AUSD, USDT, DAI]);
+
+		assert_eq!(
+			Stableswap::pools(pool_id).unwrap(),
+			PoolInfo {
+				assets: vec![
+					PoolAsset {
+						asset_id: AUSD,
+						decimals: 12
+					},
+					PoolAsset {
+						asset_id: USDT,
+						decimals: 6
+					},
+					PoolAsset {
+						asset_id: DAI,
+						decimals: 18
+					},
+				]
+				.try_into()
+				.unwrap(),
+				amplification: 100,
+				fee: (1, 1000),
+			}
+		);
+
+		expect_events(vec![Event::PoolCreated {
+			pool_id,
+			assets: vec![AUSD, USDT, DAI],
+			amplification: 100,
+			fee: (1, 1000),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_origin_is_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Stableswap::create_pool(Origin::signed(ALICE), vec![AUSD, KUSD], 100, (1, 1000)),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_assets_are_incorrect() {
+	ExtBuilder::default()
+		.with_asset_decimals(6000, 12)
+		.with_asset_decimals(7000, 12)
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stableswap::create_pool(Origin::root(), vec![AUSD], 100, (1, 1000)),
+				Error::<Test>::IncorrectAssets
+			);
+			assert_noop!(
+				Stableswap::create_pool(Origin::root(), vec![AUSD, KUSD, AUSD], 100, (1, 1000)),
+				Error::<Test>::IncorrectAssets
+			);
+			assert_noop!(
+				Stableswap::create_pool(Origin::root(), vec![AUSD, KUSD, USDT, DAI, 6000, 7000], 100, (1, 1000)),
+				Error::<Test>::IncorrectAssets
+			);
+
+			assert_ok!(Stableswap::create_pool(
+				Origin::root(),
+				vec![AUSD, KUSD, USDT, DAI, 6000],
+				100,
+				(1, 1000)
+			));
+		});
+}
+
+#[test]
+fn create_pool_should_not_work_when_asset_decimals_are_unknown() {
+	ExtBuilder::default()
+		.with_asset_decimals(6000, 19)
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stableswap::create_pool(Origin::root(), vec![AUSD, HDX], 100, (1, 1000)),
+				Error::<Test>::InvalidAssetDecimals
+			);
+			assert_noop!(
+				Stableswap::create_pool(Origin::root(), vec![AUSD, 6000], 100, (1, 1000)),
+				Error::<Test>::InvalidAssetDecimals
+			);
+		});
+}
+
+#[test]
+fn create_pool_should_not_work_when_amplification_or_fee_is_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, KUSD], 1, (1, 1000)),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, KUSD], 10_001, (1, 1000)),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, KUSD], 100, (2, 10)),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![AUSD, KUSD], 100, (0, 0)),
+			Error::<Test>::InvalidFee
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_not_work_when_pool_exists() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![AUSD, KUSD],
+			100,
+			(1, 1000)
+		));
+
+		assert_noop!(
+			Stableswap::create_pool(Origin::root(), vec![KUSD, AUSD], 200, (0, 1)),
+			Error::<Test>::PoolExists
+		);
+	});
+}
+
+#[test]
+fn update_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![AUSD, KUSD],
+			100,
+			(1, 1000)
+		));
+		let pool_id = pool_id(&[AUSD, KUSD]);
+
+		assert_ok!(Stableswap::update_pool(Origin::root(), pool_id, Some(500), None));
+		assert_ok!(Stableswap::update_pool(Origin::root(), pool_id, None, Some((3, 1000))));
+
+		let pool = Pools::<Test>::get(pool_id).unwrap();
+		assert_eq!(pool.amplification, 500);
+		assert_eq!(pool.fee, (3, 1000));
+
+		expect_events(vec![
+			Event::PoolUpdated {
+				pool_id,
+				amplification: 500,
+				fee: (1, 1000),
+			}
+			.into(),
+			Event::PoolUpdated {
+				pool_id,
+				amplification: 500,
+				fee: (3, 1000),
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn update_pool_should_not_work_when_parameters_are_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![AUSD, KUSD],
+			100,
+			(1, 1000)
+		));
+		let pool_id = pool_id(&[AUSD, KUSD]);
+
+		assert_noop!(
+			Stableswap::update_pool(Origin::signed(ALICE), pool_id, Some(500), None),
+			BadOrigin
+		);
+		assert_noop!(
+			Stableswap::update_pool(Origin::root(), pool_id, None, None),
+			Error::<Test>::NothingToUpdate
+		);
+		assert_noop!(
+			Stableswap::update_pool(Origin::root(), pool_id, Some(1), Some((1, 100))),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Stableswap::update_pool(Origin::root(), pool_id, None, Some((1, 2))),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			Stableswap::update_pool(Origin::root(), pool_id + 1, Some(500), None),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
@@ -0,0 +1,125 @@
+use super::mock::*;
+use crate::math;
+
+use proptest::prelude::*;
+
+use frame_support::assert_ok;
+use orml_traits::MultiCurrency;
+use primitive_types::U256;
+use primitives::{AssetId, Balance};
+
+fn asset_reserve() -> impl Strategy<Value = Balance> {
+	1_000 * ONE..1_000_000 * ONE
+}
+
+fn trade_amount() -> impl Strategy<Value = Balance> {
+	ONE..1_000 * ONE
+}
+
+fn amplification() -> impl Strategy<Value = u16> {
+	2u16..10_000u16
+}
+
+fn create_pool(amplification: u16, reserve_a: Balance, reserve_b: Balance, reserve_c: Balance) -> AssetId {
+	assert_ok!(Stableswap::create_pool(
+		Origin::root(),
+		vec![AUSD, KUSD, DAI],
+		amplification,
+		(1, 1000)
+	));
+
+	let pool_id = pool_id(&[AUSD, KUSD, DAI]);
+
+	assert_ok!(Stableswap::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		vec![(AUSD, reserve_a), (KUSD, reserve_b), (DAI, reserve_c * 1_000_000)],
+		0
+	));
+
+	pool_id
+}
+
+fn invariant(pool_id: AssetId) -> Balance {
+	let pool = Stableswap::pools(pool_id).unwrap();
+	let pool_account = Stableswap::pool_account(pool_id);
+
+	let reserves: Vec<Balance> = pool
+		.assets
+		.iter()
+		.map(|asset| math::normalize(Currency::free_balance(asset.asset_id, &pool_account), asset.decimals).unwrap())
+		.collect();
+
+	math::calculate_d(&reserves, pool.amplification).unwrap()
+}
+
+/// Invariant per share should not decrease.
+fn assert_invariant_per_share(old_state: (Balance, Balance), new_state: (Balance, Balance)) {
+	assert!(U256::from(new_state.0) * U256::from(old_state.1) >= U256::from(old_state.0) * U256::from(new_state.1));
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(100))]
+	#[test]
+	fn sell_should_not_decrease_invariant(
+		amplification in amplification(),
+		reserve_a in asset_reserve(),
+		reserve_b in asset_reserve(),
+		reserve_c in asset_reserve(),
+		amount in trade_amount(),
+	) {
+		new_test_ext().execute_with(|| {
+			let pool_id = create_pool(amplification, reserve_a, reserve_b, reserve_c);
+
+			let d = invariant(pool_id);
+
+			assert_ok!(Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, DAI, amount, 0));
+
+			assert!(invariant(pool_id) >= d);
+		});
+	}
+
+	#[test]
+	fn buy_should_not_decrease_invariant(
+		amplification in amplification(),
+		reserve_a in asset_reserve(),
+		reserve_b in asset_reserve(),
+		reserve_c in asset_reserve(),
+		amount in trade_amount(),
+	) {
+		new_test_ext().execute_with(|| {
+			let pool_id = create_pool(amplification, reserve_a, reserve_b, reserve_c);
+
+			let d = invariant(pool_id);
+
+			assert_ok!(Stableswap::buy(Origin::signed(BOB), pool_id, KUSD, DAI, amount, u128::MAX));
+
+			assert!(invariant(pool_id) >= d);
+		});
+	}
+
+	#[test]
+	fn liquidity_should_not_decrease_invariant_per_share(
+		amplification in amplification(),
+		reserve_a in asset_reserve(),
+		reserve_b in asset_reserve(),
+		reserve_c in asset_reserve(),
+		amount in trade_amount(),
+	) {
+		new_test_ext().execute_with(|| {
+			let pool_id = create_pool(amplification, reserve_a, reserve_b, reserve_c);
+
+			let initial_state = (invariant(pool_id), Currency::total_issuance(pool_id));
+
+			assert_ok!(Stableswap::add_liquidity(Origin::signed(BOB), pool_id, vec![(KUSD, amount)], 0));
+
+			let state = (invariant(pool_id), Currency::total_issuance(pool_id));
+			assert_invariant_per_share(initial_state, state);
+
+			let shares = Currency::free_balance(pool_id, &BOB);
+			assert_ok!(Stableswap::remove_liquidity_one_asset(Origin::signed(BOB), pool_id, AUSD, shares, 0));
+
+			assert_invariant_per_share(state, (invariant(pool_id), Currency::total_issuance(pool_id)));
+		});
+	}
+}
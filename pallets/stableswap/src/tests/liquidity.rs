@@ -0,0 +1,224 @@
+pub use super::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use primitives::AssetId;
+
+fn create_pool() -> AssetId {
+	assert_ok!(Stableswap::create_pool(
+		Origin::root(),
+		vec![AUSD, KUSD],
+		100,
+		(1, 1000)
+	));
+
+	pool_id(&[AUSD, KUSD])
+}
+
+fn create_pool_with_liquidity() -> AssetId {
+	let pool_id = create_pool();
+
+	assert_ok!(Stableswap::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		vec![(AUSD, 1_000 * ONE), (KUSD, 1_000 * ONE)],
+		0
+	));
+
+	pool_id
+}
+
+#[test]
+fn initial_liquidity_should_mint_shares_equal_to_invariant() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+		let pool_account = Stableswap::pool_account(pool_id);
+
+		assert_eq!(Currency::free_balance(pool_id, &ALICE), 2_000 * ONE_DAI);
+		assert_eq!(Currency::free_balance(AUSD, &pool_account), 1_000 * ONE);
+		assert_eq!(Currency::free_balance(KUSD, &pool_account), 1_000 * ONE);
+
+		expect_events(vec![Event::LiquidityAdded {
+			pool_id,
+			who: ALICE,
+			shares: 2_000 * ONE_DAI,
+			assets: vec![(AUSD, 1_000 * ONE), (KUSD, 1_000 * ONE)],
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn initial_liquidity_should_be_provided_in_all_assets() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool();
+
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(ALICE), pool_id, vec![(AUSD, 1_000 * ONE)], 0),
+			Error::<Test>::IncorrectInitialLiquidity
+		);
+	});
+}
+
+#[test]
+fn balanced_liquidity_should_not_pay_fee() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_ok!(Stableswap::add_liquidity(
+			Origin::signed(BOB),
+			pool_id,
+			vec![(AUSD, 100 * ONE), (KUSD, 100 * ONE)],
+			200 * ONE_DAI
+		));
+
+		assert_eq!(Currency::free_balance(pool_id, &BOB), 200 * ONE_DAI);
+	});
+}
+
+#[test]
+fn imbalanced_liquidity_should_pay_fee() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_ok!(Stableswap::add_liquidity(
+			Origin::signed(BOB),
+			pool_id,
+			vec![(AUSD, 200 * ONE)],
+			0
+		));
+
+		// 199_909_252_099_212_710_311 shares would be minted without the fee
+		assert_eq!(Currency::free_balance(pool_id, &BOB), 199_809_247_947_904_558_447);
+	});
+}
+
+#[test]
+fn add_liquidity_should_not_work_when_limits_are_not_met() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_noop!(
+			Stableswap::add_liquidity(
+				Origin::signed(BOB),
+				pool_id,
+				vec![(AUSD, 100 * ONE), (KUSD, 100 * ONE)],
+				200 * ONE_DAI + 1
+			),
+			Error::<Test>::SharesLimitNotReached
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(BOB), pool_id, vec![(AUSD, 2_000_000 * ONE)], 0),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(BOB), pool_id, vec![(AUSD, ONE), (AUSD, ONE)], 0),
+			Error::<Test>::InvalidAssetAmount
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(BOB), pool_id, vec![(USDT, ONE_USDT)], 0),
+			Error::<Test>::AssetNotInPool
+		);
+		assert_noop!(
+			Stableswap::add_liquidity(Origin::signed(BOB), pool_id + 1, vec![(AUSD, ONE)], 0),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_return_assets_proportionally() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_ok!(Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, KUSD, 100 * ONE, 0));
+
+		let pool_account = Stableswap::pool_account(pool_id);
+		let ausd_reserve = Currency::free_balance(AUSD, &pool_account);
+		let kusd_reserve = Currency::free_balance(KUSD, &pool_account);
+
+		assert_ok!(Stableswap::remove_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			500 * ONE_DAI
+		));
+
+		expect_events(vec![Event::LiquidityRemoved {
+			pool_id,
+			who: ALICE,
+			shares: 500 * ONE_DAI,
+			amounts: vec![(AUSD, ausd_reserve / 4), (KUSD, kusd_reserve / 4)],
+			fee: 0,
+		}
+		.into()]);
+
+		// all liquidity can be removed only proportionally
+		assert_ok!(Stableswap::remove_liquidity(
+			Origin::signed(ALICE),
+			pool_id,
+			1_500 * ONE_DAI
+		));
+
+		assert_eq!(Currency::total_issuance(pool_id), 0);
+		assert_eq!(Currency::free_balance(AUSD, &pool_account), 0);
+		assert_eq!(Currency::free_balance(KUSD, &pool_account), 0);
+	});
+}
+
+#[test]
+fn remove_liquidity_one_asset_should_pay_fee() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_ok!(Stableswap::remove_liquidity_one_asset(
+			Origin::signed(ALICE),
+			pool_id,
+			AUSD,
+			200 * ONE_DAI,
+			0
+		));
+
+		expect_events(vec![Event::LiquidityRemoved {
+			pool_id,
+			who: ALICE,
+			shares: 200 * ONE_DAI,
+			amounts: vec![(AUSD, 199_788_934_956_022)],
+			fee: 99_831_883_058,
+		}
+		.into()]);
+
+		assert_eq!(Currency::free_balance(pool_id, &ALICE), 1_800 * ONE_DAI);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_work_when_limits_are_not_met() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert_noop!(
+			Stableswap::remove_liquidity_one_asset(Origin::signed(ALICE), pool_id, AUSD, 200 * ONE_DAI, 200 * ONE),
+			Error::<Test>::RemoveLiquidityLimitNotReached
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity_one_asset(Origin::signed(ALICE), pool_id, AUSD, 2_000 * ONE_DAI, 0),
+			Error::<Test>::InsufficientLiquidityRemaining
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity_one_asset(Origin::signed(ALICE), pool_id, USDT, ONE_DAI, 0),
+			Error::<Test>::AssetNotInPool
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(BOB), pool_id, ONE_DAI),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(ALICE), pool_id, 2_000 * ONE_DAI - 1),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			Stableswap::remove_liquidity(Origin::signed(ALICE), pool_id, 0),
+			Error::<Test>::InvalidAssetAmount
+		);
+	});
+}
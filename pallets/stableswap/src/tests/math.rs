@@ -0,0 +1,59 @@
+use crate::math::*;
+use primitives::Balance;
+
+const ONE: Balance = 1_000_000_000_000_000_000;
+
+#[test]
+fn invariant_of_balanced_pool_should_be_sum_of_reserves() {
+	assert_eq!(calculate_d(&[1_000 * ONE, 1_000 * ONE], 100), Some(2_000 * ONE));
+	assert_eq!(calculate_d(&[0, 0, 0], 100), Some(0));
+	assert_eq!(calculate_d(&[1_000 * ONE, 0], 100), None);
+}
+
+#[test]
+fn reserve_should_keep_invariant() {
+	let reserves = [1_000 * ONE, 500 * ONE, 2_000 * ONE];
+	let d = calculate_d(&reserves, 50).unwrap();
+
+	let y = calculate_y(&reserves, 1, d, 50).unwrap();
+
+	assert!(y.abs_diff(reserves[1]) <= 2);
+}
+
+#[test]
+fn higher_amplification_should_reduce_slippage() {
+	let reserves = [1_000 * ONE, 1_000 * ONE];
+
+	let low = calculate_out_given_in(&reserves, 0, 1, 100 * ONE, 2).unwrap();
+	let high = calculate_out_given_in(&reserves, 0, 1, 100 * ONE, 1_000).unwrap();
+
+	assert!(low < high);
+	assert!(high < 100 * ONE);
+}
+
+#[test]
+fn in_given_out_should_round_in_favour_of_pool() {
+	let reserves = [1_000 * ONE, 800 * ONE, 1_200 * ONE];
+
+	let amount_in = calculate_in_given_out(&reserves, 0, 2, 10 * ONE, 100).unwrap();
+	let amount_out = calculate_out_given_in(&reserves, 0, 2, amount_in, 100).unwrap();
+
+	assert!(amount_out <= 10 * ONE);
+	assert!(10 * ONE - amount_out < 10);
+}
+
+#[test]
+fn decimals_should_be_normalized() {
+	assert_eq!(normalize(1_000_000, 6), Some(ONE));
+	assert_eq!(normalize(ONE, 18), Some(ONE));
+	assert_eq!(normalize(1, 19), None);
+	assert_eq!(denormalize(ONE + 1, 6, false), Some(1_000_000));
+	assert_eq!(denormalize(ONE + 1, 6, true), Some(1_000_001));
+}
+
+#[test]
+fn fee_should_be_rounded_up() {
+	assert_eq!(calculate_fee(1_001, (1, 1000)), Some(2));
+	assert_eq!(calculate_fee(1_000, (1, 1000)), Some(1));
+	assert_eq!(calculate_fee(1_000, (0, 1)), Some(0));
+}
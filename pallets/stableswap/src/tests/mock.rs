@@ -0,0 +1,262 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as stableswap;
+use crate::Config;
+use crate::*;
+use frame_support::parameter_types;
+use frame_system as system;
+use hydradx_traits::Registry;
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, One},
+};
+
+use frame_support::traits::{Everything, GenesisBuild, Nothing};
+use primitives::{AssetId, Balance};
+
+use frame_system::{EnsureRoot, EnsureSigned};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub type Amount = i128;
+pub type AccountId = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+
+pub const HDX: AssetId = 1000;
+pub const AUSD: AssetId = 2000;
+pub const KUSD: AssetId = 3000;
+pub const USDT: AssetId = 4000;
+pub const DAI: AssetId = 5000;
+
+/// One unit of assets with 12 decimals.
+pub const ONE: Balance = 1_000_000_000_000;
+/// One unit of USDT which has 6 decimals.
+pub const ONE_USDT: Balance = 1_000_000;
+/// One unit of DAI which has 18 decimals.
+pub const ONE_DAI: Balance = 1_000_000_000_000_000_000;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+	 Block = Block,
+	 NodeBlock = Block,
+	 UncheckedExtrinsic = UncheckedExtrinsic,
+	 {
+		 System: frame_system,
+		 Stableswap: stableswap,
+		 Currency: orml_tokens,
+		 AssetRegistry: pallet_asset_registry,
+	 }
+
+);
+
+thread_local! {
+		static ASSET_DECIMALS: RefCell<HashMap<AssetId, u8>> = RefCell::new(HashMap::default());
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 63;
+	pub const NativeAssetId: AssetId = HDX;
+	pub RegistryStringLimit: u32 = 100;
+}
+
+impl pallet_asset_registry::Config for Test {
+	type Event = Event;
+	type RegistryOrigin = EnsureSigned<AccountId>;
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type AssetNativeLocation = u8;
+	type StringLimit = RegistryStringLimit;
+	type NativeAssetId = NativeAssetId;
+	type WeightInfo = ();
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		One::one()
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type ReserveIdentifier = ();
+	type MaxReserves = ();
+}
+
+parameter_types! {
+	pub const StableswapPalletId: PalletId = PalletId(*b"stblswap");
+	pub const MinAmplification: u16 = 2;
+	pub const MaxAmplification: u16 = 10_000;
+	pub const MaxPoolFee: (u32, u32) = (1, 10);
+	pub const MinTradingLimit: Balance = 1_000;
+	pub const MinPoolLiquidity: Balance = 1_000;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = Currency;
+	type AssetRegistry = AssetRegistry;
+	type AssetDecimals = AssetDecimalsMock;
+	type AuthorityOrigin = EnsureRoot<AccountId>;
+	type PalletId = StableswapPalletId;
+	type MinAmplification = MinAmplification;
+	type MaxAmplification = MaxAmplification;
+	type MaxPoolFee = MaxPoolFee;
+	type MinTradingLimit = MinTradingLimit;
+	type MinPoolLiquidity = MinPoolLiquidity;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetDecimalsMock;
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+	asset_decimals: Vec<(AssetId, u8)>,
+}
+
+// Returns default values for genesis config
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![
+				(ALICE, AUSD, 1_000_000 * ONE),
+				(ALICE, KUSD, 1_000_000 * ONE),
+				(ALICE, USDT, 1_000_000 * ONE_USDT),
+				(ALICE, DAI, 1_000_000 * ONE_DAI),
+				(BOB, AUSD, 1_000_000 * ONE),
+				(BOB, KUSD, 1_000_000 * ONE),
+				(BOB, USDT, 1_000_000 * ONE_USDT),
+				(BOB, DAI, 1_000_000 * ONE_DAI),
+				(CHARLIE, HDX, 1_000_000 * ONE),
+			],
+			asset_decimals: vec![(AUSD, 12), (KUSD, 12), (USDT, 6), (DAI, 18)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	// builds genesis config
+
+	pub fn with_accounts(mut self, accounts: Vec<(AccountId, AssetId, Balance)>) -> Self {
+		self.endowed_accounts = accounts;
+		self
+	}
+
+	pub fn with_asset_decimals(mut self, asset_id: AssetId, decimals: u8) -> Self {
+		self.asset_decimals.push((asset_id, decimals));
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		orml_tokens::GenesisConfig::<Test> {
+			balances: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		ASSET_DECIMALS.with(|v| {
+			let mut decimals = v.borrow_mut();
+			decimals.clear();
+			decimals.extend(self.asset_decimals);
+		});
+
+		t.into()
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn expect_events(e: Vec<Event>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+/// Keeps asset decimals aside of the registry.
+pub struct AssetDecimalsMock;
+
+impl AssetDecimals<AssetId> for AssetDecimalsMock {
+	fn decimals(asset_id: AssetId) -> Option<u8> {
+		ASSET_DECIMALS.with(|v| v.borrow().get(&asset_id).copied())
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<AssetId> for AssetDecimalsMock {
+	fn register_asset(name: Vec<u8>, decimals: u8) -> Result<AssetId, DispatchError> {
+		let asset_id = AssetRegistry::create_asset(&name, One::one())?;
+		ASSET_DECIMALS.with(|v| v.borrow_mut().insert(asset_id, decimals));
+		Ok(asset_id)
+	}
+}
+
+/// Id of the pool of given assets.
+pub fn pool_id(assets: &[AssetId]) -> AssetId {
+	let mut assets = assets.to_vec();
+	assets.sort_unstable();
+	AssetRegistry::retrieve_asset(&Stableswap::share_token_name(&assets)).unwrap()
+}
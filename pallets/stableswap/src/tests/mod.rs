@@ -0,0 +1,7 @@
+mod creation;
+mod invariants;
+mod liquidity;
+mod math;
+pub(crate) mod mock;
+mod trade_execution;
+mod trades;
@@ -0,0 +1,105 @@
+pub use super::mock::*;
+use crate::Error;
+use frame_support::assert_ok;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use orml_traits::MultiCurrency;
+use primitives::AssetId;
+
+fn create_pool_with_liquidity() -> AssetId {
+	assert_ok!(Stableswap::create_pool(
+		Origin::root(),
+		vec![AUSD, KUSD, USDT],
+		100,
+		(1, 1000)
+	));
+
+	let pool_id = pool_id(&[AUSD, KUSD, USDT]);
+
+	assert_ok!(Stableswap::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		vec![(AUSD, 1_000 * ONE), (KUSD, 1_000 * ONE), (USDT, 1_000 * ONE_USDT)],
+		0
+	));
+
+	pool_id
+}
+
+#[test]
+fn execute_sell_should_receive_calculated_amount() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+		let pool_type = PoolType::Stableswap(pool_id);
+
+		let amount_out = Stableswap::calculate_sell(pool_type, USDT, KUSD, 10 * ONE_USDT).unwrap();
+
+		assert_ok!(Stableswap::execute_sell(
+			Origin::signed(BOB),
+			pool_type,
+			USDT,
+			KUSD,
+			10 * ONE_USDT,
+			amount_out
+		));
+
+		assert_eq!(Currency::free_balance(KUSD, &BOB), 1_000_000 * ONE + amount_out);
+	});
+}
+
+#[test]
+fn execute_buy_should_pay_calculated_amount() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+		let pool_type = PoolType::Stableswap(pool_id);
+
+		let amount_in = Stableswap::calculate_buy(pool_type, KUSD, USDT, 10 * ONE_USDT).unwrap();
+
+		assert_ok!(Stableswap::execute_buy(
+			Origin::signed(BOB),
+			pool_type,
+			KUSD,
+			USDT,
+			10 * ONE_USDT,
+			amount_in
+		));
+
+		assert_eq!(Currency::free_balance(KUSD, &BOB), 1_000_000 * ONE - amount_in);
+		assert_eq!(Currency::free_balance(USDT, &BOB), 1_000_010 * ONE_USDT);
+	});
+}
+
+#[test]
+fn other_pool_types_should_not_be_supported() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_liquidity();
+
+		assert!(matches!(
+			Stableswap::calculate_sell(PoolType::XYK, AUSD, KUSD, ONE),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			Stableswap::calculate_buy(PoolType::LBP, AUSD, KUSD, ONE),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			Stableswap::execute_sell(Origin::signed(BOB), PoolType::XYK, AUSD, KUSD, ONE, 0),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			Stableswap::execute_buy(Origin::signed(BOB), PoolType::XYK, AUSD, KUSD, ONE, u128::MAX),
+			Err(ExecutorError::NotSupported)
+		));
+	});
+}
+
+#[test]
+fn calculation_should_fail_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity();
+
+		assert!(matches!(
+			Stableswap::calculate_sell(PoolType::Stableswap(pool_id + 1), AUSD, KUSD, ONE),
+			Err(ExecutorError::Error(e)) if e == Error::<Test>::PoolNotFound.into()
+		));
+	});
+}
@@ -0,0 +1,200 @@
+pub use super::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use primitives::{AssetId, Balance};
+
+fn create_pool_with_liquidity(asset_a: (AssetId, Balance), asset_b: (AssetId, Balance)) -> AssetId {
+	assert_ok!(Stableswap::create_pool(
+		Origin::root(),
+		vec![asset_a.0, asset_b.0],
+		100,
+		(1, 1000)
+	));
+
+	let pool_id = pool_id(&[asset_a.0, asset_b.0]);
+
+	assert_ok!(Stableswap::add_liquidity(
+		Origin::signed(ALICE),
+		pool_id,
+		vec![asset_a, asset_b],
+		0
+	));
+
+	pool_id
+}
+
+#[test]
+fn sell_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity((AUSD, 1_000 * ONE), (KUSD, 1_000 * ONE));
+
+		assert_ok!(Stableswap::sell(
+			Origin::signed(BOB),
+			pool_id,
+			AUSD,
+			KUSD,
+			10 * ONE,
+			9 * ONE
+		));
+
+		let pool_account = Stableswap::pool_account(pool_id);
+		assert_eq!(Currency::free_balance(AUSD, &pool_account), 1_010 * ONE);
+		assert_eq!(
+			Currency::free_balance(KUSD, &pool_account),
+			1_000 * ONE - 9_989_010_892_068
+		);
+		assert_eq!(Currency::free_balance(KUSD, &BOB), 1_000_000 * ONE + 9_989_010_892_068);
+
+		expect_events(vec![Event::SellExecuted {
+			who: BOB,
+			pool_id,
+			asset_in: AUSD,
+			asset_out: KUSD,
+			amount_in: 10 * ONE,
+			amount_out: 9_989_010_892_068,
+			fee: 9_999_009_902,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity((AUSD, 1_000 * ONE), (KUSD, 1_000 * ONE));
+
+		assert_ok!(Stableswap::buy(
+			Origin::signed(BOB),
+			pool_id,
+			KUSD,
+			AUSD,
+			10 * ONE,
+			11 * ONE
+		));
+
+		let pool_account = Stableswap::pool_account(pool_id);
+		assert_eq!(
+			Currency::free_balance(AUSD, &pool_account),
+			1_000 * ONE + 10_010_991_284_442
+		);
+		assert_eq!(Currency::free_balance(KUSD, &pool_account), 990 * ONE);
+
+		expect_events(vec![Event::BuyExecuted {
+			who: BOB,
+			pool_id,
+			asset_in: AUSD,
+			asset_out: KUSD,
+			amount_in: 10_010_991_284_442,
+			amount_out: 10 * ONE,
+			fee: 10_000_990_295,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn trades_should_work_with_assets_of_different_decimals() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity((AUSD, 1_000 * ONE), (USDT, 1_000 * ONE_USDT));
+
+		assert_ok!(Stableswap::sell(
+			Origin::signed(BOB),
+			pool_id,
+			USDT,
+			AUSD,
+			10 * ONE_USDT,
+			0
+		));
+		assert_ok!(Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, USDT, 10 * ONE, 0));
+
+		expect_events(vec![
+			Event::SellExecuted {
+				who: BOB,
+				pool_id,
+				asset_in: USDT,
+				asset_out: AUSD,
+				amount_in: 10 * ONE_USDT,
+				amount_out: 9_989_010_892_068,
+				fee: 9_999_009_902,
+			}
+			.into(),
+			Event::SellExecuted {
+				who: BOB,
+				pool_id,
+				asset_in: AUSD,
+				asset_out: USDT,
+				amount_in: 10 * ONE,
+				amount_out: 9_990_988,
+				fee: 10_001,
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn trades_should_not_work_when_limits_are_not_met() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity((AUSD, 1_000 * ONE), (KUSD, 1_000 * ONE));
+
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, KUSD, 10 * ONE, 10 * ONE),
+			Error::<Test>::BuyLimitNotReached
+		);
+		assert_noop!(
+			Stableswap::buy(Origin::signed(BOB), pool_id, KUSD, AUSD, 10 * ONE, 10 * ONE),
+			Error::<Test>::SellLimitExceeded
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, KUSD, 100, 0),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, KUSD, 2_000_000 * ONE, 0),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			Stableswap::buy(Origin::signed(BOB), pool_id, KUSD, AUSD, 1_000 * ONE, u128::MAX),
+			Error::<Test>::InsufficientLiquidity
+		);
+	});
+}
+
+#[test]
+fn trades_should_not_work_with_incorrect_assets() {
+	new_test_ext().execute_with(|| {
+		let pool_id = create_pool_with_liquidity((AUSD, 1_000 * ONE), (KUSD, 1_000 * ONE));
+
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, AUSD, ONE, 0),
+			Error::<Test>::SameAssetTradeNotAllowed
+		);
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, USDT, ONE, 0),
+			Error::<Test>::AssetNotInPool
+		);
+		assert_noop!(
+			Stableswap::buy(Origin::signed(BOB), pool_id + 1, KUSD, AUSD, ONE, u128::MAX),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn trades_should_not_work_when_pool_has_no_liquidity() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Stableswap::create_pool(
+			Origin::root(),
+			vec![AUSD, KUSD],
+			100,
+			(1, 1000)
+		));
+		let pool_id = pool_id(&[AUSD, KUSD]);
+
+		assert_noop!(
+			Stableswap::sell(Origin::signed(BOB), pool_id, AUSD, KUSD, ONE, 0),
+			Error::<Test>::InsufficientLiquidity
+		);
+	});
+}
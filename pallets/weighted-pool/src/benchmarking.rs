@@ -0,0 +1,131 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+const SEED: u32 = 1;
+
+const ONE: Balance = 1_000_000_000_000;
+
+const WEIGHT_A: LBPWeight = 80_000_000;
+const FEE: (u32, u32) = (3, 1000);
+
+fn register_assets<T: Config>() -> Result<(AssetId, AssetId), DispatchError> {
+	let asset_a = T::AssetRegistry::create_asset(&b"asset_a".to_vec(), 1)?;
+	let asset_b = T::AssetRegistry::create_asset(&b"asset_b".to_vec(), 1)?;
+	Ok((asset_a, asset_b))
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32, assets: (AssetId, AssetId)) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::deposit(assets.0, &caller, 1_000_000 * ONE).unwrap();
+	T::Currency::deposit(assets.1, &caller, 1_000_000 * ONE).unwrap();
+	caller
+}
+
+/// Create pool with initial liquidity provided by `maker` and return its assets and share token.
+fn create_pool_with_liquidity<T: Config>() -> Result<(AssetId, AssetId, AssetId), DispatchError> {
+	let (asset_a, asset_b) = register_assets::<T>()?;
+	let maker = funded_account::<T>("maker", 0, (asset_a, asset_b));
+
+	Pallet::<T>::create_pool(
+		RawOrigin::Signed(maker).into(),
+		asset_a,
+		40_000 * ONE,
+		asset_b,
+		10_000 * ONE,
+		WEIGHT_A,
+		FEE,
+	)?;
+
+	let share_token = Pallet::<T>::pools(Pallet::<T>::pair_account_from_assets(asset_a, asset_b))
+		.ok_or(Error::<T>::PoolNotFound)?
+		.share_token;
+
+	Ok((asset_a, asset_b, share_token))
+}
+
+benchmarks! {
+	create_pool {
+		let (asset_a, asset_b) = register_assets::<T>()?;
+		let caller = funded_account::<T>("caller", 0, (asset_a, asset_b));
+	}: _(RawOrigin::Signed(caller), asset_a, 40_000 * ONE, asset_b, 10_000 * ONE, WEIGHT_A, FEE)
+	verify {
+		assert!(Pallet::<T>::exists(asset_a, asset_b));
+	}
+
+	add_liquidity {
+		let (asset_a, asset_b, share_token) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, (asset_a, asset_b));
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, 100 * ONE, Balance::MAX)
+	verify {
+		assert!(T::Currency::free_balance(share_token, &caller) > 0);
+	}
+
+	remove_liquidity {
+		let (asset_a, asset_b, share_token) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, (asset_a, asset_b));
+
+		Pallet::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), asset_a, asset_b, 100 * ONE, Balance::MAX)?;
+
+		let shares = T::Currency::free_balance(share_token, &caller);
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, shares, 0, 0)
+	verify {
+		assert_eq!(T::Currency::free_balance(share_token, &caller), 0);
+	}
+
+	sell {
+		let (asset_a, asset_b, _) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, (asset_a, asset_b));
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, 100 * ONE, 0)
+	verify {
+		assert!(T::Currency::free_balance(asset_b, &caller) > 1_000_000 * ONE);
+	}
+
+	buy {
+		let (asset_a, asset_b, _) = create_pool_with_liquidity::<T>()?;
+		let caller = funded_account::<T>("caller", 0, (asset_a, asset_b));
+	}: _(RawOrigin::Signed(caller.clone()), asset_b, asset_a, 100 * ONE, Balance::MAX)
+	verify {
+		assert_eq!(T::Currency::free_balance(asset_b, &caller), 1_000_100 * ONE);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::mock::{ExtBuilder, System, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		ExtBuilder::default().build().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_sell());
+			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+		});
+	}
+}
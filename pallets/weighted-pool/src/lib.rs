@@ -0,0 +1,746 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Weighted Pool Pallet
+//!
+//! ## Overview
+//!
+//! Weighted pool pallet provides permanent pools of two assets with fixed weights, such as 80/20 pools.
+//! Prices are calculated by the same weighted math as liquidity bootstrapping pools, but the weights of a pool
+//! never change and the pool is not bound to a sale period or an owner.
+//!
+//! Anyone can create a pool for an asset pair which has no weighted pool yet. Liquidity providers receive
+//! share tokens of the pool which are registered in the asset registry like XYK share tokens.
+//! Liquidity is always added and removed proportionally to the pool reserves, so the weights are not affected.
+//!
+//! Trading fee of a pool is kept in the pool and accrues to liquidity providers.
+//!
+//! Weighted pools are traded by the router through the `TradeExecution` implementation. The router has no pool type
+//! for weighted pools, so a weighted pool is identified by its share token carried by `PoolType::Stableswap`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional};
+use frame_system::ensure_signed;
+use hydra_dx_math::types::LBPWeight;
+use hydradx_traits::{AssetPairAccountIdFor, CanCreatePool, Registry};
+use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use primitives::{asset::AssetPair, Amount, AssetId, Balance};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Zero, DispatchError};
+use sp_std::vec::Vec;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+mod trade_execution;
+pub mod weights;
+
+use weights::WeightInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// Max weight corresponds to 100%
+pub const MAX_WEIGHT: LBPWeight = 100_000_000;
+
+/// Weighted pool of two assets.
+#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PoolInfo {
+	/// Assets of the pool in the order they were provided at creation.
+	pub assets: (AssetId, AssetId),
+	/// Weights of the assets, summing up to `MAX_WEIGHT`.
+	pub weights: (LBPWeight, LBPWeight),
+	/// Trading fee, kept in the pool.
+	pub fee: (u32, u32),
+	/// Share token of the pool.
+	pub share_token: AssetId,
+}
+
+impl PoolInfo {
+	/// Return weights ordered as (weight of `asset_in`, weight of the other asset).
+	pub fn sorted_weights(&self, asset_in: AssetId) -> (LBPWeight, LBPWeight) {
+		if asset_in == self.assets.0 {
+			self.weights
+		} else {
+			(self.weights.1, self.weights.0)
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::OriginFor;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Multi currency for transfer of currencies
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Amount = Amount, Balance = Balance>;
+
+		/// Registry used to register share tokens of pools
+		type AssetRegistry: Registry<AssetId, Vec<u8>, Balance, DispatchError>;
+
+		/// Mapping of asset pairs to unique pool identities
+		type AssetPairAccountId: AssetPairAccountIdFor<AssetId, Self::AccountId>;
+
+		/// Determines whether a pool can be created for an asset pair
+		type CanCreatePool: CanCreatePool<AssetId>;
+
+		/// Minimum weight of an asset in a pool
+		#[pallet::constant]
+		type MinWeight: Get<LBPWeight>;
+
+		/// Maximum trading fee of a pool
+		#[pallet::constant]
+		type MaxPoolFee: Get<(u32, u32)>;
+
+		/// Minimum trading limit, sole purpose of this is to keep the math working
+		#[pallet::constant]
+		type MinTradingLimit: Get<Balance>;
+
+		/// Minimum pool liquidity and minimum amount of shares held by an account
+		#[pallet::constant]
+		type MinPoolLiquidity: Get<Balance>;
+
+		/// Max fraction of pool to sell in single transaction
+		#[pallet::constant]
+		type MaxInRatio: Get<u128>;
+
+		/// Max fraction of pool to buy in single transaction
+		#[pallet::constant]
+		type MaxOutRatio: Get<u128>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// It is not allowed to create a pool for the asset pair.
+		CannotCreatePool,
+
+		/// Pool assets can not be the same.
+		CannotCreatePoolWithSameAssets,
+
+		/// Weight is out of range.
+		InvalidWeight,
+
+		/// Fee is zero-denominated or higher than allowed maximum.
+		InvalidFee,
+
+		/// Pool of the asset pair already exists.
+		PoolAlreadyExists,
+
+		/// Pool does not exist.
+		PoolNotFound,
+
+		/// Liquidity has not reached the required minimum.
+		InsufficientLiquidity,
+
+		/// Asset balance too low.
+		InsufficientAssetBalance,
+
+		/// Account does not have enough shares or would be left with less than minimum amount of shares.
+		InsufficientShares,
+
+		/// Amount is less than minimum trading limit.
+		InsufficientTradingAmount,
+
+		/// Liquidity being added or removed should not be zero.
+		ZeroLiquidity,
+
+		/// Required amount of the second asset exceeds the limit.
+		AssetAmountExceededLimit,
+
+		/// Minimum amount of removed assets has not been reached.
+		RemoveLiquidityLimitNotReached,
+
+		/// Trade amount is too high.
+		MaxInRatioExceeded,
+
+		/// Trade amount is too high.
+		MaxOutRatioExceeded,
+
+		/// Minimum limit has not been reached during trade.
+		BuyLimitNotReached,
+
+		/// Maximum limit has been exceeded during trade.
+		SellLimitExceeded,
+
+		/// An unexpected overflow occurred in the calculation.
+		MathError,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Pool was created.
+		PoolCreated {
+			who: T::AccountId,
+			pool: T::AccountId,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			weights: (LBPWeight, LBPWeight),
+			fee: (u32, u32),
+			share_token: AssetId,
+			initial_shares: Balance,
+		},
+
+		/// Liquidity was provided to the pool.
+		LiquidityAdded {
+			who: T::AccountId,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			amount_a: Balance,
+			amount_b: Balance,
+			shares: Balance,
+		},
+
+		/// Liquidity was removed from the pool.
+		LiquidityRemoved {
+			who: T::AccountId,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			amount_a: Balance,
+			amount_b: Balance,
+			shares: Balance,
+		},
+
+		/// Pool was destroyed after all its liquidity was removed.
+		PoolDestroyed {
+			who: T::AccountId,
+			pool: T::AccountId,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			share_token: AssetId,
+		},
+
+		/// Asset sale executed.
+		SellExecuted {
+			who: T::AccountId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			amount_out: Balance,
+			fee: Balance,
+		},
+
+		/// Asset purchase executed.
+		BuyExecuted {
+			who: T::AccountId,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			amount_out: Balance,
+			fee: Balance,
+		},
+	}
+
+	/// Existing pools identified by their account.
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub(crate) type Pools<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PoolInfo, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create new weighted pool for given asset pair with initial liquidity provided by the caller.
+		///
+		/// Weights of the pool are fixed. `weight_a` is the weight of `asset_a`, 1_000_000 corresponding to 1%
+		/// and `MAX_WEIGHT` to 100%, the rest belongs to `asset_b`.
+		///
+		/// Registers share token of the pool in asset registry. Amount of shares received by the caller
+		/// is equal to the amount of the asset with lower id.
+		///
+		/// Emits `PoolCreated` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+		#[transactional]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			amount_a: Balance,
+			asset_b: AssetId,
+			amount_b: Balance,
+			weight_a: LBPWeight,
+			fee: (u32, u32),
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::CanCreatePool::can_create(asset_a, asset_b),
+				Error::<T>::CannotCreatePool
+			);
+
+			ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
+
+			ensure!(
+				amount_a >= T::MinPoolLiquidity::get() && amount_b >= T::MinPoolLiquidity::get(),
+				Error::<T>::InsufficientLiquidity
+			);
+
+			ensure!(Self::is_weight_valid(weight_a), Error::<T>::InvalidWeight);
+			ensure!(Self::is_fee_valid(fee), Error::<T>::InvalidFee);
+
+			let pool_account = Self::pair_account_from_assets(asset_a, asset_b);
+
+			ensure!(!<Pools<T>>::contains_key(&pool_account), Error::<T>::PoolAlreadyExists);
+
+			ensure!(
+				T::Currency::free_balance(asset_a, &who) >= amount_a,
+				Error::<T>::InsufficientAssetBalance
+			);
+			ensure!(
+				T::Currency::free_balance(asset_b, &who) >= amount_b,
+				Error::<T>::InsufficientAssetBalance
+			);
+
+			let share_token = T::AssetRegistry::get_or_create_asset(
+				Self::share_token_name(asset_a, asset_b),
+				T::MinPoolLiquidity::get(),
+			)?;
+
+			let weights = (weight_a, MAX_WEIGHT - weight_a);
+
+			<Pools<T>>::insert(
+				&pool_account,
+				PoolInfo {
+					assets: (asset_a, asset_b),
+					weights,
+					fee,
+					share_token,
+				},
+			);
+
+			let initial_shares = if asset_a < asset_b { amount_a } else { amount_b };
+
+			T::Currency::transfer(asset_a, &who, &pool_account, amount_a)?;
+			T::Currency::transfer(asset_b, &who, &pool_account, amount_b)?;
+
+			T::Currency::deposit(share_token, &who, initial_shares)?;
+
+			Self::deposit_event(Event::PoolCreated {
+				who,
+				pool: pool_account,
+				asset_a,
+				asset_b,
+				weights,
+				fee,
+				share_token,
+				initial_shares,
+			});
+
+			Ok(())
+		}
+
+		/// Add liquidity to the pool of `asset_a` and `asset_b`.
+		///
+		/// Amount of `asset_b` is calculated to keep the ratio of pool reserves.
+		///
+		/// `amount_b_max_limit` - maximum amount of `asset_b` to be provided.
+		///
+		/// Emits `LiquidityAdded` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			amount_a: Balance,
+			amount_b_max_limit: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pool_account = Self::pair_account_from_assets(asset_a, asset_b);
+			let pool = Self::pools(&pool_account).ok_or(Error::<T>::PoolNotFound)?;
+
+			ensure!(
+				amount_a >= T::MinTradingLimit::get(),
+				Error::<T>::InsufficientTradingAmount
+			);
+
+			ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
+
+			ensure!(
+				T::Currency::free_balance(asset_a, &who) >= amount_a,
+				Error::<T>::InsufficientAssetBalance
+			);
+
+			let asset_a_reserve = T::Currency::free_balance(asset_a, &pool_account);
+			let asset_b_reserve = T::Currency::free_balance(asset_b, &pool_account);
+			let share_issuance = T::Currency::total_issuance(pool.share_token);
+
+			let amount_b = hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
+				.map_err(|_| Error::<T>::MathError)?;
+
+			ensure!(amount_b <= amount_b_max_limit, Error::<T>::AssetAmountExceededLimit);
+
+			ensure!(
+				T::Currency::free_balance(asset_b, &who) >= amount_b,
+				Error::<T>::InsufficientAssetBalance
+			);
+
+			let shares = hydra_dx_math::xyk::calculate_shares(asset_a_reserve, amount_a, share_issuance)
+				.ok_or(Error::<T>::MathError)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroLiquidity);
+
+			ensure!(
+				T::Currency::free_balance(pool.share_token, &who).saturating_add(shares) >= T::MinPoolLiquidity::get(),
+				Error::<T>::InsufficientShares
+			);
+
+			T::Currency::transfer(asset_a, &who, &pool_account, amount_a)?;
+			T::Currency::transfer(asset_b, &who, &pool_account, amount_b)?;
+
+			T::Currency::deposit(pool.share_token, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityAdded {
+				who,
+				asset_a,
+				asset_b,
+				amount_a,
+				amount_b,
+				shares,
+			});
+
+			Ok(())
+		}
+
+		/// Remove `shares` of liquidity from the pool of `asset_a` and `asset_b`.
+		///
+		/// Assets are removed proportionally to the pool reserves. The pool is destroyed when all its shares
+		/// are removed.
+		///
+		/// `min_amount_a` and `min_amount_b` - minimum amounts of the assets to be received.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		/// Emits `PoolDestroyed` event when the last shares are removed.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			shares: Balance,
+			min_amount_a: Balance,
+			min_amount_b: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::ZeroLiquidity);
+
+			let pool_account = Self::pair_account_from_assets(asset_a, asset_b);
+			let pool = Self::pools(&pool_account).ok_or(Error::<T>::PoolNotFound)?;
+
+			let account_shares = T::Currency::free_balance(pool.share_token, &who);
+			ensure!(account_shares >= shares, Error::<T>::InsufficientShares);
+
+			let remaining_shares = account_shares.saturating_sub(shares);
+			ensure!(
+				remaining_shares.is_zero() || remaining_shares >= T::MinPoolLiquidity::get(),
+				Error::<T>::InsufficientShares
+			);
+
+			let share_issuance = T::Currency::total_issuance(pool.share_token);
+
+			let asset_a_reserve = T::Currency::free_balance(asset_a, &pool_account);
+			let asset_b_reserve = T::Currency::free_balance(asset_b, &pool_account);
+
+			let (amount_a, amount_b) =
+				hydra_dx_math::xyk::calculate_liquidity_out(asset_a_reserve, asset_b_reserve, shares, share_issuance)
+					.map_err(|_| Error::<T>::MathError)?;
+
+			ensure!(
+				amount_a >= min_amount_a && amount_b >= min_amount_b,
+				Error::<T>::RemoveLiquidityLimitNotReached
+			);
+
+			T::Currency::transfer(asset_a, &pool_account, &who, amount_a)?;
+			T::Currency::transfer(asset_b, &pool_account, &who, amount_b)?;
+
+			T::Currency::withdraw(pool.share_token, &who, shares)?;
+
+			Self::deposit_event(Event::LiquidityRemoved {
+				who: who.clone(),
+				asset_a,
+				asset_b,
+				amount_a,
+				amount_b,
+				shares,
+			});
+
+			if share_issuance == shares {
+				<Pools<T>>::remove(&pool_account);
+
+				Self::deposit_event(Event::PoolDestroyed {
+					who,
+					pool: pool_account,
+					asset_a,
+					asset_b,
+					share_token: pool.share_token,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Trade `amount` of `asset_in` for `asset_out`.
+		///
+		/// Fee of the pool is deducted from the received amount and kept in the pool.
+		///
+		/// `min_bought` - minimum amount of `asset_out` to be received.
+		///
+		/// Emits `SellExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell())]
+		#[transactional]
+		pub fn sell(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+			min_bought: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				T::Currency::free_balance(asset_in, &who) >= amount,
+				Error::<T>::InsufficientAssetBalance
+			);
+
+			let (amount_out, fee) = Self::quote_sell(asset_in, asset_out, amount)?;
+
+			ensure!(amount_out >= min_bought, Error::<T>::BuyLimitNotReached);
+
+			let pool_account = Self::pair_account_from_assets(asset_in, asset_out);
+
+			T::Currency::transfer(asset_in, &who, &pool_account, amount)?;
+			T::Currency::transfer(asset_out, &pool_account, &who, amount_out)?;
+
+			Self::deposit_event(Event::SellExecuted {
+				who,
+				asset_in,
+				asset_out,
+				amount_in: amount,
+				amount_out,
+				fee,
+			});
+
+			Ok(())
+		}
+
+		/// Buy `amount` of `asset_out` for `asset_in`.
+		///
+		/// Fee of the pool is added to the paid amount and kept in the pool.
+		///
+		/// `max_sold` - maximum amount of `asset_in` to be paid.
+		///
+		/// Emits `BuyExecuted` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+			max_sold: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (amount_in, fee) = Self::quote_buy(asset_in, asset_out, amount)?;
+
+			ensure!(amount_in <= max_sold, Error::<T>::SellLimitExceeded);
+			ensure!(
+				T::Currency::free_balance(asset_in, &who) >= amount_in,
+				Error::<T>::InsufficientAssetBalance
+			);
+
+			let pool_account = Self::pair_account_from_assets(asset_in, asset_out);
+
+			T::Currency::transfer(asset_in, &who, &pool_account, amount_in)?;
+			T::Currency::transfer(asset_out, &pool_account, &who, amount)?;
+
+			Self::deposit_event(Event::BuyExecuted {
+				who,
+				asset_in,
+				asset_out,
+				amount_in,
+				amount_out: amount,
+				fee,
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Account holding reserves of the pool of given assets.
+	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> T::AccountId {
+		T::AssetPairAccountId::from_assets(asset_a, asset_b, "weighted")
+	}
+
+	/// Return true if a pool of given assets exists.
+	pub fn exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		<Pools<T>>::contains_key(Self::pair_account_from_assets(asset_a, asset_b))
+	}
+
+	/// Return accounts of all existing pools.
+	pub fn pool_accounts() -> Vec<T::AccountId> {
+		<Pools<T>>::iter_keys().collect()
+	}
+
+	/// Name of the share token of the pool of given assets.
+	pub fn share_token_name(asset_a: AssetId, asset_b: AssetId) -> Vec<u8> {
+		let (asset_a, asset_b) = AssetPair::new(asset_a, asset_b).ordered_pair();
+
+		let mut name = Vec::new();
+		name.extend_from_slice(&asset_a.to_le_bytes());
+		name.extend_from_slice(b"WPT");
+		name.extend_from_slice(&asset_b.to_le_bytes());
+
+		name
+	}
+
+	/// Calculate amount of `asset_out` received for `amount_in` of `asset_in`.
+	///
+	/// Returns `(amount_out, fee)`, fee is already deducted from the amount.
+	pub fn quote_sell(
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(
+			amount_in >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let pool_account = Self::pair_account_from_assets(asset_in, asset_out);
+		let pool = Self::pools(&pool_account).ok_or(Error::<T>::PoolNotFound)?;
+
+		let (weight_in, weight_out) = pool.sorted_weights(asset_in);
+		let asset_in_reserve = T::Currency::free_balance(asset_in, &pool_account);
+		let asset_out_reserve = T::Currency::free_balance(asset_out, &pool_account);
+
+		ensure!(
+			amount_in
+				<= asset_in_reserve
+					.checked_div(T::MaxInRatio::get())
+					.ok_or(Error::<T>::MathError)?,
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		let amount_out = hydra_dx_math::lbp::calculate_out_given_in(
+			asset_in_reserve,
+			asset_out_reserve,
+			weight_in,
+			weight_out,
+			amount_in,
+		)
+		.map_err(|_| Error::<T>::MathError)?;
+
+		ensure!(
+			amount_out
+				<= asset_out_reserve
+					.checked_div(T::MaxOutRatio::get())
+					.ok_or(Error::<T>::MathError)?,
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		let fee = Self::calculate_fee(&pool, amount_out)?;
+		let amount_out = amount_out.checked_sub(fee).ok_or(Error::<T>::MathError)?;
+
+		ensure!(!amount_out.is_zero(), Error::<T>::InsufficientTradingAmount);
+
+		Ok((amount_out, fee))
+	}
+
+	/// Calculate amount of `asset_in` required to receive `amount_out` of `asset_out`.
+	///
+	/// Returns `(amount_in, fee)`, fee is already included in the amount.
+	pub fn quote_buy(
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(
+			amount_out >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		let pool_account = Self::pair_account_from_assets(asset_in, asset_out);
+		let pool = Self::pools(&pool_account).ok_or(Error::<T>::PoolNotFound)?;
+
+		let (weight_in, weight_out) = pool.sorted_weights(asset_in);
+		let asset_in_reserve = T::Currency::free_balance(asset_in, &pool_account);
+		let asset_out_reserve = T::Currency::free_balance(asset_out, &pool_account);
+
+		ensure!(
+			amount_out
+				<= asset_out_reserve
+					.checked_div(T::MaxOutRatio::get())
+					.ok_or(Error::<T>::MathError)?,
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		let amount_in = hydra_dx_math::lbp::calculate_in_given_out(
+			asset_in_reserve,
+			asset_out_reserve,
+			weight_in,
+			weight_out,
+			amount_out,
+		)
+		.map_err(|_| Error::<T>::MathError)?;
+
+		let fee = Self::calculate_fee(&pool, amount_in)?;
+		let amount_in = amount_in.checked_add(fee).ok_or(Error::<T>::MathError)?;
+
+		ensure!(
+			amount_in
+				<= asset_in_reserve
+					.checked_div(T::MaxInRatio::get())
+					.ok_or(Error::<T>::MathError)?,
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		Ok((amount_in, fee))
+	}
+
+	fn calculate_fee(pool: &PoolInfo, amount: Balance) -> Result<Balance, DispatchError> {
+		hydra_dx_math::fee::calculate_pool_trade_fee(amount, pool.fee).ok_or_else(|| Error::<T>::MathError.into())
+	}
+
+	fn is_weight_valid(weight: LBPWeight) -> bool {
+		let min_weight = T::MinWeight::get();
+
+		weight >= min_weight && weight <= MAX_WEIGHT.saturating_sub(min_weight)
+	}
+
+	fn is_fee_valid(fee: (u32, u32)) -> bool {
+		let max_fee = T::MaxPoolFee::get();
+
+		fee.1 != 0 && (fee.0 as u64).saturating_mul(max_fee.1 as u64) <= (max_fee.0 as u64).saturating_mul(fee.1 as u64)
+	}
+}
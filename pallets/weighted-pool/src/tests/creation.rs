@@ -0,0 +1,285 @@
+pub use super::mock::*;
+use crate::{Error, Event, PoolInfo};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+
+#[test]
+fn create_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeightedPool::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			400 * ONE,
+			KSM,
+			100 * ONE,
+			WEIGHT_80,
+			DEFAULT_FEE
+		));
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+		let share_token = share_token(DOT, KSM);
+
+		assert_eq!(
+			WeightedPool::pools(pool_account),
+			Some(PoolInfo {
+				assets: (DOT, KSM),
+				weights: (80_000_000, 20_000_000),
+				fee: DEFAULT_FEE,
+				share_token,
+			})
+		);
+
+		assert_eq!(Currency::free_balance(DOT, &pool_account), 400 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 100 * ONE);
+		assert_eq!(Currency::free_balance(DOT, &ALICE), 999_600 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &ALICE), 999_900 * ONE);
+
+		// shares are equal to the amount of the asset with lower id
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 400 * ONE);
+		assert_eq!(Currency::total_issuance(share_token), 400 * ONE);
+
+		expect_events(vec![Event::PoolCreated {
+			who: ALICE,
+			pool: pool_account,
+			asset_a: DOT,
+			asset_b: KSM,
+			weights: (80_000_000, 20_000_000),
+			fee: DEFAULT_FEE,
+			share_token,
+			initial_shares: 400 * ONE,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_should_keep_weights_in_order_of_provided_assets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeightedPool::create_pool(
+			Origin::signed(ALICE),
+			KSM,
+			100 * ONE,
+			DOT,
+			400 * ONE,
+			20_000_000,
+			DEFAULT_FEE
+		));
+
+		let pool = WeightedPool::pools(WeightedPool::pair_account_from_assets(DOT, KSM)).unwrap();
+
+		assert_eq!(pool.assets, (KSM, DOT));
+		assert_eq!(pool.weights, (20_000_000, 80_000_000));
+		assert_eq!(pool.sorted_weights(DOT), (80_000_000, 20_000_000));
+		assert_eq!(pool.sorted_weights(KSM), (20_000_000, 80_000_000));
+
+		assert_eq!(Currency::free_balance(share_token(DOT, KSM), &ALICE), 400 * ONE);
+	});
+}
+
+#[test]
+fn create_pool_should_be_permissionless() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WeightedPool::create_pool(
+			Origin::signed(BOB),
+			DOT,
+			400 * ONE,
+			KSM,
+			100 * ONE,
+			WEIGHT_80,
+			DEFAULT_FEE
+		));
+
+		assert!(WeightedPool::exists(KSM, DOT));
+	});
+}
+
+#[test]
+fn create_pool_should_fail_when_pool_exists() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::create_pool(
+				Origin::signed(BOB),
+				KSM,
+				100 * ONE,
+				DOT,
+				100 * ONE,
+				WEIGHT_80,
+				DEFAULT_FEE
+			),
+			Error::<Test>::PoolAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_fail_when_pool_cannot_be_created() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightedPool::create_pool(
+				Origin::signed(ALICE),
+				SALE_ASSET,
+				100 * ONE,
+				DOT,
+				100 * ONE,
+				WEIGHT_80,
+				DEFAULT_FEE
+			),
+			Error::<Test>::CannotCreatePool
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_fail_when_assets_are_same() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightedPool::create_pool(
+				Origin::signed(ALICE),
+				DOT,
+				100 * ONE,
+				DOT,
+				100 * ONE,
+				WEIGHT_80,
+				DEFAULT_FEE
+			),
+			Error::<Test>::CannotCreatePoolWithSameAssets
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_fail_when_liquidity_is_below_minimum() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightedPool::create_pool(Origin::signed(ALICE), DOT, 999, KSM, 100 * ONE, WEIGHT_80, DEFAULT_FEE),
+			Error::<Test>::InsufficientLiquidity
+		);
+		assert_noop!(
+			WeightedPool::create_pool(Origin::signed(ALICE), DOT, 100 * ONE, KSM, 999, WEIGHT_80, DEFAULT_FEE),
+			Error::<Test>::InsufficientLiquidity
+		);
+	});
+}
+
+#[test]
+fn create_pool_should_fail_when_weight_is_out_of_range() {
+	new_test_ext().execute_with(|| {
+		for weight in [0, 1_999_999, 98_000_001, 100_000_000, u32::MAX] {
+			assert_noop!(
+				WeightedPool::create_pool(
+					Origin::signed(ALICE),
+					DOT,
+					100 * ONE,
+					KSM,
+					100 * ONE,
+					weight,
+					DEFAULT_FEE
+				),
+				Error::<Test>::InvalidWeight
+			);
+		}
+
+		assert_ok!(WeightedPool::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			100 * ONE,
+			KSM,
+			100 * ONE,
+			98_000_000,
+			DEFAULT_FEE
+		));
+	});
+}
+
+#[test]
+fn create_pool_should_fail_when_fee_is_invalid() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightedPool::create_pool(Origin::signed(ALICE), DOT, 100 * ONE, KSM, 100 * ONE, WEIGHT_80, (1, 0)),
+			Error::<Test>::InvalidFee
+		);
+		assert_noop!(
+			WeightedPool::create_pool(
+				Origin::signed(ALICE),
+				DOT,
+				100 * ONE,
+				KSM,
+				100 * ONE,
+				WEIGHT_80,
+				(11, 100)
+			),
+			Error::<Test>::InvalidFee
+		);
+
+		assert_ok!(WeightedPool::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			100 * ONE,
+			KSM,
+			100 * ONE,
+			WEIGHT_80,
+			(0, 1)
+		));
+	});
+}
+
+#[test]
+fn create_pool_should_fail_when_balance_is_insufficient() {
+	ExtBuilder::default()
+		.with_accounts(vec![(ALICE, DOT, 100 * ONE), (ALICE, KSM, 50 * ONE)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				WeightedPool::create_pool(
+					Origin::signed(ALICE),
+					DOT,
+					100 * ONE,
+					KSM,
+					100 * ONE,
+					WEIGHT_80,
+					DEFAULT_FEE
+				),
+				Error::<Test>::InsufficientAssetBalance
+			);
+		});
+}
+
+#[test]
+fn create_pool_should_reuse_share_token_of_destroyed_pool() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let share_token = share_token(DOT, KSM);
+
+		assert_ok!(WeightedPool::remove_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			KSM,
+			400 * ONE,
+			0,
+			0
+		));
+
+		assert!(!WeightedPool::exists(DOT, KSM));
+
+		assert_ok!(WeightedPool::create_pool(
+			Origin::signed(BOB),
+			KSM,
+			100 * ONE,
+			DOT,
+			100 * ONE,
+			50_000_000,
+			DEFAULT_FEE
+		));
+
+		assert_eq!(
+			WeightedPool::pools(WeightedPool::pair_account_from_assets(DOT, KSM))
+				.unwrap()
+				.share_token,
+			share_token
+		);
+		assert_eq!(Currency::free_balance(share_token, &BOB), 100 * ONE);
+	});
+}
@@ -0,0 +1,270 @@
+pub use super::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+
+#[test]
+fn add_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+		let share_token = share_token(DOT, KSM);
+
+		let amount_b = hydra_dx_math::xyk::calculate_liquidity_in(400 * ONE, 100 * ONE, 40 * ONE).unwrap();
+
+		assert_ok!(WeightedPool::add_liquidity(
+			Origin::signed(BOB),
+			DOT,
+			KSM,
+			40 * ONE,
+			11 * ONE
+		));
+
+		// liquidity is added proportionally to the reserves
+		assert_eq!(Currency::free_balance(DOT, &pool_account), 440 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 100 * ONE + amount_b);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 999_960 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &BOB), 1_000_000 * ONE - amount_b);
+
+		assert_eq!(Currency::free_balance(share_token, &BOB), 40 * ONE);
+		assert_eq!(Currency::total_issuance(share_token), 440 * ONE);
+
+		expect_events(vec![Event::LiquidityAdded {
+			who: BOB,
+			asset_a: DOT,
+			asset_b: KSM,
+			amount_a: 40 * ONE,
+			amount_b,
+			shares: 40 * ONE,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn add_liquidity_should_work_when_assets_are_in_reverse_order() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+
+		let amount_b = hydra_dx_math::xyk::calculate_liquidity_in(100 * ONE, 400 * ONE, 10 * ONE).unwrap();
+
+		assert_ok!(WeightedPool::add_liquidity(
+			Origin::signed(BOB),
+			KSM,
+			DOT,
+			10 * ONE,
+			41 * ONE
+		));
+
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 110 * ONE);
+		assert_eq!(Currency::free_balance(DOT, &pool_account), 400 * ONE + amount_b);
+		assert_eq!(Currency::free_balance(share_token(DOT, KSM), &BOB), 40 * ONE);
+	});
+}
+
+#[test]
+fn add_liquidity_should_fail_when_limit_is_exceeded() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::add_liquidity(Origin::signed(BOB), DOT, KSM, 40 * ONE, 9 * ONE),
+			Error::<Test>::AssetAmountExceededLimit
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_should_fail_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightedPool::add_liquidity(Origin::signed(BOB), DOT, KSM, 40 * ONE, 10 * ONE),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_should_fail_when_amount_is_below_limits() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::add_liquidity(Origin::signed(BOB), DOT, KSM, 999, ONE),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			WeightedPool::add_liquidity(Origin::signed(BOB), DOT, KSM, ONE, 0),
+			Error::<Test>::ZeroLiquidity
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_should_fail_when_balance_is_insufficient() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::add_liquidity(Origin::signed(CHARLIE), DOT, KSM, 40 * ONE, 10 * ONE),
+			Error::<Test>::InsufficientAssetBalance
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+		let share_token = share_token(DOT, KSM);
+
+		assert_ok!(WeightedPool::remove_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			KSM,
+			100 * ONE,
+			100 * ONE,
+			25 * ONE
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &pool_account), 300 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 75 * ONE);
+		assert_eq!(Currency::free_balance(DOT, &ALICE), 999_700 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &ALICE), 999_925 * ONE);
+
+		assert_eq!(Currency::free_balance(share_token, &ALICE), 300 * ONE);
+		assert_eq!(Currency::total_issuance(share_token), 300 * ONE);
+
+		assert!(WeightedPool::exists(DOT, KSM));
+
+		expect_events(vec![Event::LiquidityRemoved {
+			who: ALICE,
+			asset_a: DOT,
+			asset_b: KSM,
+			amount_a: 100 * ONE,
+			amount_b: 25 * ONE,
+			shares: 100 * ONE,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_destroy_pool_when_all_shares_are_removed() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+		let share_token = share_token(DOT, KSM);
+
+		assert_ok!(WeightedPool::remove_liquidity(
+			Origin::signed(ALICE),
+			KSM,
+			DOT,
+			400 * ONE,
+			0,
+			0
+		));
+
+		assert!(!WeightedPool::exists(DOT, KSM));
+		assert_eq!(WeightedPool::pools(pool_account), None);
+
+		assert_eq!(Currency::free_balance(DOT, &pool_account), 0);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 0);
+		assert_eq!(Currency::free_balance(DOT, &ALICE), 1_000_000 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &ALICE), 1_000_000 * ONE);
+		assert_eq!(Currency::total_issuance(share_token), 0);
+
+		expect_events(vec![Event::PoolDestroyed {
+			who: ALICE,
+			pool: pool_account,
+			asset_a: KSM,
+			asset_b: DOT,
+			share_token,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(ALICE), DOT, KSM, 100 * ONE, 100 * ONE + 1, 0),
+			Error::<Test>::RemoveLiquidityLimitNotReached
+		);
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(ALICE), DOT, KSM, 100 * ONE, 0, 25 * ONE + 1),
+			Error::<Test>::RemoveLiquidityLimitNotReached
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_shares_are_insufficient() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(ALICE), DOT, KSM, 400 * ONE + 1, 0, 0),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(BOB), DOT, KSM, ONE, 0, 0),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(ALICE), DOT, KSM, 0, 0, 0),
+			Error::<Test>::ZeroLiquidity
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_remaining_shares_are_below_minimum() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::remove_liquidity(Origin::signed(ALICE), DOT, KSM, 400 * ONE - 999, 0, 0),
+			Error::<Test>::InsufficientShares
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_include_collected_fees() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+
+		assert_ok!(WeightedPool::sell(Origin::signed(BOB), DOT, KSM, 10 * ONE, 0));
+		let received = Currency::free_balance(KSM, &BOB) - 1_000_000 * ONE;
+		assert_ok!(WeightedPool::sell(Origin::signed(BOB), KSM, DOT, received, 0));
+
+		// the round trip left fees of both trades in the pool
+		let dot_reserve = Currency::free_balance(DOT, &pool_account);
+		assert!(dot_reserve > 400 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 100 * ONE);
+
+		assert_ok!(WeightedPool::remove_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			KSM,
+			400 * ONE,
+			0,
+			0
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &ALICE), 999_600 * ONE + dot_reserve);
+		assert_eq!(Currency::free_balance(KSM, &ALICE), 1_000_000 * ONE);
+	});
+}
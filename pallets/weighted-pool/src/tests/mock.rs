@@ -0,0 +1,256 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate as weighted_pool;
+use crate::Config;
+use crate::*;
+use frame_support::parameter_types;
+use frame_system as system;
+use hydradx_traits::{AssetPairAccountIdFor, CanCreatePool, Registry};
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, One},
+};
+
+use frame_support::traits::{Everything, Nothing};
+use primitives::{
+	constants::chain::{MAX_IN_RATIO, MAX_OUT_RATIO},
+	AssetId, Balance,
+};
+
+use frame_system::EnsureSigned;
+
+pub type Amount = i128;
+pub type AccountId = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+
+pub const HDX: AssetId = 1000;
+pub const DOT: AssetId = 2000;
+pub const KSM: AssetId = 3000;
+/// Asset which is sold in a running liquidity bootstrapping sale.
+pub const SALE_ASSET: AssetId = 4000;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+/// 80% weight.
+pub const WEIGHT_80: LBPWeight = 80_000_000;
+pub const DEFAULT_FEE: (u32, u32) = (3, 1000);
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+	 Block = Block,
+	 NodeBlock = Block,
+	 UncheckedExtrinsic = UncheckedExtrinsic,
+	 {
+		 System: frame_system,
+		 WeightedPool: weighted_pool,
+		 Currency: orml_tokens,
+		 AssetRegistry: pallet_asset_registry,
+	 }
+
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 63;
+	pub const NativeAssetId: AssetId = HDX;
+	pub RegistryStringLimit: u32 = 100;
+}
+
+impl pallet_asset_registry::Config for Test {
+	type Event = Event;
+	type RegistryOrigin = EnsureSigned<AccountId>;
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type AssetNativeLocation = u8;
+	type StringLimit = RegistryStringLimit;
+	type NativeAssetId = NativeAssetId;
+	type WeightInfo = ();
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		One::one()
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type ReserveIdentifier = ();
+	type MaxReserves = ();
+}
+
+pub struct AssetPairAccountIdTest();
+
+impl AssetPairAccountIdFor<AssetId, u64> for AssetPairAccountIdTest {
+	fn from_assets(asset_a: AssetId, asset_b: AssetId, _: &str) -> u64 {
+		let mut a = asset_a as u128;
+		let mut b = asset_b as u128;
+		if a > b {
+			std::mem::swap(&mut a, &mut b)
+		}
+		(a * 1000 + b) as u64
+	}
+}
+
+/// Disallows pools of `SALE_ASSET` as if its liquidity bootstrapping sale was running.
+pub struct DisallowWhenSaleRunning;
+
+impl CanCreatePool<AssetId> for DisallowWhenSaleRunning {
+	fn can_create(asset_a: AssetId, asset_b: AssetId) -> bool {
+		asset_a != SALE_ASSET && asset_b != SALE_ASSET
+	}
+}
+
+parameter_types! {
+	pub const MinWeight: LBPWeight = 2_000_000;
+	pub const MaxPoolFee: (u32, u32) = (1, 10);
+	pub const MinTradingLimit: Balance = 1_000;
+	pub const MinPoolLiquidity: Balance = 1_000;
+	pub const MaxInRatio: u128 = MAX_IN_RATIO;
+	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = Currency;
+	type AssetRegistry = AssetRegistry;
+	type AssetPairAccountId = AssetPairAccountIdTest;
+	type CanCreatePool = DisallowWhenSaleRunning;
+	type MinWeight = MinWeight;
+	type MaxPoolFee = MaxPoolFee;
+	type MinTradingLimit = MinTradingLimit;
+	type MinPoolLiquidity = MinPoolLiquidity;
+	type MaxInRatio = MaxInRatio;
+	type MaxOutRatio = MaxOutRatio;
+	type WeightInfo = ();
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+}
+
+// Returns default values for genesis config
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![
+				(ALICE, HDX, 1_000_000 * ONE),
+				(ALICE, DOT, 1_000_000 * ONE),
+				(ALICE, KSM, 1_000_000 * ONE),
+				(ALICE, SALE_ASSET, 1_000_000 * ONE),
+				(BOB, DOT, 1_000_000 * ONE),
+				(BOB, KSM, 1_000_000 * ONE),
+				(CHARLIE, HDX, 1_000_000 * ONE),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	// builds genesis config
+
+	pub fn with_accounts(mut self, accounts: Vec<(AccountId, AssetId, Balance)>) -> Self {
+		self.endowed_accounts = accounts;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		orml_tokens::GenesisConfig::<Test> {
+			balances: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn expect_events(e: Vec<Event>) {
+	e.into_iter().for_each(frame_system::Pallet::<Test>::assert_has_event);
+}
+
+/// Share token of the pool of given assets.
+pub fn share_token(asset_a: AssetId, asset_b: AssetId) -> AssetId {
+	AssetRegistry::retrieve_asset(&WeightedPool::share_token_name(asset_a, asset_b)).unwrap()
+}
+
+/// Create 80/20 pool of 400 DOT and 100 KSM by ALICE, DOT is worth the same as KSM.
+pub fn create_dot_ksm_pool() {
+	frame_support::assert_ok!(WeightedPool::create_pool(
+		Origin::signed(ALICE),
+		DOT,
+		400 * ONE,
+		KSM,
+		100 * ONE,
+		WEIGHT_80,
+		DEFAULT_FEE
+	));
+}
@@ -0,0 +1,5 @@
+mod creation;
+mod liquidity;
+pub(crate) mod mock;
+mod trade_execution;
+mod trades;
@@ -0,0 +1,145 @@
+pub use super::mock::*;
+use frame_support::assert_ok;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use orml_traits::MultiCurrency;
+
+#[test]
+fn execute_sell_should_receive_calculated_amount() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+		let pool_type = PoolType::Stableswap(share_token(DOT, KSM));
+
+		let amount_out = WeightedPool::calculate_sell(pool_type, DOT, KSM, 10 * ONE).unwrap();
+
+		assert_eq!(amount_out, WeightedPool::quote_sell(DOT, KSM, 10 * ONE).unwrap().0);
+
+		assert_ok!(WeightedPool::execute_sell(
+			Origin::signed(BOB),
+			pool_type,
+			DOT,
+			KSM,
+			10 * ONE,
+			amount_out
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 999_990 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &BOB), 1_000_000 * ONE + amount_out);
+	});
+}
+
+#[test]
+fn execute_buy_should_pay_calculated_amount() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+		let pool_type = PoolType::Stableswap(share_token(DOT, KSM));
+
+		let amount_in = WeightedPool::calculate_buy(pool_type, KSM, DOT, 10 * ONE).unwrap();
+
+		assert_eq!(amount_in, WeightedPool::quote_buy(KSM, DOT, 10 * ONE).unwrap().0);
+
+		assert_ok!(WeightedPool::execute_buy(
+			Origin::signed(BOB),
+			pool_type,
+			KSM,
+			DOT,
+			10 * ONE,
+			amount_in
+		));
+
+		assert_eq!(Currency::free_balance(KSM, &BOB), 1_000_000 * ONE - amount_in);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_010 * ONE);
+	});
+}
+
+#[test]
+fn other_pool_types_should_not_be_supported() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		for pool_type in [PoolType::LBP, PoolType::XYK, PoolType::Omnipool] {
+			assert!(matches!(
+				WeightedPool::calculate_sell(pool_type, DOT, KSM, 10 * ONE),
+				Err(ExecutorError::NotSupported)
+			));
+			assert!(matches!(
+				WeightedPool::calculate_buy(pool_type, KSM, DOT, 10 * ONE),
+				Err(ExecutorError::NotSupported)
+			));
+			assert!(matches!(
+				WeightedPool::execute_sell(Origin::signed(BOB), pool_type, DOT, KSM, 10 * ONE, 0),
+				Err(ExecutorError::NotSupported)
+			));
+			assert!(matches!(
+				WeightedPool::execute_buy(Origin::signed(BOB), pool_type, KSM, DOT, 10 * ONE, u128::MAX),
+				Err(ExecutorError::NotSupported)
+			));
+		}
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_000 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &BOB), 1_000_000 * ONE);
+	});
+}
+
+#[test]
+fn pool_ids_other_than_share_token_should_not_be_supported() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		// stableswap pool id of the same assets
+		let pool_type = PoolType::Stableswap(share_token(DOT, KSM) + 1);
+
+		assert!(matches!(
+			WeightedPool::calculate_sell(pool_type, DOT, KSM, ONE),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			WeightedPool::calculate_buy(pool_type, DOT, KSM, ONE),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			WeightedPool::execute_sell(Origin::signed(BOB), pool_type, DOT, KSM, ONE, 0),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			WeightedPool::execute_buy(Origin::signed(BOB), pool_type, DOT, KSM, ONE, u128::MAX),
+			Err(ExecutorError::NotSupported)
+		));
+	});
+}
+
+#[test]
+fn pairs_without_pool_should_not_be_supported() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+		let pool_type = PoolType::Stableswap(share_token(DOT, KSM));
+
+		assert!(matches!(
+			WeightedPool::calculate_sell(pool_type, DOT, HDX, ONE),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			WeightedPool::calculate_buy(pool_type, DOT, HDX, ONE),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			WeightedPool::execute_sell(Origin::signed(BOB), pool_type, DOT, HDX, ONE, 0),
+			Err(ExecutorError::NotSupported)
+		));
+		assert!(matches!(
+			WeightedPool::execute_buy(Origin::signed(BOB), pool_type, DOT, HDX, ONE, u128::MAX),
+			Err(ExecutorError::NotSupported)
+		));
+	});
+}
+
+#[test]
+fn trade_errors_should_be_returned_for_existing_pool() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert!(matches!(
+			WeightedPool::calculate_sell(PoolType::Stableswap(share_token(DOT, KSM)), DOT, KSM, 999),
+			Err(ExecutorError::Error(_))
+		));
+	});
+}
@@ -0,0 +1,178 @@
+pub use super::mock::*;
+use crate::{Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use orml_traits::MultiCurrency;
+use primitives::Balance;
+
+fn fee(amount: Balance) -> Balance {
+	hydra_dx_math::fee::calculate_pool_trade_fee(amount, DEFAULT_FEE).unwrap()
+}
+
+#[test]
+fn sell_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+
+		let calculated_out =
+			hydra_dx_math::lbp::calculate_out_given_in(400 * ONE, 100 * ONE, 80_000_000, 20_000_000, 10 * ONE).unwrap();
+		let fee = fee(calculated_out);
+		let amount_out = calculated_out - fee;
+
+		assert_ok!(WeightedPool::sell(Origin::signed(BOB), DOT, KSM, 10 * ONE, amount_out));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 999_990 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &BOB), 1_000_000 * ONE + amount_out);
+
+		// fee is kept in the pool
+		assert_eq!(Currency::free_balance(DOT, &pool_account), 410 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 100 * ONE - amount_out);
+
+		expect_events(vec![Event::SellExecuted {
+			who: BOB,
+			asset_in: DOT,
+			asset_out: KSM,
+			amount_in: 10 * ONE,
+			amount_out,
+			fee,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn sell_should_use_weights_of_the_pool() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_ok!(WeightedPool::sell(Origin::signed(BOB), DOT, KSM, ONE, 0));
+
+		// 400 DOT at 80% weight are worth the same as 100 KSM at 20% weight
+		let received = Currency::free_balance(KSM, &BOB) - 1_000_000 * ONE;
+		assert!(received < ONE - fee(ONE) && received > ONE * 98 / 100);
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let pool_account = WeightedPool::pair_account_from_assets(DOT, KSM);
+
+		let calculated_in =
+			hydra_dx_math::lbp::calculate_in_given_out(100 * ONE, 400 * ONE, 20_000_000, 80_000_000, 10 * ONE).unwrap();
+		let fee = fee(calculated_in);
+		let amount_in = calculated_in + fee;
+
+		assert_ok!(WeightedPool::buy(Origin::signed(BOB), DOT, KSM, 10 * ONE, amount_in));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1_000_010 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &BOB), 1_000_000 * ONE - amount_in);
+
+		// fee is kept in the pool
+		assert_eq!(Currency::free_balance(DOT, &pool_account), 390 * ONE);
+		assert_eq!(Currency::free_balance(KSM, &pool_account), 100 * ONE + amount_in);
+
+		expect_events(vec![Event::BuyExecuted {
+			who: BOB,
+			asset_in: KSM,
+			asset_out: DOT,
+			amount_in,
+			amount_out: 10 * ONE,
+			fee,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn sell_should_fail_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let (amount_out, _) = WeightedPool::quote_sell(DOT, KSM, 10 * ONE).unwrap();
+
+		assert_noop!(
+			WeightedPool::sell(Origin::signed(BOB), DOT, KSM, 10 * ONE, amount_out + 1),
+			Error::<Test>::BuyLimitNotReached
+		);
+	});
+}
+
+#[test]
+fn buy_should_fail_when_limit_is_exceeded() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		let (amount_in, _) = WeightedPool::quote_buy(KSM, DOT, 10 * ONE).unwrap();
+
+		assert_noop!(
+			WeightedPool::buy(Origin::signed(BOB), DOT, KSM, 10 * ONE, amount_in - 1),
+			Error::<Test>::SellLimitExceeded
+		);
+	});
+}
+
+#[test]
+fn trade_should_fail_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightedPool::sell(Origin::signed(BOB), DOT, KSM, 10 * ONE, 0),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			WeightedPool::buy(Origin::signed(BOB), DOT, KSM, 10 * ONE, Balance::MAX),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn trade_should_fail_when_amount_is_below_trading_limit() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::sell(Origin::signed(BOB), DOT, KSM, 999, 0),
+			Error::<Test>::InsufficientTradingAmount
+		);
+		assert_noop!(
+			WeightedPool::buy(Origin::signed(BOB), DOT, KSM, 999, Balance::MAX),
+			Error::<Test>::InsufficientTradingAmount
+		);
+	});
+}
+
+#[test]
+fn trade_should_fail_when_max_ratio_is_exceeded() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::sell(Origin::signed(BOB), DOT, KSM, 400 * ONE / 3 + 1, 0),
+			Error::<Test>::MaxInRatioExceeded
+		);
+		assert_noop!(
+			WeightedPool::buy(Origin::signed(BOB), KSM, DOT, 100 * ONE / 3 + 1, Balance::MAX),
+			Error::<Test>::MaxOutRatioExceeded
+		);
+	});
+}
+
+#[test]
+fn trade_should_fail_when_balance_is_insufficient() {
+	new_test_ext().execute_with(|| {
+		create_dot_ksm_pool();
+
+		assert_noop!(
+			WeightedPool::sell(Origin::signed(CHARLIE), DOT, KSM, 10 * ONE, 0),
+			Error::<Test>::InsufficientAssetBalance
+		);
+		assert_noop!(
+			WeightedPool::buy(Origin::signed(CHARLIE), DOT, KSM, 10 * ONE, Balance::MAX),
+			Error::<Test>::InsufficientAssetBalance
+		);
+	});
+}
@@ -0,0 +1,85 @@
+use crate::{Config, Pallet};
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use primitives::{AssetId, Balance};
+use sp_runtime::DispatchError;
+
+/// Weighted pools are traded as `PoolType::Stableswap` identified by the share token of the pool.
+///
+/// `PoolType` of the router has no variant for weighted pools, so the pool id carried by `Stableswap` is used.
+/// Share tokens are unique assets, so the id never matches a stableswap pool. Trades of other pool types,
+/// and of pool ids which are not the share token of the weighted pool of the traded assets, are not supported,
+/// so they can be handled by another executor.
+impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pallet<T> {
+	type Error = DispatchError;
+
+	fn calculate_sell(
+		pool_type: PoolType<AssetId>,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+	) -> Result<Balance, ExecutorError<Self::Error>> {
+		if !Self::is_routed_pool(pool_type, asset_in, asset_out) {
+			return Err(ExecutorError::NotSupported);
+		}
+
+		Self::quote_sell(asset_in, asset_out, amount_in)
+			.map(|(amount_out, _)| amount_out)
+			.map_err(ExecutorError::Error)
+	}
+
+	fn calculate_buy(
+		pool_type: PoolType<AssetId>,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+	) -> Result<Balance, ExecutorError<Self::Error>> {
+		if !Self::is_routed_pool(pool_type, asset_in, asset_out) {
+			return Err(ExecutorError::NotSupported);
+		}
+
+		Self::quote_buy(asset_in, asset_out, amount_out)
+			.map(|(amount_in, _)| amount_in)
+			.map_err(ExecutorError::Error)
+	}
+
+	fn execute_sell(
+		who: T::Origin,
+		pool_type: PoolType<AssetId>,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		min_limit: Balance,
+	) -> Result<(), ExecutorError<Self::Error>> {
+		if !Self::is_routed_pool(pool_type, asset_in, asset_out) {
+			return Err(ExecutorError::NotSupported);
+		}
+
+		Self::sell(who, asset_in, asset_out, amount_in, min_limit).map_err(ExecutorError::Error)
+	}
+
+	fn execute_buy(
+		who: T::Origin,
+		pool_type: PoolType<AssetId>,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		max_limit: Balance,
+	) -> Result<(), ExecutorError<Self::Error>> {
+		if !Self::is_routed_pool(pool_type, asset_in, asset_out) {
+			return Err(ExecutorError::NotSupported);
+		}
+
+		Self::buy(who, asset_out, asset_in, amount_out, max_limit).map_err(ExecutorError::Error)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Return true if `pool_type` identifies the weighted pool of given assets.
+	fn is_routed_pool(pool_type: PoolType<AssetId>, asset_a: AssetId, asset_b: AssetId) -> bool {
+		match pool_type {
+			PoolType::Stableswap(pool_id) => Self::pools(Self::pair_account_from_assets(asset_a, asset_b))
+				.map_or(false, |pool| pool.share_token == pool_id),
+			_ => false,
+		}
+	}
+}
@@ -301,16 +301,33 @@ parameter_types! {
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
 	pub const DiscountedFee: (u32, u32) = DISCOUNTED_FEE;
+	pub const MinPoolFee: (u32, u32) = (1, 10_000);
+	pub const MaxPoolFee: (u32, u32) = (1, 10);
+	pub const ProtocolFee: (u32, u32) = (0, 0);
+	pub const ProtocolFeeReceiver: AccountId = DAVE;
+	pub const VolumePeriodLength: u64 = 10;
+	pub const MaxVolumePeriods: u32 = 3;
+	pub const MaxPriceChangePerBlock: Option<(u32, u32)> = None;
+	pub const MaxLiquidityRemovalPerBlock: Option<(u32, u32)> = None;
 }
 
 impl pallet_xyk::Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = ();
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
+	type BalanceInspect = Currency;
 	type NativeAssetId = BSXAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
+	type MinPoolFee = MinPoolFee;
+	type MaxPoolFee = MaxPoolFee;
+	type UpdatePoolFeeOrigin = EnsureRoot<AccountId>;
+	type UpdateReservesOrigin = EnsureRoot<AccountId>;
+	type UpdatePoolStateOrigin = EnsureRoot<AccountId>;
+	type ProtocolFee = ProtocolFee;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
@@ -319,6 +336,12 @@ impl pallet_xyk::Config for Test {
 	type AMMHandler = ();
 	type DiscountedFee = DiscountedFee;
 	type NonDustableWhitelistHandler = Duster;
+	type BlockNumberProvider = System;
+	type VolumePeriodLength = VolumePeriodLength;
+	type MaxVolumePeriods = MaxVolumePeriods;
+	type MaxPriceChangePerBlock = MaxPriceChangePerBlock;
+	type MaxLiquidityRemovalPerBlock = MaxLiquidityRemovalPerBlock;
+	type Call = Call;
 }
 
 impl Default for ExtBuilder {
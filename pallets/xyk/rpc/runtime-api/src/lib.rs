@@ -0,0 +1,142 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the XYK pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// State of a single XYK pool.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PoolInfo<AccountId, AssetId, Balance> {
+	/// Pool account.
+	pub pool: AccountId,
+	/// Assets of the pool with their reserves.
+	pub reserves: Vec<(AssetId, Balance)>,
+	/// Share token of the pool.
+	pub share_token: AssetId,
+	/// Total amount of issued shares.
+	pub total_liquidity: Balance,
+	/// Trade fee rate as (numerator, denominator).
+	pub fee: (u32, u32),
+}
+
+/// Result of a trade calculation.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TradeQuote<AssetId, Balance> {
+	/// Amount of asset out received for a sell (fee deducted) or amount of asset in paid for a buy (fee not included).
+	pub amount: Balance,
+	/// Asset in which the trade fee is paid.
+	pub fee_asset: AssetId,
+	/// Trade fee.
+	pub fee_amount: Balance,
+	/// Amount of native asset burnt when discount is applied.
+	pub discount_amount: Balance,
+}
+
+/// Trade volume of an asset of a pool.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AssetVolume<AssetId, Balance> {
+	/// Asset of the pool.
+	pub asset_id: AssetId,
+	/// Amount of the asset received by the pool from traders, trading fee excluded.
+	pub volume_in: Balance,
+	/// Amount of the asset sent by the pool to traders.
+	pub volume_out: Balance,
+	/// Trading fees paid in the asset.
+	pub fees: Balance,
+	/// Part of `fees` which does not go to liquidity providers.
+	pub protocol_fees: Balance,
+}
+
+/// Trade volume of a pool in a single period.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PeriodVolume<AssetId, Balance, BlockNumber> {
+	/// First block of the period.
+	pub period_start: BlockNumber,
+	/// Trade volume of both assets of the pool in the period.
+	pub volumes: Vec<AssetVolume<AssetId, Balance>>,
+}
+
+/// Trade volume of a pool since its creation and in the most recent periods.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PoolVolume<AccountId, AssetId, Balance, BlockNumber> {
+	/// Pool account.
+	pub pool: AccountId,
+	/// Cumulative trade volume of both assets of the pool.
+	pub total: Vec<AssetVolume<AssetId, Balance>>,
+	/// Length of a period in blocks.
+	pub period_length: BlockNumber,
+	/// Trade volume in the most recent periods with any trades, oldest first.
+	pub periods: Vec<PeriodVolume<AssetId, Balance, BlockNumber>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to query XYK pools, their trade volume and quote trades.
+	pub trait XYKApi<AccountId, AssetId, Balance, BlockNumber> where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Return state of the pool of given asset pair, if it exists.
+		fn get_pool(asset_a: AssetId, asset_b: AssetId) -> Option<PoolInfo<AccountId, AssetId, Balance>>;
+
+		/// Return state of all existing pools.
+		fn get_all_pools() -> Vec<PoolInfo<AccountId, AssetId, Balance>>;
+
+		/// Calculate amount of `asset_out` received for selling `amount_in` of `asset_in`.
+		fn calculate_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			discount: bool,
+		) -> Result<TradeQuote<AssetId, Balance>, DispatchError>;
+
+		/// Calculate amount of `asset_in` to be paid for buying `amount_out` of `asset_out`.
+		fn calculate_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount_out: Balance,
+			discount: bool,
+		) -> Result<TradeQuote<AssetId, Balance>, DispatchError>;
+
+		/// Return trade volume of the pool of given asset pair, if it exists.
+		fn get_pool_volume(asset_a: AssetId, asset_b: AssetId) -> Option<PoolVolume<AccountId, AssetId, Balance, BlockNumber>>;
+
+		/// Return trade volume of all existing pools.
+		fn get_all_pool_volumes() -> Vec<PoolVolume<AccountId, AssetId, Balance, BlockNumber>>;
+	}
+}
@@ -0,0 +1,215 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the XYK pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_xyk_rpc_runtime_api::XYKApi as XYKRuntimeApi;
+use pallet_xyk_rpc_runtime_api::{PoolInfo, PoolVolume, TradeQuote};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc(server)]
+pub trait XYKApi<BlockHash, AccountId, AssetId, Balance, BlockNumber> {
+	/// Return state of the pool of given asset pair.
+	#[method(name = "xyk_getPool")]
+	fn get_pool(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PoolInfo<AccountId, AssetId, Balance>>>;
+
+	/// Return state of all existing pools.
+	#[method(name = "xyk_getPools")]
+	fn get_all_pools(&self, at: Option<BlockHash>) -> RpcResult<Vec<PoolInfo<AccountId, AssetId, Balance>>>;
+
+	/// Calculate amount of `asset_out` received for selling `amount_in` of `asset_in`.
+	#[method(name = "xyk_quoteSell")]
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		discount: bool,
+		at: Option<BlockHash>,
+	) -> RpcResult<TradeQuote<AssetId, Balance>>;
+
+	/// Calculate amount of `asset_in` to be paid for buying `amount_out` of `asset_out`.
+	#[method(name = "xyk_quoteBuy")]
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount_out: Balance,
+		discount: bool,
+		at: Option<BlockHash>,
+	) -> RpcResult<TradeQuote<AssetId, Balance>>;
+
+	/// Return trade volume of the pool of given asset pair.
+	#[method(name = "xyk_getPoolVolume")]
+	fn get_pool_volume(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PoolVolume<AccountId, AssetId, Balance, BlockNumber>>>;
+
+	/// Return trade volume of all existing pools.
+	#[method(name = "xyk_getPoolVolumes")]
+	fn get_all_pool_volumes(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PoolVolume<AccountId, AssetId, Balance, BlockNumber>>>;
+}
+
+/// Provides RPC methods to query XYK pools, their trade volume and quote trades.
+pub struct XYK<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> XYK<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The trade could not be calculated.
+	CalculationError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::CalculationError => 2,
+		}
+	}
+}
+
+fn rpc_error(error: Error, message: &str, data: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(error.into(), message, Some(format!("{:?}", data)))).into()
+}
+
+impl<C, Block, AccountId, AssetId, Balance, BlockNumber>
+	XYKApiServer<<Block as BlockT>::Hash, AccountId, AssetId, Balance, BlockNumber> for XYK<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XYKRuntimeApi<Block, AccountId, AssetId, Balance, BlockNumber>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+	AssetId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+{
+	fn get_pool(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PoolInfo<AccountId, AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_pool(&at, asset_a, asset_b)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to query pool.", e))
+	}
+
+	fn get_all_pools(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<PoolInfo<AccountId, AssetId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_all_pools(&at)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to query pools.", e))
+	}
+
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		discount: bool,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<TradeQuote<AssetId, Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.calculate_sell(&at, asset_in, asset_out, amount_in, discount)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to quote sell.", e))?
+			.map_err(|e| rpc_error(Error::CalculationError, "Sell cannot be executed.", e))
+	}
+
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount_out: Balance,
+		discount: bool,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<TradeQuote<AssetId, Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.calculate_buy(&at, asset_out, asset_in, amount_out, discount)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to quote buy.", e))?
+			.map_err(|e| rpc_error(Error::CalculationError, "Buy cannot be executed.", e))
+	}
+
+	fn get_pool_volume(
+		&self,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PoolVolume<AccountId, AssetId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_pool_volume(&at, asset_a, asset_b)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to query pool volume.", e))
+	}
+
+	fn get_all_pool_volumes(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<PoolVolume<AccountId, AssetId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_all_pool_volumes(&at)
+			.map_err(|e| rpc_error(Error::RuntimeError, "Unable to query pool volumes.", e))
+	}
+}
@@ -87,6 +87,73 @@ benchmarks! {
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999992000000000 - 1);// Due to rounding in favor of pool
 	}
 
+	remove_liquidity_with_limits {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), 1, 10_000_000_000, 2, 20_000_000_000)?;
+		XYK::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), 1, 2, 5_000_000_000, 10_100_000_000)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 1_000_000_000, 2_000_000_000)
+	verify {
+		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999996000000000);
+		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999992000000000 - 1);// Due to rounding in favor of pool
+	}
+
+	add_liquidity_single_asset {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 10 * 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, 100_000_000_000, asset_b, 200_000_000_000)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 1)
+	verify {
+		let pair_account = XYK::<T>::get_pair_id(AssetPair { asset_in: asset_a, asset_out: asset_b });
+		assert!(T::Currency::free_balance(XYK::<T>::share_token(pair_account), &caller) > 0);
+		assert!(T::Currency::free_balance(asset_a, &caller) >= 1_000_000_000_000_000 - amount);
+	}
+
+	remove_liquidity_one_asset {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount : Balance = 1_000_000_000;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), 1, 10_000_000_000, 2, 20_000_000_000)?;
+		XYK::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), 1, 2, 5_000_000_000, 10_100_000_000)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b, amount, 1)
+	verify {
+		assert!(T::Currency::free_balance(asset_a, &caller) > 999996000000000);
+		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999990000000000 - 1);
+	}
+
+	flash_swap {
+		let maker = funded_account::<T>("maker", 0);
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_out: AssetId = 1;
+		let asset_in: AssetId = 2;
+		let amount : Balance = 1_000_000_000;
+		let callback: <T as Config>::Call = frame_system::Call::<T>::remark { remark: vec![] }.into();
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_out, 1_000_000_000_000, asset_in, 3_000_000_000_000)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_out, asset_in, amount, 1_000_000_000_000, Box::new(callback))
+	verify {
+		assert_eq!(T::Currency::free_balance(asset_out, &caller), 1_000_000_000_000_000 + amount);
+	}
+
 	sell {
 		let maker = funded_account::<T>("maker", 0);
 		let caller = funded_account::<T>("caller", 0);
@@ -124,6 +191,85 @@ benchmarks! {
 		assert_eq!(T::Currency::free_balance(asset_a, &caller), 1000001000000000);
 		assert_eq!(T::Currency::free_balance(asset_b, &caller), 999996990990990);
 	}
+
+	create_pool_with_fee {
+		let caller = funded_account::<T>("caller", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let amount_a : Balance = 10 * 1_000_000_000;
+		let amount_b : Balance = 20 * 1_000_000_000;
+		let fee = T::MaxPoolFee::get();
+
+	}: _(RawOrigin::Signed(caller.clone()), asset_a, amount_a, asset_b, amount_b, fee)
+	verify {
+		assert_eq!(T::Currency::free_balance(asset_a, &caller), 999990000000000);
+		assert_eq!(XYK::<T>::pool_fees(XYK::<T>::pair_account_from_assets(asset_a, asset_b)), Some(fee));
+	}
+
+	set_pool_fee {
+		let maker = funded_account::<T>("maker", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+		let fee = T::MaxPoolFee::get();
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, 1_000_000_000, asset_b, 1_000_000_000)?;
+
+	}: _(RawOrigin::Root, asset_a, asset_b, fee)
+	verify {
+		assert_eq!(XYK::<T>::pool_fees(XYK::<T>::pair_account_from_assets(asset_a, asset_b)), Some(fee));
+	}
+
+	skim {
+		let maker = funded_account::<T>("maker", 0);
+		let to: T::AccountId = account("to", 0, SEED);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker.clone()).into(), asset_a, 1_000_000_000, asset_b, 1_000_000_000)?;
+
+		let pair_account = XYK::<T>::pair_account_from_assets(asset_a, asset_b);
+		T::Currency::transfer(asset_a, &maker, &pair_account, 1_000_000)?;
+		T::Currency::transfer(asset_b, &maker, &pair_account, 1_000_000)?;
+
+	}: _(RawOrigin::Root, asset_a, asset_b, to.clone())
+	verify {
+		assert_eq!(T::Currency::free_balance(asset_a, &to), 1_000_000);
+		assert_eq!(T::Currency::free_balance(asset_b, &to), 1_000_000);
+	}
+
+	sync {
+		let maker = funded_account::<T>("maker", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker.clone()).into(), asset_a, 1_000_000_000, asset_b, 1_000_000_000)?;
+
+		let pair_account = XYK::<T>::pair_account_from_assets(asset_a, asset_b);
+		T::Currency::transfer(asset_a, &maker, &pair_account, 1_000_000)?;
+		T::Currency::transfer(asset_b, &maker, &pair_account, 1_000_000)?;
+
+	}: _(RawOrigin::Root, asset_a, asset_b)
+	verify {
+		assert_eq!(XYK::<T>::pool_reserve(&pair_account, asset_a), 1_001_000_000);
+		assert_eq!(XYK::<T>::pool_reserve(&pair_account, asset_b), 1_001_000_000);
+	}
+
+	set_pool_state {
+		let maker = funded_account::<T>("maker", 0);
+
+		let asset_a: AssetId = 1;
+		let asset_b: AssetId = 2;
+
+		XYK::<T>::create_pool(RawOrigin::Signed(maker).into(), asset_a, 1_000_000_000, asset_b, 1_000_000_000)?;
+
+	}: _(RawOrigin::Root, asset_a, asset_b, PoolState::TradingFrozen)
+	verify {
+		assert_eq!(XYK::<T>::pool_state(XYK::<T>::pair_account_from_assets(asset_a, asset_b)), PoolState::TradingFrozen);
+	}
 }
 
 #[cfg(test)]
@@ -139,8 +285,17 @@ mod tests {
 			assert_ok!(Pallet::<Test>::test_benchmark_create_pool());
 			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity());
 			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity_with_limits());
+			assert_ok!(Pallet::<Test>::test_benchmark_add_liquidity_single_asset());
+			assert_ok!(Pallet::<Test>::test_benchmark_remove_liquidity_one_asset());
+			assert_ok!(Pallet::<Test>::test_benchmark_flash_swap());
 			assert_ok!(Pallet::<Test>::test_benchmark_sell());
 			assert_ok!(Pallet::<Test>::test_benchmark_buy());
+			assert_ok!(Pallet::<Test>::test_benchmark_create_pool_with_fee());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_pool_fee());
+			assert_ok!(Pallet::<Test>::test_benchmark_skim());
+			assert_ok!(Pallet::<Test>::test_benchmark_sync());
+			assert_ok!(Pallet::<Test>::test_benchmark_set_pool_state());
 		});
 	}
 }
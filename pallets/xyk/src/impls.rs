@@ -1,28 +1,63 @@
 use hydradx_traits::pools::SpotPriceProvider;
 use hydradx_traits::AMM;
-use orml_traits::MultiCurrency;
 use primitives::asset::AssetPair;
 use primitives::{AssetId, Price};
+use sp_runtime::traits::{CheckedAdd, CheckedMul, One, Zero};
 use sp_runtime::FixedPointNumber;
 use sp_std::marker::PhantomData;
 
 pub struct XYKSpotPrice<T>(PhantomData<T>);
 
+/// Price given by the pool reserves alone, without the trading fee of the pool.
+pub struct XYKReservePrice<T>(PhantomData<T>);
+
 impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKSpotPrice<T> {
 	type Price = Price;
 
 	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
-		<crate::Pallet<T>>::exists(AssetPair::new(asset_b, asset_a))
+		<crate::Pallet<T>>::is_trading_allowed(AssetPair::new(asset_b, asset_a))
+	}
+
+	/// Price of `asset_a` denominated in `asset_b`, including the trading fee of the pool.
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		if Self::pair_exists(asset_a, asset_b) {
+			let pair_account = <crate::Pallet<T>>::get_pair_id(AssetPair {
+				asset_out: asset_a,
+				asset_in: asset_b,
+			});
+			let asset_a_reserve = <crate::Pallet<T>>::pool_reserve(&pair_account, asset_a);
+			let asset_b_reserve = <crate::Pallet<T>>::pool_reserve(&pair_account, asset_b);
+
+			let price = Price::checked_from_rational(asset_b_reserve, asset_a_reserve)?;
+
+			let (fee_numerator, fee_denominator) = <crate::Pallet<T>>::pool_fee(&pair_account);
+			let fee = Price::checked_from_rational(fee_numerator, fee_denominator).unwrap_or_else(Price::zero);
+
+			price.checked_mul(&Price::one().checked_add(&fee)?)
+		} else {
+			None
+		}
+	}
+}
+
+impl<T: crate::Config> SpotPriceProvider<AssetId> for XYKReservePrice<T> {
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		let asset_pair = AssetPair::new(asset_b, asset_a);
+		<crate::Pallet<T>>::exists(asset_pair)
+			&& !<crate::Pallet<T>>::is_pool_locked(&<crate::Pallet<T>>::get_pair_id(asset_pair))
 	}
 
+	/// Price of `asset_a` denominated in `asset_b`.
 	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
 		if Self::pair_exists(asset_a, asset_b) {
 			let pair_account = <crate::Pallet<T>>::get_pair_id(AssetPair {
 				asset_out: asset_a,
 				asset_in: asset_b,
 			});
-			let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-			let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+			let asset_a_reserve = <crate::Pallet<T>>::pool_reserve(&pair_account, asset_a);
+			let asset_b_reserve = <crate::Pallet<T>>::pool_reserve(&pair_account, asset_b);
 
 			Price::checked_from_rational(asset_b_reserve, asset_a_reserve)
 		} else {
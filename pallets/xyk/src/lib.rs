@@ -28,17 +28,30 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
-use frame_support::sp_runtime::{traits::Zero, DispatchError};
-use frame_support::{dispatch::DispatchResult, ensure, traits::Get, transactional};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::{
+	traits::{BlockNumberProvider, CheckedMul, Dispatchable, One, Saturating, Zero},
+	DispatchError, FixedPointNumber,
+};
+use frame_support::{
+	dispatch::{DispatchResult, PostDispatchInfo},
+	ensure,
+	traits::{fungibles::Inspect, Get},
+	transactional,
+	weights::{DispatchClass, GetDispatchInfo},
+};
 use frame_system::ensure_signed;
 use hydradx_traits::{
-	AMMPosition, AMMTransfer, AssetPairAccountIdFor, CanCreatePool, OnCreatePoolHandler, OnTradeHandler, AMM,
+	AMMPosition, AMMTransfer, AssetPairAccountIdFor, CanCreatePool, OnCreatePoolHandler, OnTradeHandler, Registry,
+	ShareTokenRegistry, AMM,
 };
-use primitives::{asset::AssetPair, AssetId, Balance};
-use sp_std::{vec, vec::Vec};
+use primitive_types::U256;
+use primitives::{asset::AssetPair, AssetId, Balance, Price};
+use sp_std::{boxed::Box, vec, vec::Vec};
 
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::Amount;
+use scale_info::TypeInfo;
 
 #[cfg(test)]
 mod tests;
@@ -46,13 +59,123 @@ mod tests;
 mod benchmarking;
 
 mod impls;
+pub mod migration;
 mod trade_execution;
 pub mod weights;
 
-pub use impls::XYKSpotPrice;
+pub use impls::{XYKReservePrice, XYKSpotPrice};
 
 use weights::WeightInfo;
 
+/// Result of a trade calculation which does not depend on the trader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradeQuote {
+	/// Amount of asset out received for a sell (fee deducted) or amount of asset in paid for a buy (fee not included).
+	pub amount: Balance,
+	/// Trade fee. Paid in asset out for a sell and in asset in for a buy.
+	pub fee: Balance,
+	/// Amount of native asset burnt when discount is applied.
+	pub discount_amount: Balance,
+}
+
+/// Trade volume of an asset of a pool.
+#[derive(Encode, Decode, Clone, Copy, Debug, Default, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct Volume {
+	/// Amount of the asset received by the pool from traders, trading fee excluded.
+	pub volume_in: Balance,
+	/// Amount of the asset sent by the pool to traders.
+	pub volume_out: Balance,
+	/// Trading fees paid in the asset.
+	pub fees: Balance,
+	/// Part of `fees` transferred to `ProtocolFeeReceiver`.
+	pub protocol_fees: Balance,
+}
+
+impl Volume {
+	/// Add amounts of `other` to this volume.
+	pub fn accrue(&mut self, other: &Volume) {
+		self.volume_in = self.volume_in.saturating_add(other.volume_in);
+		self.volume_out = self.volume_out.saturating_add(other.volume_out);
+		self.fees = self.fees.saturating_add(other.fees);
+		self.protocol_fees = self.protocol_fees.saturating_add(other.protocol_fees);
+	}
+}
+
+/// Reserves and share issuance of a pool before its first change in the current block.
+///
+/// Reserves are ordered by asset id.
+#[derive(Encode, Decode, Clone, Copy, Debug, Default, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct BlockStartState {
+	pub reserve_a: Balance,
+	pub reserve_b: Balance,
+	pub liquidity: Balance,
+}
+
+/// Trading state of a pool set by governance.
+#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum PoolState {
+	/// All operations are allowed.
+	Active,
+	/// Trades are not allowed, liquidity can still be added and removed.
+	TradingFrozen,
+	/// Only removing liquidity is allowed.
+	WithdrawOnly,
+}
+
+impl Default for PoolState {
+	fn default() -> Self {
+		PoolState::Active
+	}
+}
+
+impl PoolState {
+	pub fn is_trading_allowed(&self) -> bool {
+		*self == PoolState::Active
+	}
+
+	pub fn is_adding_liquidity_allowed(&self) -> bool {
+		*self != PoolState::WithdrawOnly
+	}
+}
+
+/// Asset registry metadata used to make share tokens human-readable.
+pub trait ShareTokenMetadata<AssetId> {
+	/// Return symbol and decimals of the asset, if set in the registry.
+	fn metadata(asset_id: AssetId) -> Option<(Vec<u8>, u8)>;
+
+	/// Return assets of the pool the share token was registered for.
+	fn share_token_assets(share_token: AssetId) -> Option<(AssetId, AssetId)>;
+
+	/// Update name, symbol and decimals of the share token.
+	fn set_share_token_metadata(share_token: AssetId, name: Vec<u8>, symbol: Vec<u8>, decimals: u8) -> DispatchResult;
+
+	/// Max length of name and symbol of the share token accepted by the registry.
+	fn string_limit() -> u32;
+}
+
+impl<AssetId> ShareTokenMetadata<AssetId> for () {
+	fn metadata(_asset_id: AssetId) -> Option<(Vec<u8>, u8)> {
+		None
+	}
+
+	fn share_token_assets(_share_token: AssetId) -> Option<(AssetId, AssetId)> {
+		None
+	}
+
+	fn set_share_token_metadata(
+		_share_token: AssetId,
+		_name: Vec<u8>,
+		_symbol: Vec<u8>,
+		_decimals: u8,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn string_limit() -> u32 {
+		u32::MAX
+	}
+}
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
@@ -62,13 +185,20 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::OriginFor;
 	use hydradx_traits::pools::DustRemovalAccountWhitelist;
-	use hydradx_traits::ShareTokenRegistry;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_finalize(_n: T::BlockNumber) {
+			// Removal of every entry is registered as block weight when the entry is inserted.
+			let _ = <BlockStartStates<T>>::clear(u32::MAX, None);
+		}
+	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -77,12 +207,18 @@ pub mod pallet {
 		/// Registry support
 		type AssetRegistry: ShareTokenRegistry<AssetId, Vec<u8>, Balance, DispatchError>;
 
+		/// Registry metadata of assets used to name share tokens
+		type AssetMetadata: ShareTokenMetadata<AssetId>;
+
 		/// Share token support
 		type AssetPairAccountId: AssetPairAccountIdFor<AssetId, Self::AccountId>;
 
 		/// Multi currency for transfer of currencies
 		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>;
 
+		/// Balance inspection used to get the transferable balance sold by `sell_all`
+		type BalanceInspect: Inspect<Self::AccountId, AssetId = AssetId, Balance = Balance>;
+
 		/// Native Asset Id
 		#[pallet::constant]
 		type NativeAssetId: Get<AssetId>;
@@ -91,9 +227,34 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		/// Trading fee rate
+		/// Used by pools which have no specific fee set.
 		#[pallet::constant]
 		type GetExchangeFee: Get<(u32, u32)>;
 
+		/// Minimum trading fee rate which can be set for a pool
+		#[pallet::constant]
+		type MinPoolFee: Get<(u32, u32)>;
+
+		/// Maximum trading fee rate which can be set for a pool
+		#[pallet::constant]
+		type MaxPoolFee: Get<(u32, u32)>;
+
+		/// Origin allowed to update trading fee of a pool
+		type UpdatePoolFeeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to skim or sync reserves of a pool
+		type UpdateReservesOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to freeze and unfreeze a pool
+		type UpdatePoolStateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Share of the trading fee which is transferred to `ProtocolFeeReceiver`
+		#[pallet::constant]
+		type ProtocolFee: Get<(u32, u32)>;
+
+		/// Account which receives the protocol share of trading fees
+		type ProtocolFeeReceiver: Get<Self::AccountId>;
+
 		/// Minimum trading limit
 		#[pallet::constant]
 		type MinTradingLimit: Get<Balance>;
@@ -121,6 +282,34 @@ pub mod pallet {
 
 		/// Account whitelist manager to exclude pool accounts from dusting mechanism.
 		type NonDustableWhitelistHandler: DustRemovalAccountWhitelist<Self::AccountId, Error = DispatchError>;
+
+		/// The block number provider used to check trade deadlines and to split trade volume into periods
+		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// Length of a trade volume period in blocks of `BlockNumberProvider`
+		#[pallet::constant]
+		type VolumePeriodLength: Get<Self::BlockNumber>;
+
+		/// Number of the most recent trade volume periods kept for each asset of a pool
+		#[pallet::constant]
+		type MaxVolumePeriods: Get<u32>;
+
+		/// Max change of a pool price within a block relative to the price at the start of the block.
+		/// No limit is applied if `None`.
+		#[pallet::constant]
+		type MaxPriceChangePerBlock: Get<Option<(u32, u32)>>;
+
+		/// Max fraction of pool shares which can be removed within a block, unless the pool is destroyed.
+		/// No limit is applied if `None`.
+		#[pallet::constant]
+		type MaxLiquidityRemovalPerBlock: Get<Option<(u32, u32)>>;
+
+		/// The overarching call type, dispatched as flash swap callback.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>
+			+ IsType<<Self as frame_system::Config>::Call>;
 	}
 
 	#[pallet::error]
@@ -197,6 +386,36 @@ pub mod pallet {
 
 		/// Pool cannot be created due to outside factors.
 		CannotCreatePool,
+
+		/// Trading fee is invalid or out of allowed bounds.
+		InvalidFee,
+
+		/// Amount of asset received for removed liquidity has not reached given limit.
+		RemoveLiquidityLimitNotReached,
+
+		/// Amount of minted shares has not reached given limit.
+		SharesLimitNotReached,
+
+		/// Trade deadline has passed.
+		DeadlineExpired,
+
+		/// Pool is locked by an ongoing flash swap.
+		PoolLocked,
+
+		/// Pool reserves do not satisfy the constant product invariant after a flash swap.
+		FlashSwapInvariantViolated,
+
+		/// Trading in the pool is frozen.
+		PoolTradingFrozen,
+
+		/// Pool only allows removing liquidity.
+		PoolWithdrawOnly,
+
+		/// Pool price would change by more than `MaxPriceChangePerBlock` within the block.
+		MaxPriceChangePerBlockExceeded,
+
+		/// More than `MaxLiquidityRemovalPerBlock` fraction of pool shares would be removed within the block.
+		MaxLiquidityRemovalPerBlockExceeded,
 	}
 
 	#[pallet::event]
@@ -239,6 +458,7 @@ pub mod pallet {
 		},
 
 		/// Asset sale executed.
+		/// `fee_amount` is the total trading fee, `protocol_fee_amount` is the part of it sent to `ProtocolFeeReceiver`.
 		SellExecuted {
 			who: T::AccountId,
 			asset_in: AssetId,
@@ -247,10 +467,12 @@ pub mod pallet {
 			sale_price: Balance,
 			fee_asset: AssetId,
 			fee_amount: Balance,
+			protocol_fee_amount: Balance,
 			pool: T::AccountId,
 		},
 
 		/// Asset purchase executed.
+		/// `fee_amount` is the total trading fee, `protocol_fee_amount` is the part of it sent to `ProtocolFeeReceiver`.
 		BuyExecuted {
 			who: T::AccountId,
 			asset_out: AssetId,
@@ -259,8 +481,48 @@ pub mod pallet {
 			buy_price: Balance,
 			fee_asset: AssetId,
 			fee_amount: Balance,
+			protocol_fee_amount: Balance,
+			pool: T::AccountId,
+		},
+
+		/// Trading fee of the pool was set.
+		PoolFeeSet { pool: T::AccountId, fee: (u32, u32) },
+
+		/// Flash swap executed.
+		/// `amount` of `asset_out` was lent from the pool and `buy_price` plus `fee_amount` of `asset_in` was repaid.
+		FlashSwapExecuted {
+			who: T::AccountId,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+			buy_price: Balance,
+			fee_asset: AssetId,
+			fee_amount: Balance,
+			protocol_fee_amount: Balance,
+			pool: T::AccountId,
+		},
+
+		/// Balances of the pool account above the pool reserves were transferred to `to`.
+		Skimmed {
+			pool: T::AccountId,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			amount_a: Balance,
+			amount_b: Balance,
+			to: T::AccountId,
+		},
+
+		/// Pool reserves were set to balances of the pool account.
+		ReservesSynced {
 			pool: T::AccountId,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			reserve_a: Balance,
+			reserve_b: Balance,
 		},
+
+		/// Trading state of a pool was set.
+		PoolStateSet { pool: T::AccountId, state: PoolState },
 	}
 
 	/// Asset id storage for shared pool tokens
@@ -279,6 +541,56 @@ pub mod pallet {
 	pub(crate) type PoolAssets<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, (AssetId, AssetId), OptionQuery>;
 
+	/// Trading fee rate of a pool. `GetExchangeFee` is used if not set.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_fees)]
+	pub(crate) type PoolFees<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (u32, u32), OptionQuery>;
+
+	/// Reserves of pool assets used for pricing.
+	///
+	/// Updated only by liquidity changes, trades and governance, so assets transferred directly to the pool
+	/// account do not move the price.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_reserve)]
+	pub(crate) type PoolReserves<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, Balance, ValueQuery>;
+
+	/// Cumulative trade volume of assets of a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_volume)]
+	pub(crate) type PoolVolumes<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, Volume, ValueQuery>;
+
+	/// Trade volume of assets of a pool in the most recent periods, oldest first.
+	/// Each period is identified by its first block.
+	#[pallet::storage]
+	#[pallet::getter(fn period_volumes)]
+	pub(crate) type PeriodVolumes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		AssetId,
+		BoundedVec<(T::BlockNumber, Volume), T::MaxVolumePeriods>,
+		ValueQuery,
+	>;
+
+	/// Trading state of a pool. Pools without an entry are active.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_state)]
+	pub(crate) type PoolStates<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PoolState, ValueQuery>;
+
+	/// Reserves and share issuance of pools changed in the current block, as they were at the start of the block.
+	/// Cleared in `on_finalize`.
+	#[pallet::storage]
+	#[pallet::getter(fn block_start_state)]
+	pub(crate) type BlockStartStates<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockStartState, OptionQuery>;
+
+	/// Pools whose reserves are lent out by an ongoing flash swap.
+	#[pallet::storage]
+	pub(crate) type FlashSwapLocks<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create new pool for given asset pair.
@@ -300,71 +612,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			ensure!(
-				T::CanCreatePool::can_create(asset_a, asset_b),
-				Error::<T>::CannotCreatePool
-			);
-
-			ensure!(
-				amount_a >= T::MinPoolLiquidity::get() && amount_b >= T::MinPoolLiquidity::get(),
-				Error::<T>::InsufficientLiquidity
-			);
-
-			ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
-
-			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			};
-
-			ensure!(!Self::exists(asset_pair), Error::<T>::TokenPoolAlreadyExists);
-
-			let shares_added = if asset_a < asset_b { amount_a } else { amount_b };
-
-			ensure!(
-				T::Currency::free_balance(asset_a, &who) >= amount_a,
-				Error::<T>::InsufficientAssetBalance
-			);
-
-			ensure!(
-				T::Currency::free_balance(asset_b, &who) >= amount_b,
-				Error::<T>::InsufficientAssetBalance
-			);
-
-			let pair_account = Self::get_pair_id(asset_pair);
-
-			let token_name = asset_pair.name();
-
-			let share_token = T::AssetRegistry::get_or_create_shared_asset(
-				token_name,
-				vec![asset_a, asset_b],
-				T::MinPoolLiquidity::get(),
-			)?;
-
-			let _ = T::AMMHandler::on_create_pool(asset_pair.asset_in, asset_pair.asset_out);
-
-			T::NonDustableWhitelistHandler::add_account(&pair_account)?;
-
-			<ShareToken<T>>::insert(&pair_account, &share_token);
-			<PoolAssets<T>>::insert(&pair_account, (asset_a, asset_b));
-
-			Self::deposit_event(Event::PoolCreated {
-				who: who.clone(),
-				asset_a,
-				asset_b,
-				initial_shares_amount: shares_added,
-				share_token,
-				pool: pair_account.clone(),
-			});
-
-			T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
-			T::Currency::transfer(asset_b, &who, &pair_account, amount_b)?;
-
-			T::Currency::deposit(share_token, &who, shares_added)?;
-
-			<TotalLiquidity<T>>::insert(&pair_account, shares_added);
-
-			Ok(())
+			Self::do_create_pool(who, asset_a, amount_a, asset_b, amount_b, None)
 		}
 
 		/// Add liquidity to previously created asset pair pool.
@@ -382,77 +630,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			};
-
-			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
-
-			ensure!(
-				amount_a >= T::MinTradingLimit::get(),
-				Error::<T>::InsufficientTradingAmount
-			);
-
-			ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
-
-			ensure!(
-				T::Currency::free_balance(asset_a, &who) >= amount_a,
-				Error::<T>::InsufficientAssetBalance
-			);
-
-			let pair_account = Self::get_pair_id(asset_pair);
-
-			let share_token = Self::share_token(&pair_account);
-
-			let account_shares = T::Currency::free_balance(share_token, &who);
-
-			let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-			let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
-			let share_issuance = Self::total_liquidity(&pair_account);
-
-			let amount_b = hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
-				.map_err(|_| Error::<T>::AddAssetAmountInvalid)?;
-
-			ensure!(
-				T::Currency::free_balance(asset_b, &who) >= amount_b,
-				Error::<T>::InsufficientAssetBalance
-			);
-
-			ensure!(amount_b <= amount_b_max_limit, Error::<T>::AssetAmountExceededLimit);
-
-			let shares_added = hydra_dx_math::xyk::calculate_shares(asset_a_reserve, amount_a, share_issuance)
-				.ok_or(Error::<T>::Overflow)?;
-
-			ensure!(!shares_added.is_zero(), Error::<T>::InvalidMintedLiquidity);
-
-			// Make sure that account share liquidity is at least MinPoolLiquidity
-			ensure!(
-				account_shares
-					.checked_add(shares_added)
-					.ok_or(Error::<T>::InvalidMintedLiquidity)?
-					>= T::MinPoolLiquidity::get(),
-				Error::<T>::InsufficientLiquidity
-			);
-
-			let liquidity_amount = share_issuance
-				.checked_add(shares_added)
-				.ok_or(Error::<T>::InvalidLiquidityAmount)?;
-
-			T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
-			T::Currency::transfer(asset_b, &who, &pair_account, amount_b)?;
-
-			T::Currency::deposit(share_token, &who, shares_added)?;
-
-			<TotalLiquidity<T>>::insert(&pair_account, liquidity_amount);
-
-			Self::deposit_event(Event::LiquidityAdded {
-				who,
-				asset_a,
-				asset_b,
-				amount_a,
-				amount_b,
-			});
+			Self::do_add_liquidity(who, asset_a, asset_b, amount_a, amount_b_max_limit)?;
 
 			Ok(())
 		}
@@ -472,98 +650,14 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let asset_pair = AssetPair {
-				asset_in: asset_a,
-				asset_out: asset_b,
-			};
-
-			ensure!(!liquidity_amount.is_zero(), Error::<T>::ZeroLiquidity);
-
-			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
-
-			let pair_account = Self::get_pair_id(asset_pair);
-
-			let share_token = Self::share_token(&pair_account);
-
-			let total_shares = Self::total_liquidity(&pair_account);
-
-			let account_shares = T::Currency::free_balance(share_token, &who);
-
-			ensure!(total_shares >= liquidity_amount, Error::<T>::InsufficientLiquidity);
-
-			ensure!(account_shares >= liquidity_amount, Error::<T>::InsufficientAssetBalance);
-
-			// Account's liquidity left should be either 0 or at least MinPoolLiquidity
-			ensure!(
-				(account_shares.saturating_sub(liquidity_amount)) >= T::MinPoolLiquidity::get()
-					|| (account_shares == liquidity_amount),
-				Error::<T>::InsufficientLiquidity
-			);
-
-			let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-			let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
-
-			let liquidity_out = hydra_dx_math::xyk::calculate_liquidity_out(
-				asset_a_reserve,
-				asset_b_reserve,
-				liquidity_amount,
-				total_shares,
-			)
-			.map_err(|_| Error::<T>::RemoveAssetAmountInvalid)?;
-
-			let (remove_amount_a, remove_amount_b) = liquidity_out;
-
-			ensure!(
-				T::Currency::free_balance(asset_a, &pair_account) >= remove_amount_a,
-				Error::<T>::InsufficientPoolAssetBalance
-			);
-			ensure!(
-				T::Currency::free_balance(asset_b, &pair_account) >= remove_amount_b,
-				Error::<T>::InsufficientPoolAssetBalance
-			);
-
-			let liquidity_left = total_shares
-				.checked_sub(liquidity_amount)
-				.ok_or(Error::<T>::InvalidLiquidityAmount)?;
-
-			T::Currency::transfer(asset_a, &pair_account, &who, remove_amount_a)?;
-			T::Currency::transfer(asset_b, &pair_account, &who, remove_amount_b)?;
-
-			T::Currency::withdraw(share_token, &who, liquidity_amount)?;
-
-			<TotalLiquidity<T>>::insert(&pair_account, liquidity_left);
-
-			Self::deposit_event(Event::LiquidityRemoved {
-				who: who.clone(),
+			Self::do_remove_liquidity(
+				who,
 				asset_a,
 				asset_b,
-				shares: liquidity_amount,
-			});
-
-			if liquidity_left == 0 {
-				<ShareToken<T>>::remove(&pair_account);
-				<PoolAssets<T>>::remove(&pair_account);
-				<TotalLiquidity<T>>::remove(&pair_account);
-
-				// Ignore the failure, this cant stop liquidity removal
-				let r = T::NonDustableWhitelistHandler::remove_account(&pair_account);
-
-				if r.is_err() {
-					log::trace!(
-					target: "xyk::remova_liquidity", "XYK: Failed to remove account {:?} from dust-removal whitelist. Reason {:?}",
-						pair_account,
-					r
-					);
-				}
-
-				Self::deposit_event(Event::PoolDestroyed {
-					who,
-					asset_a,
-					asset_b,
-					share_token,
-					pool: pair_account,
-				});
-			}
+				liquidity_amount,
+				Balance::zero(),
+				Balance::zero(),
+			)?;
 
 			Ok(())
 		}
@@ -613,86 +707,1241 @@ pub mod pallet {
 
 			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// Return balance of each asset in selected liquidity pool.
-	pub fn get_pool_balances(pool_address: T::AccountId) -> Option<Vec<(AssetId, Balance)>> {
-		let mut balances = Vec::new();
+		/// Create new pool for given asset pair with specific trading fee.
+		///
+		/// Same as `create_pool`, but the pool uses `fee` instead of the default `GetExchangeFee`.
+		/// `fee` has to be within `MinPoolFee` and `MaxPoolFee` bounds.
+		///
+		/// Emits `PoolCreated` and `PoolFeeSet` events when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool_with_fee())]
+		pub fn create_pool_with_fee(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			amount_a: Balance,
+			asset_b: AssetId,
+			amount_b: Balance,
+			fee: (u32, u32),
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-		if let Some(assets) = Self::get_pool_assets(&pool_address) {
-			for item in &assets {
-				let reserve = T::Currency::free_balance(*item, &pool_address);
-				balances.push((*item, reserve));
-			}
+			Self::do_create_pool(who, asset_a, amount_a, asset_b, amount_b, Some(fee))
 		}
-		Some(balances)
-	}
-	/// Calculate discounted trade fee
-	fn calculate_discounted_fee(amount: Balance) -> Result<Balance, DispatchError> {
-		Ok(
-			hydra_dx_math::fee::calculate_pool_trade_fee(amount, T::DiscountedFee::get())
-				.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?,
-		)
-	}
 
-	/// Calculate trade fee
-	fn calculate_fee(amount: Balance) -> Result<Balance, DispatchError> {
-		let fee = T::GetExchangeFee::get();
-		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(amount, (fee.0, fee.1))
-			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
+		/// Update trading fee of the pool of given asset pair.
+		///
+		/// Can only be called by `UpdatePoolFeeOrigin`.
+		/// `fee` has to be within `MinPoolFee` and `MaxPoolFee` bounds.
+		///
+		/// Emits `PoolFeeSet` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pool_fee())]
+		pub fn set_pool_fee(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			fee: (u32, u32),
+		) -> DispatchResult {
+			T::UpdatePoolFeeOrigin::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			ensure!(Self::is_fee_allowed(fee), Error::<T>::InvalidFee);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			<PoolFees<T>>::insert(&pair_account, fee);
+
+			Self::deposit_event(Event::PoolFeeSet {
+				pool: pair_account,
+				fee,
+			});
+
+			Ok(())
+		}
+
+		/// Remove liquidity from specific liquidity pool in the form of burning shares.
+		///
+		/// Same as `remove_liquidity` but fails if the received amount of either asset is below given limit.
+		///
+		/// Parameters:
+		/// - `origin`: Liquidity provider.
+		/// - `asset_a`: First asset of the pool.
+		/// - `asset_b`: Second asset of the pool.
+		/// - `liquidity_amount`: Amount of shares to burn.
+		/// - `min_amount_a`: Minimum amount of `asset_a` to receive.
+		/// - `min_amount_b`: Minimum amount of `asset_b` to receive.
+		///
+		/// Emits 'LiquidityRemoved' when successful.
+		/// Emits 'PoolDestroyed' when pool is destroyed.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity_with_limits())]
+		pub fn remove_liquidity_with_limits(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			liquidity_amount: Balance,
+			min_amount_a: Balance,
+			min_amount_b: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_remove_liquidity(who, asset_a, asset_b, liquidity_amount, min_amount_a, min_amount_b)?;
+
+			Ok(())
+		}
+
+		/// Add liquidity to previously created asset pair pool providing only `asset_a`.
+		///
+		/// The portion of `amount_a` which keeps the pool ratio is sold for `asset_b` first and the rest is added
+		/// together with the received `asset_b`. Dust left over by rounding stays with the caller.
+		///
+		/// Parameters:
+		/// - `origin`: Liquidity provider.
+		/// - `asset_a`: Asset provided by the caller.
+		/// - `asset_b`: Second asset of the pool.
+		/// - `amount_a`: Total amount of `asset_a` to provide.
+		/// - `min_shares`: Minimum amount of shares to receive.
+		///
+		/// Emits `SellExecuted` and `LiquidityAdded` events when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity_single_asset() + <T as Config>::AMMHandler::on_trade_weight())]
+		#[transactional]
+		pub fn add_liquidity_single_asset(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			amount_a: Balance,
+			min_shares: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+			let asset_a_reserve = Self::pool_reserve(&pair_account, asset_a);
+
+			let sell_amount =
+				Self::calculate_single_asset_sell_amount(asset_a_reserve, amount_a, Self::pool_fee(&pair_account))
+					.ok_or(Error::<T>::Overflow)?;
+
+			let transfer = Self::validate_sell(&who, asset_pair, sell_amount, Balance::zero(), false)?;
+			Self::execute_sell(&transfer)?;
+
+			let amount_b = transfer.amount_out;
+			let amount_a_left = amount_a.checked_sub(sell_amount).ok_or(Error::<T>::Overflow)?;
+
+			// Provide as much of the rest as the received amount of asset b allows.
+			// The required amount of asset b is rounded up in favor of the pool, hence the largest amount of asset a
+			// which requires strictly less than the received amount is matched.
+			let asset_a_reserve = Self::pool_reserve(&pair_account, asset_a);
+			let asset_b_reserve = Self::pool_reserve(&pair_account, asset_b);
+			let amount_a_matched = U256::from(amount_b)
+				.checked_mul(U256::from(asset_a_reserve))
+				.and_then(|v| v.saturating_sub(U256::one()).checked_div(U256::from(asset_b_reserve)))
+				.and_then(|v| Balance::try_from(v).ok())
+				.ok_or(Error::<T>::Overflow)?;
+
+			let shares = Self::do_add_liquidity(who, asset_a, asset_b, amount_a_left.min(amount_a_matched), amount_b)?;
+
+			ensure!(shares >= min_shares, Error::<T>::SharesLimitNotReached);
+
+			Ok(())
+		}
+
+		/// Remove liquidity from specific liquidity pool and receive only `asset_out`.
+		///
+		/// The received amount of `asset_b` is sold to the pool for `asset_out`. Removing the last liquidity of
+		/// a pool this way is not possible as the pool is destroyed before the sale.
+		///
+		/// Parameters:
+		/// - `origin`: Liquidity provider.
+		/// - `asset_out`: Asset received by the caller.
+		/// - `asset_b`: Second asset of the pool.
+		/// - `liquidity_amount`: Amount of shares to burn.
+		/// - `min_amount_out`: Minimum total amount of `asset_out` to receive.
+		///
+		/// Emits `LiquidityRemoved` and `SellExecuted` events when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity_one_asset() + <T as Config>::AMMHandler::on_trade_weight())]
+		#[transactional]
+		pub fn remove_liquidity_one_asset(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_b: AssetId,
+			liquidity_amount: Balance,
+			min_amount_out: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (amount_out, amount_b) = Self::do_remove_liquidity(
+				who.clone(),
+				asset_out,
+				asset_b,
+				liquidity_amount,
+				Balance::zero(),
+				Balance::zero(),
+			)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_b,
+				asset_out,
+			};
+			let transfer = Self::validate_sell(&who, asset_pair, amount_b, Balance::zero(), false)?;
+			Self::execute_sell(&transfer)?;
+
+			let total_out = amount_out
+				.checked_add(transfer.amount_out)
+				.ok_or(Error::<T>::Overflow)?;
+
+			ensure!(total_out >= min_amount_out, Error::<T>::RemoveLiquidityLimitNotReached);
+
+			Ok(())
+		}
+
+		/// Trade asset in for asset out if the trade is executed no later than at block `deadline`.
+		///
+		/// Same as `sell`, but fails with `DeadlineExpired` when the current block provided by
+		/// `BlockNumberProvider` is greater than `deadline`.
+		///
+		/// Emits `SellExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell() + <T as Config>::AMMHandler::on_trade_weight() + T::DbWeight::get().reads(1))]
+		pub fn sell_with_deadline(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+			max_limit: Balance,
+			discount: bool,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_deadline(deadline)?;
+
+			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, max_limit, discount)?;
+
+			Ok(())
+		}
+
+		/// Trade asset in for asset out if the trade is executed no later than at block `deadline`.
+		///
+		/// Same as `buy`, but fails with `DeadlineExpired` when the current block provided by
+		/// `BlockNumberProvider` is greater than `deadline`.
+		///
+		/// Emits `BuyExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::buy() + <T as Config>::AMMHandler::on_trade_weight() + T::DbWeight::get().reads(1))]
+		pub fn buy_with_deadline(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+			max_limit: Balance,
+			discount: bool,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_deadline(deadline)?;
+
+			<Self as AMM<_, _, _, _>>::buy(&who, AssetPair { asset_in, asset_out }, amount, max_limit, discount)?;
+
+			Ok(())
+		}
+
+		/// Borrow `amount` of `asset_out` from the pool of `asset_out` and `asset_in`, dispatch `callback`
+		/// and repay the borrowed amount in `asset_in`.
+		///
+		/// `callback` is dispatched with the signed origin of the caller, who receives `amount` of `asset_out`
+		/// before it is dispatched. Afterwards the caller pays for `amount` of `asset_out` in `asset_in`, as if
+		/// it was bought at the reserves before the flash swap, trading fee included.
+		/// The pool can not be used by other operations while `callback` is dispatched.
+		///
+		/// The whole operation is reverted if `callback` fails, the repaid amount exceeds `max_limit`
+		/// or the constant product invariant of the pool is not restored together with the fee.
+		///
+		/// Parameters:
+		/// - `origin`: The borrower.
+		/// - `asset_out`: Asset lent from the pool.
+		/// - `asset_in`: Asset used to repay.
+		/// - `amount`: Amount of `asset_out` lent from the pool.
+		/// - `max_limit`: Maximum amount of `asset_in` to repay, fee included.
+		/// - `callback`: Call dispatched with the borrowed amount.
+		///
+		/// Emits `FlashSwapExecuted` when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::flash_swap()
+			.saturating_add(<T as Config>::AMMHandler::on_trade_weight())
+			.saturating_add(callback.get_dispatch_info().weight))]
+		#[transactional]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+			max_limit: Balance,
+			callback: Box<<T as Config>::Call>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let assets = AssetPair { asset_in, asset_out };
+
+			// Checks the trading limit, the pool existence, state and lock and the max in and out ratios.
+			let quote = Self::quote_buy(assets, amount, false)?;
+
+			let pair_account = Self::get_pair_id(assets);
+
+			let repay_amount = quote.amount.checked_add(quote.fee).ok_or(Error::<T>::Overflow)?;
+			ensure!(max_limit >= repay_amount, Error::<T>::AssetAmountExceededLimit);
+
+			let asset_in_reserve = Self::pool_reserve(&pair_account, asset_in);
+			let asset_out_reserve = Self::pool_reserve(&pair_account, asset_out);
+
+			// The invariant is checked on the balances of the pool account, because the stored reserves are only
+			// updated by this call. Donations above the reserves are not part of the constant product.
+			let asset_in_excess = T::Currency::free_balance(asset_in, &pair_account).saturating_sub(asset_in_reserve);
+			let asset_out_excess =
+				T::Currency::free_balance(asset_out, &pair_account).saturating_sub(asset_out_reserve);
+
+			Self::record_block_start_state(&pair_account, assets);
+
+			<FlashSwapLocks<T>>::insert(&pair_account, ());
+
+			T::Currency::transfer(asset_out, &pair_account, &who, amount)?;
+			Self::decrease_reserve(&pair_account, asset_out, amount)?;
+
+			let callback: <T as Config>::Call = *callback;
+			callback
+				.dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+				.map_err(|e| e.error)?;
+
+			<FlashSwapLocks<T>>::remove(&pair_account);
+
+			// Fee is paid in asset in and goes to the pool, except for the protocol share.
+			let protocol_fee = Self::calculate_protocol_fee(quote.fee)?;
+			let pool_fee = quote
+				.fee
+				.checked_sub(protocol_fee)
+				.ok_or(Error::<T>::FeeAmountInvalid)?;
+
+			let repaid_to_pool = quote.amount.checked_add(pool_fee).ok_or(Error::<T>::Overflow)?;
+			T::Currency::transfer(asset_in, &who, &pair_account, repaid_to_pool)?;
+			Self::increase_reserve(&pair_account, asset_in, repaid_to_pool)?;
+
+			if !protocol_fee.is_zero() {
+				T::Currency::transfer(asset_in, &who, &T::ProtocolFeeReceiver::get(), protocol_fee)?;
+			}
+
+			// The balances without the pool fee and the donations have to keep the constant product
+			// of the reserves before the swap.
+			let new_asset_in_balance = T::Currency::free_balance(asset_in, &pair_account)
+				.saturating_sub(asset_in_excess)
+				.saturating_sub(pool_fee);
+			let new_asset_out_balance =
+				T::Currency::free_balance(asset_out, &pair_account).saturating_sub(asset_out_excess);
+			ensure!(
+				U256::from(new_asset_in_balance)
+					.checked_mul(U256::from(new_asset_out_balance))
+					.ok_or(Error::<T>::Overflow)?
+					>= U256::from(asset_in_reserve)
+						.checked_mul(U256::from(asset_out_reserve))
+						.ok_or(Error::<T>::Overflow)?,
+				Error::<T>::FlashSwapInvariantViolated
+			);
+
+			Self::ensure_price_change_within_limit(&pair_account, assets)?;
+
+			T::AMMHandler::on_trade(
+				asset_in,
+				asset_out,
+				quote.amount,
+				amount,
+				Self::total_liquidity(&pair_account),
+			);
+
+			Self::record_trade_volume(
+				&pair_account,
+				(asset_in, quote.amount),
+				(asset_out, amount),
+				(asset_in, quote.fee, protocol_fee),
+			);
+
+			Self::deposit_event(Event::<T>::FlashSwapExecuted {
+				who,
+				asset_out,
+				asset_in,
+				amount,
+				buy_price: quote.amount,
+				fee_asset: asset_in,
+				fee_amount: quote.fee,
+				protocol_fee_amount: protocol_fee,
+				pool: pair_account,
+			});
+
+			Ok(())
+		}
+
+		/// Transfer balances of the pool account above the pool reserves to `to`.
+		///
+		/// Can only be called by `UpdateReservesOrigin`.
+		/// Recovers assets transferred directly to the pool account, the reserves are not changed.
+		///
+		/// Emits `Skimmed` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::skim())]
+		#[transactional]
+		pub fn skim(origin: OriginFor<T>, asset_a: AssetId, asset_b: AssetId, to: T::AccountId) -> DispatchResult {
+			T::UpdateReservesOrigin::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			Self::ensure_pool_not_locked(&pair_account)?;
+
+			let amount_a = T::Currency::free_balance(asset_a, &pair_account)
+				.saturating_sub(Self::pool_reserve(&pair_account, asset_a));
+			let amount_b = T::Currency::free_balance(asset_b, &pair_account)
+				.saturating_sub(Self::pool_reserve(&pair_account, asset_b));
+
+			T::Currency::transfer(asset_a, &pair_account, &to, amount_a)?;
+			T::Currency::transfer(asset_b, &pair_account, &to, amount_b)?;
+
+			Self::deposit_event(Event::Skimmed {
+				pool: pair_account,
+				asset_a,
+				asset_b,
+				amount_a,
+				amount_b,
+				to,
+			});
+
+			Ok(())
+		}
+
+		/// Set reserves of the pool to balances of the pool account.
+		///
+		/// Can only be called by `UpdateReservesOrigin`.
+		/// Adds assets transferred directly to the pool account to the liquidity of the pool.
+		///
+		/// Emits `ReservesSynced` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sync())]
+		pub fn sync(origin: OriginFor<T>, asset_a: AssetId, asset_b: AssetId) -> DispatchResult {
+			T::UpdateReservesOrigin::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			Self::ensure_pool_not_locked(&pair_account)?;
+
+			let reserve_a = T::Currency::free_balance(asset_a, &pair_account);
+			let reserve_b = T::Currency::free_balance(asset_b, &pair_account);
+
+			<PoolReserves<T>>::insert(&pair_account, asset_a, reserve_a);
+			<PoolReserves<T>>::insert(&pair_account, asset_b, reserve_b);
+
+			// Price changes within the block are measured from the synced reserves.
+			<BlockStartStates<T>>::remove(&pair_account);
+
+			Self::deposit_event(Event::ReservesSynced {
+				pool: pair_account,
+				asset_a,
+				asset_b,
+				reserve_a,
+				reserve_b,
+			});
+
+			Ok(())
+		}
+
+		/// Set trading state of a pool.
+		///
+		/// Can only be called by `UpdatePoolStateOrigin`.
+		/// `TradingFrozen` stops all trades of the pool, `WithdrawOnly` additionally stops adding liquidity.
+		/// Removing liquidity is allowed in every state.
+		///
+		/// Emits `PoolStateSet` event when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pool_state())]
+		pub fn set_pool_state(
+			origin: OriginFor<T>,
+			asset_a: AssetId,
+			asset_b: AssetId,
+			state: PoolState,
+		) -> DispatchResult {
+			T::UpdatePoolStateOrigin::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair {
+				asset_in: asset_a,
+				asset_out: asset_b,
+			};
+
+			ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+			let pair_account = Self::get_pair_id(asset_pair);
+
+			if state == PoolState::Active {
+				<PoolStates<T>>::remove(&pair_account);
+			} else {
+				<PoolStates<T>>::insert(&pair_account, state);
+			}
+
+			Self::deposit_event(Event::PoolStateSet {
+				pool: pair_account,
+				state,
+			});
+
+			Ok(())
+		}
+
+		/// Trade whole transferable balance of asset in for asset out.
+		///
+		/// Same as `sell`, but the amount sold is the transferable balance of `asset_in` of the caller at the time
+		/// of execution, i.e. the free balance without locked funds after the transaction fee has been paid.
+		///
+		/// `max_limit` - minimum amount of `asset_out` to be obtained from the pool in exchange for `asset_in`.
+		///
+		/// Emits `SellExecuted` with the amount sold when successful.
+		#[pallet::weight(<T as Config>::WeightInfo::sell() + <T as Config>::AMMHandler::on_trade_weight() + T::DbWeight::get().reads(1))]
+		pub fn sell_all(
+			origin: OriginFor<T>,
+			asset_in: AssetId,
+			asset_out: AssetId,
+			max_limit: Balance,
+			discount: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let amount = T::BalanceInspect::reducible_balance(asset_in, &who, false);
+
+			<Self as AMM<_, _, _, _>>::sell(&who, AssetPair { asset_in, asset_out }, amount, max_limit, discount)?;
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure that the current block provided by `BlockNumberProvider` has not passed `deadline`.
+	fn ensure_deadline(deadline: T::BlockNumber) -> DispatchResult {
+		ensure!(
+			T::BlockNumberProvider::current_block_number() <= deadline,
+			Error::<T>::DeadlineExpired
+		);
+		Ok(())
+	}
+
+	/// Return true if the pool is locked by an ongoing flash swap.
+	///
+	/// Reserves of a locked pool do not reflect its price, so the pool must not be quoted or traded.
+	pub fn is_pool_locked(pool_account: &T::AccountId) -> bool {
+		FlashSwapLocks::<T>::contains_key(pool_account)
+	}
+
+	/// Ensure that the pool is not locked by an ongoing flash swap.
+	fn ensure_pool_not_locked(pool_account: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::is_pool_locked(pool_account), Error::<T>::PoolLocked);
+		Ok(())
+	}
+
+	/// Ensure that trading in the pool is not frozen by governance.
+	fn ensure_trading_allowed(pool_account: &T::AccountId) -> DispatchResult {
+		ensure!(
+			Self::pool_state(pool_account).is_trading_allowed(),
+			Error::<T>::PoolTradingFrozen
+		);
+		Ok(())
+	}
+
+	/// Return true if the pool exists, trading in it is not frozen by governance and it is not locked
+	/// by an ongoing flash swap.
+	pub fn is_trading_allowed(asset_pair: AssetPair) -> bool {
+		if !Self::exists(asset_pair) {
+			return false;
+		}
+		let pair_account = Self::get_pair_id(asset_pair);
+		Self::pool_state(&pair_account).is_trading_allowed() && !Self::is_pool_locked(&pair_account)
+	}
+
+	/// Store reserves and share issuance of the pool, unless the pool has already been changed in this block.
+	///
+	/// Removal of the stored state in `on_finalize` is registered as block weight.
+	fn record_block_start_state(pool_account: &T::AccountId, assets: AssetPair) {
+		if T::MaxPriceChangePerBlock::get().is_none() && T::MaxLiquidityRemovalPerBlock::get().is_none() {
+			return;
+		}
+		if <BlockStartStates<T>>::contains_key(pool_account) {
+			return;
+		}
+
+		let (asset_a, asset_b) = assets.ordered_pair();
+
+		<BlockStartStates<T>>::insert(
+			pool_account,
+			BlockStartState {
+				reserve_a: Self::pool_reserve(pool_account, asset_a),
+				reserve_b: Self::pool_reserve(pool_account, asset_b),
+				liquidity: Self::total_liquidity(pool_account),
+			},
+		);
+
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::DbWeight::get().writes(1),
+			DispatchClass::Mandatory,
+		);
+	}
+
+	/// Ensure that the pool price has not changed by more than `MaxPriceChangePerBlock` since the start of the block.
+	fn ensure_price_change_within_limit(pool_account: &T::AccountId, assets: AssetPair) -> DispatchResult {
+		let (numerator, denominator) = match T::MaxPriceChangePerBlock::get() {
+			Some(limit) => limit,
+			None => return Ok(()),
+		};
+		let start = match Self::block_start_state(pool_account) {
+			Some(start) => start,
+			None => return Ok(()),
+		};
+
+		let (asset_a, asset_b) = assets.ordered_pair();
+
+		let start_price = Price::checked_from_rational(start.reserve_b, start.reserve_a).ok_or(Error::<T>::Overflow)?;
+		let price = Price::checked_from_rational(
+			Self::pool_reserve(pool_account, asset_b),
+			Self::pool_reserve(pool_account, asset_a),
+		)
+		.ok_or(Error::<T>::Overflow)?;
+		let max_change = Price::checked_from_rational(numerator, denominator)
+			.and_then(|limit| start_price.checked_mul(&limit))
+			.ok_or(Error::<T>::Overflow)?;
+
+		ensure!(
+			price.max(start_price).saturating_sub(price.min(start_price)) <= max_change,
+			Error::<T>::MaxPriceChangePerBlockExceeded
+		);
+		Ok(())
 	}
 
-	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> T::AccountId {
-		T::AssetPairAccountId::from_assets(asset_a, asset_b, "xyk")
+	/// Ensure that no more than `MaxLiquidityRemovalPerBlock` fraction of pool shares has been removed within the block.
+	fn ensure_liquidity_removal_within_limit(pool_account: &T::AccountId, liquidity_left: Balance) -> DispatchResult {
+		let (numerator, denominator) = match T::MaxLiquidityRemovalPerBlock::get() {
+			Some(limit) => limit,
+			None => return Ok(()),
+		};
+		let start = match Self::block_start_state(pool_account) {
+			Some(start) => start,
+			None => return Ok(()),
+		};
+
+		let min_liquidity = Price::checked_from_rational(denominator.saturating_sub(numerator), denominator)
+			.and_then(|ratio| ratio.checked_mul_int(start.liquidity))
+			.ok_or(Error::<T>::Overflow)?;
+
+		ensure!(
+			liquidity_left >= min_liquidity,
+			Error::<T>::MaxLiquidityRemovalPerBlockExceeded
+		);
+		Ok(())
 	}
-}
 
-// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
-impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
-	fn exists(assets: AssetPair) -> bool {
-		<ShareToken<T>>::contains_key(&Self::get_pair_id(assets))
+	/// Increase stored reserve of `asset` in the pool by `amount`.
+	fn increase_reserve(pool_account: &T::AccountId, asset: AssetId, amount: Balance) -> DispatchResult {
+		<PoolReserves<T>>::try_mutate(pool_account, asset, |reserve| -> DispatchResult {
+			*reserve = reserve.checked_add(amount).ok_or(Error::<T>::Overflow)?;
+			Ok(())
+		})
 	}
 
-	fn get_pair_id(assets: AssetPair) -> T::AccountId {
-		Self::pair_account_from_assets(assets.asset_in, assets.asset_out)
+	/// Decrease stored reserve of `asset` in the pool by `amount`.
+	fn decrease_reserve(pool_account: &T::AccountId, asset: AssetId, amount: Balance) -> DispatchResult {
+		<PoolReserves<T>>::try_mutate(pool_account, asset, |reserve| -> DispatchResult {
+			*reserve = reserve
+				.checked_sub(amount)
+				.ok_or(Error::<T>::InsufficientPoolAssetBalance)?;
+			Ok(())
+		})
 	}
 
-	fn get_share_token(assets: AssetPair) -> AssetId {
-		let pair_account = Self::get_pair_id(assets);
-		Self::share_token(&pair_account)
+	/// Add a trade to the cumulative and the current period volume of the pool assets.
+	///
+	/// `amount_in` and `amount_out` are the amounts received and sent by the pool, trading fee excluded.
+	/// `fee` is the asset, the total amount and the protocol share of the trading fee.
+	fn record_trade_volume(
+		pool_account: &T::AccountId,
+		amount_in: (AssetId, Balance),
+		amount_out: (AssetId, Balance),
+		fee: (AssetId, Balance, Balance),
+	) {
+		let mut volume_in = Volume {
+			volume_in: amount_in.1,
+			..Default::default()
+		};
+		let mut volume_out = Volume {
+			volume_out: amount_out.1,
+			..Default::default()
+		};
+
+		let fee_volume = if fee.0 == amount_in.0 {
+			&mut volume_in
+		} else {
+			&mut volume_out
+		};
+		fee_volume.fees = fee.1;
+		fee_volume.protocol_fees = fee.2;
+
+		Self::record_volume(pool_account, amount_in.0, &volume_in);
+		Self::record_volume(pool_account, amount_out.0, &volume_out);
+	}
+
+	fn record_volume(pool_account: &T::AccountId, asset: AssetId, volume: &Volume) {
+		<PoolVolumes<T>>::mutate(pool_account, asset, |total| total.accrue(volume));
+
+		let period = Self::current_volume_period();
+
+		<PeriodVolumes<T>>::mutate(pool_account, asset, |periods| {
+			if let Some((start, period_volume)) = periods.iter_mut().last() {
+				if *start == period {
+					period_volume.accrue(volume);
+					return;
+				}
+			}
+
+			if periods.len() >= T::MaxVolumePeriods::get() as usize && !periods.is_empty() {
+				periods.remove(0);
+			}
+			// can only fail if `MaxVolumePeriods` is zero
+			let _ = periods.try_push((period, *volume));
+		});
+	}
+
+	/// Return the first block of the current trade volume period.
+	pub fn current_volume_period() -> T::BlockNumber {
+		let now = T::BlockNumberProvider::current_block_number();
+		let period_length = T::VolumePeriodLength::get().max(One::one());
+		now - now % period_length
+	}
+
+	/// Return trade volume of `asset` of the pool in the periods which are still within the last
+	/// `MaxVolumePeriods` periods, oldest first.
+	pub fn recent_period_volumes(pool_account: &T::AccountId, asset: AssetId) -> Vec<(T::BlockNumber, Volume)> {
+		let period_length = T::VolumePeriodLength::get().max(One::one());
+		let window = period_length.saturating_mul(T::MaxVolumePeriods::get().into());
+		let oldest_period = Self::current_volume_period()
+			.saturating_add(period_length)
+			.saturating_sub(window);
+
+		Self::period_volumes(pool_account, asset)
+			.into_iter()
+			.filter(|(start, _)| *start >= oldest_period)
+			.collect()
+	}
+
+	/// Return reserve of each asset in selected liquidity pool.
+	pub fn get_pool_balances(pool_address: T::AccountId) -> Option<Vec<(AssetId, Balance)>> {
+		let mut balances = Vec::new();
+
+		if let Some(assets) = Self::get_pool_assets(&pool_address) {
+			for item in &assets {
+				let reserve = Self::pool_reserve(&pool_address, *item);
+				balances.push((*item, reserve));
+			}
+		}
+		Some(balances)
+	}
+
+	/// Calculate the part of `amount` to sell to a pool with `reserve_in` so that the rest of `amount` and the
+	/// received amount of the other asset are in the ratio of the pool after the sale.
+	///
+	/// With the fee `f` taken from the amount out, the sold amount `s` is the positive root of
+	/// `s^2 + s * (R * (2 - f) - A * f) - A * R = 0`.
+	fn calculate_single_asset_sell_amount(reserve_in: Balance, amount: Balance, fee: (u32, u32)) -> Option<Balance> {
+		let (fee_numerator, fee_denominator) = if fee.1 == 0 { (0, 1) } else { fee };
+		let n = U256::from(fee_numerator);
+		let d = U256::from(fee_denominator);
+		let r = U256::from(reserve_in);
+		let a = U256::from(amount);
+
+		// Coefficients multiplied by d: s^2 * d + s * (R * (2d - n) - A * n) - A * R * d = 0
+		let positive = r.checked_mul(d.checked_mul(U256::from(2))?.checked_sub(n)?)?;
+		let negative = a.checked_mul(n)?;
+		let discriminant = positive
+			.max(negative)
+			.checked_sub(positive.min(negative))?
+			.checked_pow(U256::from(2))?
+			.checked_add(
+				U256::from(4)
+					.checked_mul(d)?
+					.checked_mul(d)?
+					.checked_mul(a)?
+					.checked_mul(r)?,
+			)?;
+
+		let sell_amount = discriminant
+			.integer_sqrt()
+			.checked_add(negative)?
+			.checked_sub(positive)?
+			.checked_div(U256::from(2).checked_mul(d)?)?;
+
+		Balance::try_from(sell_amount).ok()
+	}
+
+	/// Add `amount_a` of `asset_a` and the matching amount of `asset_b` of `who` to the pool.
+	///
+	/// Returns the amount of minted shares.
+	fn do_add_liquidity(
+		who: T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a: Balance,
+		amount_b_max_limit: Balance,
+	) -> Result<Balance, DispatchError> {
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+		ensure!(
+			amount_a >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		ensure!(!amount_b_max_limit.is_zero(), Error::<T>::ZeroLiquidity);
+
+		ensure!(
+			T::Currency::free_balance(asset_a, &who) >= amount_a,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		let pair_account = Self::get_pair_id(asset_pair);
+
+		Self::ensure_pool_not_locked(&pair_account)?;
+
+		ensure!(
+			Self::pool_state(&pair_account).is_adding_liquidity_allowed(),
+			Error::<T>::PoolWithdrawOnly
+		);
+
+		Self::record_block_start_state(&pair_account, asset_pair);
+
+		let share_token = Self::share_token(&pair_account);
+
+		let account_shares = T::Currency::free_balance(share_token, &who);
+
+		let asset_a_reserve = Self::pool_reserve(&pair_account, asset_a);
+		let asset_b_reserve = Self::pool_reserve(&pair_account, asset_b);
+		let share_issuance = Self::total_liquidity(&pair_account);
+
+		let amount_b = hydra_dx_math::xyk::calculate_liquidity_in(asset_a_reserve, asset_b_reserve, amount_a)
+			.map_err(|_| Error::<T>::AddAssetAmountInvalid)?;
+
+		ensure!(
+			T::Currency::free_balance(asset_b, &who) >= amount_b,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		ensure!(amount_b <= amount_b_max_limit, Error::<T>::AssetAmountExceededLimit);
+
+		let shares_added = hydra_dx_math::xyk::calculate_shares(asset_a_reserve, amount_a, share_issuance)
+			.ok_or(Error::<T>::Overflow)?;
+
+		ensure!(!shares_added.is_zero(), Error::<T>::InvalidMintedLiquidity);
+
+		// Make sure that account share liquidity is at least MinPoolLiquidity
+		ensure!(
+			account_shares
+				.checked_add(shares_added)
+				.ok_or(Error::<T>::InvalidMintedLiquidity)?
+				>= T::MinPoolLiquidity::get(),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let liquidity_amount = share_issuance
+			.checked_add(shares_added)
+			.ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+		T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
+		T::Currency::transfer(asset_b, &who, &pair_account, amount_b)?;
+
+		Self::increase_reserve(&pair_account, asset_a, amount_a)?;
+		Self::increase_reserve(&pair_account, asset_b, amount_b)?;
+
+		T::Currency::deposit(share_token, &who, shares_added)?;
+
+		<TotalLiquidity<T>>::insert(&pair_account, liquidity_amount);
+
+		Self::deposit_event(Event::LiquidityAdded {
+			who,
+			asset_a,
+			asset_b,
+			amount_a,
+			amount_b,
+		});
+
+		Ok(shares_added)
+	}
+
+	/// Remove `liquidity_amount` shares of `who` from the pool of `asset_a` and `asset_b`.
+	///
+	/// Fails if the amount of either asset received is below its minimum limit.
+	/// Returns the received amounts of `asset_a` and `asset_b`.
+	fn do_remove_liquidity(
+		who: T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		liquidity_amount: Balance,
+		min_amount_a: Balance,
+		min_amount_b: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		ensure!(!liquidity_amount.is_zero(), Error::<T>::ZeroLiquidity);
+
+		ensure!(Self::exists(asset_pair), Error::<T>::TokenPoolNotFound);
+
+		let pair_account = Self::get_pair_id(asset_pair);
+
+		Self::ensure_pool_not_locked(&pair_account)?;
+
+		let share_token = Self::share_token(&pair_account);
+
+		let total_shares = Self::total_liquidity(&pair_account);
+
+		let account_shares = T::Currency::free_balance(share_token, &who);
+
+		ensure!(total_shares >= liquidity_amount, Error::<T>::InsufficientLiquidity);
+
+		ensure!(account_shares >= liquidity_amount, Error::<T>::InsufficientAssetBalance);
+
+		// Account's liquidity left should be either 0 or at least MinPoolLiquidity
+		ensure!(
+			(account_shares.saturating_sub(liquidity_amount)) >= T::MinPoolLiquidity::get()
+				|| (account_shares == liquidity_amount),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let asset_a_reserve = Self::pool_reserve(&pair_account, asset_a);
+		let asset_b_reserve = Self::pool_reserve(&pair_account, asset_b);
+
+		let liquidity_out = hydra_dx_math::xyk::calculate_liquidity_out(
+			asset_a_reserve,
+			asset_b_reserve,
+			liquidity_amount,
+			total_shares,
+		)
+		.map_err(|_| Error::<T>::RemoveAssetAmountInvalid)?;
+
+		let (remove_amount_a, remove_amount_b) = liquidity_out;
+
+		ensure!(
+			remove_amount_a >= min_amount_a && remove_amount_b >= min_amount_b,
+			Error::<T>::RemoveLiquidityLimitNotReached
+		);
+
+		ensure!(
+			asset_a_reserve >= remove_amount_a,
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+		ensure!(
+			asset_b_reserve >= remove_amount_b,
+			Error::<T>::InsufficientPoolAssetBalance
+		);
+
+		let liquidity_left = total_shares
+			.checked_sub(liquidity_amount)
+			.ok_or(Error::<T>::InvalidLiquidityAmount)?;
+
+		// Destroying the pool is not limited, all shares belong to the caller.
+		if !liquidity_left.is_zero() {
+			Self::record_block_start_state(&pair_account, asset_pair);
+			Self::ensure_liquidity_removal_within_limit(&pair_account, liquidity_left)?;
+		}
+
+		T::Currency::transfer(asset_a, &pair_account, &who, remove_amount_a)?;
+		T::Currency::transfer(asset_b, &pair_account, &who, remove_amount_b)?;
+
+		Self::decrease_reserve(&pair_account, asset_a, remove_amount_a)?;
+		Self::decrease_reserve(&pair_account, asset_b, remove_amount_b)?;
+
+		T::Currency::withdraw(share_token, &who, liquidity_amount)?;
+
+		<TotalLiquidity<T>>::insert(&pair_account, liquidity_left);
+
+		Self::deposit_event(Event::LiquidityRemoved {
+			who: who.clone(),
+			asset_a,
+			asset_b,
+			shares: liquidity_amount,
+		});
+
+		if liquidity_left == 0 {
+			<ShareToken<T>>::remove(&pair_account);
+			<PoolAssets<T>>::remove(&pair_account);
+			<TotalLiquidity<T>>::remove(&pair_account);
+			<PoolFees<T>>::remove(&pair_account);
+			<PoolReserves<T>>::remove(&pair_account, asset_a);
+			<PoolReserves<T>>::remove(&pair_account, asset_b);
+			<PoolVolumes<T>>::remove(&pair_account, asset_a);
+			<PoolVolumes<T>>::remove(&pair_account, asset_b);
+			<PeriodVolumes<T>>::remove(&pair_account, asset_a);
+			<PeriodVolumes<T>>::remove(&pair_account, asset_b);
+			<PoolStates<T>>::remove(&pair_account);
+
+			// Ignore the failure, this cant stop liquidity removal
+			let r = T::NonDustableWhitelistHandler::remove_account(&pair_account);
+
+			if r.is_err() {
+				log::trace!(
+				target: "xyk::remova_liquidity", "XYK: Failed to remove account {:?} from dust-removal whitelist. Reason {:?}",
+					pair_account,
+				r
+				);
+			}
+
+			Self::deposit_event(Event::PoolDestroyed {
+				who,
+				asset_a,
+				asset_b,
+				share_token,
+				pool: pair_account,
+			});
+		}
+
+		Ok((remove_amount_a, remove_amount_b))
+	}
+
+	/// Create new pool for given asset pair with initial liquidity provided by `who`.
+	///
+	/// If `fee` is `None`, the pool uses `GetExchangeFee`.
+	fn do_create_pool(
+		who: T::AccountId,
+		asset_a: AssetId,
+		amount_a: Balance,
+		asset_b: AssetId,
+		amount_b: Balance,
+		fee: Option<(u32, u32)>,
+	) -> DispatchResult {
+		ensure!(
+			T::CanCreatePool::can_create(asset_a, asset_b),
+			Error::<T>::CannotCreatePool
+		);
+
+		ensure!(
+			amount_a >= T::MinPoolLiquidity::get() && amount_b >= T::MinPoolLiquidity::get(),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		ensure!(asset_a != asset_b, Error::<T>::CannotCreatePoolWithSameAssets);
+
+		if let Some(fee) = fee {
+			ensure!(Self::is_fee_allowed(fee), Error::<T>::InvalidFee);
+		}
+
+		let asset_pair = AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		};
+
+		ensure!(!Self::exists(asset_pair), Error::<T>::TokenPoolAlreadyExists);
+
+		let shares_added = if asset_a < asset_b { amount_a } else { amount_b };
+
+		ensure!(
+			T::Currency::free_balance(asset_a, &who) >= amount_a,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		ensure!(
+			T::Currency::free_balance(asset_b, &who) >= amount_b,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		let pair_account = Self::get_pair_id(asset_pair);
+
+		let token_name = Self::share_token_name(asset_pair);
+
+		let share_token = T::AssetRegistry::get_or_create_shared_asset(
+			token_name,
+			vec![asset_a, asset_b],
+			T::MinPoolLiquidity::get(),
+		)?;
+
+		Self::update_share_token_metadata(asset_pair, share_token)?;
+
+		let _ = T::AMMHandler::on_create_pool(asset_pair.asset_in, asset_pair.asset_out);
+
+		T::NonDustableWhitelistHandler::add_account(&pair_account)?;
+
+		<ShareToken<T>>::insert(&pair_account, &share_token);
+		<PoolAssets<T>>::insert(&pair_account, (asset_a, asset_b));
+
+		if let Some(fee) = fee {
+			<PoolFees<T>>::insert(&pair_account, fee);
+		}
+
+		Self::deposit_event(Event::PoolCreated {
+			who: who.clone(),
+			asset_a,
+			asset_b,
+			initial_shares_amount: shares_added,
+			share_token,
+			pool: pair_account.clone(),
+		});
+
+		if let Some(fee) = fee {
+			Self::deposit_event(Event::PoolFeeSet {
+				pool: pair_account.clone(),
+				fee,
+			});
+		}
+
+		T::Currency::transfer(asset_a, &who, &pair_account, amount_a)?;
+		T::Currency::transfer(asset_b, &who, &pair_account, amount_b)?;
+
+		<PoolReserves<T>>::insert(&pair_account, asset_a, amount_a);
+		<PoolReserves<T>>::insert(&pair_account, asset_b, amount_b);
+
+		T::Currency::deposit(share_token, &who, shares_added)?;
+
+		<TotalLiquidity<T>>::insert(&pair_account, shares_added);
+
+		Ok(())
+	}
+
+	/// Return name of the share token of the asset pair, e.g. `BSX-KSM LP`.
+	///
+	/// The name is made of registry symbols of both assets. Name derived from asset ids is used instead
+	/// if any of the assets has no symbol, the name is longer than the registry string limit
+	/// or the name is already registered for another asset.
+	pub fn share_token_name(asset_pair: AssetPair) -> Vec<u8> {
+		let (asset_a, asset_b) = asset_pair.ordered_pair();
+
+		let name = match Self::share_token_symbol(asset_pair) {
+			Some((symbol, _)) => symbol,
+			None => return asset_pair.name(),
+		};
+
+		match T::AssetRegistry::retrieve_asset(&name) {
+			Ok(asset_id) => {
+				let registered_for_pair = T::AssetMetadata::share_token_assets(asset_id)
+					.map(|(a, b)| AssetPair::new(a, b).ordered_pair() == (asset_a, asset_b))
+					.unwrap_or(false);
+				if registered_for_pair {
+					name
+				} else {
+					asset_pair.name()
+				}
+			}
+			Err(_) => name,
+		}
+	}
+
+	/// Set name, symbol and decimals of the share token if both assets of the pair have registry metadata
+	/// and the symbol fits in the registry string limit. Metadata of the share token is left unchanged otherwise.
+	///
+	/// Decimals are taken from the asset whose amount determines the initial shares.
+	pub fn update_share_token_metadata(asset_pair: AssetPair, share_token: AssetId) -> DispatchResult {
+		if let Some((symbol, decimals)) = Self::share_token_symbol(asset_pair) {
+			T::AssetMetadata::set_share_token_metadata(
+				share_token,
+				Self::share_token_name(asset_pair),
+				symbol,
+				decimals,
+			)?;
+		}
+
+		Ok(())
+	}
+
+	/// Return symbol of the share token, e.g. `BSX-KSM LP`, and decimals of the first asset of the pair.
+	///
+	/// `None` if any of the assets has no registry metadata or the symbol is longer than the registry string limit.
+	fn share_token_symbol(asset_pair: AssetPair) -> Option<(Vec<u8>, u8)> {
+		let (asset_a, asset_b) = asset_pair.ordered_pair();
+
+		let (symbol_a, decimals) = T::AssetMetadata::metadata(asset_a)?;
+		let (symbol_b, _) = T::AssetMetadata::metadata(asset_b)?;
+
+		let mut symbol = Vec::with_capacity(symbol_a.len() + symbol_b.len() + 4);
+		symbol.extend_from_slice(&symbol_a);
+		symbol.push(b'-');
+		symbol.extend_from_slice(&symbol_b);
+		symbol.extend_from_slice(b" LP");
+
+		if symbol.len() > T::AssetMetadata::string_limit() as usize {
+			return None;
+		}
+
+		Some((symbol, decimals))
+	}
+
+	/// Return accounts of all existing pools.
+	pub fn pool_accounts() -> Vec<T::AccountId> {
+		<PoolAssets<T>>::iter_keys().collect()
+	}
+
+	/// Calculate discounted trade fee
+	fn calculate_discounted_fee(amount: Balance) -> Result<Balance, DispatchError> {
+		Ok(
+			hydra_dx_math::fee::calculate_pool_trade_fee(amount, T::DiscountedFee::get())
+				.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?,
+		)
+	}
+
+	/// Calculate trade fee of given pool
+	fn calculate_fee(pool_account: &T::AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let fee = Self::pool_fee(pool_account);
+		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(amount, (fee.0, fee.1))
+			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
+	}
+
+	/// Calculate protocol share of trade fee
+	fn calculate_protocol_fee(fee: Balance) -> Result<Balance, DispatchError> {
+		Ok(hydra_dx_math::fee::calculate_pool_trade_fee(fee, T::ProtocolFee::get())
+			.ok_or::<Error<T>>(Error::<T>::FeeAmountInvalid)?)
 	}
 
-	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
-		let maybe_assets = <PoolAssets<T>>::get(pool_account_id);
-		maybe_assets.map(|assets| vec![assets.0, assets.1])
+	/// Return trading fee rate of given pool. Falls back to `GetExchangeFee` if no specific fee is set.
+	pub fn pool_fee(pool_account: &T::AccountId) -> (u32, u32) {
+		Self::pool_fees(pool_account).unwrap_or_else(T::GetExchangeFee::get)
 	}
 
-	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
-		let pair_account = Self::get_pair_id(AssetPair {
-			asset_out: asset_a,
-			asset_in: asset_b,
-		});
+	/// Check whether `fee` is a valid fee rate within `MinPoolFee` and `MaxPoolFee` bounds.
+	fn is_fee_allowed(fee: (u32, u32)) -> bool {
+		let (min_fee, max_fee) = (T::MinPoolFee::get(), T::MaxPoolFee::get());
 
-		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+		// Compare fractions by cross multiplication, u32 products always fit into u64.
+		let fee_ge = |a: (u32, u32), b: (u32, u32)| (a.0 as u64) * (b.1 as u64) >= (b.0 as u64) * (a.1 as u64);
 
-		hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
-			.unwrap_or_else(|_| Balance::zero())
+		fee.1 != 0 && fee.0 <= fee.1 && fee_ge(fee, min_fee) && fee_ge(max_fee, fee)
 	}
 
-	/// Validate a sell. Perform all necessary checks and calculations.
-	/// No storage changes are performed yet.
+	pub fn pair_account_from_assets(asset_a: AssetId, asset_b: AssetId) -> T::AccountId {
+		T::AssetPairAccountId::from_assets(asset_a, asset_b, "xyk")
+	}
+
+	/// Calculate a sell of `amount` of `asset_in` for `asset_out`.
 	///
-	/// Return `AMMTransfer` with all info needed to execute the transaction.
-	fn validate_sell(
-		who: &T::AccountId,
-		assets: AssetPair,
-		amount: Balance,
-		min_bought: Balance,
-		discount: bool,
-	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, sp_runtime::DispatchError> {
+	/// Performs all checks and calculations of `validate_sell` which do not depend on the trader.
+	/// Returned amount is the amount of `asset_out` received with the fee already deducted.
+	pub fn quote_sell(assets: AssetPair, amount: Balance, discount: bool) -> Result<TradeQuote, DispatchError> {
 		ensure!(
 			amount >= T::MinTradingLimit::get(),
 			Error::<T>::InsufficientTradingAmount
@@ -700,11 +1949,6 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
 
-		ensure!(
-			T::Currency::free_balance(assets.asset_in, who) >= amount,
-			Error::<T>::InsufficientAssetBalance
-		);
-
 		// If discount, pool for Sell asset and native asset must exist
 		if discount {
 			ensure!(
@@ -718,8 +1962,11 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		let pair_account = Self::get_pair_id(assets);
 
-		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
-		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
+		Self::ensure_trading_allowed(&pair_account)?;
+		Self::ensure_pool_not_locked(&pair_account)?;
+
+		let asset_in_reserve = Self::pool_reserve(&pair_account, assets.asset_in);
+		let asset_out_reserve = Self::pool_reserve(&pair_account, assets.asset_out);
 
 		ensure!(
 			amount
@@ -743,7 +1990,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		let transfer_fee = if discount {
 			Self::calculate_discounted_fee(amount_out)?
 		} else {
-			Self::calculate_fee(amount_out)?
+			Self::calculate_fee(&pair_account, amount_out)?
 		};
 
 		let amount_out_without_fee = amount_out
@@ -752,44 +1999,188 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 
 		ensure!(asset_out_reserve > amount_out, Error::<T>::InsufficientAssetBalance);
 
+		let discount_amount = if discount {
+			Self::calculate_native_fee(assets.asset_in, transfer_fee)?
+		} else {
+			Balance::zero()
+		};
+
+		Ok(TradeQuote {
+			amount: amount_out_without_fee,
+			fee: transfer_fee,
+			discount_amount,
+		})
+	}
+
+	/// Calculate a buy of `amount` of `asset_out` for `asset_in`.
+	///
+	/// Performs all checks and calculations of `validate_buy` which do not depend on the trader.
+	/// Returned amount is the amount of `asset_in` to be paid, the fee is not included.
+	pub fn quote_buy(assets: AssetPair, amount: Balance, discount: bool) -> Result<TradeQuote, DispatchError> {
 		ensure!(
-			min_bought <= amount_out_without_fee,
-			Error::<T>::AssetAmountNotReachedLimit
+			amount >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
 		);
 
-		let discount_fee = if discount {
-			let native_asset = T::NativeAssetId::get();
+		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
 
-			let native_pair_account = Self::get_pair_id(AssetPair {
-				asset_in: assets.asset_in,
-				asset_out: native_asset,
-			});
+		let pair_account = Self::get_pair_id(assets);
+
+		Self::ensure_trading_allowed(&pair_account)?;
+		Self::ensure_pool_not_locked(&pair_account)?;
 
-			let native_reserve = T::Currency::free_balance(native_asset, &native_pair_account);
-			let asset_reserve = T::Currency::free_balance(assets.asset_in, &native_pair_account);
+		let asset_out_reserve = Self::pool_reserve(&pair_account, assets.asset_out);
+		let asset_in_reserve = Self::pool_reserve(&pair_account, assets.asset_in);
+
+		ensure!(asset_out_reserve > amount, Error::<T>::InsufficientPoolAssetBalance);
 
-			let native_fee_spot_price =
-				hydra_dx_math::xyk::calculate_spot_price(asset_reserve, native_reserve, transfer_fee)
-					.map_err(|_| Error::<T>::CannotApplyDiscount)?;
+		ensure!(
+			amount
+				<= asset_out_reserve
+					.checked_div(T::MaxOutRatio::get())
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxOutRatioExceeded
+		);
 
+		// If discount, pool for Sell asset and native asset must exist
+		if discount {
 			ensure!(
-				T::Currency::free_balance(native_asset, who) >= native_fee_spot_price,
-				Error::<T>::InsufficientNativeCurrencyBalance
+				Self::exists(AssetPair {
+					asset_in: assets.asset_out,
+					asset_out: T::NativeAssetId::get()
+				}),
+				Error::<T>::CannotApplyDiscount
 			);
+		}
 
-			native_fee_spot_price
+		let buy_price = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount)
+			.map_err(|_| Error::<T>::BuyAssetAmountInvalid)?;
+
+		ensure!(
+			buy_price
+				<= asset_in_reserve
+					.checked_div(T::MaxInRatio::get())
+					.ok_or(Error::<T>::Overflow)?,
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		let transfer_fee = if discount {
+			Self::calculate_discounted_fee(buy_price)?
+		} else {
+			Self::calculate_fee(&pair_account, buy_price)?
+		};
+
+		let discount_amount = if discount {
+			Self::calculate_native_fee(assets.asset_out, transfer_fee)?
 		} else {
 			Balance::zero()
 		};
 
+		Ok(TradeQuote {
+			amount: buy_price,
+			fee: transfer_fee,
+			discount_amount,
+		})
+	}
+
+	/// Convert `fee` in `asset` to native asset using spot price of the `asset`/native pool.
+	fn calculate_native_fee(asset: AssetId, fee: Balance) -> Result<Balance, DispatchError> {
+		let native_asset = T::NativeAssetId::get();
+
+		let native_pair_account = Self::get_pair_id(AssetPair {
+			asset_in: asset,
+			asset_out: native_asset,
+		});
+
+		let native_reserve = Self::pool_reserve(&native_pair_account, native_asset);
+		let asset_reserve = Self::pool_reserve(&native_pair_account, asset);
+
+		hydra_dx_math::xyk::calculate_spot_price(asset_reserve, native_reserve, fee)
+			.map_err(|_| Error::<T>::CannotApplyDiscount.into())
+	}
+}
+
+// Implementation of AMM API which makes possible to plug the AMM pool into the exchange pallet.
+impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
+	fn exists(assets: AssetPair) -> bool {
+		<ShareToken<T>>::contains_key(&Self::get_pair_id(assets))
+	}
+
+	fn get_pair_id(assets: AssetPair) -> T::AccountId {
+		Self::pair_account_from_assets(assets.asset_in, assets.asset_out)
+	}
+
+	fn get_share_token(assets: AssetPair) -> AssetId {
+		let pair_account = Self::get_pair_id(assets);
+		Self::share_token(&pair_account)
+	}
+
+	fn get_pool_assets(pool_account_id: &T::AccountId) -> Option<Vec<AssetId>> {
+		let maybe_assets = <PoolAssets<T>>::get(pool_account_id);
+		maybe_assets.map(|assets| vec![assets.0, assets.1])
+	}
+
+	fn get_spot_price_unchecked(asset_a: AssetId, asset_b: AssetId, amount: Balance) -> Balance {
+		let pair_account = Self::get_pair_id(AssetPair {
+			asset_out: asset_a,
+			asset_in: asset_b,
+		});
+
+		if Self::is_pool_locked(&pair_account) {
+			return Balance::zero();
+		}
+
+		let asset_a_reserve = Self::pool_reserve(&pair_account, asset_a);
+		let asset_b_reserve = Self::pool_reserve(&pair_account, asset_b);
+
+		hydra_dx_math::xyk::calculate_spot_price(asset_a_reserve, asset_b_reserve, amount)
+			.unwrap_or_else(|_| Balance::zero())
+	}
+
+	/// Validate a sell. Perform all necessary checks and calculations.
+	/// No storage changes are performed yet.
+	///
+	/// Return `AMMTransfer` with all info needed to execute the transaction.
+	fn validate_sell(
+		who: &T::AccountId,
+		assets: AssetPair,
+		amount: Balance,
+		min_bought: Balance,
+		discount: bool,
+	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, sp_runtime::DispatchError> {
+		ensure!(
+			amount >= T::MinTradingLimit::get(),
+			Error::<T>::InsufficientTradingAmount
+		);
+
+		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
+
+		Self::ensure_pool_not_locked(&Self::get_pair_id(assets))?;
+
+		ensure!(
+			T::Currency::free_balance(assets.asset_in, who) >= amount,
+			Error::<T>::InsufficientAssetBalance
+		);
+
+		let quote = Self::quote_sell(assets, amount, discount)?;
+
+		ensure!(min_bought <= quote.amount, Error::<T>::AssetAmountNotReachedLimit);
+
+		if discount {
+			ensure!(
+				T::Currency::free_balance(T::NativeAssetId::get(), who) >= quote.discount_amount,
+				Error::<T>::InsufficientNativeCurrencyBalance
+			);
+		}
+
 		let transfer = AMMTransfer {
 			origin: who.clone(),
 			assets,
 			amount,
-			amount_out: amount_out_without_fee,
+			amount_out: quote.amount,
 			discount,
-			discount_amount: discount_fee,
-			fee: (assets.asset_out, transfer_fee),
+			discount_amount: quote.discount_amount,
+			fee: (assets.asset_out, quote.fee),
 		};
 
 		Ok(transfer)
@@ -802,14 +2193,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 	fn execute_sell(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
 		let pair_account = Self::get_pair_id(transfer.assets);
 
-		let total_liquidity = Self::total_liquidity(&pair_account);
-		T::AMMHandler::on_trade(
-			transfer.assets.asset_in,
-			transfer.assets.asset_out,
-			transfer.amount,
-			transfer.amount_out,
-			total_liquidity,
-		);
+		Self::record_block_start_state(&pair_account, transfer.assets);
 
 		if transfer.discount && transfer.discount_amount > 0u128 {
 			let native_asset = T::NativeAssetId::get();
@@ -829,6 +2213,45 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			transfer.amount_out,
 		)?;
 
+		// Fee is paid in asset out and stays in the pool, except for the protocol share.
+		let protocol_fee = Self::calculate_protocol_fee(transfer.fee.1)?;
+		if !protocol_fee.is_zero() {
+			T::Currency::transfer(
+				transfer.fee.0,
+				&pair_account,
+				&T::ProtocolFeeReceiver::get(),
+				protocol_fee,
+			)?;
+		}
+
+		Self::increase_reserve(&pair_account, transfer.assets.asset_in, transfer.amount)?;
+		Self::decrease_reserve(
+			&pair_account,
+			transfer.assets.asset_out,
+			transfer
+				.amount_out
+				.checked_add(protocol_fee)
+				.ok_or(Error::<T>::Overflow)?,
+		)?;
+
+		Self::ensure_price_change_within_limit(&pair_account, transfer.assets)?;
+
+		// reported after the reserves are updated, so the oracle records the price of the pool after the trade
+		T::AMMHandler::on_trade(
+			transfer.assets.asset_in,
+			transfer.assets.asset_out,
+			transfer.amount,
+			transfer.amount_out,
+			Self::total_liquidity(&pair_account),
+		);
+
+		Self::record_trade_volume(
+			&pair_account,
+			(transfer.assets.asset_in, transfer.amount),
+			(transfer.assets.asset_out, transfer.amount_out),
+			(transfer.fee.0, transfer.fee.1, protocol_fee),
+		);
+
 		Self::deposit_event(Event::<T>::SellExecuted {
 			who: transfer.origin.clone(),
 			asset_in: transfer.assets.asset_in,
@@ -837,6 +2260,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			sale_price: transfer.amount_out,
 			fee_asset: transfer.fee.0,
 			fee_amount: transfer.fee.1,
+			protocol_fee_amount: protocol_fee,
 			pool: pair_account,
 		});
 
@@ -854,58 +2278,11 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		max_limit: Balance,
 		discount: bool,
 	) -> Result<AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>, DispatchError> {
-		ensure!(
-			amount >= T::MinTradingLimit::get(),
-			Error::<T>::InsufficientTradingAmount
-		);
-
-		ensure!(Self::exists(assets), Error::<T>::TokenPoolNotFound);
-
-		let pair_account = Self::get_pair_id(assets);
-
-		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
-		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
-
-		ensure!(asset_out_reserve > amount, Error::<T>::InsufficientPoolAssetBalance);
-
-		ensure!(
-			amount
-				<= asset_out_reserve
-					.checked_div(T::MaxOutRatio::get())
-					.ok_or(Error::<T>::Overflow)?,
-			Error::<T>::MaxOutRatioExceeded
-		);
-
-		// If discount, pool for Sell asset and native asset must exist
-		if discount {
-			ensure!(
-				Self::exists(AssetPair {
-					asset_in: assets.asset_out,
-					asset_out: T::NativeAssetId::get()
-				}),
-				Error::<T>::CannotApplyDiscount
-			);
-		}
-
-		let buy_price = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount)
-			.map_err(|_| Error::<T>::BuyAssetAmountInvalid)?;
-
-		ensure!(
-			buy_price
-				<= asset_in_reserve
-					.checked_div(T::MaxInRatio::get())
-					.ok_or(Error::<T>::Overflow)?,
-			Error::<T>::MaxInRatioExceeded
-		);
-
-		let transfer_fee = if discount {
-			Self::calculate_discounted_fee(buy_price)?
-		} else {
-			Self::calculate_fee(buy_price)?
-		};
+		let quote = Self::quote_buy(assets, amount, discount)?;
 
-		let buy_price_with_fee = buy_price
-			.checked_add(transfer_fee)
+		let buy_price_with_fee = quote
+			.amount
+			.checked_add(quote.fee)
 			.ok_or(Error::<T>::BuyAssetAmountInvalid)?;
 
 		ensure!(max_limit >= buy_price_with_fee, Error::<T>::AssetAmountExceededLimit);
@@ -915,38 +2292,21 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			Error::<T>::InsufficientAssetBalance
 		);
 
-		let discount_fee = if discount {
-			let native_asset = T::NativeAssetId::get();
-
-			let native_pair_account = Self::get_pair_id(AssetPair {
-				asset_in: assets.asset_out,
-				asset_out: native_asset,
-			});
-
-			let native_reserve = T::Currency::free_balance(native_asset, &native_pair_account);
-			let asset_reserve = T::Currency::free_balance(assets.asset_out, &native_pair_account);
-
-			let native_fee_spot_price =
-				hydra_dx_math::xyk::calculate_spot_price(asset_reserve, native_reserve, transfer_fee)
-					.map_err(|_| Error::<T>::CannotApplyDiscount)?;
-
+		if discount {
 			ensure!(
-				T::Currency::free_balance(native_asset, who) >= native_fee_spot_price,
+				T::Currency::free_balance(T::NativeAssetId::get(), who) >= quote.discount_amount,
 				Error::<T>::InsufficientNativeCurrencyBalance
 			);
-			native_fee_spot_price
-		} else {
-			Balance::zero()
-		};
+		}
 
 		let transfer = AMMTransfer {
 			origin: who.clone(),
 			assets,
 			amount,
-			amount_out: buy_price,
+			amount_out: quote.amount,
 			discount,
-			discount_amount: discount_fee,
-			fee: (assets.asset_in, transfer_fee),
+			discount_amount: quote.discount_amount,
+			fee: (assets.asset_in, quote.fee),
 		};
 
 		Ok(transfer)
@@ -959,14 +2319,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 	fn execute_buy(transfer: &AMMTransfer<T::AccountId, AssetId, AssetPair, Balance>) -> DispatchResult {
 		let pair_account = Self::get_pair_id(transfer.assets);
 
-		let total_liquidity = Self::total_liquidity(&pair_account);
-		T::AMMHandler::on_trade(
-			transfer.assets.asset_in,
-			transfer.assets.asset_out,
-			transfer.amount,
-			transfer.amount_out,
-			total_liquidity,
-		);
+		Self::record_block_start_state(&pair_account, transfer.assets);
 
 		if transfer.discount && transfer.discount_amount > 0 {
 			let native_asset = T::NativeAssetId::get();
@@ -979,12 +2332,46 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			&transfer.origin,
 			transfer.amount,
 		)?;
-		T::Currency::transfer(
+		// Fee is paid in asset in and goes to the pool, except for the protocol share.
+		let protocol_fee = Self::calculate_protocol_fee(transfer.fee.1)?;
+		let pool_fee = transfer
+			.fee
+			.1
+			.checked_sub(protocol_fee)
+			.ok_or(Error::<T>::FeeAmountInvalid)?;
+		let amount_in = transfer.amount_out.checked_add(pool_fee).ok_or(Error::<T>::Overflow)?;
+
+		T::Currency::transfer(transfer.assets.asset_in, &transfer.origin, &pair_account, amount_in)?;
+
+		if !protocol_fee.is_zero() {
+			T::Currency::transfer(
+				transfer.fee.0,
+				&transfer.origin,
+				&T::ProtocolFeeReceiver::get(),
+				protocol_fee,
+			)?;
+		}
+
+		Self::decrease_reserve(&pair_account, transfer.assets.asset_out, transfer.amount)?;
+		Self::increase_reserve(&pair_account, transfer.assets.asset_in, amount_in)?;
+
+		Self::ensure_price_change_within_limit(&pair_account, transfer.assets)?;
+
+		// `amount` is the bought amount of `asset_out` and `amount_out` is the amount of `asset_in` paid for it
+		T::AMMHandler::on_trade(
 			transfer.assets.asset_in,
-			&transfer.origin,
+			transfer.assets.asset_out,
+			transfer.amount_out,
+			transfer.amount,
+			Self::total_liquidity(&pair_account),
+		);
+
+		Self::record_trade_volume(
 			&pair_account,
-			transfer.amount_out + transfer.fee.1,
-		)?;
+			(transfer.assets.asset_in, transfer.amount_out),
+			(transfer.assets.asset_out, transfer.amount),
+			(transfer.fee.0, transfer.fee.1, protocol_fee),
+		);
 
 		Self::deposit_event(Event::<T>::BuyExecuted {
 			who: transfer.origin.clone(),
@@ -994,6 +2381,7 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 			buy_price: transfer.amount_out,
 			fee_asset: transfer.fee.0,
 			fee_amount: transfer.fee.1,
+			protocol_fee_amount: protocol_fee,
 			pool: pair_account,
 		});
 
@@ -1016,8 +2404,8 @@ impl<T: Config> AMM<T::AccountId, AssetId, AssetPair, Balance> for Pallet<T> {
 		T::MaxOutRatio::get()
 	}
 
-	fn get_fee(_pool_account_id: &T::AccountId) -> (u32, u32) {
-		T::GetExchangeFee::get()
+	fn get_fee(pool_account_id: &T::AccountId) -> (u32, u32) {
+		Self::pool_fee(pool_account_id)
 	}
 }
 
@@ -1046,8 +2434,8 @@ impl<T: Config> AMMPosition<AssetId, Balance> for Pallet<T> {
 
 		let total_shares = Self::total_liquidity(&pair_account);
 
-		let asset_a_reserve = T::Currency::free_balance(asset_a, &pair_account);
-		let asset_b_reserve = T::Currency::free_balance(asset_b, &pair_account);
+		let asset_a_reserve = Self::pool_reserve(&pair_account, asset_a);
+		let asset_b_reserve = Self::pool_reserve(&pair_account, asset_b);
 
 		hydra_dx_math::xyk::calculate_liquidity_out(asset_a_reserve, asset_b_reserve, shares_amount, total_shares)
 			.map_err(|_| Error::<T>::RemoveAssetAmountInvalid.into())
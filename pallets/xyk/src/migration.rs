@@ -0,0 +1,95 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Config, Pallet, PoolAssets, PoolReserves};
+use frame_support::{
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+use orml_traits::MultiCurrency;
+use primitives::asset::AssetPair;
+
+/// Initialize stored reserves of all pools from balances of the pool accounts.
+///
+/// Runs only if the on-chain storage version is lower than 1.
+pub fn init_pool_reserves<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 1 {
+		log::info!(
+			target: "runtime::xyk",
+			"Pool reserves are already initialized, skipping migration"
+		);
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut pools: u64 = 0;
+
+	for (pool, (asset_a, asset_b)) in <PoolAssets<T>>::iter() {
+		<PoolReserves<T>>::insert(&pool, asset_a, T::Currency::free_balance(asset_a, &pool));
+		<PoolReserves<T>>::insert(&pool, asset_b, T::Currency::free_balance(asset_b, &pool));
+		pools = pools.saturating_add(1);
+	}
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+
+	log::info!(
+		target: "runtime::xyk",
+		"Initialized reserves of {} pools",
+		pools
+	);
+
+	T::DbWeight::get().reads_writes(
+		pools.saturating_mul(3).saturating_add(1),
+		pools.saturating_mul(2).saturating_add(1),
+	)
+}
+
+/// Give share tokens of all pools name, symbol and decimals derived from registry metadata of the pool assets.
+///
+/// Runs only if the on-chain storage version is 1, i.e. after `init_pool_reserves`.
+pub fn update_share_token_metadata<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version != 1 {
+		log::info!(
+			target: "runtime::xyk",
+			"Storage version is {:?}, skipping share token metadata migration",
+			on_chain_version
+		);
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut reads: u64 = 1;
+	let mut writes: u64 = 1;
+
+	for (pool, (asset_a, asset_b)) in <PoolAssets<T>>::iter() {
+		// pool assets, share token and registry metadata of both assets
+		reads = reads.saturating_add(6);
+
+		// pools whose assets have no metadata are left with the share token name derived from asset ids
+		if Pallet::<T>::update_share_token_metadata(AssetPair::new(asset_a, asset_b), Pallet::<T>::share_token(&pool))
+			.is_ok()
+		{
+			writes = writes.saturating_add(3);
+		}
+	}
+
+	StorageVersion::new(2).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(reads, writes)
+}
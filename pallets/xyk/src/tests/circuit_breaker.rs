@@ -0,0 +1,120 @@
+pub use super::mock::*;
+use crate::{BlockStartStates, Error};
+use frame_support::traits::OnFinalize;
+use frame_support::{assert_noop, assert_ok};
+
+fn with_pool(builder: ExtBuilder) -> sp_io::TestExternalities {
+	let mut ext = builder.build();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+	});
+	ext
+}
+
+fn with_price_change_limit() -> sp_io::TestExternalities {
+	with_pool(ExtBuilder::default().with_max_price_change_per_block((10, 100)))
+}
+
+fn with_liquidity_removal_limit() -> sp_io::TestExternalities {
+	with_pool(ExtBuilder::default().with_max_liquidity_removal_per_block((10, 100)))
+}
+
+#[test]
+fn trades_should_not_work_when_price_change_within_block_exceeds_limit() {
+	with_price_change_limit().execute_with(|| {
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 4 * ONE, 0, false));
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, 2 * ONE, 0, false),
+			Error::<Test>::MaxPriceChangePerBlockExceeded
+		);
+		assert_noop!(
+			XYK::buy(Origin::signed(BOB), DOT, HDX, 4 * ONE, u128::MAX, false),
+			Error::<Test>::MaxPriceChangePerBlockExceeded
+		);
+
+		// trade in the opposite direction moves the price back
+		assert_ok!(XYK::sell(Origin::signed(BOB), DOT, HDX, 4 * ONE, 0, false));
+	});
+}
+
+#[test]
+fn price_change_limit_should_be_reset_in_next_block() {
+	with_price_change_limit().execute_with(|| {
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 4 * ONE, 0, false));
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, 2 * ONE, 0, false),
+			Error::<Test>::MaxPriceChangePerBlockExceeded
+		);
+
+		XYK::on_finalize(1);
+		assert_eq!(BlockStartStates::<Test>::iter().count(), 0);
+
+		System::set_block_number(2);
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 2 * ONE, 0, false));
+	});
+}
+
+#[test]
+fn flash_swap_should_not_work_when_price_change_within_block_exceeds_limit() {
+	with_price_change_limit().execute_with(|| {
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				15 * ONE,
+				u128::MAX,
+				Box::new(Call::System(frame_system::Call::remark { remark: vec![] }))
+			),
+			Error::<Test>::MaxPriceChangePerBlockExceeded
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_work_when_removed_shares_within_block_exceed_limit() {
+	with_liquidity_removal_limit().execute_with(|| {
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 10 * ONE));
+
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, ONE),
+			Error::<Test>::MaxLiquidityRemovalPerBlockExceeded
+		);
+
+		XYK::on_finalize(1);
+		System::set_block_number(2);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, ONE));
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_be_limited_when_pool_is_destroyed() {
+	with_liquidity_removal_limit().execute_with(|| {
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100 * ONE));
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_be_limited_by_price_change_limit() {
+	with_price_change_limit().execute_with(|| {
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 10 * ONE));
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 10 * ONE));
+	});
+}
+
+#[test]
+fn trades_should_not_be_limited_by_liquidity_removal_limit() {
+	with_liquidity_removal_limit().execute_with(|| {
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 4 * ONE, 0, false));
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, 20 * ONE, 0, false));
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 10 * ONE));
+		assert_noop!(
+			XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, ONE),
+			Error::<Test>::MaxLiquidityRemovalPerBlockExceeded
+		);
+	});
+}
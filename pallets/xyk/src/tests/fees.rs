@@ -1,16 +1,18 @@
 pub use super::mock::*;
 use crate::{Error, Event};
 use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::router::{PoolType, TradeExecution};
 use hydradx_traits::AMM as AmmPool;
 use orml_traits::MultiCurrency;
 
 use primitives::asset::AssetPair;
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn fee_calculation() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(XYK::calculate_fee(100_000), Ok(200));
-		assert_eq!(XYK::calculate_fee(10_000), Ok(20));
+		assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 100_000), Ok(200));
+		assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 10_000), Ok(20));
 
 		assert_eq!(XYK::calculate_discounted_fee(9_999), Ok(0));
 		assert_eq!(XYK::calculate_discounted_fee(10_000), Ok(7));
@@ -21,8 +23,8 @@ fn fee_calculation() {
 		.with_discounted_fee((10, 1000))
 		.build()
 		.execute_with(|| {
-			assert_eq!(XYK::calculate_fee(100_000), Ok(1_000));
-			assert_eq!(XYK::calculate_fee(10_000), Ok(100));
+			assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 100_000), Ok(1_000));
+			assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 10_000), Ok(100));
 
 			assert_eq!(XYK::calculate_discounted_fee(999), Ok(0));
 			assert_eq!(XYK::calculate_discounted_fee(1_000), Ok(10));
@@ -33,14 +35,17 @@ fn fee_calculation() {
 		.with_exchange_fee((10, 0))
 		.build()
 		.execute_with(|| {
-			assert_eq!(XYK::calculate_fee(100000), Ok(0));
+			assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 100000), Ok(0));
 		});
 
 	ExtBuilder::default()
 		.with_exchange_fee((10, 1))
 		.build()
 		.execute_with(|| {
-			assert_noop!(XYK::calculate_fee(u128::MAX), Error::<Test>::FeeAmountInvalid);
+			assert_noop!(
+				XYK::calculate_fee(&HDX_DOT_POOL_ID, u128::MAX),
+				Error::<Test>::FeeAmountInvalid
+			);
 		});
 }
 
@@ -64,6 +69,226 @@ fn get_fee_should_work() {
 	});
 }
 
+#[test]
+fn fee_calculation_should_use_pool_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool_with_fee(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000,
+			DOT,
+			2_000_000_000,
+			(10, 1_000),
+		));
+
+		assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 100_000), Ok(1_000));
+		assert_eq!(XYK::calculate_fee(&HDX_DOT_POOL_ID, 10_000), Ok(100));
+
+		// default fee is used by other pools
+		assert_eq!(XYK::calculate_fee(&1_234, 100_000), Ok(200));
+	});
+}
+
+#[test]
+fn create_pool_with_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool_with_fee(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000,
+			DOT,
+			2_000_000_000,
+			(5, 1_000),
+		));
+
+		assert_eq!(XYK::pool_fees(HDX_DOT_POOL_ID), Some((5, 1_000)));
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (5, 1_000));
+
+		expect_events(vec![Event::PoolFeeSet {
+			pool: HDX_DOT_POOL_ID,
+			fee: (5, 1_000),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn create_pool_with_fee_should_not_work_when_fee_is_out_of_bounds() {
+	new_test_ext().execute_with(|| {
+		// below min fee
+		assert_noop!(
+			XYK::create_pool_with_fee(
+				Origin::signed(ALICE),
+				HDX,
+				1_000_000_000,
+				DOT,
+				2_000_000_000,
+				(0, 1_000)
+			),
+			Error::<Test>::InvalidFee
+		);
+
+		// above max fee
+		assert_noop!(
+			XYK::create_pool_with_fee(Origin::signed(ALICE), HDX, 1_000_000_000, DOT, 2_000_000_000, (2, 10)),
+			Error::<Test>::InvalidFee
+		);
+
+		// zero denominator
+		assert_noop!(
+			XYK::create_pool_with_fee(Origin::signed(ALICE), HDX, 1_000_000_000, DOT, 2_000_000_000, (1, 0)),
+			Error::<Test>::InvalidFee
+		);
+
+		// bounds are inclusive
+		assert_ok!(XYK::create_pool_with_fee(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000,
+			DOT,
+			2_000_000_000,
+			(1, 10),
+		));
+		assert_ok!(XYK::create_pool_with_fee(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000,
+			ACA,
+			2_000_000_000,
+			(1, 10_000),
+		));
+	});
+}
+
+#[test]
+fn set_pool_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000,
+			DOT,
+			2_000_000_000,
+		));
+
+		assert_eq!(XYK::pool_fees(HDX_DOT_POOL_ID), None);
+
+		assert_ok!(XYK::set_pool_fee(Origin::root(), DOT, HDX, (7, 1_000)));
+
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (7, 1_000));
+
+		expect_events(vec![Event::PoolFeeSet {
+			pool: HDX_DOT_POOL_ID,
+			fee: (7, 1_000),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn set_pool_fee_should_not_work_when_called_with_invalid_params() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, DOT, (7, 1_000)),
+			Error::<Test>::TokenPoolNotFound
+		);
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000,
+			DOT,
+			2_000_000_000,
+		));
+
+		assert_noop!(
+			XYK::set_pool_fee(Origin::signed(ALICE), HDX, DOT, (7, 1_000)),
+			BadOrigin
+		);
+
+		assert_noop!(
+			XYK::set_pool_fee(Origin::root(), HDX, DOT, (1, 2)),
+			Error::<Test>::InvalidFee
+		);
+	});
+}
+
+#[test]
+fn pool_fee_should_be_removed_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool_with_fee(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000,
+			DOT,
+			2_000_000_000,
+			(5, 1_000),
+		));
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 1_000_000_000));
+
+		assert_eq!(XYK::pool_fees(HDX_DOT_POOL_ID), None);
+		assert_eq!(XYK::get_fee(&HDX_DOT_POOL_ID), (2, 1_000));
+	});
+}
+
+#[test]
+fn trades_should_use_pool_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool_with_fee(
+			Origin::signed(ALICE),
+			HDX,
+			1_000_000_000_000,
+			DOT,
+			2_000_000_000_000,
+			(10, 1_000),
+		));
+
+		let assets = AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		};
+
+		let quote = XYK::quote_sell(assets, 1_000_000_000, false).unwrap();
+		assert_eq!(quote.fee, (quote.amount + quote.fee) / 100);
+
+		assert_eq!(
+			XYK::calculate_sell(PoolType::XYK, HDX, DOT, 1_000_000_000).ok(),
+			Some(quote.amount)
+		);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(BOB),
+			HDX,
+			DOT,
+			1_000_000_000,
+			quote.amount,
+			false
+		));
+
+		expect_events(vec![Event::SellExecuted {
+			who: BOB,
+			asset_in: HDX,
+			asset_out: DOT,
+			amount: 1_000_000_000,
+			sale_price: quote.amount,
+			fee_asset: DOT,
+			fee_amount: quote.fee,
+			protocol_fee_amount: 0,
+			pool: HDX_DOT_POOL_ID,
+		}
+		.into()]);
+
+		let quote = XYK::quote_buy(assets, 1_000_000_000, false).unwrap();
+		assert_eq!(quote.fee, quote.amount / 100);
+
+		assert_eq!(
+			XYK::calculate_buy(PoolType::XYK, HDX, DOT, 1_000_000_000).ok(),
+			Some(quote.amount + quote.fee)
+		);
+	});
+}
+
 #[test]
 fn discount_sell_fees_should_work() {
 	let accounts = vec![
@@ -137,6 +362,7 @@ fn discount_sell_fees_should_work() {
 			sale_price: 19_986_006,
 			fee_asset: asset_b,
 			fee_amount: 13_993,
+			protocol_fee_amount: 0,
 			pool: pair_account,
 		}
 		.into()]);
@@ -208,6 +434,7 @@ fn discount_sell_fees_should_work() {
 			sale_price: 19_980_009,
 			fee_asset: asset_b,
 			fee_amount: 19_990,
+			protocol_fee_amount: 0,
 			pool: pair_account,
 		}
 		.into()]);
@@ -272,6 +499,7 @@ fn discount_sell_fees_should_work() {
 			sale_price: 19_999_999,
 			fee_asset: asset_b,
 			fee_amount: 0,
+			protocol_fee_amount: 0,
 			pool: pair_account,
 		}
 		.into()]);
@@ -353,6 +581,7 @@ fn discount_buy_fees_should_work() {
 			buy_price: 20_000_002,
 			fee_asset: asset_b,
 			fee_amount: 14_000,
+			protocol_fee_amount: 0,
 			pool: pair_account,
 		}
 		.into()]);
@@ -426,6 +655,7 @@ fn discount_buy_fees_should_work() {
 			buy_price: 20_000_002,
 			fee_asset: asset_b,
 			fee_amount: 20_000,
+			protocol_fee_amount: 0,
 			pool: pair_account,
 		}
 		.into()]);
@@ -491,8 +721,130 @@ fn discount_buy_fees_should_work() {
 			buy_price: 20_000_002,
 			fee_asset: asset_b,
 			fee_amount: 0,
+			protocol_fee_amount: 0,
 			pool: pair_account,
 		}
 		.into()]);
 	});
 }
+
+#[test]
+fn sell_should_transfer_protocol_fee_to_receiver() {
+	ExtBuilder::default()
+		.with_protocol_fee((1, 5))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				1_000_000_000_000,
+				DOT,
+				2_000_000_000_000,
+			));
+
+			let assets = AssetPair {
+				asset_in: HDX,
+				asset_out: DOT,
+			};
+
+			let quote = XYK::quote_sell(assets, 1_000_000_000, false).unwrap();
+			let protocol_fee = quote.fee / 5;
+			assert!(protocol_fee > 0);
+
+			let bob_balance = Currency::free_balance(DOT, &BOB);
+
+			assert_ok!(XYK::sell(
+				Origin::signed(BOB),
+				HDX,
+				DOT,
+				1_000_000_000,
+				quote.amount,
+				false
+			));
+
+			assert_eq!(Currency::free_balance(DOT, &BOB), bob_balance + quote.amount);
+			assert_eq!(Currency::free_balance(DOT, &TREASURY), protocol_fee);
+			assert_eq!(
+				Currency::free_balance(DOT, &HDX_DOT_POOL_ID),
+				2_000_000_000_000 - quote.amount - protocol_fee
+			);
+			assert_eq!(Currency::free_balance(HDX, &HDX_DOT_POOL_ID), 1_001_000_000_000);
+
+			expect_events(vec![Event::SellExecuted {
+				who: BOB,
+				asset_in: HDX,
+				asset_out: DOT,
+				amount: 1_000_000_000,
+				sale_price: quote.amount,
+				fee_asset: DOT,
+				fee_amount: quote.fee,
+				protocol_fee_amount: protocol_fee,
+				pool: HDX_DOT_POOL_ID,
+			}
+			.into()]);
+		});
+}
+
+#[test]
+fn buy_should_transfer_protocol_fee_to_receiver() {
+	ExtBuilder::default()
+		.with_protocol_fee((1, 5))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				HDX,
+				1_000_000_000_000,
+				DOT,
+				2_000_000_000_000,
+			));
+
+			let assets = AssetPair {
+				asset_in: HDX,
+				asset_out: DOT,
+			};
+
+			let quote = XYK::quote_buy(assets, 1_000_000_000, false).unwrap();
+			let protocol_fee = quote.fee / 5;
+			assert!(protocol_fee > 0);
+
+			let bob_balance = Currency::free_balance(HDX, &BOB);
+
+			assert_ok!(XYK::buy(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				1_000_000_000,
+				quote.amount + quote.fee,
+				false
+			));
+
+			assert_eq!(
+				Currency::free_balance(HDX, &BOB),
+				bob_balance - quote.amount - quote.fee
+			);
+			assert_eq!(Currency::free_balance(HDX, &TREASURY), protocol_fee);
+			assert_eq!(
+				Currency::free_balance(HDX, &HDX_DOT_POOL_ID),
+				1_000_000_000_000 + quote.amount + quote.fee - protocol_fee
+			);
+			assert_eq!(Currency::free_balance(DOT, &HDX_DOT_POOL_ID), 1_999_000_000_000);
+
+			expect_events(vec![Event::BuyExecuted {
+				who: BOB,
+				asset_out: DOT,
+				asset_in: HDX,
+				amount: 1_000_000_000,
+				buy_price: quote.amount,
+				fee_asset: HDX,
+				fee_amount: quote.fee,
+				protocol_fee_amount: protocol_fee,
+				pool: HDX_DOT_POOL_ID,
+			}
+			.into()]);
+		});
+}
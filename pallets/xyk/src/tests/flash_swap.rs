@@ -0,0 +1,293 @@
+pub use super::mock::*;
+use crate::{Error, Event, FlashSwapLocks, XYKReservePrice, XYKSpotPrice};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::pools::SpotPriceProvider;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use hydradx_traits::AMM as AmmPool;
+use orml_traits::MultiCurrency;
+
+use primitives::asset::AssetPair;
+
+fn remark_call() -> Box<Call> {
+	Box::new(Call::System(frame_system::Call::remark { remark: vec![] }))
+}
+
+fn create_pools() {
+	assert_ok!(XYK::create_pool(
+		Origin::signed(ALICE),
+		HDX,
+		1_000_000_000_000,
+		DOT,
+		2_000_000_000_000,
+	));
+	assert_ok!(XYK::create_pool(
+		Origin::signed(ALICE),
+		ACA,
+		1_000_000_000_000,
+		DOT,
+		1_000_000_000_000,
+	));
+}
+
+#[test]
+fn flash_swap_should_work() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		let assets = AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		};
+		let pair_account = XYK::get_pair_id(assets);
+		let amount = 10_000_000_000;
+
+		let quote = XYK::quote_buy(assets, amount, false).unwrap();
+
+		let hdx_balance = Currency::free_balance(HDX, &BOB);
+		let dot_balance = Currency::free_balance(DOT, &BOB);
+		let aca_balance = Currency::free_balance(ACA, &BOB);
+		let hdx_reserve = Currency::free_balance(HDX, &pair_account);
+		let dot_reserve = Currency::free_balance(DOT, &pair_account);
+
+		// the borrowed DOT is sold in another pool
+		let callback = Box::new(Call::XYK(crate::Call::sell {
+			asset_in: DOT,
+			asset_out: ACA,
+			amount,
+			max_limit: 0,
+			discount: false,
+		}));
+
+		assert_ok!(XYK::flash_swap(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			amount,
+			quote.amount + quote.fee,
+			callback
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &BOB), dot_balance);
+		assert_eq!(
+			Currency::free_balance(HDX, &BOB),
+			hdx_balance - quote.amount - quote.fee
+		);
+		assert!(Currency::free_balance(ACA, &BOB) > aca_balance);
+
+		assert_eq!(
+			Currency::free_balance(HDX, &pair_account),
+			hdx_reserve + quote.amount + quote.fee
+		);
+		assert_eq!(Currency::free_balance(DOT, &pair_account), dot_reserve - amount);
+
+		expect_events(vec![Event::FlashSwapExecuted {
+			who: BOB,
+			asset_out: DOT,
+			asset_in: HDX,
+			amount,
+			buy_price: quote.amount,
+			fee_asset: HDX,
+			fee_amount: quote.fee,
+			protocol_fee_amount: 0,
+			pool: pair_account,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn flash_swap_should_not_work_when_callback_trades_in_the_same_pool() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		let callback = Box::new(Call::XYK(crate::Call::sell {
+			asset_in: DOT,
+			asset_out: HDX,
+			amount: 10_000_000_000,
+			max_limit: 0,
+			discount: false,
+		}));
+
+		assert_noop!(
+			XYK::flash_swap(Origin::signed(BOB), DOT, HDX, 10_000_000_000, u128::MAX, callback),
+			Error::<Test>::PoolLocked
+		);
+	});
+}
+
+#[test]
+fn flash_swap_should_not_work_when_callback_adds_liquidity_to_the_same_pool() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		let callback = Box::new(Call::XYK(crate::Call::add_liquidity {
+			asset_a: HDX,
+			asset_b: DOT,
+			amount_a: 1_000_000_000,
+			amount_b_max_limit: u128::MAX,
+		}));
+
+		assert_noop!(
+			XYK::flash_swap(Origin::signed(BOB), DOT, HDX, 10_000_000_000, u128::MAX, callback),
+			Error::<Test>::PoolLocked
+		);
+	});
+}
+
+#[test]
+fn flash_swap_should_not_work_when_limit_is_exceeded() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		let assets = AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		};
+		let quote = XYK::quote_buy(assets, 10_000_000_000, false).unwrap();
+
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				10_000_000_000,
+				quote.amount + quote.fee - 1,
+				remark_call()
+			),
+			Error::<Test>::AssetAmountExceededLimit
+		);
+	});
+}
+
+#[test]
+fn flash_swap_should_not_work_when_max_out_ratio_is_exceeded() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				1_000_000_000_000,
+				u128::MAX,
+				remark_call()
+			),
+			Error::<Test>::MaxOutRatioExceeded
+		);
+	});
+}
+
+#[test]
+fn flash_swap_should_revert_when_borrowed_amount_is_not_repaid() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		// the borrower has no HDX to repay with
+		let borrower = 42;
+
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(borrower),
+				DOT,
+				HDX,
+				10_000_000_000,
+				u128::MAX,
+				remark_call()
+			),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn flash_swap_should_not_work_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::flash_swap(Origin::signed(BOB), DOT, HDX, 10_000_000_000, u128::MAX, remark_call()),
+			Error::<Test>::TokenPoolNotFound
+		);
+	});
+}
+
+#[test]
+fn flash_swap_should_work_when_pool_has_donations() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		let assets = AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		};
+		let pair_account = XYK::get_pair_id(assets);
+		let amount = 10_000_000_000;
+
+		// donations above the reserves are not part of the constant product
+		assert_ok!(<Currency as MultiCurrency<_>>::transfer(
+			HDX,
+			&BOB,
+			&pair_account,
+			500_000_000_000
+		));
+
+		let quote = XYK::quote_buy(assets, amount, false).unwrap();
+
+		assert_ok!(XYK::flash_swap(
+			Origin::signed(BOB),
+			DOT,
+			HDX,
+			amount,
+			quote.amount + quote.fee,
+			remark_call()
+		));
+
+		assert_eq!(
+			Currency::free_balance(HDX, &pair_account),
+			1_000_000_000_000 + 500_000_000_000 + quote.amount + quote.fee
+		);
+	});
+}
+
+#[test]
+fn locked_pool_should_not_be_quoted() {
+	new_test_ext().execute_with(|| {
+		create_pools();
+
+		let assets = AssetPair {
+			asset_in: HDX,
+			asset_out: DOT,
+		};
+		let pair_account = XYK::get_pair_id(assets);
+
+		<FlashSwapLocks<Test>>::insert(&pair_account, ());
+
+		assert!(!XYK::is_trading_allowed(assets));
+		assert_eq!(XYKSpotPrice::<Test>::spot_price(HDX, DOT), None);
+		assert_eq!(XYKReservePrice::<Test>::spot_price(HDX, DOT), None);
+		assert_eq!(XYK::get_spot_price_unchecked(HDX, DOT, 1_000_000_000), 0);
+
+		assert!(matches!(
+			XYK::quote_sell(assets, 1_000_000_000, false),
+			Err(e) if e == Error::<Test>::PoolLocked.into()
+		));
+		assert!(matches!(
+			XYK::quote_buy(assets, 1_000_000_000, false),
+			Err(e) if e == Error::<Test>::PoolLocked.into()
+		));
+
+		assert!(matches!(
+			XYK::calculate_sell(PoolType::XYK, HDX, DOT, 1_000_000_000),
+			Err(ExecutorError::Error(e)) if e == Error::<Test>::PoolLocked.into()
+		));
+		assert!(matches!(
+			XYK::calculate_buy(PoolType::XYK, HDX, DOT, 1_000_000_000),
+			Err(ExecutorError::Error(e)) if e == Error::<Test>::PoolLocked.into()
+		));
+
+		<FlashSwapLocks<Test>>::remove(&pair_account);
+
+		assert!(XYKSpotPrice::<Test>::spot_price(HDX, DOT).is_some());
+		assert!(XYKReservePrice::<Test>::spot_price(HDX, DOT).is_some());
+		assert!(XYK::calculate_sell(PoolType::XYK, HDX, DOT, 1_000_000_000).is_ok());
+	});
+}
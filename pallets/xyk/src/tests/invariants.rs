@@ -558,3 +558,83 @@ proptest! {
 			});
 	}
 }
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(1000))]
+	#[test]
+	fn trade_invariant_with_protocol_fee(initial_liquidity in asset_reserve(),
+		amount in trade_amount(),
+		price in price(),
+	) {
+		let asset_a = HDX;
+		let asset_b = DOT;
+
+		ExtBuilder::default()
+			.with_exchange_fee((3, 1_000))
+			.with_protocol_fee((1, 2))
+			.with_accounts(vec![
+				(ALICE, asset_a,initial_liquidity),
+				(ALICE, asset_b,initial_liquidity * 1000),
+				(CHARLIE, asset_a, amount * 1_000),
+			])
+			.build()
+			.execute_with(|| {
+				assert_ok!(XYK::create_pool(
+					Origin::signed(ALICE),
+					asset_a,
+					initial_liquidity,
+					asset_b,
+					FixedU128::from_float(price).saturating_mul_int(initial_liquidity),
+				));
+
+				let pool_account = XYK::get_pair_id(AssetPair {
+					asset_in: asset_a,
+					asset_out: asset_b,
+				});
+
+				let pool_balance_a = Currency::free_balance(asset_a, &pool_account);
+				let pool_balance_b = Currency::free_balance(asset_b, &pool_account);
+
+				assert_ok!(XYK::sell(
+						Origin::signed(CHARLIE),
+						asset_a,
+						asset_b,
+						amount,
+						0u128, // limit not interesting here,
+						false,
+				));
+
+				let new_pool_balance_a = Currency::free_balance(asset_a, &pool_account);
+				let new_pool_balance_b = Currency::free_balance(asset_b, &pool_account);
+
+				// Invariant grows by the LP part of the fee, so it is only checked not to decrease
+				assert!(
+					U256::from(new_pool_balance_a) * U256::from(new_pool_balance_b)
+						>= U256::from(pool_balance_a) * U256::from(pool_balance_b),
+					"Invariant decreased for sell with protocol fee"
+				);
+
+				let pool_balance_a = new_pool_balance_a;
+				let pool_balance_b = new_pool_balance_b;
+
+				assert_ok!(XYK::buy(
+						Origin::signed(CHARLIE),
+						asset_b,
+						asset_a,
+						amount / 10,
+						u128::MAX, // limit not interesting here,
+						false,
+				));
+
+				let new_pool_balance_a = Currency::free_balance(asset_a, &pool_account);
+				let new_pool_balance_b = Currency::free_balance(asset_b, &pool_account);
+
+				// Invariant grows by the LP part of the fee, so it is only checked not to decrease
+				assert!(
+					U256::from(new_pool_balance_a) * U256::from(new_pool_balance_b)
+						>= U256::from(pool_balance_a) * U256::from(pool_balance_b),
+					"Invariant decreased for buy with protocol fee"
+				);
+			});
+	}
+}
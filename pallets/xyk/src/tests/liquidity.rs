@@ -656,3 +656,230 @@ fn share_ratio_calculations_are_correct() {
 			}
 		});
 }
+
+#[test]
+fn remove_liquidity_with_limits_should_work() {
+	new_test_ext().execute_with(|| {
+		let user = ALICE;
+		let asset_a = HDX;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(user),
+			asset_a,
+			100_000_000,
+			asset_b,
+			1_000_000_000_000
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_ok!(XYK::remove_liquidity_with_limits(
+			Origin::signed(user),
+			asset_a,
+			asset_b,
+			355_000,
+			355_000,
+			3_550_000_000
+		));
+
+		assert_eq!(Currency::free_balance(asset_a, &pair_account), 99_645_000);
+		assert_eq!(Currency::free_balance(asset_b, &pair_account), 996_450_000_000);
+		assert_eq!(Currency::free_balance(asset_a, &user), 999_999_900_355_000);
+		assert_eq!(Currency::free_balance(share_token, &user), 99_645_000);
+		assert_eq!(XYK::total_liquidity(&pair_account), 99_645_000);
+
+		expect_events(vec![Event::LiquidityRemoved {
+			who: ALICE,
+			asset_a,
+			asset_b,
+			shares: 355_000,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn remove_liquidity_with_limits_should_not_work_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		let user = ALICE;
+		let asset_a = HDX;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(user),
+			asset_a,
+			100_000_000,
+			asset_b,
+			1_000_000_000_000
+		));
+
+		assert_noop!(
+			XYK::remove_liquidity_with_limits(Origin::signed(user), asset_a, asset_b, 355_000, 355_001, 0),
+			Error::<Test>::RemoveLiquidityLimitNotReached
+		);
+		assert_noop!(
+			XYK::remove_liquidity_with_limits(Origin::signed(user), asset_a, asset_b, 355_000, 0, 3_550_000_001),
+			Error::<Test>::RemoveLiquidityLimitNotReached
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = HDX;
+		let asset_b = DOT;
+		let amount: Balance = 10_000_000_000;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			asset_a,
+			100_000_000_000,
+			asset_b,
+			200_000_000_000
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+		let share_token = XYK::share_token(pair_account);
+
+		assert_ok!(XYK::add_liquidity_single_asset(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			amount,
+			1
+		));
+
+		let spent_a = 1_000_000_000_000_000 - Currency::free_balance(asset_a, &BOB);
+		let received_b = Currency::free_balance(asset_b, &BOB) - 1_000_000_000_000_000;
+
+		assert!(spent_a <= amount);
+		// only rounding dust is left with the provider
+		assert!(amount - spent_a < 1_000);
+		assert!(received_b < 1_000);
+		assert!(Currency::free_balance(share_token, &BOB) > 0);
+		assert_eq!(
+			XYK::total_liquidity(&pair_account),
+			Currency::free_balance(share_token, &ALICE) + Currency::free_balance(share_token, &BOB)
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_single_asset_should_not_work_when_shares_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		let asset_a = HDX;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			asset_a,
+			100_000_000_000,
+			asset_b,
+			200_000_000_000
+		));
+
+		assert_noop!(
+			XYK::add_liquidity_single_asset(Origin::signed(BOB), asset_a, asset_b, 10_000_000_000, 10_000_000_000),
+			Error::<Test>::SharesLimitNotReached
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_one_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_a = HDX;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			asset_a,
+			100_000_000_000,
+			asset_b,
+			200_000_000_000
+		));
+		assert_ok!(XYK::add_liquidity(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			10_000_000_000,
+			20_100_000_000
+		));
+
+		let pair_account = XYK::get_pair_id(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		});
+		let share_token = XYK::share_token(pair_account);
+		let shares = Currency::free_balance(share_token, &BOB);
+		let balance_a = Currency::free_balance(asset_a, &BOB);
+		let balance_b = Currency::free_balance(asset_b, &BOB);
+
+		assert_ok!(XYK::remove_liquidity_one_asset(
+			Origin::signed(BOB),
+			asset_a,
+			asset_b,
+			shares,
+			19_000_000_000
+		));
+
+		assert_eq!(Currency::free_balance(share_token, &BOB), 0);
+		assert_eq!(Currency::free_balance(asset_b, &BOB), balance_b);
+		assert!(Currency::free_balance(asset_a, &BOB) - balance_a >= 19_000_000_000);
+		assert!(XYK::exists(AssetPair {
+			asset_in: asset_a,
+			asset_out: asset_b,
+		}));
+	});
+}
+
+#[test]
+fn remove_liquidity_one_asset_should_not_work_when_limit_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		let asset_a = HDX;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			asset_a,
+			100_000_000_000,
+			asset_b,
+			200_000_000_000
+		));
+
+		assert_noop!(
+			XYK::remove_liquidity_one_asset(Origin::signed(ALICE), asset_a, asset_b, 1_000_000_000, 2_000_000_000),
+			Error::<Test>::RemoveLiquidityLimitNotReached
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_one_asset_should_not_work_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		let asset_a = HDX;
+		let asset_b = DOT;
+
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			asset_a,
+			100_000_000_000,
+			asset_b,
+			200_000_000_000
+		));
+
+		assert_noop!(
+			XYK::remove_liquidity_one_asset(Origin::signed(ALICE), asset_a, asset_b, 100_000_000_000, 0),
+			Error::<Test>::TokenPoolNotFound
+		);
+	});
+}
@@ -34,9 +34,11 @@ use primitives::{
 	AssetId, Balance,
 };
 
-use frame_system::EnsureSigned;
+use frame_system::{EnsureRoot, EnsureSigned};
 use hydradx_traits::pools::DustRemovalAccountWhitelist;
+use pallet_asset_registry::AssetType;
 use std::cell::RefCell;
+use std::collections::HashMap;
 
 pub type Amount = i128;
 pub type AccountId = u64;
@@ -44,6 +46,7 @@ pub type AccountId = u64;
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CHARLIE: AccountId = 3;
+pub const TREASURY: AccountId = 99;
 
 pub const HDX: AssetId = 1000;
 pub const DOT: AssetId = 2000;
@@ -74,6 +77,10 @@ thread_local! {
 		static EXCHANGE_FEE: RefCell<(u32, u32)> = RefCell::new((2, 1_000));
 		static DISCOUNTED_FEE: RefCell<(u32, u32)> = RefCell::new(primitives::constants::chain::DISCOUNTED_FEE);
 		static MAX_OUT_RATIO: RefCell<u128> = RefCell::new(primitives::constants::chain::MAX_OUT_RATIO);
+		static PROTOCOL_FEE: RefCell<(u32, u32)> = RefCell::new((0, 0));
+		static MAX_PRICE_CHANGE: RefCell<Option<(u32, u32)>> = RefCell::new(None);
+		static MAX_LIQUIDITY_REMOVAL: RefCell<Option<(u32, u32)>> = RefCell::new(None);
+		static ASSET_METADATA: RefCell<HashMap<AssetId, (Vec<u8>, u8)>> = RefCell::new(HashMap::default());
 }
 
 struct ExchangeFee;
@@ -90,6 +97,27 @@ impl Get<(u32, u32)> for DiscountedFee {
 	}
 }
 
+struct ProtocolFee;
+impl Get<(u32, u32)> for ProtocolFee {
+	fn get() -> (u32, u32) {
+		PROTOCOL_FEE.with(|v| *v.borrow())
+	}
+}
+
+struct MaxPriceChange;
+impl Get<Option<(u32, u32)>> for MaxPriceChange {
+	fn get() -> Option<(u32, u32)> {
+		MAX_PRICE_CHANGE.with(|v| *v.borrow())
+	}
+}
+
+struct MaxLiquidityRemoval;
+impl Get<Option<(u32, u32)>> for MaxLiquidityRemoval {
+	fn get() -> Option<(u32, u32)> {
+		MAX_LIQUIDITY_REMOVAL.with(|v| *v.borrow())
+	}
+}
+
 struct MaximumOutRatio;
 impl Get<u128> for MaximumOutRatio {
 	fn get() -> u128 {
@@ -184,6 +212,14 @@ parameter_types! {
 	pub MaxOutRatio: u128 = MaximumOutRatio::get();
 	pub ExchangeFeeRate: (u32, u32) = ExchangeFee::get();
 	pub DiscountedFeeRate: (u32, u32) = DiscountedFee::get();
+	pub const MinPoolFee: (u32, u32) = (1, 10_000);
+	pub const MaxPoolFee: (u32, u32) = (1, 10);
+	pub ProtocolFeeRate: (u32, u32) = ProtocolFee::get();
+	pub const ProtocolFeeReceiver: AccountId = TREASURY;
+	pub const VolumePeriodLength: u64 = 10;
+	pub const MaxVolumePeriods: u32 = 3;
+	pub MaxPriceChangePerBlock: Option<(u32, u32)> = MaxPriceChange::get();
+	pub MaxLiquidityRemovalPerBlock: Option<(u32, u32)> = MaxLiquidityRemoval::get();
 }
 
 pub struct Disallow10_10Pool();
@@ -197,11 +233,20 @@ impl CanCreatePool<AssetId> for Disallow10_10Pool {
 impl Config for Test {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = AssetMetadataMock;
 	type AssetPairAccountId = AssetPairAccountIdTest;
 	type Currency = Currency;
+	type BalanceInspect = Currency;
 	type NativeAssetId = NativeAssetId;
 	type WeightInfo = ();
 	type GetExchangeFee = ExchangeFeeRate;
+	type MinPoolFee = MinPoolFee;
+	type MaxPoolFee = MaxPoolFee;
+	type UpdatePoolFeeOrigin = EnsureRoot<AccountId>;
+	type UpdateReservesOrigin = EnsureRoot<AccountId>;
+	type UpdatePoolStateOrigin = EnsureRoot<AccountId>;
+	type ProtocolFee = ProtocolFeeRate;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
@@ -210,6 +255,12 @@ impl Config for Test {
 	type AMMHandler = ();
 	type DiscountedFee = DiscountedFeeRate;
 	type NonDustableWhitelistHandler = Whitelist;
+	type BlockNumberProvider = System;
+	type VolumePeriodLength = VolumePeriodLength;
+	type MaxVolumePeriods = MaxVolumePeriods;
+	type MaxPriceChangePerBlock = MaxPriceChangePerBlock;
+	type MaxLiquidityRemovalPerBlock = MaxLiquidityRemovalPerBlock;
+	type Call = Call;
 }
 
 pub struct ExtBuilder {
@@ -251,11 +302,31 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn with_protocol_fee(self, f: (u32, u32)) -> Self {
+		PROTOCOL_FEE.with(|v| *v.borrow_mut() = f);
+		self
+	}
+
 	pub fn with_max_out_ratio(self, f: u128) -> Self {
 		MAX_OUT_RATIO.with(|v| *v.borrow_mut() = f);
 		self
 	}
 
+	pub fn with_max_price_change_per_block(self, f: (u32, u32)) -> Self {
+		MAX_PRICE_CHANGE.with(|v| *v.borrow_mut() = Some(f));
+		self
+	}
+
+	pub fn with_max_liquidity_removal_per_block(self, f: (u32, u32)) -> Self {
+		MAX_LIQUIDITY_REMOVAL.with(|v| *v.borrow_mut() = Some(f));
+		self
+	}
+
+	pub fn with_asset_metadata(self, asset_id: AssetId, symbol: &[u8], decimals: u8) -> Self {
+		set_asset_metadata(asset_id, symbol, decimals);
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
@@ -269,6 +340,10 @@ impl ExtBuilder {
 	}
 }
 
+pub fn set_asset_metadata(asset_id: AssetId, symbol: &[u8], decimals: u8) {
+	ASSET_METADATA.with(|v| v.borrow_mut().insert(asset_id, (symbol.to_vec(), decimals)));
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext = ExtBuilder::default().build();
 	ext.execute_with(|| System::set_block_number(1));
@@ -292,3 +367,28 @@ impl DustRemovalAccountWhitelist<AccountId> for Whitelist {
 		Ok(())
 	}
 }
+
+/// Keeps asset metadata aside of the registry. Name of the share token is set on registration.
+pub struct AssetMetadataMock;
+
+impl ShareTokenMetadata<AssetId> for AssetMetadataMock {
+	fn metadata(asset_id: AssetId) -> Option<(Vec<u8>, u8)> {
+		ASSET_METADATA.with(|v| v.borrow().get(&asset_id).cloned())
+	}
+
+	fn share_token_assets(share_token: AssetId) -> Option<(AssetId, AssetId)> {
+		match AssetRegistry::assets(share_token).map(|details| details.asset_type) {
+			Some(AssetType::PoolShare(asset_a, asset_b)) => Some((asset_a, asset_b)),
+			_ => None,
+		}
+	}
+
+	fn set_share_token_metadata(share_token: AssetId, _name: Vec<u8>, symbol: Vec<u8>, decimals: u8) -> DispatchResult {
+		ASSET_METADATA.with(|v| v.borrow_mut().insert(share_token, (symbol, decimals)));
+		Ok(())
+	}
+
+	fn string_limit() -> u32 {
+		RegistryStringLimit::get()
+	}
+}
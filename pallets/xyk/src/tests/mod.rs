@@ -1,8 +1,14 @@
 mod amm_position;
+mod circuit_breaker;
 mod creation;
 mod fees;
+mod flash_swap;
 mod invariants;
 mod liquidity;
 pub(crate) mod mock;
+mod pool_state;
+mod reserves;
+mod share_token;
 mod spot_price;
 mod trades;
+mod volume;
@@ -0,0 +1,166 @@
+pub use super::mock::*;
+use crate::{Error, Event, PoolState, PoolStates, XYKSpotPrice};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::pools::SpotPriceProvider;
+use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
+use hydradx_traits::AMM as AmmPool;
+
+use primitives::asset::AssetPair;
+use sp_runtime::traits::BadOrigin;
+
+fn create_pool_with_state(state: PoolState) -> AccountId {
+	assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+	assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, state));
+
+	XYK::get_pair_id(AssetPair::new(HDX, DOT))
+}
+
+#[test]
+fn set_pool_state_should_work() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool_with_state(PoolState::TradingFrozen);
+
+		assert_eq!(XYK::pool_state(&pair_account), PoolState::TradingFrozen);
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), DOT, HDX, PoolState::Active));
+
+		assert_eq!(XYK::pool_state(&pair_account), PoolState::Active);
+		assert!(!PoolStates::<Test>::contains_key(&pair_account));
+
+		expect_events(vec![
+			Event::PoolStateSet {
+				pool: pair_account,
+				state: PoolState::TradingFrozen,
+			}
+			.into(),
+			Event::PoolStateSet {
+				pool: pair_account,
+				state: PoolState::Active,
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+fn set_pool_state_should_not_work_when_origin_is_not_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+
+		assert_noop!(
+			XYK::set_pool_state(Origin::signed(ALICE), HDX, DOT, PoolState::TradingFrozen),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_pool_state_should_not_work_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::set_pool_state(Origin::root(), HDX, ACA, PoolState::TradingFrozen),
+			Error::<Test>::TokenPoolNotFound
+		);
+	});
+}
+
+#[test]
+fn trades_should_not_work_when_trading_is_frozen() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_state(PoolState::TradingFrozen);
+
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false),
+			Error::<Test>::PoolTradingFrozen
+		);
+		assert_noop!(
+			XYK::buy(Origin::signed(BOB), DOT, HDX, ONE, u128::MAX, false),
+			Error::<Test>::PoolTradingFrozen
+		);
+		assert_noop!(
+			XYK::flash_swap(
+				Origin::signed(BOB),
+				DOT,
+				HDX,
+				ONE,
+				u128::MAX,
+				Box::new(Call::System(frame_system::Call::remark { remark: vec![] }))
+			),
+			Error::<Test>::PoolTradingFrozen
+		);
+		assert_noop!(
+			XYK::add_liquidity_single_asset(Origin::signed(BOB), HDX, DOT, 10 * ONE, 0),
+			Error::<Test>::PoolTradingFrozen
+		);
+		assert_noop!(
+			XYK::remove_liquidity_one_asset(Origin::signed(ALICE), HDX, DOT, 10 * ONE, 0),
+			Error::<Test>::PoolTradingFrozen
+		);
+	});
+}
+
+#[test]
+fn liquidity_should_be_added_and_removed_when_trading_is_frozen() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_state(PoolState::TradingFrozen);
+
+		assert_ok!(XYK::add_liquidity(Origin::signed(BOB), HDX, DOT, 10 * ONE, 20 * ONE));
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 10 * ONE));
+	});
+}
+
+#[test]
+fn only_removing_liquidity_should_work_when_pool_is_withdraw_only() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_state(PoolState::WithdrawOnly);
+
+		assert_noop!(
+			XYK::add_liquidity(Origin::signed(BOB), HDX, DOT, 10 * ONE, 20 * ONE),
+			Error::<Test>::PoolWithdrawOnly
+		);
+		assert_noop!(
+			XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false),
+			Error::<Test>::PoolTradingFrozen
+		);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 10 * ONE));
+	});
+}
+
+#[test]
+fn frozen_pool_should_not_be_quoted() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_state(PoolState::TradingFrozen);
+
+		assert!(matches!(
+			XYK::calculate_sell(PoolType::XYK, HDX, DOT, ONE),
+			Err(ExecutorError::Error(e)) if e == Error::<Test>::PoolTradingFrozen.into()
+		));
+		assert!(matches!(
+			XYK::calculate_buy(PoolType::XYK, HDX, DOT, ONE),
+			Err(ExecutorError::Error(e)) if e == Error::<Test>::PoolTradingFrozen.into()
+		));
+
+		assert!(!XYKSpotPrice::<Test>::pair_exists(HDX, DOT));
+		assert_eq!(XYKSpotPrice::<Test>::spot_price(HDX, DOT), None);
+
+		assert_ok!(XYK::set_pool_state(Origin::root(), HDX, DOT, PoolState::Active));
+
+		assert!(XYK::calculate_sell(PoolType::XYK, HDX, DOT, ONE).is_ok());
+		assert!(XYKSpotPrice::<Test>::pair_exists(HDX, DOT));
+	});
+}
+
+#[test]
+fn pool_state_should_be_removed_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool_with_state(PoolState::WithdrawOnly);
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100 * ONE));
+
+		assert!(!PoolStates::<Test>::contains_key(&pair_account));
+
+		assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false));
+	});
+}
@@ -0,0 +1,172 @@
+pub use super::mock::*;
+use crate::{Error, Event, PoolReserves};
+use frame_support::traits::{GetStorageVersion, StorageVersion};
+use frame_support::{assert_noop, assert_ok};
+use hydradx_traits::AMM as AmmPool;
+use orml_traits::MultiCurrency;
+
+use primitives::asset::AssetPair;
+use sp_runtime::traits::BadOrigin;
+
+fn create_pool_with_donation() -> AccountId {
+	assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+
+	let pair_account = XYK::get_pair_id(AssetPair::new(HDX, DOT));
+
+	assert_ok!(Currency::transfer(Origin::signed(BOB), pair_account, HDX, 10 * ONE));
+	assert_ok!(Currency::transfer(Origin::signed(BOB), pair_account, DOT, 30 * ONE));
+
+	pair_account
+}
+
+#[test]
+fn reserves_should_be_updated_by_liquidity_changes_and_trades() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+
+		let pair_account = XYK::get_pair_id(AssetPair::new(HDX, DOT));
+
+		assert_eq!(XYK::pool_reserve(&pair_account, HDX), 100 * ONE);
+		assert_eq!(XYK::pool_reserve(&pair_account, DOT), 200 * ONE);
+
+		assert_ok!(XYK::add_liquidity(Origin::signed(BOB), HDX, DOT, 10 * ONE, 30 * ONE));
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false));
+		assert_ok!(XYK::buy(Origin::signed(BOB), HDX, DOT, ONE, u128::MAX, false));
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 50 * ONE));
+
+		assert_eq!(
+			XYK::pool_reserve(&pair_account, HDX),
+			Currency::free_balance(HDX, &pair_account)
+		);
+		assert_eq!(
+			XYK::pool_reserve(&pair_account, DOT),
+			Currency::free_balance(DOT, &pair_account)
+		);
+	});
+}
+
+#[test]
+fn donation_should_not_change_price_of_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+
+		let asset_pair = AssetPair::new(HDX, DOT);
+		let pair_account = XYK::get_pair_id(asset_pair);
+
+		let sell_quote = XYK::quote_sell(asset_pair, ONE, false).unwrap();
+		let spot_price = XYK::get_spot_price_unchecked(HDX, DOT, ONE);
+
+		assert_ok!(Currency::transfer(Origin::signed(BOB), pair_account, DOT, 100 * ONE));
+
+		assert_eq!(XYK::quote_sell(asset_pair, ONE, false).unwrap(), sell_quote);
+		assert_eq!(XYK::get_spot_price_unchecked(HDX, DOT, ONE), spot_price);
+		assert_eq!(XYK::pool_reserve(&pair_account, DOT), 200 * ONE);
+	});
+}
+
+#[test]
+fn skim_should_transfer_balance_above_reserves() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool_with_donation();
+
+		assert_ok!(XYK::skim(Origin::root(), HDX, DOT, CHARLIE));
+
+		assert_eq!(Currency::free_balance(HDX, &pair_account), 100 * ONE);
+		assert_eq!(Currency::free_balance(DOT, &pair_account), 200 * ONE);
+		assert_eq!(XYK::pool_reserve(&pair_account, HDX), 100 * ONE);
+		assert_eq!(XYK::pool_reserve(&pair_account, DOT), 200 * ONE);
+		assert_eq!(Currency::free_balance(DOT, &CHARLIE), 30 * ONE);
+
+		expect_events(vec![Event::Skimmed {
+			pool: pair_account,
+			asset_a: HDX,
+			asset_b: DOT,
+			amount_a: 10 * ONE,
+			amount_b: 30 * ONE,
+			to: CHARLIE,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn sync_should_set_reserves_to_balances() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool_with_donation();
+
+		assert_ok!(XYK::sync(Origin::root(), DOT, HDX));
+
+		assert_eq!(XYK::pool_reserve(&pair_account, HDX), 110 * ONE);
+		assert_eq!(XYK::pool_reserve(&pair_account, DOT), 230 * ONE);
+
+		expect_events(vec![Event::ReservesSynced {
+			pool: pair_account,
+			asset_a: DOT,
+			asset_b: HDX,
+			reserve_a: 230 * ONE,
+			reserve_b: 110 * ONE,
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn skim_and_sync_should_not_work_when_origin_is_not_allowed() {
+	new_test_ext().execute_with(|| {
+		create_pool_with_donation();
+
+		assert_noop!(XYK::skim(Origin::signed(ALICE), HDX, DOT, ALICE), BadOrigin);
+		assert_noop!(XYK::sync(Origin::signed(ALICE), HDX, DOT), BadOrigin);
+	});
+}
+
+#[test]
+fn skim_and_sync_should_not_work_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XYK::skim(Origin::root(), HDX, ACA, ALICE),
+			Error::<Test>::TokenPoolNotFound
+		);
+		assert_noop!(XYK::sync(Origin::root(), HDX, ACA), Error::<Test>::TokenPoolNotFound);
+	});
+}
+
+#[test]
+fn reserves_should_be_removed_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool_with_donation();
+
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100 * ONE));
+
+		assert!(!PoolReserves::<Test>::contains_key(&pair_account, HDX));
+		assert!(!PoolReserves::<Test>::contains_key(&pair_account, DOT));
+
+		// donation stays in the pool account and is not part of the recreated pool
+		assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+
+		assert_eq!(XYK::pool_reserve(&pair_account, HDX), 100 * ONE);
+		assert_eq!(XYK::pool_reserve(&pair_account, DOT), 200 * ONE);
+	});
+}
+
+#[test]
+fn migration_should_initialize_reserves_from_balances() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool_with_donation();
+		PoolReserves::<Test>::remove(&pair_account, HDX);
+		PoolReserves::<Test>::remove(&pair_account, DOT);
+
+		crate::migration::init_pool_reserves::<Test>();
+
+		assert_eq!(XYK::pool_reserve(&pair_account, HDX), 110 * ONE);
+		assert_eq!(XYK::pool_reserve(&pair_account, DOT), 230 * ONE);
+		assert_eq!(XYK::on_chain_storage_version(), StorageVersion::new(1));
+
+		// second run does not add donations made after the migration
+		assert_ok!(Currency::transfer(Origin::signed(BOB), pair_account, HDX, ONE));
+
+		crate::migration::init_pool_reserves::<Test>();
+
+		assert_eq!(XYK::pool_reserve(&pair_account, HDX), 110 * ONE);
+	});
+}
@@ -0,0 +1,168 @@
+pub use super::mock::*;
+use crate::ShareTokenMetadata;
+use frame_support::{
+	assert_ok,
+	traits::{GetStorageVersion, StorageVersion},
+	BoundedVec,
+};
+use hydradx_traits::AMM as AmmPool;
+use pallet_asset_registry::AssetType;
+use sp_std::convert::TryInto;
+
+use primitives::{asset::AssetPair, AssetId};
+
+fn registered_event(share_token: AssetId, name: &[u8], asset_a: AssetId, asset_b: AssetId) -> Event {
+	let bounded_name: BoundedVec<u8, <Test as pallet_asset_registry::Config>::StringLimit> =
+		name.to_vec().try_into().unwrap();
+
+	pallet_asset_registry::Event::Registered {
+		asset_id: share_token,
+		asset_name: bounded_name,
+		asset_type: AssetType::PoolShare(asset_a, asset_b),
+	}
+	.into()
+}
+
+#[test]
+fn create_pool_should_register_share_token_with_symbols_of_assets() {
+	ExtBuilder::default()
+		.with_asset_metadata(HDX, b"HDX", 12)
+		.with_asset_metadata(DOT, b"DOT", 10)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(Origin::signed(ALICE), DOT, 100 * ONE, HDX, 100 * ONE));
+
+			let share_token = XYK::get_share_token(AssetPair::new(DOT, HDX));
+
+			// assets are ordered by id and decimals are taken from HDX, as HDX amount determines the shares
+			assert_eq!(
+				AssetMetadataMock::metadata(share_token),
+				Some((b"HDX-DOT LP".to_vec(), 12))
+			);
+			expect_events(vec![registered_event(share_token, b"HDX-DOT LP", DOT, HDX)]);
+		});
+}
+
+#[test]
+fn create_pool_should_name_share_token_by_asset_ids_when_asset_has_no_symbol() {
+	ExtBuilder::default()
+		.with_asset_metadata(HDX, b"HDX", 12)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, ACA, 100 * ONE));
+
+			let asset_pair = AssetPair::new(HDX, ACA);
+			let share_token = XYK::get_share_token(asset_pair);
+
+			assert_eq!(AssetMetadataMock::metadata(share_token), None);
+			expect_events(vec![registered_event(share_token, &asset_pair.name(), HDX, ACA)]);
+		});
+}
+
+#[test]
+fn create_pool_should_name_share_token_by_asset_ids_when_name_is_taken() {
+	ExtBuilder::default()
+		.with_asset_metadata(HDX, b"HDX", 12)
+		.with_asset_metadata(DOT, b"USD", 6)
+		.with_asset_metadata(ACA, b"USD", 6)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 100 * ONE));
+			assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, ACA, 100 * ONE));
+
+			let hdx_dot_share_token = XYK::get_share_token(AssetPair::new(HDX, DOT));
+			let asset_pair = AssetPair::new(HDX, ACA);
+			let hdx_aca_share_token = XYK::get_share_token(asset_pair);
+
+			assert_ne!(hdx_dot_share_token, hdx_aca_share_token);
+			assert_eq!(
+				AssetMetadataMock::metadata(hdx_aca_share_token),
+				Some((b"HDX-USD LP".to_vec(), 12))
+			);
+			expect_events(vec![
+				registered_event(hdx_dot_share_token, b"HDX-USD LP", HDX, DOT),
+				registered_event(hdx_aca_share_token, &asset_pair.name(), HDX, ACA),
+			]);
+		});
+}
+
+#[test]
+fn recreated_pool_should_reuse_share_token() {
+	ExtBuilder::default()
+		.with_asset_metadata(HDX, b"HDX", 12)
+		.with_asset_metadata(DOT, b"DOT", 10)
+		.build()
+		.execute_with(|| {
+			assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 100 * ONE));
+
+			let asset_pair = AssetPair::new(HDX, DOT);
+			let share_token = XYK::get_share_token(asset_pair);
+
+			assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100 * ONE));
+			assert!(!XYK::exists(asset_pair));
+
+			assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 100 * ONE));
+
+			assert_eq!(XYK::get_share_token(asset_pair), share_token);
+			assert_eq!(XYK::share_token_name(asset_pair), b"HDX-DOT LP".to_vec());
+		});
+}
+
+#[test]
+fn create_pool_should_name_share_token_by_asset_ids_when_symbol_is_too_long() {
+	ExtBuilder::default()
+		.with_asset_metadata(HDX, &[b'H'; 50], 12)
+		.with_asset_metadata(DOT, &[b'D'; 50], 10)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 100 * ONE));
+
+			let asset_pair = AssetPair::new(HDX, DOT);
+			let share_token = XYK::get_share_token(asset_pair);
+
+			assert_eq!(XYK::share_token_name(asset_pair), asset_pair.name());
+			assert_eq!(AssetMetadataMock::metadata(share_token), None);
+			expect_events(vec![registered_event(share_token, &asset_pair.name(), HDX, DOT)]);
+		});
+}
+
+#[test]
+fn migration_should_update_share_token_metadata_once_after_reserves_are_initialized() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 100 * ONE));
+		let share_token = XYK::get_share_token(AssetPair::new(HDX, DOT));
+
+		set_asset_metadata(HDX, b"HDX", 12);
+		set_asset_metadata(DOT, b"DOT", 10);
+
+		// reserves are not initialized yet
+		crate::migration::update_share_token_metadata::<Test>();
+		assert_eq!(AssetMetadataMock::metadata(share_token), None);
+
+		crate::migration::init_pool_reserves::<Test>();
+		crate::migration::update_share_token_metadata::<Test>();
+
+		assert_eq!(
+			AssetMetadataMock::metadata(share_token),
+			Some((b"HDX-DOT LP".to_vec(), 12))
+		);
+		assert_eq!(XYK::on_chain_storage_version(), StorageVersion::new(2));
+
+		// second run does not touch the metadata
+		set_asset_metadata(DOT, b"DOT2", 10);
+		crate::migration::update_share_token_metadata::<Test>();
+
+		assert_eq!(
+			AssetMetadataMock::metadata(share_token),
+			Some((b"HDX-DOT LP".to_vec(), 12))
+		);
+	});
+}
@@ -1,11 +1,12 @@
 use super::mock::*;
-use crate::XYKSpotPrice;
 use crate::*;
+use crate::{XYKReservePrice, XYKSpotPrice};
 use frame_support::assert_ok;
 use frame_support::dispatch::RawOrigin;
 use hydradx_traits::pools::SpotPriceProvider;
 use primitives::asset::AssetPair;
 use primitives::Price;
+use sp_runtime::FixedPointNumber;
 
 #[test]
 fn spot_price_provider_should_return_correct_price_when_pool_exists() {
@@ -28,7 +29,35 @@ fn spot_price_provider_should_return_correct_price_when_pool_exists() {
 
 			let price = XYKSpotPrice::<Test>::spot_price(asset_a, asset_b);
 
-			assert_eq!(price, Some(Price::from_float(0.4)));
+			// 0.4 + 0.2% pool fee
+			assert_eq!(price, Some(Price::saturating_from_rational(4_008, 10_000)));
+		});
+}
+
+#[test]
+fn spot_price_provider_should_include_pool_fee() {
+	let asset_a = ACA;
+	let asset_b = DOT;
+
+	let initial = 99_000_000_000_000u128;
+
+	ExtBuilder::default()
+		.with_accounts(vec![(ALICE, asset_a, initial), (ALICE, asset_b, initial)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(XYK::create_pool_with_fee(
+				Origin::signed(ALICE),
+				asset_a,
+				initial,
+				asset_b,
+				39_600_000_000_000,
+				(10, 1_000)
+			));
+
+			let price = XYKSpotPrice::<Test>::spot_price(asset_a, asset_b);
+
+			// 0.4 + 1% pool fee
+			assert_eq!(price, Some(Price::saturating_from_rational(404, 1_000)));
 		});
 }
 
@@ -82,3 +111,36 @@ fn spot_price_provider_should_return_none_when_asset_reserve_is_zero() {
 			assert_eq!(price, None);
 		});
 }
+
+#[test]
+fn reserve_price_provider_should_not_include_pool_fee() {
+	let asset_a = ACA;
+	let asset_b = DOT;
+
+	let initial = 99_000_000_000_000u128;
+
+	ExtBuilder::default()
+		.with_accounts(vec![(ALICE, asset_a, initial), (ALICE, asset_b, initial)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(XYKReservePrice::<Test>::spot_price(asset_a, asset_b), None);
+
+			assert_ok!(XYK::create_pool_with_fee(
+				Origin::signed(ALICE),
+				asset_a,
+				initial,
+				asset_b,
+				39_600_000_000_000,
+				(10, 1_000)
+			));
+
+			assert_eq!(
+				XYKReservePrice::<Test>::spot_price(asset_a, asset_b),
+				Some(Price::saturating_from_rational(4, 10))
+			);
+			assert_eq!(
+				XYKReservePrice::<Test>::spot_price(asset_b, asset_a),
+				Some(Price::saturating_from_rational(10, 4))
+			);
+		});
+}
@@ -1,8 +1,8 @@
 pub use super::mock::*;
-use crate::{Error, Event};
+use crate::{Error, Event, TradeQuote};
 use frame_support::{assert_noop, assert_ok};
 use hydradx_traits::AMM as AmmPool;
-use orml_traits::MultiCurrency;
+use orml_traits::{MultiCurrency, MultiLockableCurrency};
 
 use primitives::asset::AssetPair;
 
@@ -67,6 +67,7 @@ fn sell_test() {
 				sale_price: 1363483591788,
 				fee_asset: asset_b,
 				fee_amount: 2732432046,
+				protocol_fee_amount: 0,
 				pool: pair_account,
 			}
 			.into(),
@@ -342,6 +343,7 @@ fn sell_with_correct_fees_should_work() {
 				sale_price: 19_762_378,
 				fee_asset: asset_b,
 				fee_amount: 39_602,
+				protocol_fee_amount: 0,
 				pool: pair_account,
 			}
 			.into(),
@@ -522,6 +524,7 @@ fn single_buy_should_work() {
 				buy_price: 22_068_963_235,
 				fee_asset: asset_b,
 				fee_amount: 44_137_926,
+				protocol_fee_amount: 0,
 				pool: pair_account,
 			}
 			.into(),
@@ -1010,3 +1013,287 @@ fn buy_with_excesive_amount_should_not_work() {
 		);
 	});
 }
+
+#[test]
+fn quote_sell_should_match_executed_sell() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			200_000_000_000,
+			DOT,
+			600_000_000_000_000,
+		));
+
+		let quote = XYK::quote_sell(AssetPair::new(ACA, DOT), 456_444_678, false).unwrap();
+
+		assert_eq!(
+			quote,
+			TradeQuote {
+				amount: 1_363_483_591_788,
+				fee: 2_732_432_046,
+				discount_amount: 0,
+			}
+		);
+
+		let balance_before = Currency::free_balance(DOT, &ALICE);
+
+		assert_ok!(XYK::sell(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			456_444_678,
+			quote.amount,
+			false
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &ALICE), balance_before + quote.amount);
+	});
+}
+
+#[test]
+fn quote_buy_should_match_executed_buy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			200_000_000_000,
+			DOT,
+			600_000_000_000_000,
+		));
+
+		let quote = XYK::quote_buy(AssetPair::new(ACA, DOT), 6_666_666_666, false).unwrap();
+
+		let balance_before = Currency::free_balance(ACA, &ALICE);
+
+		assert_ok!(XYK::buy(
+			Origin::signed(ALICE),
+			DOT,
+			ACA,
+			6_666_666_666,
+			quote.amount + quote.fee,
+			false
+		));
+
+		assert_eq!(
+			Currency::free_balance(ACA, &ALICE),
+			balance_before - quote.amount - quote.fee
+		);
+	});
+}
+
+#[test]
+fn quote_should_not_work_when_pool_does_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			XYK::quote_sell(AssetPair::new(ACA, DOT), 1_000_000, false),
+			Err(Error::<Test>::TokenPoolNotFound.into())
+		);
+		assert_eq!(
+			XYK::quote_buy(AssetPair::new(ACA, DOT), 1_000_000, false),
+			Err(Error::<Test>::TokenPoolNotFound.into())
+		);
+	});
+}
+
+#[test]
+fn sell_with_deadline_should_work_when_deadline_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			200_000_000_000,
+			DOT,
+			600_000_000_000_000,
+		));
+
+		System::set_block_number(10);
+
+		let quote = XYK::quote_sell(AssetPair::new(ACA, DOT), 456_444_678, false).unwrap();
+		let balance_before = Currency::free_balance(DOT, &ALICE);
+
+		assert_ok!(XYK::sell_with_deadline(
+			Origin::signed(ALICE),
+			ACA,
+			DOT,
+			456_444_678,
+			quote.amount,
+			false,
+			10
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &ALICE), balance_before + quote.amount);
+	});
+}
+
+#[test]
+fn buy_with_deadline_should_work_when_deadline_is_not_reached() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			200_000_000_000,
+			DOT,
+			600_000_000_000_000,
+		));
+
+		System::set_block_number(10);
+
+		let balance_before = Currency::free_balance(DOT, &ALICE);
+
+		assert_ok!(XYK::buy_with_deadline(
+			Origin::signed(ALICE),
+			DOT,
+			ACA,
+			6_666_666_666,
+			1_000_000_000,
+			false,
+			11
+		));
+
+		assert_eq!(Currency::free_balance(DOT, &ALICE), balance_before + 6_666_666_666);
+	});
+}
+
+#[test]
+fn trade_with_deadline_should_not_work_when_deadline_has_passed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			200_000_000_000,
+			DOT,
+			600_000_000_000_000,
+		));
+
+		System::set_block_number(10);
+
+		assert_noop!(
+			XYK::sell_with_deadline(Origin::signed(ALICE), ACA, DOT, 456_444_678, 0, false, 9),
+			Error::<Test>::DeadlineExpired
+		);
+		assert_noop!(
+			XYK::buy_with_deadline(Origin::signed(ALICE), DOT, ACA, 6_666_666_666, 1_000_000_000, false, 9),
+			Error::<Test>::DeadlineExpired
+		);
+	});
+}
+
+#[test]
+fn sell_all_should_sell_whole_free_balance() {
+	ExtBuilder::default()
+		.with_accounts(vec![
+			(ALICE, ACA, 1_000_000_000_000_000),
+			(ALICE, DOT, 1_000_000_000_000_000),
+			(CHARLIE, ACA, 456_444_678),
+		])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				ACA,
+				200_000_000_000,
+				DOT,
+				600_000_000_000_000,
+			));
+
+			let pair_account = XYK::get_pair_id(AssetPair::new(ACA, DOT));
+			let quote = XYK::quote_sell(AssetPair::new(ACA, DOT), 456_444_678, false).unwrap();
+
+			assert_ok!(XYK::sell_all(Origin::signed(CHARLIE), ACA, DOT, quote.amount, false));
+
+			assert_eq!(Currency::free_balance(ACA, &CHARLIE), 0);
+			assert_eq!(Currency::free_balance(DOT, &CHARLIE), quote.amount);
+
+			expect_events(vec![Event::SellExecuted {
+				who: CHARLIE,
+				asset_in: ACA,
+				asset_out: DOT,
+				amount: 456_444_678,
+				sale_price: quote.amount,
+				fee_asset: DOT,
+				fee_amount: quote.fee,
+				protocol_fee_amount: 0,
+				pool: pair_account,
+			}
+			.into()]);
+		});
+}
+
+#[test]
+fn sell_all_should_not_sell_locked_balance() {
+	ExtBuilder::default()
+		.with_accounts(vec![
+			(ALICE, ACA, 1_000_000_000_000_000),
+			(ALICE, DOT, 1_000_000_000_000_000),
+			(CHARLIE, ACA, 456_444_678),
+		])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				ACA,
+				200_000_000_000,
+				DOT,
+				600_000_000_000_000,
+			));
+
+			assert_ok!(Currency::set_lock(*b"testlock", ACA, &CHARLIE, 56_444_678));
+
+			let quote = XYK::quote_sell(AssetPair::new(ACA, DOT), 400_000_000, false).unwrap();
+
+			assert_ok!(XYK::sell_all(Origin::signed(CHARLIE), ACA, DOT, quote.amount, false));
+
+			assert_eq!(Currency::free_balance(ACA, &CHARLIE), 56_444_678);
+			assert_eq!(Currency::free_balance(DOT, &CHARLIE), quote.amount);
+		});
+}
+
+#[test]
+fn sell_all_should_respect_limit() {
+	ExtBuilder::default()
+		.with_accounts(vec![
+			(ALICE, ACA, 1_000_000_000_000_000),
+			(ALICE, DOT, 1_000_000_000_000_000),
+			(CHARLIE, ACA, 456_444_678),
+		])
+		.build()
+		.execute_with(|| {
+			assert_ok!(XYK::create_pool(
+				Origin::signed(ALICE),
+				ACA,
+				200_000_000_000,
+				DOT,
+				600_000_000_000_000,
+			));
+
+			let quote = XYK::quote_sell(AssetPair::new(ACA, DOT), 456_444_678, false).unwrap();
+
+			assert_noop!(
+				XYK::sell_all(Origin::signed(CHARLIE), ACA, DOT, quote.amount + 1, false),
+				Error::<Test>::AssetAmountNotReachedLimit
+			);
+		});
+}
+
+#[test]
+fn sell_all_should_not_work_when_balance_is_zero() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(XYK::create_pool(
+			Origin::signed(ALICE),
+			ACA,
+			200_000_000_000,
+			DOT,
+			600_000_000_000_000,
+		));
+
+		assert_noop!(
+			XYK::sell_all(Origin::signed(CHARLIE), ACA, DOT, 0, false),
+			Error::<Test>::InsufficientTradingAmount
+		);
+	});
+}
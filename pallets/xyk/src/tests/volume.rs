@@ -0,0 +1,140 @@
+pub use super::mock::*;
+use crate::{PeriodVolumes, PoolVolumes, Volume};
+use frame_support::assert_ok;
+use hydradx_traits::AMM as AmmPool;
+
+use primitives::asset::AssetPair;
+
+fn create_pool() -> AccountId {
+	assert_ok!(XYK::create_pool(Origin::signed(ALICE), HDX, 100 * ONE, DOT, 200 * ONE));
+
+	XYK::get_pair_id(AssetPair::new(HDX, DOT))
+}
+
+#[test]
+fn sell_should_record_volume_of_both_assets() {
+	ExtBuilder::default()
+		.with_protocol_fee((1, 5))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let pair_account = create_pool();
+
+			let quote = XYK::quote_sell(AssetPair::new(HDX, DOT), ONE, false).unwrap();
+			let protocol_fee = XYK::calculate_protocol_fee(quote.fee).unwrap();
+			assert!(protocol_fee > 0);
+
+			assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false));
+
+			assert_eq!(
+				XYK::pool_volume(&pair_account, HDX),
+				Volume {
+					volume_in: ONE,
+					..Default::default()
+				}
+			);
+			assert_eq!(
+				XYK::pool_volume(&pair_account, DOT),
+				Volume {
+					volume_out: quote.amount,
+					fees: quote.fee,
+					protocol_fees: protocol_fee,
+					..Default::default()
+				}
+			);
+		});
+}
+
+#[test]
+fn buy_should_record_volume_of_both_assets() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool();
+
+		let quote = XYK::quote_buy(AssetPair::new(HDX, DOT), ONE, false).unwrap();
+
+		assert_ok!(XYK::buy(Origin::signed(BOB), DOT, HDX, ONE, u128::MAX, false));
+
+		assert_eq!(
+			XYK::pool_volume(&pair_account, HDX),
+			Volume {
+				volume_in: quote.amount,
+				fees: quote.fee,
+				..Default::default()
+			}
+		);
+		assert_eq!(
+			XYK::pool_volume(&pair_account, DOT),
+			Volume {
+				volume_out: ONE,
+				..Default::default()
+			}
+		);
+	});
+}
+
+#[test]
+fn volume_should_accumulate_over_trades() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool();
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false));
+		assert_ok!(XYK::sell(Origin::signed(BOB), DOT, HDX, 2 * ONE, 0, false));
+		assert_ok!(XYK::buy(Origin::signed(BOB), HDX, DOT, ONE, u128::MAX, false));
+
+		let hdx_volume = XYK::pool_volume(&pair_account, HDX);
+		assert!(hdx_volume.volume_out > ONE);
+		assert_eq!(hdx_volume.volume_in, ONE);
+		assert!(XYK::pool_volume(&pair_account, DOT).volume_in > 2 * ONE);
+
+		// all trades happened in the same period
+		assert_eq!(XYK::recent_period_volumes(&pair_account, HDX), vec![(0, hdx_volume)]);
+	});
+}
+
+#[test]
+fn volume_should_be_split_into_periods() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool();
+
+		// periods are 10 blocks long and 3 most recent periods are kept
+		for block in [1, 5, 12, 35, 47] {
+			System::set_block_number(block);
+			assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false));
+		}
+
+		let volume = |amount| Volume {
+			volume_in: amount,
+			..Default::default()
+		};
+
+		assert_eq!(
+			XYK::period_volumes(&pair_account, HDX).into_inner(),
+			vec![(10, volume(ONE)), (30, volume(ONE)), (40, volume(ONE))]
+		);
+		assert_eq!(XYK::pool_volume(&pair_account, HDX).volume_in, 5 * ONE);
+
+		// period starting at block 10 is older than the last 3 periods
+		assert_eq!(
+			XYK::recent_period_volumes(&pair_account, HDX),
+			vec![(30, volume(ONE)), (40, volume(ONE))]
+		);
+
+		System::set_block_number(80);
+		assert_eq!(XYK::recent_period_volumes(&pair_account, HDX), vec![]);
+	});
+}
+
+#[test]
+fn volume_should_be_removed_when_pool_is_destroyed() {
+	new_test_ext().execute_with(|| {
+		let pair_account = create_pool();
+
+		assert_ok!(XYK::sell(Origin::signed(BOB), HDX, DOT, ONE, 0, false));
+		assert_ok!(XYK::remove_liquidity(Origin::signed(ALICE), HDX, DOT, 100 * ONE));
+
+		assert!(!PoolVolumes::<Test>::contains_key(&pair_account, HDX));
+		assert!(!PoolVolumes::<Test>::contains_key(&pair_account, DOT));
+		assert!(!PeriodVolumes::<Test>::contains_key(&pair_account, HDX));
+		assert!(!PeriodVolumes::<Test>::contains_key(&pair_account, DOT));
+	});
+}
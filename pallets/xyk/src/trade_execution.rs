@@ -3,7 +3,6 @@ use frame_support::ensure;
 use frame_support::traits::Get;
 use hydradx_traits::router::{ExecutorError, PoolType, TradeExecution};
 use hydradx_traits::AMM;
-use orml_traits::MultiCurrency;
 use primitives::asset::AssetPair;
 use primitives::{AssetId, Balance};
 use sp_runtime::DispatchError;
@@ -29,8 +28,18 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 
 		let pair_account = Self::get_pair_id(assets);
 
-		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
-		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
+		ensure!(
+			Self::pool_state(&pair_account).is_trading_allowed(),
+			ExecutorError::Error(Error::<T>::PoolTradingFrozen.into())
+		);
+
+		ensure!(
+			!Self::is_pool_locked(&pair_account),
+			ExecutorError::Error(Error::<T>::PoolLocked.into())
+		);
+
+		let asset_in_reserve = Self::pool_reserve(&pair_account, assets.asset_in);
+		let asset_out_reserve = Self::pool_reserve(&pair_account, assets.asset_out);
 
 		let amount_out = hydra_dx_math::xyk::calculate_out_given_in(asset_in_reserve, asset_out_reserve, amount_in)
 			.map_err(|_| ExecutorError::Error(Error::<T>::SellAssetAmountInvalid.into()))?;
@@ -40,7 +49,7 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 			ExecutorError::Error(Error::<T>::InsufficientPoolAssetBalance.into())
 		);
 
-		let transfer_fee = Self::calculate_fee(amount_out).map_err(ExecutorError::Error)?;
+		let transfer_fee = Self::calculate_fee(&pair_account, amount_out).map_err(ExecutorError::Error)?;
 
 		let amount_out_without_fee = amount_out
 			.checked_sub(transfer_fee)
@@ -68,8 +77,18 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 
 		let pair_account = Self::get_pair_id(assets);
 
-		let asset_out_reserve = T::Currency::free_balance(assets.asset_out, &pair_account);
-		let asset_in_reserve = T::Currency::free_balance(assets.asset_in, &pair_account);
+		ensure!(
+			Self::pool_state(&pair_account).is_trading_allowed(),
+			ExecutorError::Error(Error::<T>::PoolTradingFrozen.into())
+		);
+
+		ensure!(
+			!Self::is_pool_locked(&pair_account),
+			ExecutorError::Error(Error::<T>::PoolLocked.into())
+		);
+
+		let asset_out_reserve = Self::pool_reserve(&pair_account, assets.asset_out);
+		let asset_in_reserve = Self::pool_reserve(&pair_account, assets.asset_in);
 
 		ensure!(
 			asset_out_reserve > amount_out,
@@ -84,7 +103,7 @@ impl<T: Config> TradeExecution<T::Origin, T::AccountId, AssetId, Balance> for Pa
 		let amount_in = hydra_dx_math::xyk::calculate_in_given_out(asset_out_reserve, asset_in_reserve, amount_out)
 			.map_err(|_| ExecutorError::Error(Error::<T>::BuyAssetAmountInvalid.into()))?;
 
-		let transfer_fee = Self::calculate_fee(amount_in).map_err(ExecutorError::Error)?;
+		let transfer_fee = Self::calculate_fee(&pair_account, amount_in).map_err(ExecutorError::Error)?;
 
 		let amount_in_with_fee = amount_in
 			.checked_add(transfer_fee)
@@ -52,6 +52,15 @@ pub trait WeightInfo {
 	fn remove_liquidity() -> Weight;
 	fn sell() -> Weight;
 	fn buy() -> Weight;
+	fn create_pool_with_fee() -> Weight;
+	fn set_pool_fee() -> Weight;
+	fn remove_liquidity_with_limits() -> Weight;
+	fn add_liquidity_single_asset() -> Weight;
+	fn remove_liquidity_one_asset() -> Weight;
+	fn flash_swap() -> Weight;
+	fn skim() -> Weight;
+	fn sync() -> Weight;
+	fn set_pool_state() -> Weight;
 }
 
 /// Weights for amm using the hydraDX node and recommended hardware.
@@ -60,56 +69,146 @@ pub struct HydraWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 	fn create_pool() -> Weight {
 		Weight::from_ref_time(189_645_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(11 as u64))
-			.saturating_add(T::DbWeight::get().writes(13 as u64))
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(16 as u64))
 	}
 	fn add_liquidity() -> Weight {
 		Weight::from_ref_time(171_602_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(9 as u64))
-			.saturating_add(T::DbWeight::get().writes(8 as u64))
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
 	}
 	fn remove_liquidity() -> Weight {
 		Weight::from_ref_time(170_846_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(8 as u64))
-			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
 	}
 	fn sell() -> Weight {
-		Weight::from_ref_time(122_125_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(5 as u64))
-			.saturating_add(T::DbWeight::get().writes(4 as u64))
+		Weight::from_ref_time(139_518_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
 	}
 	fn buy() -> Weight {
-		Weight::from_ref_time(121_289_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(5 as u64))
+		Weight::from_ref_time(138_407_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	fn create_pool_with_fee() -> Weight {
+		Weight::from_ref_time(192_375_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(17 as u64))
+	}
+	fn set_pool_fee() -> Weight {
+		Weight::from_ref_time(24_117_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn remove_liquidity_with_limits() -> Weight {
+		Weight::from_ref_time(171_934_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		Weight::from_ref_time(298_713_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(21 as u64))
+			.saturating_add(T::DbWeight::get().writes(20 as u64))
+	}
+	fn remove_liquidity_one_asset() -> Weight {
+		Weight::from_ref_time(297_405_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(20 as u64))
+	}
+	fn flash_swap() -> Weight {
+		Weight::from_ref_time(158_306_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
+	}
+	fn skim() -> Weight {
+		Weight::from_ref_time(62_418_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
 			.saturating_add(T::DbWeight::get().writes(4 as u64))
 	}
+	fn sync() -> Weight {
+		Weight::from_ref_time(31_256_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn set_pool_state() -> Weight {
+		Weight::from_ref_time(27_034_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	fn create_pool() -> Weight {
 		Weight::from_ref_time(189_645_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(11 as u64))
-			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+			.saturating_add(RocksDbWeight::get().reads(16 as u64))
+			.saturating_add(RocksDbWeight::get().writes(16 as u64))
 	}
 	fn add_liquidity() -> Weight {
 		Weight::from_ref_time(171_602_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(9 as u64))
-			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+			.saturating_add(RocksDbWeight::get().reads(13 as u64))
+			.saturating_add(RocksDbWeight::get().writes(12 as u64))
 	}
 	fn remove_liquidity() -> Weight {
 		Weight::from_ref_time(170_846_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(8 as u64))
-			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(12 as u64))
 	}
 	fn sell() -> Weight {
-		Weight::from_ref_time(122_125_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(5 as u64))
-			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+		Weight::from_ref_time(139_518_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64))
 	}
 	fn buy() -> Weight {
-		Weight::from_ref_time(121_289_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+		Weight::from_ref_time(138_407_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+	}
+	fn create_pool_with_fee() -> Weight {
+		Weight::from_ref_time(192_375_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(16 as u64))
+			.saturating_add(RocksDbWeight::get().writes(17 as u64))
+	}
+	fn set_pool_fee() -> Weight {
+		Weight::from_ref_time(24_117_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn remove_liquidity_with_limits() -> Weight {
+		Weight::from_ref_time(171_934_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(12 as u64))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		Weight::from_ref_time(298_713_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(21 as u64))
+			.saturating_add(RocksDbWeight::get().writes(20 as u64))
+	}
+	fn remove_liquidity_one_asset() -> Weight {
+		Weight::from_ref_time(297_405_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(20 as u64))
+			.saturating_add(RocksDbWeight::get().writes(20 as u64))
+	}
+	fn flash_swap() -> Weight {
+		Weight::from_ref_time(158_306_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(15 as u64))
+	}
+	fn skim() -> Weight {
+		Weight::from_ref_time(62_418_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
 			.saturating_add(RocksDbWeight::get().writes(4 as u64))
 	}
+	fn sync() -> Weight {
+		Weight::from_ref_time(31_256_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn set_pool_state() -> Weight {
+		Weight::from_ref_time(27_034_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
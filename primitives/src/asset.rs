@@ -51,7 +51,8 @@ impl AssetPair {
 		}
 	}
 
-	/// Return share token name
+	/// Return share token name derived from asset ids
+	/// Used when assets of the pair have no registry symbols
 	pub fn name(&self) -> Vec<u8> {
 		let mut buf: Vec<u8> = Vec::new();
 
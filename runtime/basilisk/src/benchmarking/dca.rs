@@ -0,0 +1,178 @@
+// This file is part of Basilisk-node
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	AccountId, AssetId, Balance, BlockNumber, Currencies, DCAPriceOraclePeriod, PriceOracle, Runtime, System, DCA, XYK,
+};
+
+use super::*;
+
+use frame_benchmarking::account;
+use frame_benchmarking::BenchmarkError;
+use frame_support::traits::{Get, Hooks};
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+
+use hydradx_traits::router::PoolType;
+use orml_traits::MultiReservableCurrency;
+use pallet_route_executor::Trade;
+use primitives::IntentionType;
+use sp_runtime::Permill;
+use sp_std::vec;
+
+const SEED: u32 = 1;
+pub const UNITS: Balance = 100_000_000_000;
+const MAX_SCHEDULES_PER_BLOCK: u32 = 20;
+
+fn create_account(name: &'static str) -> AccountId {
+	account(name, 0, SEED)
+}
+
+fn setup_pool() -> Result<(AssetId, AssetId), BenchmarkError> {
+	let pool_maker: AccountId = create_account("pool_maker");
+
+	let asset_in =
+		register_asset(b"TST".to_vec(), 0u128).map_err(|_| BenchmarkError::Stop("Failed to register asset"))?;
+	let asset_out =
+		register_asset(b"TST2".to_vec(), 0u128).map_err(|_| BenchmarkError::Stop("Failed to register asset"))?;
+
+	update_balance(asset_in, &pool_maker, 10_000 * UNITS);
+	update_balance(asset_out, &pool_maker, 10_000 * UNITS);
+
+	create_pool(pool_maker.clone(), asset_in, 1_000 * UNITS, asset_out, 1_000 * UNITS);
+
+	// trades are limited by the oracle price, which is provided once the oracle has tracked the pair for a full period
+	System::set_block_number(1);
+	XYK::sell(
+		RawOrigin::Signed(pool_maker).into(),
+		asset_in,
+		asset_out,
+		UNITS,
+		0,
+		false,
+	)
+	.map_err(|_| BenchmarkError::Stop("Failed to sell"))?;
+	PriceOracle::on_finalize(1);
+	System::set_block_number(1 + DCAPriceOraclePeriod::get().as_period());
+
+	Ok((asset_in, asset_out))
+}
+
+fn schedule_sell(
+	who: AccountId,
+	asset_in: AssetId,
+	asset_out: AssetId,
+	start_execution_block: Option<BlockNumber>,
+) -> Result<(), BenchmarkError> {
+	update_balance(asset_in, &who, 100 * UNITS);
+
+	DCA::schedule(
+		RawOrigin::Signed(who).into(),
+		asset_in,
+		asset_out,
+		IntentionType::SELL,
+		UNITS,
+		10,
+		100 * UNITS,
+		Permill::from_percent(10),
+		vec![Trade {
+			pool: PoolType::XYK,
+			asset_in,
+			asset_out,
+		}],
+		start_execution_block,
+	)
+	.map_err(|_| BenchmarkError::Stop("Failed to schedule"))
+}
+
+runtime_benchmarks! {
+	{ Runtime, pallet_dca }
+
+	schedule {
+		let (asset_in, asset_out) = setup_pool()?;
+		let caller: AccountId = create_account("caller");
+	}: {
+		schedule_sell(caller.clone(), asset_in, asset_out, None)?
+	}
+	verify {
+		assert_eq!(<Currencies as MultiReservableCurrency<_>>::reserved_balance(asset_in, &caller), 100 * UNITS);
+	}
+
+	pause {
+		let (asset_in, asset_out) = setup_pool()?;
+		let caller: AccountId = create_account("caller");
+		schedule_sell(caller.clone(), asset_in, asset_out, None)?;
+	}: {
+		DCA::pause(RawOrigin::Signed(caller).into(), 0)?
+	}
+	verify {
+		assert_eq!(DCA::schedules(0).unwrap().status, pallet_dca::ScheduleStatus::Paused);
+	}
+
+	resume {
+		let (asset_in, asset_out) = setup_pool()?;
+		let caller: AccountId = create_account("caller");
+		schedule_sell(caller.clone(), asset_in, asset_out, None)?;
+		DCA::pause(RawOrigin::Signed(caller.clone()).into(), 0)?;
+	}: {
+		DCA::resume(RawOrigin::Signed(caller).into(), 0, None)?
+	}
+	verify {
+		assert_eq!(DCA::schedules(0).unwrap().status, pallet_dca::ScheduleStatus::Active);
+	}
+
+	terminate {
+		let (asset_in, asset_out) = setup_pool()?;
+		let caller: AccountId = create_account("caller");
+		schedule_sell(caller.clone(), asset_in, asset_out, None)?;
+	}: {
+		DCA::terminate(RawOrigin::Signed(caller.clone()).into(), 0)?
+	}
+	verify {
+		assert_eq!(<Currencies as MultiReservableCurrency<_>>::reserved_balance(asset_in, &caller), 0);
+	}
+
+	on_initialize {
+		let n in 1 .. MAX_SCHEDULES_PER_BLOCK;
+
+		let (asset_in, asset_out) = setup_pool()?;
+		let execution_block: BlockNumber = 100;
+		for i in 0..n {
+			let caller: AccountId = account("caller", i, SEED);
+			schedule_sell(caller, asset_in, asset_out, Some(execution_block))?;
+		}
+	}: {
+		DCA::on_initialize(execution_block);
+	}
+	verify {
+		assert!(DCA::schedule_ids_per_block(execution_block).is_empty());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<crate::Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,111 @@
+// This file is part of Basilisk-node
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{AccountId, AssetId, Balance, Currencies, LimitOrders, Runtime};
+
+use super::*;
+
+use frame_benchmarking::account;
+use frame_benchmarking::BenchmarkError;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+
+use orml_traits::MultiReservableCurrency;
+use primitives::IntentionType;
+
+const SEED: u32 = 1;
+pub const UNITS: Balance = 100_000_000_000;
+
+fn create_account(name: &'static str) -> AccountId {
+	account(name, 0, SEED)
+}
+
+fn setup_pool() -> Result<(AssetId, AssetId), BenchmarkError> {
+	let pool_maker: AccountId = create_account("pool_maker");
+
+	let asset_a =
+		register_asset(b"TST".to_vec(), 0u128).map_err(|_| BenchmarkError::Stop("Failed to register asset"))?;
+	let asset_b =
+		register_asset(b"TST2".to_vec(), 0u128).map_err(|_| BenchmarkError::Stop("Failed to register asset"))?;
+
+	update_balance(asset_a, &pool_maker, 10_000 * UNITS);
+	update_balance(asset_b, &pool_maker, 10_000 * UNITS);
+
+	create_pool(pool_maker, asset_a, 1_000 * UNITS, asset_b, 2_000 * UNITS);
+
+	Ok((asset_a, asset_b))
+}
+
+runtime_benchmarks! {
+	{ Runtime, pallet_limit_orders }
+
+	place_order {
+		let (asset_in, asset_out) = setup_pool()?;
+
+		let caller: AccountId = create_account("caller");
+		update_balance(asset_in, &caller, 1_000 * UNITS);
+	}: {
+		LimitOrders::place_order(RawOrigin::Signed(caller.clone()).into(), asset_in, asset_out, IntentionType::SELL, 100 * UNITS, 210 * UNITS)?
+	}
+	verify {
+		assert_eq!(<Currencies as MultiReservableCurrency<_>>::reserved_balance(asset_in, &caller), 100 * UNITS);
+	}
+
+	cancel_order {
+		let (asset_in, asset_out) = setup_pool()?;
+
+		let caller: AccountId = create_account("caller");
+		update_balance(asset_in, &caller, 1_000 * UNITS);
+
+		LimitOrders::place_order(RawOrigin::Signed(caller.clone()).into(), asset_in, asset_out, IntentionType::SELL, 100 * UNITS, 210 * UNITS)?;
+	}: {
+		LimitOrders::cancel_order(RawOrigin::Signed(caller.clone()).into(), 0)?
+	}
+	verify {
+		assert_eq!(<Currencies as MultiReservableCurrency<_>>::reserved_balance(asset_in, &caller), 0);
+	}
+
+	// The order is filled partially, which is the most expensive fill as the order is updated.
+	fill_order {
+		let (asset_in, asset_out) = setup_pool()?;
+
+		let caller: AccountId = create_account("caller");
+		update_balance(asset_in, &caller, 1_000 * UNITS);
+
+		LimitOrders::place_order(RawOrigin::Signed(caller.clone()).into(), asset_in, asset_out, IntentionType::SELL, 1_000 * UNITS, 1_900 * UNITS)?;
+	}: {
+		LimitOrders::fill_order(0)?
+	}
+	verify {
+		let reserved = <Currencies as MultiReservableCurrency<_>>::reserved_balance(asset_in, &caller);
+		assert!(reserved > 0 && reserved < 1_000 * UNITS);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<crate::Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -1,7 +1,9 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 pub mod currencies;
+pub mod dca;
 pub mod duster;
+pub mod limit_orders;
 pub mod multi_payment;
 pub mod route_executor;
 pub mod tokens;
@@ -15,14 +15,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{AccountId, Runtime};
+use crate::{AccountId, FeePriceOraclePeriod, PriceOracle, Runtime, System, XYK};
 use primitives::Price;
+use sp_runtime::FixedPointNumber;
 
 use super::*;
 
 use frame_benchmarking::account;
 use frame_benchmarking::BenchmarkError;
 use frame_support::assert_ok;
+use frame_support::traits::{Get, OnFinalize};
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 
@@ -72,13 +74,18 @@ runtime_benchmarks! {
 		update_balance(asset_out, &maker, 2_000_000_000_000_000);
 		update_balance(asset_id, &maker, 2_000_000_000_000_000);
 
-		create_pool(maker, asset_out, 1_000_000_000_000_000, asset_id, 500_000_000_000_000);
+		create_pool(maker.clone(), asset_out, 1_000_000_000_000_000, asset_id, 500_000_000_000_000);
+
+		// the oracle provides a price once it has tracked the pair for a full period
+		System::set_block_number(1);
+		assert_ok!(XYK::sell(RawOrigin::Signed(maker).into(), asset_id, asset_out, 1_000_000_000_000, 0, false));
+		PriceOracle::on_finalize(1);
+		System::set_block_number(1 + FeePriceOraclePeriod::get().as_period());
 
 	}: { <Runtime as pallet_transaction_multi_payment::Config>::SpotPriceProvider::spot_price(asset_id, asset_out) }
 	verify{
-		assert_eq!(<Runtime as pallet_transaction_multi_payment::Config>::SpotPriceProvider::spot_price(asset_id, asset_out),
-			Some(Price::from((2,1))));
-
+		let price = <Runtime as pallet_transaction_multi_payment::Config>::SpotPriceProvider::spot_price(asset_id, asset_out);
+		assert!(price > Some(Price::saturating_from_rational(19, 10)) && price < Some(Price::from(2)));
 	}
 }
 
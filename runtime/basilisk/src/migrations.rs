@@ -25,3 +25,28 @@ impl OnRuntimeUpgrade for OnRuntimeUpgradeMigration {
 		Ok(())
 	}
 }
+
+/// Initialize stored reserves of existing XYK pools from balances of the pool accounts.
+pub struct XykPoolReservesMigration;
+impl OnRuntimeUpgrade for XykPoolReservesMigration {
+	fn on_runtime_upgrade() -> Weight {
+		pallet_xyk::migration::init_pool_reserves::<Runtime>()
+	}
+}
+
+/// Give share tokens of existing XYK pools human-readable name, symbol and decimals
+/// derived from registry metadata of the pool assets. Has to run after `XykPoolReservesMigration`.
+pub struct XykShareTokenMetadataMigration;
+impl OnRuntimeUpgrade for XykShareTokenMetadataMigration {
+	fn on_runtime_upgrade() -> Weight {
+		pallet_xyk::migration::update_share_token_metadata::<Runtime>()
+	}
+}
+
+/// Add empty graduation and the default repay fee to existing LBP pools.
+pub struct LbpPoolDataMigration;
+impl OnRuntimeUpgrade for LbpPoolDataMigration {
+	fn on_runtime_upgrade() -> Weight {
+		pallet_lbp::migration::migrate_to_v2::<Runtime>()
+	}
+}
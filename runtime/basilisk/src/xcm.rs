@@ -104,7 +104,8 @@ impl Config for XcmConfig {
 	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
 	// We calculate weight fees the same way as for regular extrinsics and use the prices and choice
 	// of accepted currencies of the transaction payment pallet. Fees go to the same fee receiver as
-	// configured in `MultiTransactionPayment`.
+	// configured in `MultiTransactionPayment`. Assets which are not accepted by `MultiTransactionPayment`
+	// have no price and cannot be used to buy execution.
 	type Trader = MultiCurrencyTrader<
 		AssetId,
 		Balance,
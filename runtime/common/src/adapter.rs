@@ -1,11 +1,21 @@
 use frame_support::dispatch::DispatchError;
 use frame_support::sp_runtime::DispatchResult;
-use frame_support::traits::BalanceStatus;
+use frame_support::traits::{BalanceStatus, Get};
+use frame_support::weights::Weight;
+use frame_system::RawOrigin;
+use hydradx_traits::pools::SpotPriceProvider;
 use orml_traits::currency::TransferAll;
 use orml_traits::{
 	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
 	NamedMultiReservableCurrency,
 };
+use pallet_asset_registry::AssetType;
+use pallet_limit_orders::PoolReserves;
+use pallet_stableswap::AssetDecimals;
+use pallet_xyk::weights::WeightInfo as XykWeightInfo;
+use pallet_xyk::ShareTokenMetadata;
+use primitives::{AssetId, Balance};
+use sp_std::vec::Vec;
 
 pub struct OrmlTokensAdapter<T>(sp_std::marker::PhantomData<T>);
 
@@ -280,3 +290,110 @@ impl<T: orml_tokens::Config> NamedMultiReservableCurrency<T::AccountId> for Orml
 		)
 	}
 }
+
+/// Metadata of assets and XYK share tokens kept in the asset registry.
+pub struct AssetRegistryMetadata<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: pallet_asset_registry::Config<AssetId = AssetId>> ShareTokenMetadata<AssetId> for AssetRegistryMetadata<T> {
+	fn metadata(asset_id: AssetId) -> Option<(Vec<u8>, u8)> {
+		pallet_asset_registry::Pallet::<T>::asset_metadata(asset_id)
+			.map(|metadata| (metadata.symbol.into_inner(), metadata.decimals))
+	}
+
+	fn share_token_assets(share_token: AssetId) -> Option<(AssetId, AssetId)> {
+		match pallet_asset_registry::Pallet::<T>::assets(share_token).map(|details| details.asset_type) {
+			Some(AssetType::PoolShare(asset_a, asset_b)) => Some((asset_a, asset_b)),
+			_ => None,
+		}
+	}
+
+	fn set_share_token_metadata(share_token: AssetId, name: Vec<u8>, symbol: Vec<u8>, decimals: u8) -> DispatchResult {
+		let details = pallet_asset_registry::Pallet::<T>::assets(share_token)
+			.ok_or(pallet_asset_registry::Error::<T>::AssetNotFound)?;
+
+		// share tokens are updated on behalf of the chain, `RegistryOrigin` has to accept root
+		if details.name.as_slice() != name.as_slice() {
+			pallet_asset_registry::Pallet::<T>::update(
+				RawOrigin::Root.into(),
+				share_token,
+				name,
+				details.asset_type,
+				None,
+			)?;
+		}
+
+		pallet_asset_registry::Pallet::<T>::set_metadata(RawOrigin::Root.into(), share_token, symbol, decimals)
+	}
+
+	fn string_limit() -> u32 {
+		T::StringLimit::get()
+	}
+}
+
+/// Spot price given by `P`, or by `F` when `P` has no price of the pair.
+pub struct PriceProviderWithFallback<P, F>(sp_std::marker::PhantomData<(P, F)>);
+
+impl<AssetId: Copy, Price, P, F> SpotPriceProvider<AssetId> for PriceProviderWithFallback<P, F>
+where
+	P: SpotPriceProvider<AssetId, Price = Price>,
+	F: SpotPriceProvider<AssetId, Price = Price>,
+{
+	type Price = Price;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		P::pair_exists(asset_a, asset_b) || F::pair_exists(asset_a, asset_b)
+	}
+
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		P::spot_price(asset_a, asset_b).or_else(|| F::spot_price(asset_a, asset_b))
+	}
+}
+
+impl<T: pallet_asset_registry::Config<AssetId = AssetId>> AssetDecimals<AssetId> for AssetRegistryMetadata<T> {
+	fn decimals(asset_id: AssetId) -> Option<u8> {
+		pallet_asset_registry::Pallet::<T>::asset_metadata(asset_id).map(|metadata| metadata.decimals)
+	}
+}
+
+/// Reserves of XYK pools kept by the XYK pallet, unavailable while a pool is locked by a flash swap.
+pub struct XykPoolReserves<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: pallet_xyk::Config> PoolReserves<T::AccountId> for XykPoolReserves<T> {
+	fn pool_reserve(pool_account: &T::AccountId, asset: AssetId) -> Option<Balance> {
+		if pallet_xyk::Pallet::<T>::is_pool_locked(pool_account) {
+			return None;
+		}
+		Some(pallet_xyk::Pallet::<T>::pool_reserve(pool_account, asset))
+	}
+}
+
+/// Seeds XYK pools with liquidity of graduated LBP sales.
+pub struct XYKGraduation<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: pallet_xyk::Config> pallet_lbp::GraduationHandler<T::AccountId> for XYKGraduation<T> {
+	fn create_pool(
+		who: &T::AccountId,
+		asset_a: AssetId,
+		amount_a: Balance,
+		asset_b: AssetId,
+		amount_b: Balance,
+	) -> Result<(AssetId, Balance), DispatchError> {
+		pallet_xyk::Pallet::<T>::create_pool(
+			RawOrigin::Signed(who.clone()).into(),
+			asset_a,
+			amount_a,
+			asset_b,
+			amount_b,
+		)?;
+
+		let pair_account = pallet_xyk::Pallet::<T>::pair_account_from_assets(asset_a, asset_b);
+		let share_token = pallet_xyk::Pallet::<T>::share_token(&pair_account);
+		let shares = <T as pallet_xyk::Config>::Currency::free_balance(share_token, who);
+
+		Ok((share_token, shares))
+	}
+
+	fn create_pool_weight() -> Weight {
+		<T as pallet_xyk::Config>::WeightInfo::create_pool()
+	}
+}
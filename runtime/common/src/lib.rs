@@ -19,6 +19,7 @@
 
 pub mod adapter;
 pub mod locked_balance;
+pub mod route_finder;
 pub mod weights;
 
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -163,12 +164,54 @@ parameter_types! {
 // pallet xyk
 parameter_types! {
 	pub ExchangeFee: (u32, u32) = (3, 1_000);
+	pub const MinPoolFee: (u32, u32) = (1, 10_000);
+	pub const MaxPoolFee: (u32, u32) = (1, 10);
+	pub const ProtocolFee: (u32, u32) = (1, 5);
 	pub const MinTradingLimit: Balance = MIN_TRADING_LIMIT;
 	pub const MinPoolLiquidity: Balance = MIN_POOL_LIQUIDITY;
 	pub const MaxInRatio: u128 = MAX_IN_RATIO;
 	pub const MaxOutRatio: u128 = MAX_OUT_RATIO;
 	pub const RegistryStrLimit: u32 = 32;
 	pub const DiscountedFee: (u32, u32) = DISCOUNTED_FEE;
+	// one day of relay chain blocks
+	pub const VolumePeriodLength: BlockNumber = 14_400;
+	pub const MaxVolumePeriods: u32 = 30;
+	pub const MaxPriceChangePerBlock: Option<(u32, u32)> = Some((50, 100));
+	pub const MaxLiquidityRemovalPerBlock: Option<(u32, u32)> = Some((50, 100));
+}
+
+// pallet price oracle
+parameter_types! {
+	pub const XYKOracleSource: pallet_price_oracle::Source = *b"xyk_pool";
+	pub const LBPOracleSource: pallet_price_oracle::Source = *b"lbp_pool";
+	pub const MaxUniqueOracleEntries: u32 = 1_000;
+	pub const FeePriceOraclePeriod: pallet_price_oracle::OraclePeriod = pallet_price_oracle::OraclePeriod::TenMinutes;
+}
+
+// pallet limit orders
+parameter_types! {
+	pub const MaxLimitOrdersPerBlock: u32 = 20;
+}
+
+// pallet dca
+parameter_types! {
+	pub const MaxSchedulesPerBlock: u32 = 20;
+	pub const MaxConsecutiveFailures: u8 = 3;
+	pub const DCAPriceOraclePeriod: pallet_price_oracle::OraclePeriod = pallet_price_oracle::OraclePeriod::TenMinutes;
+}
+
+// pallet stableswap
+parameter_types! {
+	pub const StableswapPalletId: PalletId = PalletId(*b"stblswap");
+	pub const StableswapMinAmplification: u16 = 2;
+	pub const StableswapMaxAmplification: u16 = 10_000;
+	pub const StableswapMaxPoolFee: (u32, u32) = (1, 10);
+}
+
+// pallet weighted pool
+parameter_types! {
+	pub const WeightedPoolMinWeight: u32 = 2_000_000;
+	pub const WeightedPoolMaxPoolFee: (u32, u32) = (1, 10);
 }
 
 // pallet duster
@@ -179,6 +222,8 @@ parameter_types! {
 // pallet lbp
 parameter_types! {
 	pub LBPExchangeFee: (u32, u32) = (2, 1_000);
+	pub const LBPMinRepayFee: (u32, u32) = (0, 1);
+	pub const LBPMaxRepayFee: (u32, u32) = (2, 10);
 }
 
 // pallet democracy
@@ -0,0 +1,253 @@
+// This file is part of Basilisk-node.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Best route discovery for the route executor.
+//!
+//! All XYK pools which are not frozen, running LBP pools, weighted pools and stableswap pools are searched for routes
+//! of at most `MaxNumberOfTrades` trades
+//! which do not visit any asset twice. Every route is scored by the `TradeExecution` implementation
+//! of the route executor, so the amounts are the same as when the route is submitted.
+//!
+//! The search is bounded: routes are extended one trade per hop and only the best route reaching an asset is kept
+//! in every hop, so at most `MaxNumberOfTrades` times the number of available trades are calculated.
+
+use crate::{AssetId, Balance};
+use frame_support::traits::Get;
+use hydradx_traits::{router::TradeExecution, AMM};
+use pallet_router_rpc_runtime_api::{PoolType, RouteQuote, Trade};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, vec::Vec};
+
+pub struct RouteFinder<T>(PhantomData<T>);
+
+impl<T> RouteFinder<T>
+where
+	T: pallet_xyk::Config
+		+ pallet_lbp::Config
+		+ pallet_stableswap::Config
+		+ pallet_weighted_pool::Config
+		+ pallet_route_executor::Config<AssetId = AssetId, Balance = Balance>,
+{
+	/// Find the route selling `amount_in` of `asset_in` for the biggest amount of `asset_out`.
+	pub fn find_best_sell_route(
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+	) -> Option<RouteQuote<AssetId, Balance>> {
+		if asset_in == asset_out || amount_in == 0 {
+			return None;
+		}
+
+		Self::search_sell(&Self::available_trades(), asset_in, amount_in, asset_out)
+	}
+
+	/// Find the route buying `amount_out` of `asset_out` for the smallest amount of `asset_in`.
+	pub fn find_best_buy_route(
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+	) -> Option<RouteQuote<AssetId, Balance>> {
+		if asset_in == asset_out || amount_out == 0 {
+			return None;
+		}
+
+		Self::search_buy(&Self::available_trades(), asset_in, asset_out, amount_out)
+	}
+
+	/// Extends routes forward from `asset_in` one trade per hop and keeps the route with the biggest amount out.
+	///
+	/// Only the route with the biggest amount received is kept for every asset reached in a hop, so every trade
+	/// is calculated at most once per hop.
+	fn search_sell(
+		trades: &[Trade<AssetId>],
+		asset_in: AssetId,
+		amount_in: Balance,
+		asset_out: AssetId,
+	) -> Option<RouteQuote<AssetId, Balance>> {
+		let mut frontier = BTreeMap::new();
+		frontier.insert(asset_in, (amount_in, Vec::new()));
+		let mut best: Option<RouteQuote<AssetId, Balance>> = None;
+
+		for _ in 0..Self::max_number_of_trades() {
+			let mut next: BTreeMap<AssetId, (Balance, Vec<Trade<AssetId>>)> = BTreeMap::new();
+
+			for (asset, (amount, route)) in frontier.iter() {
+				for trade in trades.iter().filter(|t| t.asset_in == *asset) {
+					if route.iter().any(|t| t.asset_in == trade.asset_out) {
+						continue;
+					}
+
+					let received = match <T as pallet_route_executor::Config>::AMM::calculate_sell(
+						Self::pool_type(trade.pool),
+						trade.asset_in,
+						trade.asset_out,
+						*amount,
+					) {
+						Ok(received) if received > 0 => received,
+						_ => continue,
+					};
+
+					if trade.asset_out == asset_out {
+						if best.as_ref().map_or(true, |b| received > b.amount_out) {
+							let mut route = route.clone();
+							route.push(*trade);
+							best = Some(RouteQuote {
+								route,
+								amount_in,
+								amount_out: received,
+							});
+						}
+					} else if next.get(&trade.asset_out).map_or(true, |(r, _)| received > *r) {
+						let mut route = route.clone();
+						route.push(*trade);
+						next.insert(trade.asset_out, (received, route));
+					}
+				}
+			}
+
+			frontier = next;
+		}
+
+		best
+	}
+
+	/// Extends routes backward from `asset_out` one trade per hop and keeps the route with the smallest amount in.
+	///
+	/// Only the route with the smallest amount required is kept for every asset reached in a hop, so every trade
+	/// is calculated at most once per hop. Routes are built from asset out and reversed when a quote is kept.
+	fn search_buy(
+		trades: &[Trade<AssetId>],
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+	) -> Option<RouteQuote<AssetId, Balance>> {
+		let mut frontier = BTreeMap::new();
+		frontier.insert(asset_out, (amount_out, Vec::new()));
+		let mut best: Option<RouteQuote<AssetId, Balance>> = None;
+
+		for _ in 0..Self::max_number_of_trades() {
+			let mut next: BTreeMap<AssetId, (Balance, Vec<Trade<AssetId>>)> = BTreeMap::new();
+
+			for (asset, (amount, route)) in frontier.iter() {
+				for trade in trades.iter().filter(|t| t.asset_out == *asset) {
+					if route.iter().any(|t| t.asset_out == trade.asset_in) {
+						continue;
+					}
+
+					let required = match <T as pallet_route_executor::Config>::AMM::calculate_buy(
+						Self::pool_type(trade.pool),
+						trade.asset_in,
+						trade.asset_out,
+						*amount,
+					) {
+						Ok(required) => required,
+						Err(_) => continue,
+					};
+
+					if trade.asset_in == asset_in {
+						if best.as_ref().map_or(true, |b| required < b.amount_in) {
+							best = Some(RouteQuote {
+								route: sp_std::iter::once(*trade).chain(route.iter().rev().cloned()).collect(),
+								amount_in: required,
+								amount_out,
+							});
+						}
+					} else if next.get(&trade.asset_in).map_or(true, |(r, _)| required < *r) {
+						let mut route = route.clone();
+						route.push(*trade);
+						next.insert(trade.asset_in, (required, route));
+					}
+				}
+			}
+
+			frontier = next;
+		}
+
+		best
+	}
+
+	/// Both directions of every XYK pool which is neither frozen nor locked by a flash swap, every running LBP pool,
+	/// every weighted pool and every pair of assets of stableswap pools.
+	///
+	/// Weighted pools are traded as `PoolType::Stableswap` identified by the share token of the pool.
+	fn available_trades() -> Vec<Trade<AssetId>> {
+		let xyk_pairs = pallet_xyk::Pallet::<T>::pool_accounts()
+			.into_iter()
+			.filter(|pool| {
+				pallet_xyk::Pallet::<T>::pool_state(pool).is_trading_allowed()
+					&& !pallet_xyk::Pallet::<T>::is_pool_locked(pool)
+			})
+			.filter_map(|pool| pallet_xyk::Pallet::<T>::get_pool_assets(&pool))
+			.filter(|assets| assets.len() == 2)
+			.map(|assets| (PoolType::XYK, assets[0], assets[1]));
+
+		let lbp_pairs = pallet_lbp::PoolData::<T>::iter_values()
+			.filter(|pool| pallet_lbp::Pallet::<T>::is_pool_running(pool))
+			.map(|pool| (PoolType::LBP, pool.assets.0, pool.assets.1));
+
+		let weighted_pairs = pallet_weighted_pool::Pallet::<T>::pool_accounts()
+			.into_iter()
+			.filter_map(|pool| pallet_weighted_pool::Pallet::<T>::pools(pool))
+			.map(|pool| (PoolType::Stableswap(pool.share_token), pool.assets.0, pool.assets.1));
+
+		let stableswap_pairs = pallet_stableswap::Pallet::<T>::pool_ids()
+			.into_iter()
+			.filter_map(|pool_id| {
+				pallet_stableswap::Pallet::<T>::pools(pool_id).map(|pool| (pool_id, pool.asset_ids()))
+			})
+			.flat_map(|(pool_id, assets)| {
+				let mut pairs = Vec::new();
+				for (i, asset_a) in assets.iter().enumerate() {
+					for asset_b in assets.iter().skip(i + 1) {
+						pairs.push((PoolType::Stableswap(pool_id), *asset_a, *asset_b));
+					}
+				}
+				pairs
+			});
+
+		xyk_pairs
+			.chain(lbp_pairs)
+			.chain(weighted_pairs)
+			.chain(stableswap_pairs)
+			.flat_map(|(pool, asset_a, asset_b)| {
+				[
+					Trade {
+						pool,
+						asset_in: asset_a,
+						asset_out: asset_b,
+					},
+					Trade {
+						pool,
+						asset_in: asset_b,
+						asset_out: asset_a,
+					},
+				]
+			})
+			.collect()
+	}
+
+	fn max_number_of_trades() -> usize {
+		<T as pallet_route_executor::Config>::MaxNumberOfTrades::get().into()
+	}
+
+	fn pool_type(pool: PoolType<AssetId>) -> hydradx_traits::router::PoolType<AssetId> {
+		match pool {
+			PoolType::XYK => hydradx_traits::router::PoolType::XYK,
+			PoolType::LBP => hydradx_traits::router::PoolType::LBP,
+			PoolType::Stableswap(pool_id) => hydradx_traits::router::PoolType::Stableswap(pool_id),
+		}
+	}
+}
@@ -2,14 +2,18 @@ pub mod asset_registry;
 pub mod balances;
 pub mod collator_selection;
 pub mod currencies;
+pub mod dca;
 pub mod democracy;
 pub mod duster;
 pub mod lbp;
+pub mod limit_orders;
 pub mod marketplace;
 pub mod nft;
 pub mod payment;
+pub mod price_oracle;
 pub mod route_executor;
 pub mod scheduler;
+pub mod stableswap;
 pub mod system;
 pub mod timestamp;
 pub mod tokens;
@@ -17,6 +21,7 @@ pub mod transaction_pause;
 pub mod treasury;
 pub mod utility;
 pub mod vesting;
+pub mod weighted_pool;
 pub mod xcmp_queue;
 pub mod xyk;
 pub mod xyk_liquidity_mining;
@@ -52,27 +52,72 @@ pub struct BasiliskWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for BasiliskWeight<T> {
 	fn create_pool() -> Weight {
 		Weight::from_ref_time(216_229_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(17 as u64))
-			.saturating_add(T::DbWeight::get().writes(16 as u64))
+			.saturating_add(T::DbWeight::get().reads(22 as u64))
+			.saturating_add(T::DbWeight::get().writes(19 as u64))
 	}
 	fn add_liquidity() -> Weight {
 		Weight::from_ref_time(175_194_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(13 as u64))
-			.saturating_add(T::DbWeight::get().writes(8 as u64))
+			.saturating_add(T::DbWeight::get().reads(17 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
 	}
 	fn remove_liquidity() -> Weight {
 		Weight::from_ref_time(163_145_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(12 as u64))
-			.saturating_add(T::DbWeight::get().writes(7 as u64))
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
 	}
 	fn sell() -> Weight {
-		Weight::from_ref_time(134_820_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(10 as u64))
-			.saturating_add(T::DbWeight::get().writes(4 as u64))
+		Weight::from_ref_time(151_064_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
 	}
 	fn buy() -> Weight {
-		Weight::from_ref_time(135_347_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(10 as u64))
+		Weight::from_ref_time(152_223_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	fn create_pool_with_fee() -> Weight {
+		Weight::from_ref_time(219_481_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(22 as u64))
+			.saturating_add(T::DbWeight::get().writes(20 as u64))
+	}
+	fn set_pool_fee() -> Weight {
+		Weight::from_ref_time(27_391_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn remove_liquidity_with_limits() -> Weight {
+		Weight::from_ref_time(164_287_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
+	fn add_liquidity_single_asset() -> Weight {
+		Weight::from_ref_time(312_648_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(26 as u64))
+			.saturating_add(T::DbWeight::get().writes(20 as u64))
+	}
+	fn remove_liquidity_one_asset() -> Weight {
+		Weight::from_ref_time(301_872_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(25 as u64))
+			.saturating_add(T::DbWeight::get().writes(20 as u64))
+	}
+	fn flash_swap() -> Weight {
+		Weight::from_ref_time(171_540_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(23 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
+	}
+	fn skim() -> Weight {
+		Weight::from_ref_time(68_735_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
 			.saturating_add(T::DbWeight::get().writes(4 as u64))
 	}
+	fn sync() -> Weight {
+		Weight::from_ref_time(34_102_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn set_pool_state() -> Weight {
+		Weight::from_ref_time(29_118_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
@@ -129,7 +129,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("testing-basilisk"),
 	impl_name: create_runtime_str!("testing-basilisk"),
 	authoring_version: 1,
-	spec_version: 84,
+	spec_version: 85,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -145,7 +145,9 @@ pub fn native_version() -> NativeVersion {
 	}
 }
 
-use common_runtime::adapter::OrmlTokensAdapter;
+use common_runtime::adapter::{
+	AssetRegistryMetadata, OrmlTokensAdapter, PriceProviderWithFallback, XYKGraduation, XykPoolReserves,
+};
 use common_runtime::locked_balance::MultiCurrencyLockedBalance;
 use primitives::{CollectionId, ItemId};
 use smallvec::smallvec;
@@ -317,7 +319,8 @@ impl pallet_transaction_multi_payment::Config for Runtime {
 	type Event = Event;
 	type AcceptedCurrencyOrigin = SuperMajorityTechCommitteeOrRoot;
 	type Currencies = Currencies;
-	type SpotPriceProvider = pallet_xyk::XYKSpotPrice<Runtime>;
+	// Prices of fee currencies follow the XYK oracle, falling back to the prices set with the accepted currency
+	type SpotPriceProvider = pallet_price_oracle::OraclePriceProvider<Runtime, XYKOracleSource, FeePriceOraclePeriod>;
 	type WeightInfo = weights::payment::BasiliskWeight<Runtime>;
 	type WithdrawFeeForSetCurrency = MultiPaymentCurrencySetFee;
 	type WeightToFee = WeightToFee;
@@ -343,7 +346,10 @@ impl InstanceFilter<Call> for ProxyType {
 					| Call::Treasury(..) | Call::Tips(..)
 					| Call::Utility(..)
 			),
-			ProxyType::Exchange => matches!(c, Call::XYK(..) | Call::LBP(..) | Call::NFT(..)),
+			ProxyType::Exchange => matches!(
+				c,
+				Call::XYK(..) | Call::LBP(..) | Call::Stableswap(..) | Call::WeightedPool(..) | Call::NFT(..)
+			),
 			// Transfer group doesn't include cross-chain transfers
 			ProxyType::Transfer => matches!(c, Call::Balances(..) | Call::Currencies(..) | Call::Tokens(..)),
 		}
@@ -438,34 +444,119 @@ impl pallet_asset_registry::Config for Runtime {
 impl pallet_xyk::Config for Runtime {
 	type Event = Event;
 	type AssetRegistry = AssetRegistry;
+	type AssetMetadata = AssetRegistryMetadata<Runtime>;
 	type AssetPairAccountId = AssetPairAccountId<Self>;
 	type Currency = Currencies;
+	type BalanceInspect = MultiInspectAdapter<AccountId, AssetId, Balance, Balances, Tokens, NativeAssetId>;
 	type NativeAssetId = NativeAssetId;
 	type WeightInfo = weights::xyk::BasiliskWeight<Runtime>;
 	type GetExchangeFee = ExchangeFee;
+	type MinPoolFee = MinPoolFee;
+	type MaxPoolFee = MaxPoolFee;
+	type UpdatePoolFeeOrigin = SuperMajorityTechCommitteeOrRoot;
+	type UpdateReservesOrigin = SuperMajorityTechCommitteeOrRoot;
+	type UpdatePoolStateOrigin = SuperMajorityTechCommitteeOrRoot;
+	type ProtocolFee = ProtocolFee;
+	type ProtocolFeeReceiver = TreasuryAccount;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
 	type CanCreatePool = pallet_lbp::DisallowWhenLBPPoolRunning<Runtime>;
-	type AMMHandler = ();
+	type AMMHandler =
+		pallet_price_oracle::OnActivityHandler<Runtime, XYKOracleSource, pallet_xyk::XYKReservePrice<Runtime>>;
 	type DiscountedFee = DiscountedFee;
 	type NonDustableWhitelistHandler = Duster;
+	type BlockNumberProvider = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>;
+	type VolumePeriodLength = VolumePeriodLength;
+	type MaxVolumePeriods = MaxVolumePeriods;
+	type MaxPriceChangePerBlock = MaxPriceChangePerBlock;
+	type MaxLiquidityRemovalPerBlock = MaxLiquidityRemovalPerBlock;
+	type Call = Call;
 }
 
 impl pallet_lbp::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Currencies;
+	type BalanceInspect = MultiInspectAdapter<AccountId, AssetId, Balance, Balances, Tokens, NativeAssetId>;
 	type LockedBalance = MultiCurrencyLockedBalance<Runtime>;
 	type CreatePoolOrigin = SuperMajorityTechCommitteeOrRoot;
+	type SaleAdminOrigin = SuperMajorityTechCommitteeOrRoot;
 	type LBPWeightFunction = pallet_lbp::LBPWeightFunction;
 	type AssetPairAccountId = AssetPairAccountId<Self>;
 	type MinTradingLimit = MinTradingLimit;
 	type MinPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MinRepayFee = LBPMinRepayFee;
+	type MaxRepayFee = LBPMaxRepayFee;
 	type WeightInfo = weights::lbp::BasiliskWeight<Runtime>;
 	type BlockNumberProvider = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>;
+	type AMMHandler =
+		pallet_price_oracle::OnActivityHandler<Runtime, LBPOracleSource, pallet_lbp::LBPSpotPrice<Runtime>>;
+	type GraduationHandler = XYKGraduation<Runtime>;
+}
+
+impl pallet_stableswap::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type AssetRegistry = AssetRegistry;
+	type AssetDecimals = AssetRegistryMetadata<Runtime>;
+	type AuthorityOrigin = SuperMajorityTechCommitteeOrRoot;
+	type PalletId = StableswapPalletId;
+	type MinAmplification = StableswapMinAmplification;
+	type MaxAmplification = StableswapMaxAmplification;
+	type MaxPoolFee = StableswapMaxPoolFee;
+	type MinTradingLimit = MinTradingLimit;
+	type MinPoolLiquidity = MinPoolLiquidity;
+	type WeightInfo = weights::stableswap::BasiliskWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = StableswapBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct StableswapBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_stableswap::BenchmarkHelper<AssetId> for StableswapBenchmarkHelper {
+	fn register_asset(name: Vec<u8>, decimals: u8) -> Result<AssetId, sp_runtime::DispatchError> {
+		let asset_id = AssetRegistry::register_asset(
+			AssetRegistry::to_bounded_name(name.clone())?,
+			pallet_asset_registry::AssetType::<AssetId>::Token,
+			1,
+		)?;
+		AssetRegistry::set_metadata(RawOrigin::Root.into(), asset_id, name, decimals)?;
+		Ok(asset_id)
+	}
+}
+
+impl pallet_weighted_pool::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type AssetRegistry = AssetRegistry;
+	type AssetPairAccountId = AssetPairAccountId<Self>;
+	type CanCreatePool = pallet_lbp::DisallowWhenLBPPoolRunning<Runtime>;
+	type MinWeight = WeightedPoolMinWeight;
+	type MaxPoolFee = WeightedPoolMaxPoolFee;
+	type MinTradingLimit = MinTradingLimit;
+	type MinPoolLiquidity = MinPoolLiquidity;
+	type MaxInRatio = MaxInRatio;
+	type MaxOutRatio = MaxOutRatio;
+	type WeightInfo = weights::weighted_pool::BasiliskWeight<Runtime>;
+}
+
+impl pallet_price_oracle::Config for Runtime {
+	type WeightInfo = weights::price_oracle::BasiliskWeight<Runtime>;
+	type MaxUniqueEntries = MaxUniqueOracleEntries;
+}
+
+impl pallet_limit_orders::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type AMM = XYK;
+	type PoolReserves = XykPoolReserves<Runtime>;
+	type MaxOrdersPerBlock = MaxLimitOrdersPerBlock;
+	type WeightInfo = weights::limit_orders::BasiliskWeight<Runtime>;
 }
 
 // Parachain Config
@@ -893,10 +984,28 @@ impl pallet_route_executor::Config for Runtime {
 	type Balance = Balance;
 	type MaxNumberOfTrades = MaxNumberOfTrades;
 	type Currency = MultiInspectAdapter<AccountId, AssetId, Balance, Balances, Tokens, NativeAssetId>;
-	type AMM = (XYK, LBP);
+	// weighted pools are traded as stableswap pools identified by their share token, so they are tried first
+	type AMM = (XYK, LBP, WeightedPool, Stableswap);
 	type WeightInfo = common_runtime::weights::route_executor::BasiliskWeight<Runtime>;
 }
 
+impl pallet_dca::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type MaxSchedulesPerBlock = MaxSchedulesPerBlock;
+	type MaxConsecutiveFailures = MaxConsecutiveFailures;
+	type PriceProvider = PriceProviderWithFallback<
+		pallet_price_oracle::OraclePriceProvider<Runtime, XYKOracleSource, DCAPriceOraclePeriod>,
+		pallet_price_oracle::OraclePriceProvider<Runtime, LBPOracleSource, DCAPriceOraclePeriod>,
+	>;
+	type WeightInfo = weights::dca::BasiliskWeight<Runtime>;
+}
+
+impl pallet_router_extensions::Config for Runtime {
+	type BlockNumberProvider = cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>;
+	type BalanceInspect = MultiInspectAdapter<AccountId, AssetId, Balance, Balances, Tokens, NativeAssetId>;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -954,6 +1063,12 @@ construct_runtime!(
 
 		XYKLiquidityMining: pallet_xyk_liquidity_mining = 112,
 		XYKWarehouseLM: warehouse_liquidity_mining::<Instance1> = 113,
+		PriceOracle: pallet_price_oracle = 114,
+		LimitOrders: pallet_limit_orders = 115,
+		DCA: pallet_dca = 116,
+		Stableswap: pallet_stableswap = 117,
+		WeightedPool: pallet_weighted_pool = 118,
+		RouterExtensions: pallet_router_extensions = 119,
 
 		// ORML related modules - starts at 150
 		Currencies: pallet_currencies = 150,
@@ -1003,6 +1118,76 @@ pub type Executive = frame_executive::Executive<
 	AllPalletsReversedWithSystemFirst,
 >;
 
+/// Assemble state of the XYK pool for the runtime API.
+fn xyk_pool_info(pool: AccountId) -> Option<pallet_xyk_rpc_runtime_api::PoolInfo<AccountId, AssetId, Balance>> {
+	use hydradx_traits::AMM;
+
+	XYK::get_pool_assets(&pool)?;
+
+	Some(pallet_xyk_rpc_runtime_api::PoolInfo {
+		reserves: XYK::get_pool_balances(pool.clone()).unwrap_or_default(),
+		share_token: XYK::share_token(&pool),
+		total_liquidity: XYK::total_liquidity(&pool),
+		fee: XYK::get_fee(&pool),
+		pool,
+	})
+}
+
+/// Assemble trade volume of the XYK pool for the runtime API.
+fn xyk_pool_volume(
+	pool: AccountId,
+) -> Option<pallet_xyk_rpc_runtime_api::PoolVolume<AccountId, AssetId, Balance, BlockNumber>> {
+	use pallet_xyk_rpc_runtime_api::{AssetVolume, PeriodVolume, PoolVolume};
+
+	let (asset_a, asset_b) = XYK::pool_assets(&pool)?;
+
+	let asset_volume = |asset_id: AssetId, volume: pallet_xyk::Volume| AssetVolume {
+		asset_id,
+		volume_in: volume.volume_in,
+		volume_out: volume.volume_out,
+		fees: volume.fees,
+		protocol_fees: volume.protocol_fees,
+	};
+
+	let periods_a = XYK::recent_period_volumes(&pool, asset_a);
+	let periods_b = XYK::recent_period_volumes(&pool, asset_b);
+
+	let volume_in_period = |periods: &[(BlockNumber, pallet_xyk::Volume)], period_start: BlockNumber| {
+		periods
+			.iter()
+			.find(|(start, _)| *start == period_start)
+			.map(|(_, volume)| *volume)
+			.unwrap_or_default()
+	};
+
+	let mut period_starts: Vec<BlockNumber> = periods_a
+		.iter()
+		.chain(periods_b.iter())
+		.map(|(start, _)| *start)
+		.collect();
+	period_starts.sort_unstable();
+	period_starts.dedup();
+
+	Some(PoolVolume {
+		total: vec![
+			asset_volume(asset_a, XYK::pool_volume(&pool, asset_a)),
+			asset_volume(asset_b, XYK::pool_volume(&pool, asset_b)),
+		],
+		period_length: <Runtime as pallet_xyk::Config>::VolumePeriodLength::get(),
+		periods: period_starts
+			.into_iter()
+			.map(|period_start| PeriodVolume {
+				period_start,
+				volumes: vec![
+					asset_volume(asset_a, volume_in_period(&periods_a, period_start)),
+					asset_volume(asset_b, volume_in_period(&periods_b, period_start)),
+				],
+			})
+			.collect(),
+		pool,
+	})
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -1111,6 +1296,74 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xyk_rpc_runtime_api::XYKApi<Block, AccountId, AssetId, Balance, BlockNumber> for Runtime {
+		fn get_pool(asset_a: AssetId, asset_b: AssetId) -> Option<pallet_xyk_rpc_runtime_api::PoolInfo<AccountId, AssetId, Balance>> {
+			xyk_pool_info(XYK::pair_account_from_assets(asset_a, asset_b))
+		}
+
+		fn get_all_pools() -> Vec<pallet_xyk_rpc_runtime_api::PoolInfo<AccountId, AssetId, Balance>> {
+			XYK::pool_accounts().into_iter().filter_map(xyk_pool_info).collect()
+		}
+
+		fn calculate_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+			discount: bool,
+		) -> Result<pallet_xyk_rpc_runtime_api::TradeQuote<AssetId, Balance>, sp_runtime::DispatchError> {
+			XYK::quote_sell(primitives::asset::AssetPair::new(asset_in, asset_out), amount_in, discount).map(|quote| {
+				pallet_xyk_rpc_runtime_api::TradeQuote {
+					amount: quote.amount,
+					fee_asset: asset_out,
+					fee_amount: quote.fee,
+					discount_amount: quote.discount_amount,
+				}
+			})
+		}
+
+		fn calculate_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount_out: Balance,
+			discount: bool,
+		) -> Result<pallet_xyk_rpc_runtime_api::TradeQuote<AssetId, Balance>, sp_runtime::DispatchError> {
+			XYK::quote_buy(primitives::asset::AssetPair::new(asset_in, asset_out), amount_out, discount).map(|quote| {
+				pallet_xyk_rpc_runtime_api::TradeQuote {
+					amount: quote.amount,
+					fee_asset: asset_in,
+					fee_amount: quote.fee,
+					discount_amount: quote.discount_amount,
+				}
+			})
+		}
+
+		fn get_pool_volume(asset_a: AssetId, asset_b: AssetId) -> Option<pallet_xyk_rpc_runtime_api::PoolVolume<AccountId, AssetId, Balance, BlockNumber>> {
+			xyk_pool_volume(XYK::pair_account_from_assets(asset_a, asset_b))
+		}
+
+		fn get_all_pool_volumes() -> Vec<pallet_xyk_rpc_runtime_api::PoolVolume<AccountId, AssetId, Balance, BlockNumber>> {
+			XYK::pool_accounts().into_iter().filter_map(xyk_pool_volume).collect()
+		}
+	}
+
+	impl pallet_router_rpc_runtime_api::RouterApi<Block, AssetId, Balance> for Runtime {
+		fn find_best_sell_route(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+		) -> Option<pallet_router_rpc_runtime_api::RouteQuote<AssetId, Balance>> {
+			common_runtime::route_finder::RouteFinder::<Runtime>::find_best_sell_route(asset_in, asset_out, amount_in)
+		}
+
+		fn find_best_buy_route(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_out: Balance,
+		) -> Option<pallet_router_rpc_runtime_api::RouteQuote<AssetId, Balance>> {
+			common_runtime::route_finder::RouteFinder::<Runtime>::find_best_buy_route(asset_in, asset_out, amount_out)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -1127,6 +1380,9 @@ impl_runtime_apis! {
 
 			list_benchmark!(list, extra, pallet_xyk, XYK);
 			list_benchmark!(list, extra, pallet_lbp, LBP);
+			list_benchmark!(list, extra, pallet_stableswap, Stableswap);
+			list_benchmark!(list, extra, pallet_weighted_pool, WeightedPool);
+			list_benchmark!(list, extra, pallet_price_oracle, PriceOracle);
 			list_benchmark!(list, extra, pallet_nft, NFT);
 			list_benchmark!(list, extra, pallet_marketplace, Marketplace);
 			list_benchmark!(list, extra, pallet_asset_registry, AssetRegistry);
@@ -1179,6 +1435,9 @@ impl_runtime_apis! {
 			// Basilisk pallets
 			add_benchmark!(params, batches, pallet_xyk, XYK);
 			add_benchmark!(params, batches, pallet_lbp, LBP);
+			add_benchmark!(params, batches, pallet_stableswap, Stableswap);
+			add_benchmark!(params, batches, pallet_weighted_pool, WeightedPool);
+			add_benchmark!(params, batches, pallet_price_oracle, PriceOracle);
 			add_benchmark!(params, batches, pallet_nft, NFT);
 			add_benchmark!(params, batches, pallet_marketplace, Marketplace);
 			add_benchmark!(params, batches, pallet_asset_registry, AssetRegistry);